@@ -1,29 +1,106 @@
+mod analyze;
+mod backup;
+mod bulk;
+mod clone_db;
+mod cluster;
+mod config;
+mod connect;
+mod convert;
+mod copy;
+mod counter;
+mod debug;
+mod delete;
+mod diff;
+mod dry_run;
+mod edit;
+mod exists;
+mod export;
+mod favorites;
+mod filter;
+mod get;
+mod glance;
+mod hash;
+mod health;
+mod history;
+mod import;
+mod info;
+mod inspect;
+mod keys;
+mod leaderboard;
+mod list;
+mod load;
+mod migrate;
+mod monitor;
+mod notify;
+mod ping;
+mod pubsub;
+mod queue;
+mod raw;
+mod rename;
+mod restore;
+mod scripting;
+mod search;
+mod sentinel;
+mod server_config;
+mod set;
+mod set_ops;
+mod snapshot;
+mod stats;
+mod string;
+mod swapdb;
+mod sync;
+mod topology;
+mod ttl;
+mod txn;
+mod version;
+mod wait_for;
+mod watch;
+mod zset;
+
 use clap::Parser;
 use colored::*;
 use log::info;
+use std::time::Duration;
 
-use crate::cli::{Cli, Commands};
-use crate::commands::{
-    backup, bulk, cluster, config, connect, copy, debug, delete, edit, export, favorites, filter,
-    get, history, inspect, keys, monitor, pubsub, search, sentinel, set, stats, version,
+use solt::cli::{
+    AnalyzeOperation, BulkOperation, Cli, ClusterOperation, Commands, ConfigOperation,
+    FunctionOperation, ListOperation, LoadSource, ScriptOperation, SentinelOperation,
+    ServerConfigOperation, SnapshotOperation, StrOperation, ZsetOperation,
 };
-use crate::error::AppError;
+use solt::commands::cluster::ReshardOptions;
+use solt::commands::filter::Criteria;
+use solt::commands::leaderboard::LeaderboardOptions;
+use solt::commands::load::SqlLoadOptions;
+use solt::commands::monitor::{CaptureOptions, MonitorFilter};
+use solt::commands::notify::NotifyOptions;
+use solt::commands::queue::QueueOptions;
+use solt::error::AppError;
+use solt::format::ValueDisplay;
+use solt::redis_client::SetOptions;
+use solt::trace;
+
+use crate::guard;
+use crate::output::{self, rule};
 
 pub async fn run() -> Result<(), AppError> {
     // Parse command line arguments
     let cli = Cli::parse();
+    output::init(cli.no_color, cli.quiet);
+    trace::init(cli.trace.as_deref())?;
 
     // Validate environment if provided
     if let Some(ref env) = cli.environment {
-        let valid_environments = vec!["dev", "staging", "prod"];
+        let valid_environments = ["dev", "staging", "prod"];
         if !valid_environments.contains(&env.as_str()) {
-            println!("{}", "Error: Invalid environment specified".red());
             println!(
                 "{}",
                 format!("Available environments: {}", valid_environments.join(", ")).yellow()
             );
             println!("{}", "Example: cargo run -- -e dev keys".cyan());
-            return Ok(());
+            return Err(AppError::ConfigError(format!(
+                "Invalid environment '{}'",
+                env
+            )));
         }
     }
 
@@ -35,11 +112,36 @@ pub async fn run() -> Result<(), AppError> {
     // Initialize logging
     env_logger::init();
 
+    // Record every invocation except browsing the history itself
+    if cli.command.is_some()
+        && !cli.no_history
+        && !matches!(cli.command, Some(Commands::History(_)))
+    {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        if let Err(e) = history::record(&args) {
+            info!("Could not record history: {}", e);
+        }
+    }
+
+    // Show which environment the command is about to use
+    if let Some(command) = &cli.command {
+        if shows_banner(command) {
+            guard::banner(cli.environment.as_deref());
+        }
+    }
+
+    // Refuse writes to read-only environments before anything connects
+    if let Some(command) = &cli.command {
+        for (env, name) in write_targets(command, cli.environment.as_deref()) {
+            guard::ensure_writable(env, name)?;
+        }
+    }
+
     match cli.command {
         None => {
             // Show welcome message and available environments
             println!("{}", "Welcome to Solt - Redis CLI Management Tool!".bold());
-            println!("{}", "==================================================");
+            rule(50);
             println!("Use --help to see available commands.");
             println!();
             println!("{}", "Quick Start:".bold());
@@ -63,7 +165,9 @@ pub async fn run() -> Result<(), AppError> {
 
         // Connection & Config commands
         Some(Commands::Connect(args)) => {
-            if args.test {
+            if args.test_all {
+                connect::test_all(args.timeout).await?;
+            } else if args.test {
                 let env = cli.environment.unwrap_or_else(|| "dev".to_string());
                 connect::test_connection(&env).await?;
             } else {
@@ -80,7 +184,23 @@ pub async fn run() -> Result<(), AppError> {
             }
         }
         Some(Commands::Config(args)) => {
-            if args.show {
+            if let Some(operation) = args.operation {
+                match operation {
+                    ConfigOperation::Diff { left, right, all } => {
+                        config::diff(left, right, all).await?;
+                    }
+                    ConfigOperation::Export {
+                        file,
+                        environments,
+                        include_passwords,
+                    } => {
+                        config::export(file, environments, include_passwords).await?;
+                    }
+                    ConfigOperation::Import { file, merge } => {
+                        config::import(file, merge).await?;
+                    }
+                }
+            } else if args.show {
                 config::run().await?;
             } else if let Some(name) = args.add_env {
                 // For simplicity, using default values. In a real app, you'd prompt for these
@@ -100,41 +220,115 @@ pub async fn run() -> Result<(), AppError> {
                 config::set_default_environment(&name).await?;
             } else if let Some(format) = args.output_format {
                 let output_format = match format.as_str() {
-                    "json" => crate::config::OutputFormat::Json,
-                    "table" => crate::config::OutputFormat::Table,
-                    "csv" => crate::config::OutputFormat::Csv,
-                    "plain" => crate::config::OutputFormat::Plain,
+                    "json" => solt::config::OutputFormat::Json,
+                    "table" => solt::config::OutputFormat::Table,
+                    "csv" => solt::config::OutputFormat::Csv,
+                    "plain" => solt::config::OutputFormat::Plain,
+                    "yaml" => solt::config::OutputFormat::Yaml,
                     _ => {
-                        println!(
-                            "{}",
-                            "Invalid output format. Use: json, table, csv, plain".red()
-                        );
-                        return Ok(());
+                        return Err(AppError::ConfigError(
+                            "Invalid output format. Use: json, yaml, table, csv, plain".to_string(),
+                        ))
                     }
                 };
                 config::set_output_format(output_format).await?;
             } else if let Some(size) = args.history_size {
                 config::set_history_size(size).await?;
+            } else if let Some(name) = args.protect {
+                config::set_protected(&name, true).await?;
+            } else if let Some(name) = args.unprotect {
+                config::set_protected(&name, false).await?;
+            } else if let Some(name) = args.read_only {
+                config::set_read_only(&name, true).await?;
+            } else if let Some(name) = args.read_write {
+                config::set_read_only(&name, false).await?;
+            } else if let Some(name) = args.annotate {
+                config::annotate_environment(
+                    &name,
+                    args.description,
+                    args.owner,
+                    args.dashboard_url,
+                    args.runbook_url,
+                    args.banner_color,
+                )
+                .await?;
             } else {
                 config::run().await?;
             }
         }
 
+        Some(Commands::ServerConfig(args)) => match args.operation {
+            ServerConfigOperation::Get { pattern } => {
+                server_config::get(pattern, cli.environment).await?;
+            }
+            ServerConfigOperation::Set { param, value } => {
+                guard::confirm_protected(
+                    cli.environment.as_deref(),
+                    &format!("set server parameter '{}'", param),
+                )?;
+                server_config::set(param, value, cli.environment).await?;
+            }
+            ServerConfigOperation::Rewrite => {
+                guard::confirm_protected(
+                    cli.environment.as_deref(),
+                    "rewrite the server's configuration file",
+                )?;
+                server_config::rewrite(cli.environment).await?;
+            }
+        },
+
         // Key Inspection commands
         Some(Commands::Keys(args)) => {
             if args.count {
                 keys::count_keys(Some(args.pattern), cli.environment).await?;
+            } else if args.cluster_wide {
+                keys::cluster_wide(args.pattern, args.key_type, cli.environment).await?;
+            } else if args.all_dbs {
+                keys::all_dbs(args.pattern, args.key_type, cli.environment).await?;
             } else {
-                keys::run(Some(args.pattern), cli.environment, args.detailed).await?;
+                keys::run(
+                    Some(args.pattern),
+                    cli.environment,
+                    args.detailed,
+                    args.limit,
+                    args.cursor,
+                    args.key_type,
+                    args.sort.map(|sort| (sort, args.desc)),
+                )
+                .await?;
+            }
+        }
+        Some(Commands::Inspect(args)) => {
+            inspect::run(args.keys, args.pattern, cli.environment).await?;
+        }
+        Some(Commands::Exists(args)) => {
+            if !exists::run(args.keys, args.pattern, args.touch, cli.environment).await? {
+                return Err(AppError::NotFound("Not all keys exist".to_string()));
             }
         }
-        Some(Commands::Inspect(_args)) => {
-            inspect::run().await?;
+        Some(Commands::WaitFor(args)) => {
+            wait_for::run(
+                args.key,
+                args.gone,
+                args.timeout,
+                args.interval,
+                cli.environment,
+            )
+            .await?;
         }
 
         // Value Viewing commands
         Some(Commands::Get(args)) => {
-            if let Some(hash_field) = args.hash_field {
+            let key = args.key.unwrap_or_default();
+            if !args.multi.is_empty() {
+                get::multi(args.multi, cli.environment).await?;
+            } else if let Some(pattern) = args.pattern {
+                get::pattern(pattern, args.json, cli.environment).await?;
+            } else if let Some(path) = args.out {
+                get::to_file(key, path, cli.environment).await?;
+            } else if let Some(query) = args.query {
+                get::query(key, query, args.decode, cli.environment).await?;
+            } else if let Some(hash_field) = args.hash_field {
                 let parts: Vec<&str> = hash_field.split(':').collect();
                 if parts.len() == 2 {
                     get::get_hash_field(
@@ -144,7 +338,9 @@ pub async fn run() -> Result<(), AppError> {
                     )
                     .await?;
                 } else {
-                    println!("{}", "Hash field format should be 'key:field'".red());
+                    return Err(AppError::ConfigError(
+                        "Hash field format should be 'key:field'".to_string(),
+                    ));
                 }
             } else if let Some(list_range) = args.list_range {
                 let parts: Vec<&str> = list_range.split('-').collect();
@@ -152,22 +348,50 @@ pub async fn run() -> Result<(), AppError> {
                     if let (Ok(start), Ok(stop)) =
                         (parts[0].parse::<isize>(), parts[1].parse::<isize>())
                     {
-                        get::get_list_range(args.key, start, stop, cli.environment).await?;
+                        get::get_list_range(key, start, stop, cli.environment).await?;
                     } else {
-                        println!(
-                            "{}",
-                            "List range format should be 'start-stop' (numbers)".red()
-                        );
+                        return Err(AppError::ConfigError(
+                            "List range format should be 'start-stop' (numbers)".to_string(),
+                        ));
                     }
                 } else {
-                    println!("{}", "List range format should be 'start-stop'".red());
+                    return Err(AppError::ConfigError(
+                        "List range format should be 'start-stop'".to_string(),
+                    ));
                 }
+            } else if let Some(interval) = args.watch {
+                get::watch(key, cli.environment, interval, args.pretty).await?;
             } else {
-                get::run(args.key, cli.environment, args.pretty).await?;
+                let display = if args.raw {
+                    ValueDisplay::Raw
+                } else if args.hex {
+                    ValueDisplay::Hex
+                } else if args.base64 {
+                    ValueDisplay::Base64
+                } else {
+                    ValueDisplay::Escaped
+                };
+                get::run(
+                    key,
+                    cli.environment,
+                    args.pretty,
+                    !args.no_pretty,
+                    args.decode,
+                    display,
+                )
+                .await?;
             }
         }
         Some(Commands::Set(args)) => {
-            if let Some(hash_field) = args.hash_field {
+            let key = args.key.unwrap_or_default();
+            if args.bulk_stdin {
+                set::bulk_stdin(args.format, cli.environment, args.ttl).await?;
+                return Ok(());
+            }
+            let value = set::read_value(args.value, args.stdin, args.file)?;
+            if !args.multi.is_empty() {
+                set::multi(args.multi, cli.environment, args.ttl).await?;
+            } else if let Some(hash_field) = args.hash_field {
                 let parts: Vec<&str> = hash_field.split(':').collect();
                 if parts.len() == 3 {
                     set::set_hash_field(
@@ -178,114 +402,841 @@ pub async fn run() -> Result<(), AppError> {
                     )
                     .await?;
                 } else {
-                    println!("{}", "Hash field format should be 'key:field:value'".red());
+                    return Err(AppError::ConfigError(
+                        "Hash field format should be 'key:field:value'".to_string(),
+                    ));
                 }
             } else if let Some(push_list) = args.push_list {
                 let left = push_list.to_lowercase() == "left";
-                set::push_list(args.key, args.value, cli.environment, left).await?;
+                set::push_list(key, value, cli.environment, left).await?;
             } else if let Some(add_set) = args.add_set {
-                set::add_to_set(args.key, add_set, cli.environment).await?;
+                set::add_to_set(key, add_set, cli.environment).await?;
             } else if let Some(add_zset) = args.add_zset {
                 let parts: Vec<&str> = add_zset.split(':').collect();
                 if parts.len() == 2 {
                     if let Ok(score) = parts[1].parse::<f64>() {
-                        set::add_to_sorted_set(
-                            args.key,
-                            parts[0].to_string(),
-                            score,
-                            cli.environment,
-                        )
-                        .await?;
+                        set::add_to_sorted_set(key, parts[0].to_string(), score, cli.environment)
+                            .await?;
                     } else {
-                        println!("{}", "Score should be a number".red());
+                        return Err(AppError::ConfigError(
+                            "Score should be a number".to_string(),
+                        ));
                     }
                 } else {
-                    println!("{}", "Sorted set format should be 'member:score'".red());
+                    return Err(AppError::ConfigError(
+                        "Sorted set format should be 'member:score'".to_string(),
+                    ));
                 }
             } else {
-                set::run(args.key, args.value, cli.environment, args.ttl).await?;
+                let options = SetOptions {
+                    ttl: args.ttl.map(Duration::from_secs),
+                    condition: if args.nx {
+                        Some("NX")
+                    } else if args.xx {
+                        Some("XX")
+                    } else {
+                        None
+                    },
+                    keep_ttl: args.keepttl,
+                    get: args.get,
+                };
+                set::run(key, value, cli.environment, options).await?;
             }
         }
 
         // Search & Filter commands
-        Some(Commands::Search(_args)) => {
-            search::run().await?;
+        Some(Commands::Search(args)) => {
+            if args.in_hash {
+                search::in_hashes(args.pattern, args.keys, args.count, cli.environment).await?;
+            } else {
+                search::run(args.pattern, args.count, cli.environment).await?;
+            }
         }
-        Some(Commands::Filter(_args)) => {
-            filter::run().await?;
+        Some(Commands::Filter(args)) => {
+            let criteria = Criteria {
+                ttl: args.ttl,
+                size: args.size,
+                key_type: args.type_filter,
+                encodings: args.encoding,
+            };
+            filter::run(args.pattern, criteria, cli.environment).await?;
         }
 
         // Editing & Writing commands
-        Some(Commands::Edit(_args)) => {
-            edit::run().await?;
+        Some(Commands::Edit(args)) => {
+            if args.value.is_none() && !args.stdin && args.file.is_none() {
+                edit::in_editor(args.key, args.json, cli.environment).await?;
+            } else {
+                let value = set::read_value(args.value, args.stdin, args.file)?;
+                edit::run(args.key, value, cli.environment).await?;
+            }
         }
         Some(Commands::Delete(args)) => {
+            let env = cli.environment.as_deref();
             if let Some(pattern) = args.pattern {
-                delete::delete_by_pattern(pattern, cli.environment, args.confirm).await?;
+                if args.confirm && !args.dry_run {
+                    guard::confirm_protected(
+                        env,
+                        &format!("delete every key matching '{}'", pattern),
+                    )?;
+                }
+                delete::delete_by_pattern(pattern, cli.environment, args.confirm, args.dry_run)
+                    .await?;
             } else if args.flush_db {
-                delete::flush_db(cli.environment, args.confirm).await?;
+                if args.confirm && !args.dry_run {
+                    guard::confirm_protected(env, "delete every key in the database")?;
+                }
+                delete::flush_db(cli.environment, args.confirm, args.dry_run).await?;
             } else if args.flush_all {
-                delete::flush_all(cli.environment, args.confirm).await?;
+                if args.confirm && !args.dry_run {
+                    guard::confirm_protected(env, "delete every key in every database")?;
+                }
+                delete::flush_all(cli.environment, args.confirm, args.dry_run).await?;
             } else {
-                delete::run(args.key, cli.environment).await?;
+                if !args.dry_run {
+                    guard::confirm_protected(env, &format!("delete key '{}'", args.key))?;
+                }
+                delete::run(args.key, cli.environment, args.dry_run, args.lazy).await?;
             }
         }
 
+        Some(Commands::Rename(args)) => {
+            if args.force {
+                guard::confirm_protected(
+                    cli.environment.as_deref(),
+                    &format!("rename '{}' over '{}'", args.old, args.new),
+                )?;
+            }
+            rename::run(args.old, args.new, args.force, cli.environment).await?;
+        }
+        Some(Commands::Convert(args)) => {
+            convert::run(args.key, args.to, args.yes, cli.environment).await?;
+        }
+        Some(Commands::Incr(args)) => {
+            counter::run(args.key, args.field, args.by, cli.environment).await?;
+        }
+        Some(Commands::Decr(args)) => {
+            counter::run(args.key, args.field, args.by.negated(), cli.environment).await?;
+        }
+        Some(Commands::Str(args)) => match args.operation {
+            StrOperation::Getrange { key, start, end } => {
+                string::getrange(key, start, end, cli.environment).await?;
+            }
+            StrOperation::Setrange { key, offset, value } => {
+                string::setrange(key, offset, value, cli.environment).await?;
+            }
+            StrOperation::Append { key, value } => {
+                string::append(key, value, cli.environment).await?;
+            }
+            StrOperation::Strlen { key } => {
+                string::strlen(key, cli.environment).await?;
+            }
+        },
+        Some(Commands::Hash(args)) => {
+            if !args.del.is_empty() {
+                hash::delete_fields(args.key, args.del, cli.environment).await?;
+            } else if let Some(field) = args.exists {
+                hash::exists(args.key, field, cli.environment).await?;
+            } else if let Some(count) = args.random {
+                hash::random(args.key, count, args.with_values, cli.environment).await?;
+            } else if let Some(ttl) = args.expire {
+                hash::expire(args.key, ttl, args.fields, cli.environment).await?;
+            } else {
+                hash::ttl(args.key, args.fields, cli.environment).await?;
+            }
+        }
+        Some(Commands::SetOps(args)) => {
+            set_ops::run(
+                args.operation,
+                args.keys,
+                args.store,
+                args.count,
+                cli.environment,
+            )
+            .await?;
+        }
+        Some(Commands::Leaderboard(args)) => {
+            let options = LeaderboardOptions {
+                top: args.top,
+                page: args.page,
+                around: args.around,
+                ascending: args.asc,
+            };
+            leaderboard::run(args.key, options, cli.environment).await?;
+        }
+        Some(Commands::Zset(args)) => match args.operation {
+            ZsetOperation::Rank { key, member, rev } => {
+                zset::rank(key, member, rev, cli.environment).await?;
+            }
+            ZsetOperation::Score { key, member } => {
+                zset::score(key, member, cli.environment).await?;
+            }
+            ZsetOperation::Range {
+                key,
+                min,
+                max,
+                rev,
+                limit,
+                offset,
+            } => {
+                zset::range(key, min, max, rev, limit, offset, cli.environment).await?;
+            }
+            ZsetOperation::Incr { key, member, by } => {
+                zset::incr(key, member, by, cli.environment).await?;
+            }
+            ZsetOperation::Remove { key, min, max } => {
+                guard::confirm_protected(
+                    cli.environment.as_deref(),
+                    &format!("remove members of '{}' scored {} to {}", key, min, max),
+                )?;
+                zset::remove(key, min, max, cli.environment).await?;
+            }
+        },
+        Some(Commands::List(args)) => match args.operation {
+            ListOperation::Pop { key, right, count } => {
+                list::pop(key, right, count, cli.environment).await?;
+            }
+            ListOperation::Trim { key, start, stop } => {
+                guard::confirm_protected(
+                    cli.environment.as_deref(),
+                    &format!("trim list '{}'", key),
+                )?;
+                list::trim(key, start, stop, cli.environment).await?;
+            }
+            ListOperation::Insert {
+                key,
+                before,
+                after,
+                value,
+            } => {
+                let (is_before, pivot) = match (before, after) {
+                    (Some(pivot), _) => (true, pivot),
+                    (None, pivot) => (false, pivot.unwrap_or_default()),
+                };
+                list::insert(key, is_before, pivot, value, cli.environment).await?;
+            }
+            ListOperation::Set { key, index, value } => {
+                list::set(key, index, value, cli.environment).await?;
+            }
+        },
+
+        Some(Commands::Ttl(args)) => {
+            let condition = if args.nx {
+                Some("NX")
+            } else if args.xx {
+                Some("XX")
+            } else if args.gt {
+                Some("GT")
+            } else if args.lt {
+                Some("LT")
+            } else {
+                None
+            };
+            ttl::run(args.key, args.set, args.persist, condition, cli.environment).await?;
+        }
+
         // Bulk Operations commands
-        Some(Commands::Bulk(_args)) => {
-            bulk::run().await?;
+        Some(Commands::Bulk(args)) => {
+            if let (BulkOperation::Copy, Some(to_prefix)) = (&args.operation, args.to_prefix) {
+                if args.confirm && args.replace && !args.dry_run {
+                    guard::confirm_protected(
+                        cli.environment.as_deref(),
+                        &format!("overwrite keys under '{}'", to_prefix),
+                    )?;
+                }
+                let options = bulk::CopyOptions {
+                    to_prefix,
+                    ttl: args.ttl,
+                    replace: args.replace,
+                    confirm: args.confirm,
+                    dry_run: args.dry_run,
+                };
+                bulk::copy(args.pattern, options, cli.environment).await?;
+            } else {
+                bulk::run(args.operation)?;
+            }
+        }
+        Some(Commands::Copy(args)) => {
+            if let Some(path) = args.retry_failed {
+                copy::retry_failed(&path, args.max_retries).await?;
+            } else if let Some(source) = args.source {
+                let env = cli.environment.as_deref();
+                let dest_env = args.dest_env.as_deref().or(env);
+                let source_env = args.source_env.as_deref().or(env);
+                if args.replace {
+                    guard::confirm_protected(
+                        dest_env,
+                        &format!(
+                            "overwrite key '{}'",
+                            args.destination.as_deref().unwrap_or(&source)
+                        ),
+                    )?;
+                }
+                if args.move_key {
+                    guard::confirm_protected(source_env, &format!("move key '{}'", source))?;
+                }
+                let target = copy::CopyTarget {
+                    source_env: source_env.map(String::from),
+                    dest_env: dest_env.map(String::from),
+                    destination: args.destination,
+                    to_db: args.to_db,
+                    replace: args.replace,
+                    move_key: args.move_key,
+                };
+                copy::copy_key(source, target).await?;
+            } else {
+                copy::run(args.max_retries).await?;
+            }
         }
-        Some(Commands::Copy(_args)) => {
-            copy::run().await?;
+        Some(Commands::Diff(args)) => {
+            diff::run(args.source, args.dest, args.pattern).await?;
+        }
+        Some(Commands::Migrate(args)) => {
+            if !args.dry_run {
+                guard::confirm_protected(
+                    Some(&args.dest),
+                    &format!(
+                        "receive keys matching '{}' from '{}'",
+                        args.pattern, args.source
+                    ),
+                )?;
+            }
+            migrate::run(
+                args.source,
+                args.dest,
+                args.pattern,
+                args.replace,
+                args.dry_run,
+            )
+            .await?;
+        }
+        Some(Commands::CloneDb(args)) => {
+            let action = if args.flush_dest {
+                format!("be flushed and receive a clone of '{}'", args.source)
+            } else {
+                format!("receive a clone of '{}'", args.source)
+            };
+            guard::confirm_protected(Some(&args.dest), &action)?;
+            let options = clone_db::CloneOptions {
+                source_db: args.source_db,
+                dest_db: args.dest_db,
+                flush_dest: args.flush_dest,
+                replace: args.replace,
+                rate: args.rate,
+                verify: !args.no_verify,
+            };
+            clone_db::run(args.source, args.dest, options).await?;
+        }
+        Some(Commands::Swapdb(args)) => {
+            guard::confirm_protected(
+                cli.environment.as_deref(),
+                &format!("swap db {} and db {}", args.first, args.second),
+            )?;
+            swapdb::run(args.first, args.second, args.yes, cli.environment).await?;
+        }
+        Some(Commands::Sync(args)) => {
+            guard::confirm_protected(
+                Some(&args.to),
+                &format!(
+                    "be overwritten with keys matching '{}' from '{}'",
+                    args.pattern, args.from
+                ),
+            )?;
+            sync::run(
+                args.from,
+                args.to,
+                args.pattern,
+                args.interval,
+                args.delete_extra,
+                args.once,
+            )
+            .await?;
         }
 
         // Monitoring & Debug commands
         Some(Commands::Monitor(args)) => {
-            if args.slowlog {
+            if args.slowlog && args.reset {
+                monitor::slowlog_reset(cli.environment).await?;
+            } else if args.slowlog && args.follow {
+                monitor::slowlog_follow(args.interval, cli.environment).await?;
+            } else if args.slowlog {
                 monitor::slowlog_get(Some(args.slowlog_count), cli.environment).await?;
             } else if args.clients {
                 monitor::client_list(cli.environment).await?;
             } else {
-                monitor::run(cli.environment).await?;
+                let filter = MonitorFilter {
+                    key_pattern: args.key_pattern,
+                    commands: args.command.iter().map(|c| c.to_uppercase()).collect(),
+                    client_addr: args.client_addr,
+                };
+                if args.stats {
+                    monitor::traffic_stats(filter, args.duration, args.top, cli.environment)
+                        .await?;
+                } else {
+                    let capture = args.out.map(|path| CaptureOptions {
+                        path,
+                        format: args.out_format,
+                        max_size: args.max_size,
+                        keep: args.keep,
+                    });
+                    monitor::run(filter, capture, cli.environment).await?;
+                }
             }
         }
+        Some(Commands::Watch(args)) => {
+            watch::run(args.interval, args.command, cli.environment).await?;
+        }
         Some(Commands::Debug(_args)) => {
             debug::run().await?;
         }
-        Some(Commands::Stats(_args)) => {
-            stats::run().await?;
+        Some(Commands::Cmd(args)) => {
+            let name = args.args[0].to_uppercase();
+            if !READ_COMMANDS.contains(&name.as_str()) {
+                guard::confirm_protected(cli.environment.as_deref(), &format!("run {}", name))?;
+            }
+            raw::run(args.args, cli.environment).await?;
+        }
+        Some(Commands::Txn(args)) => {
+            guard::confirm_protected(cli.environment.as_deref(), "run a transaction")?;
+            txn::run(args.commands, args.file, cli.environment).await?;
+        }
+        Some(Commands::Eval(args)) => {
+            guard::confirm_protected(cli.environment.as_deref(), "run a Lua script")?;
+            scripting::eval(args.script, args.sha, args.keys, args.args, cli.environment).await?;
+        }
+        Some(Commands::Script(args)) => match args.operation {
+            ScriptOperation::Load { file } => {
+                scripting::script_load(file, cli.environment).await?;
+            }
+            ScriptOperation::Exists { shas } => {
+                scripting::script_exists(shas, cli.environment).await?;
+            }
+            ScriptOperation::Flush { confirm } => {
+                guard::confirm_protected(cli.environment.as_deref(), "flush the script cache")?;
+                scripting::script_flush(cli.environment, confirm).await?;
+            }
+        },
+        Some(Commands::Function(args)) => match args.operation {
+            FunctionOperation::List { pattern } => {
+                scripting::function_list(pattern, cli.environment).await?;
+            }
+            FunctionOperation::Load { file, replace } => {
+                scripting::function_load(file, replace, cli.environment).await?;
+            }
+            FunctionOperation::Delete { library } => {
+                guard::confirm_protected(
+                    cli.environment.as_deref(),
+                    &format!("delete function library '{}'", library),
+                )?;
+                scripting::function_delete(library, cli.environment).await?;
+            }
+        },
+        Some(Commands::Info(args)) => {
+            info::run(args.section, args.all, cli.environment).await?;
+        }
+        Some(Commands::Ping(args)) => {
+            ping::run(args.count, args.interval, cli.environment).await?;
+        }
+        Some(Commands::Health(args)) => {
+            health::run(
+                Duration::from_millis(args.max_latency_ms),
+                args.min_memory_free,
+                cli.environment,
+            )
+            .await?;
+        }
+        Some(Commands::Queue(args)) => {
+            if let Some(dest) = args.requeue_to {
+                queue::requeue(args.key, dest, args.count, args.rpush, cli.environment).await?;
+            } else if args.drain {
+                guard::confirm_protected(
+                    cli.environment.as_deref(),
+                    &format!("drain queue '{}'", args.key),
+                )?;
+                queue::drain(args.key, args.count, args.rpush, cli.environment).await?;
+            } else {
+                let options = QueueOptions {
+                    show: args.show,
+                    sample: args.sample,
+                    rpush: args.rpush,
+                };
+                queue::run(args.key, options, cli.environment).await?;
+            }
+        }
+        Some(Commands::Analyze(args)) => match args.operation {
+            AnalyzeOperation::Sessions { pattern, top } => {
+                analyze::sessions(pattern, top, cli.environment).await?;
+            }
+            AnalyzeOperation::Locks { pattern } => {
+                analyze::locks(pattern, cli.environment).await?;
+            }
+        },
+        Some(Commands::Stats(args)) => {
+            if args.memory {
+                stats::memory_stats(cli.environment).await?;
+            } else if args.memory_doctor {
+                stats::memory_doctor(cli.environment).await?;
+            } else if args.commands {
+                stats::command_stats(args.sort, cli.environment).await?;
+            } else if args.replication {
+                stats::replication(args.lag_threshold, cli.environment).await?;
+            } else if args.keyspace {
+                stats::keyspace(cli.environment).await?;
+            } else {
+                stats::run(cli.environment, args.watch).await?;
+            }
         }
 
         // Backup & Export commands
-        Some(Commands::Backup(_args)) => {
-            backup::run().await?;
+        Some(Commands::Backup(args)) => {
+            if let Some(path) = args.rdb {
+                backup::rdb(path, cli.environment).await?;
+            } else {
+                backup::run().await?;
+            }
+        }
+        Some(Commands::Snapshot(args)) => match args.operation {
+            SnapshotOperation::Save { file, pattern } => {
+                snapshot::save(file, pattern, cli.environment).await?;
+            }
+            SnapshotOperation::Diff { before, after } => {
+                snapshot::diff(before, after).await?;
+            }
+        },
+        Some(Commands::Export(args)) => {
+            export::run(args.format, args.output, args.pattern, cli.environment).await?;
         }
-        Some(Commands::Export(_args)) => {
-            export::run().await?;
+        Some(Commands::Import(args)) => {
+            if !args.dry_run {
+                guard::confirm_protected(
+                    cli.environment.as_deref(),
+                    &format!("replay the commands in '{}'", args.pipe_file),
+                )?;
+            }
+            import::pipe_file(args.pipe_file, cli.environment, args.dry_run).await?;
+        }
+        Some(Commands::Restore(args)) => {
+            guard::confirm_protected(
+                cli.environment.as_deref(),
+                &format!("restore keys from '{}'", args.file),
+            )?;
+            restore::run(
+                args.file,
+                args.pattern,
+                args.prefix,
+                args.on_conflict,
+                cli.environment,
+            )
+            .await?;
         }
+        Some(Commands::Load(args)) => match args.source {
+            LoadSource::Sql(sql) => {
+                let options = SqlLoadOptions {
+                    dsn: sql.dsn,
+                    query: sql.query,
+                    key_template: sql.key_template,
+                    value_column: sql.value_column,
+                    ttl: sql.ttl,
+                    batch_size: sql.batch_size,
+                };
+                load::load_sql(options, cli.environment).await?;
+            }
+        },
 
         // Pub/Sub commands
-        Some(Commands::Pubsub(_args)) => {
-            pubsub::run().await?;
+        Some(Commands::Pubsub(args)) => {
+            if let Some(channel) = args.publish {
+                if args.message.is_none() && !args.stdin && args.file.is_none() {
+                    return Err(AppError::ConfigError(
+                        "Nothing to publish: give a message, --file or --stdin".to_string(),
+                    ));
+                }
+                let payload = set::read_value(args.message, args.stdin, args.file)?;
+                let messages = if args.lines {
+                    payload
+                        .split(|b| *b == b'\n')
+                        .map(|line| line.strip_suffix(b"\r").unwrap_or(line).to_vec())
+                        .filter(|line| !line.is_empty())
+                        .collect()
+                } else {
+                    vec![payload]
+                };
+                pubsub::publish(
+                    channel,
+                    messages,
+                    args.repeat,
+                    args.interval,
+                    cli.environment,
+                )
+                .await?;
+            } else if let Some(pattern) = args.channels {
+                pubsub::channels(pattern, cli.environment).await?;
+            } else if !args.numsub.is_empty() {
+                pubsub::numsub(args.numsub, cli.environment).await?;
+            } else if let Some(channels) = args.subscribe {
+                let channels = channels
+                    .split(',')
+                    .map(|c| c.trim().to_string())
+                    .filter(|c| !c.is_empty())
+                    .collect();
+                pubsub::subscribe(channels, cli.environment).await?;
+            } else {
+                return Err(AppError::ConfigError(
+                    "Use --publish, --subscribe, --channels or --numsub".to_string(),
+                ));
+            }
         }
 
-        // Cluster & Sentinel commands
-        Some(Commands::Cluster(_args)) => {
-            cluster::run().await?;
+        Some(Commands::Notify(args)) => {
+            let options = NotifyOptions {
+                patterns: args.pattern,
+                expired: args.expired,
+                evicted: args.evicted,
+                set: args.set,
+                enable: args.enable,
+            };
+            notify::run(options, cli.environment).await?;
         }
-        Some(Commands::Sentinel(_args)) => {
-            sentinel::run().await?;
+
+        // Cluster & Sentinel commands
+        Some(Commands::Cluster(args)) => match args.operation {
+            Some(ClusterOperation::Stats) => cluster::stats(cli.environment).await?,
+            Some(ClusterOperation::Check) => cluster::check(cli.environment).await?,
+            Some(ClusterOperation::Locate { key }) => cluster::locate(key, cli.environment).await?,
+            Some(ClusterOperation::Reshard {
+                from,
+                to,
+                slots,
+                batch,
+                timeout,
+                resume,
+                dry_run,
+            }) => {
+                if !dry_run {
+                    guard::confirm_protected(
+                        cli.environment.as_deref(),
+                        &format!("move slots from {} to {}", from, to),
+                    )?;
+                }
+                let options = ReshardOptions {
+                    from,
+                    to,
+                    slots: slots.unwrap_or(0),
+                    batch,
+                    timeout_ms: timeout,
+                    resume,
+                    dry_run,
+                };
+                cluster::reshard(options, cli.environment).await?;
+            }
+            None => cluster::run().await?,
+        },
+        Some(Commands::Sentinel(args)) => match args.operation {
+            Some(SentinelOperation::Failover { master, wait }) => {
+                guard::confirm_protected(
+                    cli.environment.as_deref(),
+                    &format!("fail over master '{}'", master),
+                )?;
+                sentinel::failover(master, wait, cli.environment).await?;
+            }
+            Some(SentinelOperation::Events { event }) => {
+                sentinel::events(event, cli.environment).await?;
+            }
+            None => sentinel::run().await?,
+        },
+        Some(Commands::Topology) => {
+            topology::run(cli.environment).await?;
         }
 
         // UX Features commands
         Some(Commands::Favorites(_args)) => {
             favorites::run().await?;
         }
-        Some(Commands::History(_args)) => {
-            history::run().await?;
+        Some(Commands::Glance) => {
+            glance::run(cli.environment).await?;
+        }
+        Some(Commands::History(args)) => {
+            if args.clear {
+                history::clear().await?;
+            } else if let Some(target) = args.replay {
+                history::replay(target, args.yes).await?;
+            } else {
+                history::run(args.search).await?;
+            }
         }
     }
 
     info!("CLI application completed successfully.");
     Ok(())
 }
+
+/// Commands passed to `solt cmd` that never modify data, so they stay
+/// available on read-only environments.
+const READ_COMMANDS: &[&str] = &[
+    "BITCOUNT",
+    "BITPOS",
+    "DBSIZE",
+    "DUMP",
+    "EXISTS",
+    "GET",
+    "GETRANGE",
+    "HEXISTS",
+    "HGET",
+    "HGETALL",
+    "HKEYS",
+    "HLEN",
+    "HMGET",
+    "HSCAN",
+    "HSTRLEN",
+    "HVALS",
+    "INFO",
+    "KEYS",
+    "LINDEX",
+    "LLEN",
+    "LPOS",
+    "LRANGE",
+    "MEMORY",
+    "MGET",
+    "OBJECT",
+    "PING",
+    "PTTL",
+    "SCAN",
+    "SCARD",
+    "SISMEMBER",
+    "SMEMBERS",
+    "SMISMEMBER",
+    "SRANDMEMBER",
+    "SSCAN",
+    "STRLEN",
+    "TTL",
+    "TYPE",
+    "XINFO",
+    "XLEN",
+    "XRANGE",
+    "XREVRANGE",
+    "ZCARD",
+    "ZCOUNT",
+    "ZRANGE",
+    "ZRANGEBYSCORE",
+    "ZRANK",
+    "ZREVRANGE",
+    "ZREVRANK",
+    "ZSCAN",
+    "ZSCORE",
+];
+
+/// Whether a command runs against the `-e` environment. Commands that only
+/// touch the config file, or name their environments explicitly, skip the
+/// banner.
+fn shows_banner(command: &Commands) -> bool {
+    match command {
+        Commands::Version
+        | Commands::Config(_)
+        | Commands::History(_)
+        | Commands::Sync(_)
+        | Commands::Diff(_)
+        | Commands::Migrate(_)
+        | Commands::CloneDb(_)
+        | Commands::Watch(_) => false,
+        Commands::Connect(args) => !args.test_all,
+        _ => true,
+    }
+}
+
+/// The environments a command would write to, with the command name used
+/// in the refusal message. `None` stands for the default environment.
+fn write_targets<'a>(
+    command: &'a Commands,
+    environment: Option<&'a str>,
+) -> Vec<(Option<&'a str>, &'static str)> {
+    match command {
+        Commands::Set(_) => vec![(environment, "set")],
+        Commands::Edit(_) => vec![(environment, "edit")],
+        Commands::Delete(args) if !args.dry_run => {
+            let name = if args.flush_db || args.flush_all {
+                "flush"
+            } else {
+                "delete"
+            };
+            vec![(environment, name)]
+        }
+        Commands::Rename(_) => vec![(environment, "rename")],
+        Commands::Convert(_) => vec![(environment, "convert")],
+        Commands::Incr(_) | Commands::Decr(_) => vec![(environment, "incr")],
+        Commands::Str(args)
+            if !matches!(
+                args.operation,
+                StrOperation::Getrange { .. } | StrOperation::Strlen { .. }
+            ) =>
+        {
+            vec![(environment, "str")]
+        }
+        Commands::List(_) => vec![(environment, "list")],
+        Commands::SetOps(args) if args.store.is_some() => vec![(environment, "set-ops")],
+        Commands::Zset(args)
+            if matches!(
+                args.operation,
+                ZsetOperation::Incr { .. } | ZsetOperation::Remove { .. }
+            ) =>
+        {
+            vec![(environment, "zset")]
+        }
+        Commands::Hash(args) if !args.del.is_empty() || args.expire.is_some() => {
+            vec![(environment, "hash")]
+        }
+        Commands::Queue(args) if args.requeue_to.is_some() || args.drain => {
+            vec![(environment, "queue")]
+        }
+        Commands::Ttl(args) if args.set.is_some() || args.persist => vec![(environment, "ttl")],
+        Commands::Bulk(args) if !args.dry_run => vec![(environment, "bulk")],
+        Commands::Copy(args) => {
+            let mut targets = vec![(args.dest_env.as_deref().or(environment), "copy")];
+            if args.move_key {
+                targets.push((args.source_env.as_deref().or(environment), "copy"));
+            }
+            targets
+        }
+        Commands::Migrate(args) if !args.dry_run => vec![(Some(args.dest.as_str()), "migrate")],
+        Commands::CloneDb(args) => vec![(Some(args.dest.as_str()), "clone-db")],
+        Commands::Swapdb(_) => vec![(environment, "swapdb")],
+        Commands::Txn(_) => vec![(environment, "txn")],
+        Commands::Sync(args) => vec![(Some(args.to.as_str()), "sync")],
+        Commands::Import(args) if !args.dry_run => vec![(environment, "import")],
+        Commands::Restore(_) => vec![(environment, "restore")],
+        Commands::Load(_) => vec![(environment, "load")],
+        Commands::ServerConfig(args)
+            if !matches!(args.operation, ServerConfigOperation::Get { .. }) =>
+        {
+            vec![(environment, "server-config")]
+        }
+        Commands::Eval(_) => vec![(environment, "eval")],
+        Commands::Script(args) if !matches!(args.operation, ScriptOperation::Exists { .. }) => {
+            vec![(environment, "script")]
+        }
+        Commands::Function(args) if !matches!(args.operation, FunctionOperation::List { .. }) => {
+            vec![(environment, "function")]
+        }
+        Commands::Notify(args) if args.enable => vec![(environment, "notify --enable")],
+        Commands::Cluster(args) => match &args.operation {
+            Some(ClusterOperation::Reshard { dry_run: false, .. }) => {
+                vec![(environment, "cluster reshard")]
+            }
+            _ => vec![],
+        },
+        Commands::Sentinel(args)
+            if matches!(args.operation, Some(SentinelOperation::Failover { .. })) =>
+        {
+            vec![(environment, "sentinel failover")]
+        }
+        Commands::Pubsub(args) if args.publish.is_some() => vec![(environment, "publish")],
+        Commands::Cmd(args) => {
+            let name = args.args[0].to_uppercase();
+            if READ_COMMANDS.contains(&name.as_str()) {
+                vec![]
+            } else {
+                vec![(environment, "cmd")]
+            }
+        }
+        _ => vec![],
+    }
+}
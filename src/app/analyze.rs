@@ -0,0 +1,199 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::analyze;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::format::{format_bytes, format_seconds};
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, progress_bar, rule};
+
+/// Owner tokens longer than this are cut short in the locks table
+const MAX_OWNER_WIDTH: usize = 48;
+
+#[derive(Tabled)]
+struct LockRow {
+    #[tabled(rename = "Lock")]
+    key: String,
+    #[tabled(rename = "Owner")]
+    owner: String,
+    #[tabled(rename = "TTL")]
+    ttl: String,
+    #[tabled(rename = "Status")]
+    status: String,
+}
+
+#[derive(Tabled)]
+struct BucketRow {
+    #[tabled(rename = "TTL")]
+    range: String,
+    #[tabled(rename = "Sessions")]
+    sessions: usize,
+    #[tabled(rename = "Share")]
+    share: String,
+}
+
+/// Report on a session store: how many sessions there are, how their TTLs
+/// are distributed, how big they are on average, and which ones never
+/// expire — the usual cause of session stores growing without bound.
+pub async fn sessions(
+    pattern: String,
+    top: usize,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let keys = client.scan_keys(&pattern).await?;
+    if keys.is_empty() {
+        println!(
+            "{}",
+            format!("No sessions matching pattern '{}'", pattern).yellow()
+        );
+        return Ok(());
+    }
+
+    let progress = progress_bar(keys.len() as u64);
+    progress.set_message("Collecting session details...");
+    let report = analyze::sessions(&mut client, pattern, &keys, top, |done| {
+        progress.inc(done as u64)
+    })
+    .await?;
+    progress.finish_and_clear();
+    let sessions = report.sessions;
+
+    if print_structured(&config.output_format, &report)? {
+        return Ok(());
+    }
+
+    println!("{}", format!("Sessions ({}):", report.pattern).bold());
+    rule(50);
+    println!("Sessions: {}", report.sessions.to_string().cyan());
+    println!("Total memory: {}", format_bytes(report.total_memory).cyan());
+    println!(
+        "Average size: {}",
+        format_bytes(report.average_memory).cyan()
+    );
+    if let Some(payload) = report.average_payload {
+        println!("Average payload: {}", format_bytes(payload).cyan());
+    }
+
+    let share = |count: usize| format!("{:.1}%", count as f64 * 100.0 / sessions as f64);
+    let mut rows: Vec<BucketRow> = report
+        .ttl_distribution
+        .iter()
+        .map(|bucket| BucketRow {
+            range: bucket.range.clone(),
+            sessions: bucket.sessions,
+            share: share(bucket.sessions),
+        })
+        .collect();
+    rows.push(BucketRow {
+        range: "No expiry".to_string(),
+        sessions: report.without_expiry,
+        share: share(report.without_expiry),
+    });
+    println!("\n{}", "TTL distribution:".bold());
+    println!("{}", Table::new(rows));
+
+    if report.without_expiry == 0 {
+        println!("{}", "✓ Every session has an expiry".green());
+        return Ok(());
+    }
+
+    println!(
+        "\n{}",
+        format!(
+            "⚠ {} sessions never expire, holding {}",
+            report.without_expiry,
+            format_bytes(report.without_expiry_memory)
+        )
+        .red()
+        .bold()
+    );
+    println!("{}", "Largest sessions without expiry:".bold());
+    for sample in &report.largest_without_expiry {
+        let memory = sample
+            .memory
+            .map(format_bytes)
+            .unwrap_or_else(|| "?".to_string());
+        println!("  • {} ({})", sample.key.cyan(), memory);
+    }
+    println!(
+        "{}",
+        "Set a TTL in the session middleware; expire existing ones with: solt ttl <key> --set 86400"
+            .yellow()
+    );
+
+    Ok(())
+}
+
+/// List lock-style keys with their owner tokens and remaining TTL, flagging
+/// locks without an expiry: if their holder dies they are never released.
+pub async fn locks(pattern: String, environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let keys = client.scan_keys(&pattern).await?;
+    if keys.is_empty() {
+        println!(
+            "{}",
+            format!("No locks matching pattern '{}'", pattern).yellow()
+        );
+        return Ok(());
+    }
+
+    let locks = analyze::locks(&mut client, &keys).await?;
+    if print_structured(&config.output_format, &locks)? {
+        return Ok(());
+    }
+
+    let suspicious = locks.iter().filter(|lock| lock.suspicious).count();
+    let rows: Vec<LockRow> = locks
+        .into_iter()
+        .map(|lock| LockRow {
+            key: lock.key,
+            owner: lock
+                .owners
+                .iter()
+                .map(|owner| {
+                    if owner.chars().count() > MAX_OWNER_WIDTH {
+                        let truncated: String = owner.chars().take(MAX_OWNER_WIDTH - 3).collect();
+                        format!("{}...", truncated)
+                    } else {
+                        owner.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ttl: match lock.ttl_ms {
+                Some(ms) if ms < 10_000 => format!("{:.1}s", ms as f64 / 1000.0),
+                Some(ms) => format_seconds(ms as u64 / 1000),
+                None => "No expiry".to_string(),
+            },
+            status: if lock.suspicious {
+                "⚠ no TTL".red().bold().to_string()
+            } else {
+                "held".green().to_string()
+            },
+        })
+        .collect();
+
+    println!("{}", format!("Locks ({}):", pattern).bold());
+    println!("{}", Table::new(rows));
+
+    if suspicious > 0 {
+        println!(
+            "{}",
+            format!(
+                "⚠ {} locks have no TTL and are never released if their holder dies",
+                suspicious
+            )
+            .red()
+            .bold()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use colored::*;
+use indicatif::{ProgressBar, ProgressStyle};
+use log::info;
+
+use solt::commands::backup::{self, Persistence};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::format::format_bytes;
+use solt::redis_client::RedisClient;
+
+use crate::output::{progress_bar, spinner, success};
+
+pub async fn run() -> Result<(), AppError> {
+    info!("Backup command invoked");
+    let mut client = RedisClient::for_environment(None).await?;
+
+    println!(
+        "{}",
+        "Choose backup operation: [1] SAVE, [2] BGSAVE, [3] BGREWRITEAOF".cyan()
+    );
+    println!("{}", "1. SAVE (synchronous save, blocks Redis)".yellow());
+    println!("{}", "2. BGSAVE (background save, non-blocking)".yellow());
+    println!("{}", "3. BGREWRITEAOF (background AOF rewrite)".yellow());
+    print!("Enter choice (1/2/3): ");
+    use std::io::{self, Write};
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let choice = input.trim();
+
+    match choice {
+        "1" => {
+            println!("{}", "Running SAVE...".cyan());
+            backup::persist(&mut client, Persistence::Save).await?;
+            println!("{}", "SAVE completed successfully.".green());
+        }
+        "2" => {
+            println!("{}", "Running BGSAVE...".cyan());
+            backup::persist(&mut client, Persistence::Bgsave).await?;
+            println!("{}", "BGSAVE triggered successfully.".green());
+        }
+        "3" => {
+            println!("{}", "Running BGREWRITEAOF...".cyan());
+            backup::persist(&mut client, Persistence::Bgrewriteaof).await?;
+            println!("{}", "BGREWRITEAOF triggered successfully.".green());
+        }
+        _ => {
+            println!("{}", "Invalid choice. Aborting.".red());
+        }
+    }
+    Ok(())
+}
+
+pub async fn rdb(path: String, environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let redis_config = config.redis_config(&env_name)?;
+
+    println!(
+        "{}",
+        format!(
+            "Requesting RDB from '{}' ({}:{})...",
+            env_name, redis_config.host, redis_config.port
+        )
+        .cyan()
+    );
+
+    // A bar when the server announces the size, a spinner for diskless
+    // transfers where it does not
+    let mut progress: Option<ProgressBar> = None;
+    let written = backup::download_rdb(&redis_config, &path, |received, total| {
+        let bar = progress.get_or_insert_with(|| match total {
+            Some(size) => {
+                let bar = progress_bar(size);
+                if let Ok(style) =
+                    ProgressStyle::with_template("{bar:40} {bytes}/{total_bytes} ({eta})")
+                {
+                    bar.set_style(style);
+                }
+                bar
+            }
+            None => spinner(),
+        });
+        match total {
+            Some(_) => bar.set_position(received),
+            None => {
+                bar.set_message(format!("{} received", format_bytes(received)));
+                bar.tick();
+            }
+        }
+    })
+    .await?;
+    if let Some(progress) = progress {
+        progress.finish_and_clear();
+    }
+
+    success(&format!(
+        "Saved RDB ({}) to {}",
+        format_bytes(written),
+        path
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use colored::*;
+use log::info;
+use tabled::{Table, Tabled};
+
+use solt::cli::{BulkOperation, TtlPolicy};
+use solt::commands::bulk;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::app::dry_run;
+use crate::output::{progress_bar, success};
+
+/// Source → destination pairs listed before asking for --confirm
+const PREVIEW_KEYS: usize = 10;
+
+pub struct CopyOptions {
+    pub to_prefix: String,
+    pub ttl: TtlPolicy,
+    pub replace: bool,
+    pub confirm: bool,
+    pub dry_run: bool,
+}
+
+#[derive(Tabled)]
+struct SummaryRow {
+    #[tabled(rename = "Type")]
+    key_type: String,
+    #[tabled(rename = "Copied")]
+    copied: usize,
+    #[tabled(rename = "Skipped")]
+    skipped: usize,
+    #[tabled(rename = "Failed")]
+    failed: usize,
+}
+
+/// Delete, rename and dump by pattern are not implemented yet, so they are
+/// refused rather than reported as done (or as a dry run of nothing).
+pub fn run(operation: BulkOperation) -> Result<(), AppError> {
+    let name = match operation {
+        BulkOperation::Delete => "delete",
+        BulkOperation::Rename => "rename",
+        BulkOperation::Copy => "copy",
+        BulkOperation::Dump => "dump",
+    };
+    info!("Bulk {} is not implemented", name);
+    Err(AppError::ConfigError(format!(
+        "bulk {} is not implemented yet; only bulk copy is available",
+        name
+    )))
+}
+
+/// Copy every key matching `pattern` to a new name under `to_prefix` with
+/// DUMP/RESTORE, so values of every type arrive intact. The literal part of
+/// the pattern before its first wildcard is the prefix that gets replaced.
+pub async fn copy(
+    pattern: String,
+    options: CopyOptions,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    info!(
+        "Bulk copying '{}' to prefix '{}'",
+        pattern, options.to_prefix
+    );
+
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let keys = client.scan_keys(&pattern).await?;
+    if keys.is_empty() {
+        println!(
+            "{}",
+            format!("No keys found matching pattern '{}'", pattern).yellow()
+        );
+        return Ok(());
+    }
+
+    let destination = |key: &str| bulk::destination(key, &pattern, &options.to_prefix);
+
+    println!(
+        "{}",
+        format!(
+            "Found {} keys matching pattern '{}' to copy to '{}'",
+            keys.len(),
+            pattern,
+            options.to_prefix
+        )
+        .cyan()
+        .bold()
+    );
+
+    if options.dry_run {
+        let existing =
+            bulk::existing_targets(&mut client, &keys, &pattern, &options.to_prefix).await?;
+        dry_run::report(
+            &mut client,
+            &keys,
+            &format!("copying keys to '{}'", options.to_prefix),
+        )
+        .await?;
+        if existing > 0 {
+            let outcome = if options.replace {
+                "overwritten"
+            } else {
+                "skipped"
+            };
+            println!(
+                "{}",
+                format!(
+                    "{} destination keys already exist and would be {}",
+                    existing, outcome
+                )
+                .yellow()
+            );
+        }
+        return Ok(());
+    }
+
+    if !options.confirm {
+        println!("{}", "Keys to be copied:".yellow());
+        for key in keys.iter().take(PREVIEW_KEYS) {
+            println!("  • {} → {}", key, destination(key));
+        }
+        if keys.len() > PREVIEW_KEYS {
+            println!("  ... and {} more", keys.len() - PREVIEW_KEYS);
+        }
+        println!("{}", "Use --confirm to proceed with the copy".red().bold());
+        return Ok(());
+    }
+
+    let progress = progress_bar(keys.len() as u64);
+    progress.set_message("Copying keys...");
+
+    let report = bulk::copy(
+        &mut client,
+        &keys,
+        &pattern,
+        &options.to_prefix,
+        &options.ttl,
+        options.replace,
+        || progress.inc(1),
+    )
+    .await?;
+    progress.finish_and_clear();
+
+    for (key, target, error) in &report.errors {
+        println!(
+            "{}",
+            format!("Error copying '{}' to '{}': {}", key, target, error).red()
+        );
+    }
+
+    let totals = report.totals();
+    let rows: Vec<SummaryRow> = report
+        .by_type
+        .into_iter()
+        .map(|(key_type, stats)| SummaryRow {
+            key_type,
+            copied: stats.copied,
+            skipped: stats.skipped,
+            failed: stats.failed,
+        })
+        .collect();
+
+    println!("{}", "Copy Summary:".bold());
+    println!("{}", Table::new(rows));
+
+    if totals.failed == 0 {
+        success(&format!("Copied {} keys", totals.copied));
+    }
+    if totals.skipped > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} destination keys already existed (use --replace to overwrite)",
+                totals.skipped
+            )
+            .yellow()
+        );
+    }
+    if totals.failed > 0 {
+        return Err(AppError::Aborted(format!(
+            "{} keys failed to copy",
+            totals.failed
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use colored::*;
+use log::info;
+use std::time::Instant;
+
+use solt::commands::clone_db;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{progress_bar, success};
+
+pub struct CloneOptions {
+    pub source_db: Option<u8>,
+    pub dest_db: Option<u8>,
+    pub flush_dest: bool,
+    pub replace: bool,
+    /// Keys per second, 0 for unlimited
+    pub rate: u64,
+    pub verify: bool,
+}
+
+/// Copy every key of one database to another with DUMP/RESTORE, keeping
+/// types and TTLs, then compare the two databases key by key.
+pub async fn run(source: String, dest: String, options: CloneOptions) -> Result<(), AppError> {
+    info!("Cloning database from '{}' to '{}'", source, dest);
+
+    let config = AppConfig::load()?;
+    let mut source_config = config
+        .get_environment(&source)
+        .ok_or_else(|| AppError::ConfigError(format!("Source environment '{}' not found", source)))?
+        .config
+        .clone();
+    let mut dest_config = config
+        .get_environment(&dest)
+        .ok_or_else(|| {
+            AppError::ConfigError(format!("Destination environment '{}' not found", dest))
+        })?
+        .config
+        .clone();
+    if let Some(db) = options.source_db {
+        source_config.db = db;
+    }
+    if let Some(db) = options.dest_db {
+        dest_config.db = db;
+    }
+
+    if source_config.host == dest_config.host
+        && source_config.port == dest_config.port
+        && source_config.db == dest_config.db
+    {
+        return Err(AppError::ConfigError(
+            "Source and destination are the same database".to_string(),
+        ));
+    }
+
+    let source_label = format!("{} db {}", source, source_config.db);
+    let dest_label = format!("{} db {}", dest, dest_config.db);
+
+    let mut source_client = RedisClient::connect(source_config).await?;
+    let mut dest_client = RedisClient::connect(dest_config).await?;
+
+    let total = source_client.dbsize().await?;
+    println!(
+        "{}",
+        format!(
+            "Cloning {} keys from '{}' to '{}'",
+            total, source_label, dest_label
+        )
+        .cyan()
+        .bold()
+    );
+
+    if options.flush_dest {
+        let flushed = clone_db::flush(&mut dest_client).await?;
+        println!(
+            "{}",
+            format!("Flushed {} keys from '{}'", flushed, dest_label).yellow()
+        );
+    }
+
+    // DBSIZE is only an estimate of what SCAN will visit, so the bar can
+    // finish slightly early or late if keys change during the clone
+    let progress = progress_bar(total);
+    let started = Instant::now();
+    let counts = clone_db::run(
+        &mut source_client,
+        &mut dest_client,
+        options.replace,
+        options.rate,
+        |done| {
+            progress.inc(1);
+            let elapsed = started.elapsed().as_secs_f64().max(0.001);
+            progress.set_message(format!("{:.0} keys/sec", done as f64 / elapsed));
+        },
+    )
+    .await?;
+    progress.finish_and_clear();
+
+    for (key, error) in &counts.errors {
+        println!("{}", format!("Error cloning '{}': {}", key, error).red());
+    }
+
+    let elapsed = started.elapsed().as_secs_f64();
+    println!("{}", "Clone Summary:".bold());
+    println!("  Copied:  {}", counts.copied.to_string().green());
+    println!("  Skipped: {}", counts.skipped.to_string().yellow());
+    println!("  Failed:  {}", counts.errors.len().to_string().red());
+    println!(
+        "  Time:    {:.1}s ({:.0} keys/sec)",
+        elapsed,
+        counts.copied as f64 / elapsed.max(0.001)
+    );
+    if counts.skipped > 0 {
+        println!(
+            "{}",
+            "Existing destination keys were kept (use --replace or --flush-dest)".yellow()
+        );
+    }
+
+    if options.verify {
+        verify(&mut source_client, &mut dest_client).await?;
+    }
+    if !counts.errors.is_empty() {
+        return Err(AppError::Aborted(format!(
+            "{} keys could not be cloned",
+            counts.errors.len()
+        )));
+    }
+    if !options.verify {
+        success(&format!("Cloned {} keys", counts.copied));
+    }
+
+    Ok(())
+}
+
+/// Compare the two databases and fail with `AppError::Unhealthy` when any
+/// key is missing or different on the destination.
+async fn verify(
+    source_client: &mut RedisClient,
+    dest_client: &mut RedisClient,
+) -> Result<(), AppError> {
+    let progress = progress_bar(source_client.dbsize().await?);
+    progress.set_message("Verifying...");
+    let verification = clone_db::verify(source_client, dest_client, || progress.inc(1)).await?;
+    progress.finish_and_clear();
+
+    if verification.passed() {
+        success(&format!("Verified {} keys", verification.checked));
+        return Ok(());
+    }
+
+    for key in verification.missing.iter().take(10) {
+        println!("  missing:   {}", key);
+    }
+    for key in verification.different.iter().take(10) {
+        println!("  different: {}", key);
+    }
+
+    Err(AppError::Unhealthy(format!(
+        "Verification failed: {} of {} keys missing and {} different on the destination",
+        verification.missing.len(),
+        verification.checked,
+        verification.different.len()
+    )))
+}
@@ -0,0 +1,285 @@
+use colored::*;
+use log::info;
+use std::time::Instant;
+use tabled::{Table, Tabled};
+
+use solt::commands::cluster::{self, ReshardOptions, ReshardPlan, SlotProgress};
+use solt::config::{AppConfig, RedisConfig};
+use solt::error::AppError;
+use solt::format::format_bytes;
+use solt::redis_client::RedisClient;
+
+use crate::app::dry_run;
+use crate::output::{print_structured, progress_bar, rule, success};
+
+pub async fn run() -> Result<(), AppError> {
+    info!("Cluster command - placeholder");
+    println!("{}", "Cluster command - not yet implemented".yellow());
+    Ok(())
+}
+
+#[derive(Tabled)]
+struct NodeRow {
+    #[tabled(rename = "Node")]
+    address: String,
+    #[tabled(rename = "Role")]
+    role: String,
+    #[tabled(rename = "Slots")]
+    slots: String,
+    #[tabled(rename = "Keys")]
+    keys: String,
+    #[tabled(rename = "Memory")]
+    memory: String,
+    #[tabled(rename = "Ops/sec")]
+    ops_per_sec: String,
+}
+
+/// Connect to the environment and make sure it is a Redis Cluster. Returns
+/// the environment's settings too, for connecting to the other nodes.
+async fn connect(
+    config: &AppConfig,
+    environment: Option<String>,
+) -> Result<(RedisClient, RedisConfig), AppError> {
+    let env_name = config.resolve_environment(environment.as_deref());
+    let redis_config = config.redis_config(&env_name)?;
+    let mut client = RedisClient::connect(redis_config.clone()).await?;
+    cluster::ensure_cluster(&mut client, &env_name).await?;
+    Ok((client, redis_config))
+}
+
+/// Show memory, keys, throughput and role per node, with totals.
+pub async fn stats(environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let (mut client, redis_config) = connect(&config, environment).await?;
+    let report = cluster::stats(&mut client, &redis_config).await?;
+    if print_structured(&config.output_format, &report)? {
+        return Ok(());
+    }
+
+    println!("{}", "Cluster Statistics:".bold());
+    rule(50);
+
+    let unknown = || "?".to_string();
+    let mut rows: Vec<NodeRow> = report
+        .nodes
+        .iter()
+        .map(|node| NodeRow {
+            address: match &node.error {
+                Some(_) => node.address.red().to_string(),
+                None => node.address.clone(),
+            },
+            role: if node.failing {
+                format!("{} (fail)", node.role).red().to_string()
+            } else {
+                node.role.clone()
+            },
+            slots: if node.role == "master" {
+                node.slots.to_string()
+            } else {
+                "-".to_string()
+            },
+            keys: node.keys.map(|k| k.to_string()).unwrap_or_else(unknown),
+            memory: node.memory.map(format_bytes).unwrap_or_else(unknown),
+            ops_per_sec: node
+                .ops_per_sec
+                .map(|o| o.to_string())
+                .unwrap_or_else(unknown),
+        })
+        .collect();
+    rows.push(NodeRow {
+        address: "Total".bold().to_string(),
+        role: format!("{}M / {}R", report.total.masters, report.total.replicas),
+        slots: report
+            .nodes
+            .iter()
+            .filter(|node| node.role == "master")
+            .map(|node| node.slots)
+            .sum::<usize>()
+            .to_string(),
+        keys: report.total.keys.to_string(),
+        memory: format_bytes(report.total.memory),
+        ops_per_sec: report.total.ops_per_sec.to_string(),
+    });
+    println!("{}", Table::new(rows));
+
+    for node in report.nodes.iter().filter(|node| node.error.is_some()) {
+        println!(
+            "{}",
+            format!(
+                "✗ {}: {}",
+                node.address,
+                node.error.as_deref().unwrap_or_default()
+            )
+            .red()
+        );
+    }
+
+    Ok(())
+}
+
+/// Show which slot `key` hashes to, the node that owns it and whether the
+/// slot is being moved.
+pub async fn locate(key: String, environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let (mut client, _) = connect(&config, environment).await?;
+    let location = cluster::locate(&mut client, key).await?;
+    if print_structured(&config.output_format, &location)? {
+        return Ok(());
+    }
+
+    println!("{}", "Key Location:".bold());
+    rule(50);
+    println!("Key: {}", location.key.cyan());
+    println!("Slot: {}", location.slot.to_string().cyan());
+    match &location.hash_tag {
+        Some(tag) => println!(
+            "Hash tag: {} (keys sharing it map to the same slot)",
+            format!("{{{}}}", tag).cyan()
+        ),
+        None => println!("Hash tag: {}", "none (whole key is hashed)".dimmed()),
+    }
+    match &location.owner {
+        Some(owner) => println!("Owner: {}", owner.green().bold()),
+        None => println!("{}", "Owner: no node serves this slot".red().bold()),
+    }
+    if !location.replicas.is_empty() {
+        println!("Replicas: {}", location.replicas.join(", ").cyan());
+    }
+    if location.migrating_to.is_some() || location.importing_into.is_some() {
+        println!(
+            "{}",
+            format!(
+                "⚠ Slot is migrating: {} -> {} (expect ASK redirects)",
+                location.owner.as_deref().unwrap_or("?"),
+                location
+                    .migrating_to
+                    .as_deref()
+                    .or(location.importing_into.as_deref())
+                    .unwrap_or("?")
+            )
+            .yellow()
+            .bold()
+        );
+    } else {
+        println!("Migration: {}", "none".green());
+    }
+
+    Ok(())
+}
+
+/// Validate the cluster, failing with `AppError::Unhealthy` when any check
+/// does not pass.
+pub async fn check(environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let (mut client, redis_config) = connect(&config, environment).await?;
+    let report = cluster::check(&mut client, &redis_config).await?;
+
+    if !print_structured(&config.output_format, &report)? {
+        println!("{}", "Cluster Check:".bold());
+        rule(50);
+        println!(
+            "Nodes: {} ({} masters)",
+            report.nodes.to_string().cyan(),
+            report.masters.to_string().cyan()
+        );
+        for check in &report.checks {
+            if check.ok {
+                println!("{} {}", "PASS".green().bold(), check.name);
+            } else {
+                println!("{} {}", "FAIL".red().bold(), check.name);
+                for problem in &check.problems {
+                    println!("     {}", problem.red());
+                }
+            }
+        }
+        if report.passed {
+            success("All checks passed");
+        }
+    }
+
+    if report.passed {
+        return Ok(());
+    }
+    let failed: Vec<&str> = report
+        .checks
+        .iter()
+        .filter(|check| !check.ok)
+        .map(|check| check.name)
+        .collect();
+    Err(AppError::Unhealthy(format!(
+        "Cluster check failed: {}",
+        failed.join(", ")
+    )))
+}
+
+/// Move slots between two masters, finishing slots left open by an
+/// interrupted run first.
+pub async fn reshard(options: ReshardOptions, environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let (mut client, redis_config) = connect(&config, environment).await?;
+    let mut plan = ReshardPlan::new(&mut client, &redis_config, &options).await?;
+
+    println!(
+        "{}",
+        format!(
+            "Moving {} slots from {} to {}",
+            plan.slots.len(),
+            plan.source_address,
+            plan.target_address
+        )
+        .cyan()
+        .bold()
+    );
+    if !plan.open.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "Resuming {} slots left open by an earlier run",
+                plan.open.len()
+            )
+            .yellow()
+        );
+    }
+    if plan.slots.is_empty() {
+        println!("{}", "Nothing to move".yellow());
+        return Ok(());
+    }
+
+    if options.dry_run {
+        let keys = plan.count_keys().await?;
+        println!("Slots: {}", plan.slot_ranges().join(", "));
+        println!("Keys to migrate: {}", keys.to_string().cyan());
+        dry_run::print_no_changes();
+        return Ok(());
+    }
+
+    let slots = plan.slots.len();
+    let source_address = plan.source_address.clone();
+    let target_address = plan.target_address.clone();
+    let progress = progress_bar(slots as u64);
+    let started = Instant::now();
+    let moved_keys = plan
+        .execute(
+            &redis_config,
+            options.batch,
+            options.timeout_ms,
+            |event| match event {
+                SlotProgress::Started(slot) => progress.set_message(format!("slot {}", slot)),
+                SlotProgress::Moved(_) => progress.inc(1),
+            },
+        )
+        .await
+        .inspect_err(|_| progress.abandon())?;
+    progress.finish_and_clear();
+
+    success(&format!(
+        "Moved {} slots ({} keys) from {} to {} in {:.1}s",
+        slots,
+        moved_keys,
+        source_address,
+        target_address,
+        started.elapsed().as_secs_f64()
+    ));
+
+    Ok(())
+}
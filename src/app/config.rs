@@ -0,0 +1,371 @@
+use colored::*;
+use tabled::builder::Builder;
+use tabled::{Table, Tabled};
+
+use solt::commands::config::{self as env_config, Annotations, ConfigDiff};
+use solt::config::{AppConfig, Environment, OutputFormat, RedisConfig};
+use solt::error::AppError;
+
+use crate::output::{print_structured, rule, success};
+
+#[derive(Tabled)]
+struct EnvironmentRow {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Host")]
+    host: String,
+    #[tabled(rename = "Port")]
+    port: String,
+    #[tabled(rename = "Database")]
+    db: String,
+    #[tabled(rename = "TLS")]
+    tls: String,
+    #[tabled(rename = "Owner")]
+    owner: String,
+    #[tabled(rename = "Safety")]
+    safety: String,
+    #[tabled(rename = "Default")]
+    default: String,
+}
+
+pub async fn run() -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+
+    // Never print passwords in machine-readable output
+    if print_structured(&config.output_format, &env_config::redacted(&config))? {
+        return Ok(());
+    }
+
+    println!("{}", "Current Configuration:".bold());
+    rule(50);
+
+    // Show default environment
+    if let Some(default_env) = &config.default_environment {
+        println!("Default Environment: {}", default_env.cyan().bold());
+    }
+
+    // Show output format
+    let format_str = match config.output_format {
+        OutputFormat::Json => "JSON",
+        OutputFormat::Table => "Table",
+        OutputFormat::Csv => "CSV",
+        OutputFormat::Plain => "Plain",
+        OutputFormat::Yaml => "YAML",
+    };
+    println!("Output Format: {}", format_str.cyan());
+    println!("History Size: {}", config.history_size.to_string().cyan());
+
+    // Show environments
+    println!("\n{}", "Environments:".bold());
+    rule(50);
+
+    let mut rows = Vec::new();
+    for (name, env) in &config.environments {
+        let is_default = config
+            .default_environment
+            .as_ref()
+            .is_some_and(|d| d == name);
+        rows.push(EnvironmentRow {
+            name: name.clone(),
+            host: env.config.host.clone(),
+            port: env.config.port.to_string(),
+            db: env.config.db.to_string(),
+            tls: if env.config.tls {
+                "Yes".green().to_string()
+            } else {
+                "No".red().to_string()
+            },
+            owner: env.owner.clone().unwrap_or_default(),
+            safety: [
+                env.read_only.then_some("read-only"),
+                env.protected.then_some("protected"),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(", ")
+            .yellow()
+            .to_string(),
+            default: if is_default {
+                "✓".green().to_string()
+            } else {
+                "".to_string()
+            },
+        });
+    }
+
+    let table = Table::new(rows).to_string();
+    println!("{}", table);
+
+    // Show environment annotations
+    let mut annotated: Vec<&Environment> = config
+        .environments
+        .values()
+        .filter(|env| env.has_annotations())
+        .collect();
+    if !annotated.is_empty() {
+        annotated.sort_by(|a, b| a.name.cmp(&b.name));
+        println!("\n{}", "Environment Notes:".bold());
+        rule(50);
+        for env in annotated {
+            println!("{}", env.name.cyan().bold());
+            print_annotations(env);
+        }
+    }
+
+    // Show favorites
+    if !config.favorites.is_empty() {
+        println!("\n{}", "Favorites:".bold());
+        rule(50);
+        for favorite in &config.favorites {
+            println!("• {}", favorite.cyan());
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn add_environment(
+    name: String,
+    host: String,
+    port: u16,
+    password: Option<String>,
+    db: u8,
+    timeout: Option<u64>,
+    tls: bool,
+) -> Result<(), AppError> {
+    let redis_config = RedisConfig {
+        host,
+        port,
+        password,
+        db,
+        timeout,
+        tls,
+    };
+    env_config::add_environment(&name, redis_config)?;
+    success(&format!("Environment '{}' added successfully!", name));
+    Ok(())
+}
+
+pub async fn remove_environment(name: &str) -> Result<(), AppError> {
+    env_config::remove_environment(name)?;
+    success(&format!("Environment '{}' removed successfully!", name));
+    Ok(())
+}
+
+pub async fn set_default_environment(name: &str) -> Result<(), AppError> {
+    env_config::set_default_environment(name)?;
+    success(&format!("Default environment set to '{}'", name));
+    Ok(())
+}
+
+pub async fn set_output_format(format: OutputFormat) -> Result<(), AppError> {
+    let format_str = match format {
+        OutputFormat::Json => "JSON",
+        OutputFormat::Table => "Table",
+        OutputFormat::Csv => "CSV",
+        OutputFormat::Plain => "Plain",
+        OutputFormat::Yaml => "YAML",
+    };
+
+    env_config::set_output_format(format)?;
+    success(&format!("Output format set to {}", format_str));
+    Ok(())
+}
+
+pub async fn set_history_size(size: usize) -> Result<(), AppError> {
+    env_config::set_history_size(size)?;
+    success(&format!("History size set to {}", size));
+    Ok(())
+}
+
+pub async fn set_protected(name: &str, protected: bool) -> Result<(), AppError> {
+    env_config::set_protected(name, protected)?;
+
+    if protected {
+        success(&format!(
+            "Environment '{}' is protected: destructive commands require typing its name",
+            name
+        ));
+    } else {
+        success(&format!("Environment '{}' is no longer protected", name));
+    }
+    Ok(())
+}
+
+pub async fn set_read_only(name: &str, read_only: bool) -> Result<(), AppError> {
+    env_config::set_read_only(name, read_only)?;
+
+    if read_only {
+        success(&format!(
+            "Environment '{}' is read-only: write commands will be refused",
+            name
+        ));
+    } else {
+        success(&format!("Environment '{}' accepts writes again", name));
+    }
+    Ok(())
+}
+
+/// Print the operator annotations of an environment, one per line.
+pub fn print_annotations(env: &Environment) {
+    if let Some(description) = &env.description {
+        println!("  Description: {}", description);
+    }
+    if let Some(owner) = &env.owner {
+        println!("  Owner: {}", owner.yellow());
+    }
+    if let Some(dashboard) = &env.dashboard_url {
+        println!("  Dashboard: {}", dashboard.blue().underline());
+    }
+    if let Some(runbook) = &env.runbook_url {
+        println!("  Runbook: {}", runbook.blue().underline());
+    }
+}
+
+pub async fn annotate_environment(
+    name: &str,
+    description: Option<String>,
+    owner: Option<String>,
+    dashboard_url: Option<String>,
+    runbook_url: Option<String>,
+    banner_color: Option<String>,
+) -> Result<(), AppError> {
+    let annotations = Annotations {
+        description,
+        owner,
+        dashboard_url,
+        runbook_url,
+        banner_color,
+    };
+    let env = env_config::annotate_environment(name, annotations)?;
+
+    success(&format!("Environment '{}' annotated successfully!", name));
+    print_annotations(&env);
+    Ok(())
+}
+
+/// Export environments for teammates, noting any redacted passwords.
+pub async fn export(
+    path: String,
+    environments: Vec<String>,
+    include_passwords: bool,
+) -> Result<(), AppError> {
+    let summary = env_config::export(&path, &environments, include_passwords)?;
+
+    success(&format!(
+        "Exported {} environments to '{}'",
+        summary.environments, path
+    ));
+    if summary.redacted > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} passwords were redacted (use --include-passwords to keep them)",
+                summary.redacted
+            )
+            .yellow()
+        );
+    } else if include_passwords {
+        println!(
+            "{}",
+            "⚠ The file contains passwords; share it with care".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Import exported environments and list what was added, updated and
+/// removed.
+pub async fn import(path: String, merge: bool) -> Result<(), AppError> {
+    let summary = env_config::import(&path, merge)?;
+
+    success(&format!(
+        "Imported {} environments from '{}'",
+        summary.added.len() + summary.updated.len(),
+        path
+    ));
+    for (label, names) in [
+        ("Added", &summary.added),
+        ("Updated", &summary.updated),
+        ("Removed", &summary.removed),
+    ] {
+        if !names.is_empty() {
+            println!("{}: {}", label, names.join(", ").cyan());
+        }
+    }
+    if !summary.need_password.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "⚠ Passwords were redacted for {}; add them in {}",
+                summary.need_password.join(", "),
+                AppConfig::config_path()?.display()
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Show the settings of two environments side by side, highlighting the
+/// ones that differ.
+pub async fn diff(left: String, right: String, all: bool) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let ConfigDiff {
+        settings,
+        left_error,
+        right_error,
+    } = env_config::diff(&left, &right, all).await?;
+
+    if print_structured(&config.output_format, &settings)? {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Configuration: '{}' vs '{}'", left, right).bold()
+    );
+    rule(50);
+    for (env, error) in [(&left, &left_error), (&right, &right_error)] {
+        if let Some(e) = error {
+            println!(
+                "{}",
+                format!("⚠ Server settings of '{}' unavailable: {}", env, e).yellow()
+            );
+        }
+    }
+
+    let mut builder = Builder::default();
+    builder.push_record(["Setting", left.as_str(), right.as_str()]);
+    for setting in &settings {
+        if setting.differs {
+            builder.push_record([
+                setting.setting.yellow().bold().to_string(),
+                setting.left.red().to_string(),
+                setting.right.red().to_string(),
+            ]);
+        } else {
+            builder.push_record([
+                setting.setting.clone(),
+                setting.left.clone(),
+                setting.right.clone(),
+            ]);
+        }
+    }
+    println!("{}", builder.build());
+
+    let differing = settings.iter().filter(|setting| setting.differs).count();
+    if differing == 0 {
+        println!("{}", "✓ No differences".green());
+    } else {
+        println!(
+            "{}",
+            format!("{} settings differ", differing).yellow().bold()
+        );
+    }
+
+    Ok(())
+}
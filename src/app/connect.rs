@@ -0,0 +1,172 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::connect::{self, ConnectOptions};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::app::config::print_annotations;
+use crate::output::{print_structured, rule, spinner, success};
+
+#[derive(Tabled)]
+struct StatusRow {
+    #[tabled(rename = "Environment")]
+    environment: String,
+    #[tabled(rename = "Address")]
+    address: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Latency")]
+    latency: String,
+    #[tabled(rename = "Version")]
+    version: String,
+}
+
+pub async fn run(
+    host: Option<String>,
+    port: Option<u16>,
+    password: Option<String>,
+    db: Option<u8>,
+    environment: Option<String>,
+    timeout: Option<u64>,
+    tls: bool,
+) -> Result<(), AppError> {
+    let mut config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let options = ConnectOptions {
+        host,
+        port,
+        password,
+        db,
+        timeout,
+        tls,
+    };
+    let redis_config = connect::resolve(&config, &env_name, &options);
+
+    println!("{}", "Connecting to Redis...".yellow());
+    if let Some(env) = config.get_environment(&env_name) {
+        println!("Environment: {}", env.name.cyan().bold());
+        print_annotations(env);
+    }
+    println!("Host: {}", redis_config.host.cyan());
+    println!("Port: {}", redis_config.port.to_string().cyan());
+    println!("Database: {}", redis_config.db.to_string().cyan());
+    println!(
+        "TLS: {}",
+        if redis_config.tls {
+            "Yes".green()
+        } else {
+            "No".red()
+        }
+    );
+
+    // Test connection
+    let test_config = redis_config.clone();
+    match RedisClient::connect(test_config).await {
+        Ok(mut client) => {
+            success("Connected successfully!");
+
+            // Test ping
+            match client.ping().await {
+                Ok(response) => println!("Ping: {}", response.green()),
+                Err(e) => println!("{}", format!("Ping failed: {}", e).red()),
+            }
+
+            // Show INFO
+            match connect::server_summary(&mut client).await {
+                Ok(fields) => {
+                    println!("\n{}", "Redis Server Information:".bold());
+                    rule(50);
+                    for (field, value) in fields {
+                        println!("{}: {}", field.cyan(), value.yellow());
+                    }
+                }
+                Err(e) => println!("{}", format!("Failed to get INFO: {}", e).red()),
+            }
+
+            // Save environment if it's new
+            if connect::remember(&mut config, &env_name, redis_config)? {
+                println!("{}", "Environment saved to config".green());
+            }
+
+            Ok(())
+        }
+        Err(e) => {
+            println!("{}", format!("✗ Connection failed: {}", e).red().bold());
+            Err(AppError::ConnectionError(e.to_string()))
+        }
+    }
+}
+
+pub async fn test_connection(environment: &str) -> Result<(), AppError> {
+    let redis_config = AppConfig::load()?.redis_config(environment)?;
+
+    match RedisClient::connect(redis_config).await {
+        Ok(mut client) => match client.ping().await {
+            Ok(_) => {
+                success("Connection test successful!");
+                Ok(())
+            }
+            Err(e) => {
+                println!(
+                    "{}",
+                    format!("✗ Connection test failed: {}", e).red().bold()
+                );
+                Err(AppError::ConnectionError(e.to_string()))
+            }
+        },
+        Err(e) => {
+            println!("{}", format!("✗ Connection failed: {}", e).red().bold());
+            Err(AppError::ConnectionError(e.to_string()))
+        }
+    }
+}
+
+/// PING every configured environment at once and print a status table.
+/// Fails with the connection exit code when any environment is unreachable,
+/// so it can gate scripts after VPN or credential changes.
+pub async fn test_all(timeout: Option<u64>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+
+    let progress = spinner();
+    progress.set_message(format!(
+        "Testing {} environments...",
+        config.environments.len()
+    ));
+    let statuses = connect::test_all(&config, timeout).await;
+    progress.finish_and_clear();
+
+    if !print_structured(&config.output_format, &statuses)? {
+        let rows: Vec<StatusRow> = statuses
+            .iter()
+            .map(|status| StatusRow {
+                environment: status.environment.clone(),
+                address: status.address.clone(),
+                status: match &status.error {
+                    None => "✓ reachable".green().to_string(),
+                    Some(error) => format!("✗ {}", error).red().to_string(),
+                },
+                latency: status
+                    .latency_ms
+                    .map(|ms| format!("{:.1}ms", ms))
+                    .unwrap_or_else(|| "-".to_string()),
+                version: status.version.clone().unwrap_or_else(|| "-".to_string()),
+            })
+            .collect();
+        println!("{}", "Environment Connectivity:".bold());
+        println!("{}", Table::new(rows));
+    }
+
+    let unreachable = statuses.iter().filter(|status| !status.reachable).count();
+    if unreachable > 0 {
+        return Err(AppError::ConnectionError(format!(
+            "{} of {} environments unreachable",
+            unreachable,
+            statuses.len()
+        )));
+    }
+    success(&format!("All {} environments reachable", statuses.len()));
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use colored::*;
+use std::io::{self, Write};
+
+use solt::cli::CollectionType;
+use solt::commands::convert;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+pub async fn run(
+    key: String,
+    to: CollectionType,
+    yes: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let conversion = convert::plan(&mut client, &key, to).await?;
+    let target = to.as_str();
+
+    if conversion.is_noop() {
+        println!(
+            "{}",
+            format!("Key '{}' is already a {}", key, target).yellow()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Convert '{}' from {} ({} elements) to {} ({} elements)",
+            key,
+            conversion.from,
+            conversion.elements.len(),
+            target,
+            conversion.unique
+        )
+        .cyan()
+        .bold()
+    );
+    if conversion.unique < conversion.elements.len() {
+        println!(
+            "{}",
+            format!(
+                "{} duplicate elements will be dropped",
+                conversion.elements.len() - conversion.unique
+            )
+            .yellow()
+        );
+    }
+
+    if !yes {
+        print!("Proceed? [y/N]: ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("{}", "Conversion cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    let backup_key = convert::apply(&mut client, &key, &conversion).await?;
+
+    success(&format!("Converted '{}' to {}", key, target));
+    println!("Original saved as: {}", backup_key.cyan());
+    println!(
+        "{}",
+        format!("Remove it once verified: solt delete {}", backup_key).cyan()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,402 @@
+use colored::*;
+use log::info;
+
+use solt::commands::copy::{self, CopyEvent, CopyFailures};
+use solt::config::{AppConfig, RedisConfig};
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::guard;
+use crate::output::success;
+
+/// Where `copy_key` writes, with environments already defaulted to
+/// `--environment` by the caller (`None` means the default environment).
+pub struct CopyTarget {
+    pub source_env: Option<String>,
+    pub dest_env: Option<String>,
+    pub destination: Option<String>,
+    /// Another database of the source instance, instead of `dest_env`
+    pub to_db: Option<u8>,
+    pub replace: bool,
+    /// Delete the source key after a successful copy
+    pub move_key: bool,
+}
+
+/// Copy one key, of any type, without prompting. The value moves with
+/// DUMP/RESTORE (or COPY within an instance) and keeps its TTL; the
+/// destination may be another key, another environment or database, or both.
+pub async fn copy_key(source: String, target: CopyTarget) -> Result<(), AppError> {
+    info!("Copying key '{}'", source);
+
+    let config = AppConfig::load()?;
+    let source_env = config.resolve_environment(target.source_env.as_deref());
+    let destination = target.destination.unwrap_or_else(|| source.clone());
+
+    let source_config = config
+        .get_environment(&source_env)
+        .ok_or_else(|| {
+            AppError::ConfigError(format!("Source environment '{}' not found", source_env))
+        })?
+        .config
+        .clone();
+    let mut source_client = RedisClient::connect(source_config.clone()).await?;
+
+    let (from, to) = if let Some(db) = target.to_db {
+        if db == source_config.db && source == destination {
+            return Err(AppError::ConfigError(
+                "Source and destination are the same key; give a destination key or another --to-db"
+                    .to_string(),
+            ));
+        }
+        copy::to_db(
+            &mut source_client,
+            &source_config,
+            &source,
+            &destination,
+            db,
+            target.replace,
+        )
+        .await?;
+        (
+            format!("{} db {}", source_env, source_config.db),
+            format!("{} db {}", source_env, db),
+        )
+    } else {
+        let dest_env = config.resolve_environment(target.dest_env.as_deref());
+        if source_env == dest_env && source == destination {
+            return Err(AppError::ConfigError(
+                "Source and destination are the same key; give a destination key or --dest-env"
+                    .to_string(),
+            ));
+        }
+        let dest_config = config
+            .get_environment(&dest_env)
+            .ok_or_else(|| {
+                AppError::ConfigError(format!("Destination environment '{}' not found", dest_env))
+            })?
+            .config
+            .clone();
+
+        let mut dest_client = RedisClient::connect(dest_config).await?;
+        copy::to_environment(
+            &mut source_client,
+            &mut dest_client,
+            &source,
+            &destination,
+            target.replace,
+            &source_env,
+            &dest_env,
+        )
+        .await?;
+        (source_env, dest_env)
+    };
+
+    let verb = if target.move_key {
+        source_client.delete_key(&source).await?;
+        "Moved"
+    } else {
+        "Copied"
+    };
+    success(&format!(
+        "{} '{}' ({}) to '{}' ({})",
+        verb, source, from, destination, to
+    ));
+    Ok(())
+}
+
+pub async fn run(max_retries: u32) -> Result<(), AppError> {
+    info!("Copy command invoked");
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(None).await?;
+
+    println!("{}", "Copy keys between databases or environments".cyan());
+    println!("{}", "1. Copy within same environment".yellow());
+    println!("{}", "2. Copy between different environments".yellow());
+    print!("Enter choice (1/2): ");
+    use std::io::{self, Write};
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let choice = input.trim();
+
+    match choice {
+        "1" => {
+            copy_within_environment(&mut client).await?;
+        }
+        "2" => {
+            copy_between_environments(&config, max_retries).await?;
+        }
+        _ => {
+            println!("{}", "Invalid choice. Aborting.".red());
+        }
+    }
+    Ok(())
+}
+
+async fn copy_within_environment(client: &mut RedisClient) -> Result<(), AppError> {
+    println!("{}", "Copying within same environment".cyan());
+    print!("Enter source key pattern (e.g., 'user:*'): ");
+    use std::io::{self, Write};
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let source_pattern = input.trim();
+
+    print!("Enter destination prefix (e.g., 'backup:'): ");
+    io::stdout().flush().unwrap();
+    let mut input2 = String::new();
+    io::stdin().read_line(&mut input2).unwrap();
+    let dest_prefix = input2.trim();
+
+    println!(
+        "{}",
+        format!(
+            "Copying keys matching '{}' to '{}*'",
+            source_pattern, dest_prefix
+        )
+        .cyan()
+    );
+    let keys = client.keys(source_pattern).await?;
+    if keys.is_empty() {
+        println!("{}", "No keys found matching the pattern.".yellow());
+        return Ok(());
+    }
+
+    println!("{}", format!("Found {} keys to copy", keys.len()).green());
+    let mut copied = 0;
+    for key in keys {
+        let dest_key = format!("{}{}", dest_prefix, key);
+        // Get value from source key
+        if let Some(value) = client.get_string(&key).await? {
+            // Set value in destination key
+            match client.set_string(&dest_key, &value, None).await {
+                Ok(_) => {
+                    println!("{}", format!("Copied '{}' -> '{}'", key, dest_key).green());
+                    copied += 1;
+                }
+                Err(e) => {
+                    println!("{}", format!("Error copying '{}': {}", key, e).red());
+                }
+            }
+        } else {
+            println!(
+                "{}",
+                format!("Key '{}' not found or is not a string", key).yellow()
+            );
+        }
+    }
+    println!(
+        "{}",
+        format!("Copy operation completed. {} keys copied.", copied).green()
+    );
+    Ok(())
+}
+
+async fn copy_between_environments(config: &AppConfig, max_retries: u32) -> Result<(), AppError> {
+    println!("{}", "Copying between environments".cyan());
+    println!("Available environments:");
+    for name in config.environments.keys() {
+        println!("  - {}", name);
+    }
+
+    print!("Enter source environment: ");
+    use std::io::{self, Write};
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let source_env = input.trim();
+
+    print!("Enter destination environment: ");
+    io::stdout().flush().unwrap();
+    let mut input2 = String::new();
+    io::stdin().read_line(&mut input2).unwrap();
+    let dest_env = input2.trim();
+
+    let source_config = config
+        .get_environment(source_env)
+        .ok_or_else(|| {
+            AppError::ConfigError(format!("Source environment '{}' not found", source_env))
+        })?
+        .config
+        .clone();
+    let dest_config = config
+        .get_environment(dest_env)
+        .ok_or_else(|| {
+            AppError::ConfigError(format!("Destination environment '{}' not found", dest_env))
+        })?
+        .config
+        .clone();
+    guard::ensure_writable(Some(dest_env), "copy")?;
+
+    print!("Enter source key pattern (e.g., 'user:*'): ");
+    io::stdout().flush().unwrap();
+    let mut input3 = String::new();
+    io::stdin().read_line(&mut input3).unwrap();
+    let source_pattern = input3.trim();
+
+    println!(
+        "{}",
+        format!(
+            "Copying from '{}' to '{}' with pattern '{}'",
+            source_env, dest_env, source_pattern
+        )
+        .cyan()
+    );
+
+    // Connect to source environment
+    let mut source_client = RedisClient::connect(source_config.clone()).await?;
+    let keys = source_client.keys(source_pattern).await?;
+    if keys.is_empty() {
+        println!("{}", "No keys found matching the pattern.".yellow());
+        return Ok(());
+    }
+
+    println!("{}", format!("Found {} keys to copy", keys.len()).green());
+
+    copy_keys_between(
+        source_config,
+        dest_config,
+        source_env,
+        dest_env,
+        keys,
+        max_retries,
+    )
+    .await
+}
+
+/// Re-attempt the keys recorded in a failure file written by a previous
+/// cross-environment copy.
+pub async fn retry_failed(path: &str, max_retries: u32) -> Result<(), AppError> {
+    info!("Retrying failed copy from: {}", path);
+
+    let failures = CopyFailures::load(path)?;
+
+    if failures.keys.is_empty() {
+        println!("{}", "No failed keys recorded in file.".yellow());
+        return Ok(());
+    }
+
+    let config = AppConfig::load()?;
+    let source_config = config
+        .get_environment(&failures.source_env)
+        .ok_or_else(|| {
+            AppError::ConfigError(format!(
+                "Source environment '{}' not found",
+                failures.source_env
+            ))
+        })?
+        .config
+        .clone();
+    let dest_config = config
+        .get_environment(&failures.dest_env)
+        .ok_or_else(|| {
+            AppError::ConfigError(format!(
+                "Destination environment '{}' not found",
+                failures.dest_env
+            ))
+        })?
+        .config
+        .clone();
+    guard::ensure_writable(Some(&failures.dest_env), "copy --retry-failed")?;
+
+    println!(
+        "{}",
+        format!(
+            "Retrying {} failed keys from '{}' to '{}'",
+            failures.keys.len(),
+            failures.source_env,
+            failures.dest_env
+        )
+        .cyan()
+    );
+
+    let keys = failures.keys.into_iter().map(|f| f.key).collect();
+    copy_keys_between(
+        source_config,
+        dest_config,
+        &failures.source_env,
+        &failures.dest_env,
+        keys,
+        max_retries,
+    )
+    .await
+}
+
+/// Copy keys one by one, retrying each with exponential backoff. Keys that
+/// still fail are written to a failure file that `copy --retry-failed` accepts.
+async fn copy_keys_between(
+    source_config: RedisConfig,
+    dest_config: RedisConfig,
+    source_env: &str,
+    dest_env: &str,
+    keys: Vec<String>,
+    max_retries: u32,
+) -> Result<(), AppError> {
+    let report =
+        copy::copy_keys_between(
+            source_config,
+            dest_config,
+            keys,
+            max_retries,
+            |event| match event {
+                CopyEvent::Copied(key) => println!("{}", format!("Copied '{}'", key).green()),
+                CopyEvent::Skipped(key) => println!(
+                    "{}",
+                    format!("Key '{}' not found or is not a string", key).yellow()
+                ),
+                CopyEvent::Retrying {
+                    key,
+                    error,
+                    attempt,
+                    backoff,
+                } => println!(
+                    "{}",
+                    format!(
+                        "Error copying '{}': {} (retry {}/{} in {}ms)",
+                        key,
+                        error,
+                        attempt,
+                        max_retries,
+                        backoff.as_millis()
+                    )
+                    .yellow()
+                ),
+                CopyEvent::Failed(key, error) => {
+                    println!("{}", format!("Error copying '{}': {}", key, error).red())
+                }
+            },
+        )
+        .await?;
+
+    println!(
+        "{}",
+        format!(
+            "Cross-environment copy completed. {} keys copied, {} skipped, {} failed.",
+            report.copied,
+            report.skipped,
+            report.failed.len()
+        )
+        .green()
+    );
+
+    if !report.failed.is_empty() {
+        let failed = report.failed.len();
+        let failures = CopyFailures {
+            source_env: source_env.to_string(),
+            dest_env: dest_env.to_string(),
+            keys: report.failed,
+        };
+        let path = failures.save()?;
+        println!(
+            "{}",
+            format!("Failed keys written to '{}'", path).red().bold()
+        );
+        println!(
+            "{}",
+            format!("Use 'solt copy --retry-failed {}' to re-attempt them", path).cyan()
+        );
+        return Err(AppError::Aborted(format!("{} keys failed to copy", failed)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use colored::*;
+
+use solt::commands::counter;
+use solt::error::AppError;
+use solt::format::Amount;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+pub async fn run(
+    key: String,
+    field: Option<String>,
+    by: Amount,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let value = counter::run(&mut client, &key, field.as_deref(), by).await?;
+
+    let target = match &field {
+        Some(field) => format!("field '{}' of '{}'", field, key),
+        None => format!("'{}'", key),
+    };
+    success(&format!("Changed {} by {}", target, by));
+    println!("{}", value.cyan());
+
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use colored::*;
+use std::time::Instant;
+
+use solt::commands::delete;
+use solt::error::AppError;
+use solt::format::format_bytes;
+use solt::redis_client::RedisClient;
+
+use crate::app::dry_run;
+use crate::output::{spinner, success};
+
+pub async fn run(
+    key: String,
+    environment: Option<String>,
+    dry_run: bool,
+    lazy: bool,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    if dry_run {
+        if !client.exists(&key).await? {
+            return Err(AppError::NotFound(format!("Key '{}' not found", key)));
+        }
+        return dry_run::report(&mut client, std::slice::from_ref(&key), "deleting the key").await;
+    }
+
+    delete::run(&mut client, &key, lazy).await?;
+    success(&format!("Successfully deleted key '{}'", key));
+
+    Ok(())
+}
+
+pub async fn delete_by_pattern(
+    pattern: String,
+    environment: Option<String>,
+    confirm: bool,
+    dry_run: bool,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    if confirm && !dry_run {
+        return unlink_matching(&mut client, &pattern).await;
+    }
+
+    // Preview the keys that match the pattern
+    let keys = client.scan_keys(&pattern).await?;
+
+    if keys.is_empty() {
+        println!(
+            "{}",
+            format!("No keys found matching pattern '{}'", pattern).yellow()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Found {} keys matching pattern '{}'", keys.len(), pattern)
+            .cyan()
+            .bold()
+    );
+
+    if dry_run {
+        return dry_run::report(
+            &mut client,
+            &keys,
+            &format!("deleting keys matching '{}'", pattern),
+        )
+        .await;
+    }
+
+    println!("{}", "Keys to be deleted:".yellow());
+    for key in &keys {
+        println!("  • {}", key);
+    }
+    println!("{}", "Use --confirm to proceed with deletion".red().bold());
+
+    Ok(())
+}
+
+async fn unlink_matching(client: &mut RedisClient, pattern: &str) -> Result<(), AppError> {
+    let progress = spinner();
+    let started = Instant::now();
+    let deleted = delete::unlink_matching(client, pattern, |deleted| {
+        let elapsed = started.elapsed().as_secs_f64().max(0.001);
+        progress.set_message(format!(
+            "{} keys deleted ({:.0} keys/sec)",
+            deleted,
+            deleted as f64 / elapsed
+        ));
+        progress.tick();
+    })
+    .await?;
+    progress.finish_and_clear();
+
+    if deleted == 0 {
+        println!(
+            "{}",
+            format!("No keys found matching pattern '{}'", pattern).yellow()
+        );
+        return Ok(());
+    }
+
+    let elapsed = started.elapsed().as_secs_f64();
+    success(&format!(
+        "Successfully deleted {} keys in {:.1}s ({:.0} keys/sec)",
+        deleted,
+        elapsed,
+        deleted as f64 / elapsed.max(0.001)
+    ));
+
+    Ok(())
+}
+
+pub async fn flush_db(
+    environment: Option<String>,
+    confirm: bool,
+    dry_run: bool,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    if dry_run {
+        return report_flush(&mut client, false).await;
+    }
+
+    if !confirm {
+        println!(
+            "{}",
+            "WARNING: This will delete ALL keys in the current database!"
+                .red()
+                .bold()
+        );
+        println!("{}", "Use --confirm to proceed".red());
+        return Ok(());
+    }
+
+    let result = delete::flush(&mut client, false).await?;
+    success(&format!("Database flushed: {}", result));
+
+    Ok(())
+}
+
+pub async fn flush_all(
+    environment: Option<String>,
+    confirm: bool,
+    dry_run: bool,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    if dry_run {
+        return report_flush(&mut client, true).await;
+    }
+
+    if !confirm {
+        println!(
+            "{}",
+            "WARNING: This will delete ALL keys in ALL databases!"
+                .red()
+                .bold()
+        );
+        println!("{}", "Use --confirm to proceed".red());
+        return Ok(());
+    }
+
+    let result = delete::flush(&mut client, true).await?;
+    success(&format!("All databases flushed: {}", result));
+
+    Ok(())
+}
+
+/// Dry run of FLUSHDB / FLUSHALL.
+async fn report_flush(client: &mut RedisClient, all: bool) -> Result<(), AppError> {
+    let impact = delete::flush_impact(client, all).await?;
+
+    println!(
+        "{}",
+        format!(
+            "Dry run: {} would delete {} keys",
+            if all { "FLUSHALL" } else { "FLUSHDB" },
+            impact.affected
+        )
+        .yellow()
+        .bold()
+    );
+    if all {
+        for (name, keys) in &impact.databases {
+            println!("  {}: {} keys", name, keys);
+        }
+    }
+    if let Some(memory) = impact.memory {
+        let memory = memory
+            .trim()
+            .parse::<u64>()
+            .map(format_bytes)
+            .unwrap_or_else(|_| memory.clone());
+        println!("Dataset memory (whole instance): {}", memory.cyan());
+    }
+
+    dry_run::print_no_changes();
+    Ok(())
+}
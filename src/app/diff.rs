@@ -0,0 +1,81 @@
+use colored::*;
+use log::info;
+use tabled::{Table, Tabled};
+
+use solt::commands::diff;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{rule, success};
+
+#[derive(Tabled)]
+struct DiffRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Difference")]
+    difference: String,
+    #[tabled(rename = "Source")]
+    source: String,
+    #[tabled(rename = "Destination")]
+    dest: String,
+}
+
+pub async fn run(source: String, dest: String, pattern: String) -> Result<(), AppError> {
+    info!("Diffing '{}' between '{}' and '{}'", pattern, source, dest);
+
+    let config = AppConfig::load()?;
+    let source_config = config
+        .get_environment(&source)
+        .ok_or_else(|| AppError::ConfigError(format!("Source environment '{}' not found", source)))?
+        .config
+        .clone();
+    let dest_config = config
+        .get_environment(&dest)
+        .ok_or_else(|| {
+            AppError::ConfigError(format!("Destination environment '{}' not found", dest))
+        })?
+        .config
+        .clone();
+
+    let mut source_client = RedisClient::connect(source_config).await?;
+    let mut dest_client = RedisClient::connect(dest_config).await?;
+
+    println!(
+        "{}",
+        format!(
+            "Comparing keys matching '{}' between '{}' and '{}'",
+            pattern, source, dest
+        )
+        .cyan()
+        .bold()
+    );
+
+    let report = diff::run(&mut source_client, &mut dest_client, &pattern).await?;
+
+    if report.differences.is_empty() {
+        success(&format!("{} keys are identical", report.identical));
+        return Ok(());
+    }
+
+    let rows: Vec<DiffRow> = report
+        .differences
+        .into_iter()
+        .map(|difference| DiffRow {
+            key: difference.key,
+            difference: difference.kind.as_str().to_string(),
+            source: difference.source,
+            dest: difference.dest,
+        })
+        .collect();
+    println!("{}", Table::new(rows));
+
+    println!("{}", "Summary:".bold());
+    rule(50);
+    println!("Identical:       {}", report.identical.to_string().green());
+    println!("Differing:       {}", report.differing.to_string().yellow());
+    println!("Missing in dest: {}", report.missing.to_string().red());
+    println!("Extra in dest:   {}", report.extra.to_string().red());
+
+    Ok(())
+}
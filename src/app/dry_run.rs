@@ -0,0 +1,81 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::dry_run;
+use solt::error::AppError;
+use solt::format::format_bytes;
+use solt::redis_client::RedisClient;
+
+/// Keys listed by name before the rest are summarized
+const LISTED_KEYS: usize = 20;
+
+#[derive(Tabled)]
+struct ImpactRow {
+    #[tabled(rename = "Type")]
+    key_type: String,
+    #[tabled(rename = "Keys")]
+    keys: usize,
+    #[tabled(rename = "Memory")]
+    memory: String,
+}
+
+/// Report what an operation would do to `keys` without changing anything:
+/// the keys themselves, and a per-type breakdown of counts and memory.
+pub async fn report(
+    client: &mut RedisClient,
+    keys: &[String],
+    action: &str,
+) -> Result<(), AppError> {
+    summarize(client, keys, action).await?;
+    print_no_changes();
+    Ok(())
+}
+
+/// The body of [`report`] without its closing line, for callers that have
+/// more to add before it.
+pub async fn summarize(
+    client: &mut RedisClient,
+    keys: &[String],
+    action: &str,
+) -> Result<(), AppError> {
+    let impact = dry_run::measure(client, keys).await?;
+
+    println!(
+        "{}",
+        format!(
+            "Dry run: {} would affect {} keys ({})",
+            action,
+            impact.total_keys(),
+            format_bytes(impact.total_memory())
+        )
+        .yellow()
+        .bold()
+    );
+
+    for key in keys.iter().take(LISTED_KEYS) {
+        println!("  • {}", key);
+    }
+    if keys.len() > LISTED_KEYS {
+        println!("  ... and {} more", keys.len() - LISTED_KEYS);
+    }
+
+    if !impact.by_type.is_empty() {
+        let rows: Vec<ImpactRow> = impact
+            .by_type
+            .into_iter()
+            .map(|(key_type, impact)| ImpactRow {
+                key_type,
+                keys: impact.keys,
+                memory: format_bytes(impact.memory),
+            })
+            .collect();
+        println!("{}", Table::new(rows));
+    }
+
+    Ok(())
+}
+
+/// Closing line of every dry run.
+pub fn print_no_changes() {
+    println!("{}", "No changes were made (--dry-run)".cyan());
+}
@@ -0,0 +1,358 @@
+use colored::*;
+use log::info;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+
+use solt::commands::edit::{self, Collection};
+use solt::error::AppError;
+use solt::format::is_binary;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+const CONFLICT_START: &str = "<<<<<<< your edit";
+const CONFLICT_MIDDLE: &str = "=======";
+const CONFLICT_END: &str = ">>>>>>> current value in Redis";
+
+/// What to do when the value changed while it was open in the editor
+enum Resolution {
+    Merge,
+    Overwrite,
+    Discard,
+}
+
+pub async fn run(key: String, value: Vec<u8>, environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    edit::run(&mut client, &key, &value).await?;
+    success(&format!("Updated key '{}' ({} bytes)", key, value.len()));
+
+    Ok(())
+}
+
+/// Open a value in `$VISUAL`/`$EDITOR` and write it back. The key is
+/// WATCHed while the editor is open and the write goes through MULTI/EXEC,
+/// so a concurrent update is never silently overwritten: the user is asked
+/// to merge, overwrite or discard instead. Hashes open as TOML, or JSON with
+/// `json`.
+pub async fn in_editor(
+    key: String,
+    json: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    info!("Editing key {} in an editor", key);
+
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    client.watch(&key).await?;
+    let key_type = client.key_type(&key).await?;
+    match key_type.as_str() {
+        "string" => edit_string(&mut client, &key).await,
+        "hash" | "list" | "set" | "zset" => {
+            edit_collection(&mut client, &key, &key_type, json).await
+        }
+        "none" => Err(AppError::NotFound(format!("Key '{}' not found", key))),
+        other => Err(AppError::Anyhow(anyhow::anyhow!(
+            "Key '{}' is a {}, which cannot be edited",
+            key,
+            other
+        ))),
+    }
+}
+
+async fn edit_string(client: &mut RedisClient, key: &str) -> Result<(), AppError> {
+    let mut base = client
+        .get_bytes(key)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Key '{}' not found", key)))?;
+    if is_binary(&base) {
+        return Err(AppError::Aborted(format!(
+            "Key '{}' holds binary data; edit it with --file or --stdin instead",
+            key
+        )));
+    }
+
+    let mut must_exist = true;
+    let mut draft = open_in_editor(&base)?;
+    loop {
+        if draft == base {
+            client.unwatch().await?;
+            println!("{}", format!("No changes made to '{}'", key).yellow());
+            return Ok(());
+        }
+        if has_conflict_markers(&draft) {
+            eprintln!(
+                "{}",
+                "The value still contains conflict markers; opening it again".yellow()
+            );
+            draft = open_in_editor(&draft)?;
+            continue;
+        }
+
+        match client
+            .replace_bytes_if_unchanged(key, &draft, must_exist)
+            .await?
+        {
+            Some(true) => {
+                success(&format!("Updated key '{}' ({} bytes)", key, draft.len()));
+                return Ok(());
+            }
+            Some(false) => return Err(AppError::NotFound(format!("Key '{}' not found", key))),
+            None => {}
+        }
+
+        // EXEC was aborted: someone changed the key while the editor was open
+        client.watch(key).await?;
+        let current = client.get_bytes(key).await?;
+        match &current {
+            Some(value) => println!(
+                "{}",
+                format!(
+                    "'{}' was changed while you were editing it (now {} bytes)",
+                    key,
+                    value.len()
+                )
+                .red()
+                .bold()
+            ),
+            None => println!(
+                "{}",
+                format!("'{}' was deleted while you were editing it", key)
+                    .red()
+                    .bold()
+            ),
+        }
+
+        match ask_resolution(current.is_some())? {
+            Resolution::Merge => {
+                let current = current.unwrap_or_default();
+                draft = open_in_editor(&with_conflict_markers(&draft, &current))?;
+                base = current;
+            }
+            Resolution::Overwrite => {
+                must_exist = current.is_some();
+                base = current.unwrap_or_default();
+            }
+            Resolution::Discard => {
+                client.unwatch().await?;
+                let path = write_scratch(&draft)?;
+                println!(
+                    "{}",
+                    format!(
+                        "Kept the value in Redis. Your version was saved to {}",
+                        path.display()
+                    )
+                    .yellow()
+                );
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Edit a hash, list, set or sorted set as text and apply only what
+/// changed. Hash, set and sorted set changes are field and member updates,
+/// so concurrent changes to other entries are kept; the user is only asked
+/// to resolve a conflict when the same entries changed, or for lists, when
+/// anything did.
+async fn edit_collection(
+    client: &mut RedisClient,
+    key: &str,
+    key_type: &str,
+    json: bool,
+) -> Result<(), AppError> {
+    let mut base = Collection::read(client, key, key_type).await?;
+    let original = base.render(key, json)?;
+
+    let mut edited = open_in_editor(original.as_bytes())?;
+    let draft = loop {
+        match base.parse(&String::from_utf8_lossy(&edited), json) {
+            Ok(draft) => break draft,
+            Err(e) => {
+                eprintln!("{}", e.red());
+                if !ask_edit_again()? {
+                    client.unwatch().await?;
+                    return Err(AppError::Aborted("Edit discarded".to_string()));
+                }
+                edited = open_in_editor(&edited)?;
+            }
+        }
+    };
+    if draft == base {
+        client.unwatch().await?;
+        println!("{}", format!("No changes made to '{}'", key).yellow());
+        return Ok(());
+    }
+
+    loop {
+        if edit::apply(client, key, &base, &draft).await? {
+            let changes = base.changes(&draft);
+            success(&format!(
+                "Updated '{}': {} added, {} changed, {} removed",
+                key, changes.added, changes.changed, changes.removed
+            ));
+            if draft.is_empty() {
+                println!("{}", "It is now empty, so the key was deleted".yellow());
+            }
+            return Ok(());
+        }
+
+        // EXEC was aborted: someone changed the key while the editor was open
+        client.watch(key).await?;
+        let current_type = client.key_type(key).await?;
+        if current_type != key_type && current_type != "none" {
+            client.unwatch().await?;
+            return Err(AppError::Aborted(format!(
+                "'{}' was replaced by a {} while you were editing it",
+                key, current_type
+            )));
+        }
+        let current = Collection::read(client, key, key_type).await?;
+
+        // Writing only the changed entries would recreate a deleted key
+        // with just those, so a deletion always needs the user's say
+        let deleted = current_type == "none";
+        let conflicts = edit::conflicts(&base, &current, &draft);
+        if conflicts.is_empty() && !deleted {
+            println!(
+                "{}",
+                format!(
+                    "'{}' changed while you were editing it, but not where you did; applying your changes on top",
+                    key
+                )
+                .yellow()
+            );
+            continue;
+        }
+
+        let message = if deleted {
+            format!("'{}' was deleted while you were editing it", key)
+        } else {
+            format!(
+                "'{}' was changed while you were editing it: {}",
+                key,
+                conflicts.join(", ")
+            )
+        };
+        println!("{}", message.red().bold());
+        match ask_resolution(false)? {
+            Resolution::Overwrite => {
+                // Lists are rewritten against what is there now, and a
+                // deleted key is recreated from the whole draft
+                if deleted || matches!(current, Collection::List(_)) {
+                    base = current;
+                }
+            }
+            Resolution::Merge | Resolution::Discard => {
+                client.unwatch().await?;
+                let path = write_scratch(&edited)?;
+                println!(
+                    "{}",
+                    format!(
+                        "Kept the value in Redis. Your version was saved to {}",
+                        path.display()
+                    )
+                    .yellow()
+                );
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn ask_edit_again() -> Result<bool, AppError> {
+    print!("Edit again? [Y/n]: ");
+    io::stdout().flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Ok(false);
+    }
+    Ok(!input.trim().eq_ignore_ascii_case("n"))
+}
+
+fn ask_resolution(can_merge: bool) -> Result<Resolution, AppError> {
+    loop {
+        if can_merge {
+            print!("[m]erge in editor, [o]verwrite with your version, [d]iscard your edit: ");
+        } else {
+            print!("[o]verwrite with your version, [d]iscard your edit: ");
+        }
+        io::stdout().flush()?;
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Ok(Resolution::Discard);
+        }
+        match input.trim().to_lowercase().as_str() {
+            "m" | "merge" if can_merge => return Ok(Resolution::Merge),
+            "o" | "overwrite" => return Ok(Resolution::Overwrite),
+            "d" | "discard" => return Ok(Resolution::Discard),
+            _ => {}
+        }
+    }
+}
+
+/// Write `initial` to a scratch file, open it in the user's editor and
+/// return what was saved.
+fn open_in_editor(initial: &[u8]) -> Result<Vec<u8>, AppError> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| AppError::ConfigError("$EDITOR is empty".to_string()))?;
+
+    let path = write_scratch(initial)?;
+    let status = Command::new(program).args(parts).arg(&path).status();
+    let edited = std::fs::read(&path);
+    let _ = std::fs::remove_file(&path);
+
+    let status = status
+        .map_err(|e| AppError::ConfigError(format!("Cannot run editor '{}': {}", editor, e)))?;
+    if !status.success() {
+        return Err(AppError::Aborted(format!(
+            "Editor '{}' exited with {}, nothing was written",
+            editor, status
+        )));
+    }
+    let mut edited = edited?;
+    // Most editors end the file with a newline the value never had
+    if !initial.ends_with(b"\n") && edited.ends_with(b"\n") {
+        edited.pop();
+    }
+    Ok(edited)
+}
+
+/// Write `contents` to a new file in the temp directory. It holds a value
+/// from Redis, so only the current user may read it.
+fn write_scratch(contents: &[u8]) -> io::Result<PathBuf> {
+    let path = std::env::temp_dir().join(format!("solt-edit-{}.txt", uuid::Uuid::new_v4()));
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(&path)?.write_all(contents)?;
+    Ok(path)
+}
+
+fn with_conflict_markers(draft: &[u8], current: &[u8]) -> Vec<u8> {
+    let mut merged = Vec::new();
+    for (part, marker) in [(draft, CONFLICT_START), (current, CONFLICT_MIDDLE)] {
+        merged.extend_from_slice(marker.as_bytes());
+        merged.push(b'\n');
+        merged.extend_from_slice(part);
+        if !part.ends_with(b"\n") {
+            merged.push(b'\n');
+        }
+    }
+    merged.extend_from_slice(CONFLICT_END.as_bytes());
+    merged.push(b'\n');
+    merged
+}
+
+fn has_conflict_markers(value: &[u8]) -> bool {
+    String::from_utf8_lossy(value)
+        .lines()
+        .any(|line| line == CONFLICT_START || line == CONFLICT_END)
+}
@@ -0,0 +1,50 @@
+use colored::*;
+
+use solt::commands::exists;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+/// Report which keys exist. Returns whether every key (or at least one key
+/// matching `pattern`) exists, so the caller can set the exit code.
+pub async fn run(
+    keys: Vec<String>,
+    pattern: Option<String>,
+    touch: bool,
+    environment: Option<String>,
+) -> Result<bool, AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    if let Some(pattern) = pattern {
+        let matches = exists::matching(&mut client, &pattern, touch).await?;
+        if matches.is_empty() {
+            println!("{}", format!("✗ No keys match pattern '{}'", pattern).red());
+            return Ok(false);
+        }
+        println!(
+            "{}",
+            format!("✓ {} keys match pattern '{}'", matches.len(), pattern).green()
+        );
+        return Ok(true);
+    }
+
+    let exists = exists::run(&mut client, &keys, touch).await?;
+
+    let mut missing = 0;
+    for (key, exists) in keys.iter().zip(&exists) {
+        if *exists {
+            println!("{} {}", "✓".green(), key);
+        } else {
+            println!("{} {}", "✗".red(), key);
+            missing += 1;
+        }
+    }
+
+    if keys.len() > 1 {
+        println!(
+            "{}",
+            format!("{} of {} keys exist", keys.len() - missing, keys.len()).bold()
+        );
+    }
+
+    Ok(missing == 0)
+}
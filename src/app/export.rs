@@ -0,0 +1,59 @@
+use colored::*;
+use std::time::Instant;
+
+use solt::cli::ExportFormat;
+use solt::commands::export;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{spinner, success};
+
+pub async fn run(
+    format: ExportFormat,
+    output: String,
+    pattern: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let mut client = RedisClient::connect(config.redis_config(&env_name)?).await?;
+
+    let progress = spinner();
+    let started = Instant::now();
+    let summary = export::run(
+        &mut client,
+        &format,
+        &output,
+        &env_name,
+        &pattern,
+        |exported| {
+            let elapsed = started.elapsed().as_secs_f64().max(0.001);
+            progress.set_message(format!(
+                "{} keys exported ({:.0} keys/sec)",
+                exported,
+                exported as f64 / elapsed
+            ));
+            progress.tick();
+        },
+    )
+    .await?;
+    progress.finish_and_clear();
+
+    let elapsed = started.elapsed().as_secs_f64();
+    success(&format!(
+        "Exported {} keys to {} in {:.1}s ({:.0} keys/sec)",
+        summary.exported,
+        output,
+        elapsed,
+        summary.exported as f64 / elapsed.max(0.001)
+    ));
+    if summary.unsupported > 0 {
+        println!(
+            "{}",
+            format!("Skipped {} keys of unsupported types", summary.unsupported).yellow()
+        );
+    }
+
+    Ok(())
+}
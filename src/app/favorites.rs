@@ -1,7 +1,7 @@
 use colored::*;
 use log::info;
 
-use crate::error::AppError;
+use solt::error::AppError;
 
 pub async fn run() -> Result<(), AppError> {
     info!("Favorites command - placeholder");
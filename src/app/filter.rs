@@ -0,0 +1,81 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::filter::{self, Criteria};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::format::format_bytes;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, spinner};
+
+#[derive(Tabled)]
+struct FilterRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Type")]
+    key_type: String,
+    #[tabled(rename = "Encoding")]
+    encoding: String,
+    #[tabled(rename = "TTL")]
+    ttl: String,
+    #[tabled(rename = "Memory")]
+    memory: String,
+}
+
+pub async fn run(
+    pattern: String,
+    criteria: Criteria,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let progress = spinner();
+    let matches = filter::run(&mut client, &pattern, &criteria, |scanned, matched| {
+        progress.set_message(format!("Scanned {} keys, {} match", scanned, matched));
+    })
+    .await?;
+    progress.finish_and_clear();
+
+    if print_structured(&config.output_format, &matches)? {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Found {} keys matching pattern '{}' and filters",
+            matches.len(),
+            pattern
+        )
+        .cyan()
+        .bold()
+    );
+
+    if matches.is_empty() {
+        println!("{}", "No keys found.".yellow());
+        return Ok(());
+    }
+
+    let rows: Vec<FilterRow> = matches
+        .into_iter()
+        .map(|info| FilterRow {
+            key: info.key,
+            key_type: info.key_type,
+            encoding: info.encoding,
+            ttl: match info.ttl {
+                Some(t) if t >= 0 => format!("{}s", t),
+                _ => "No expiry".to_string(),
+            },
+            memory: info
+                .memory_usage
+                .map(|m| format_bytes(m as u64))
+                .unwrap_or_else(|| "Unknown".to_string()),
+        })
+        .collect();
+
+    println!("{}", Table::new(rows));
+
+    Ok(())
+}
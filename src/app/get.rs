@@ -0,0 +1,448 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::Local;
+use colored::*;
+use std::io::Write;
+use std::time::Duration;
+use tabled::{Table, Tabled};
+
+use solt::cli::DecodeFormat;
+use solt::commands::get::{self, Contents};
+use solt::config::{AppConfig, OutputFormat};
+use solt::decode::decode_value;
+use solt::error::AppError;
+use solt::format::{escape_bytes, format_bytes, hex_dump, is_binary, ValueDisplay};
+use solt::query::parse_query;
+use solt::redis_client::RedisClient;
+
+use crate::output::{clear_screen, print_structured, rule, success};
+
+fn print_string_value(
+    client: &RedisClient,
+    bytes: &[u8],
+    pretty: bool,
+    display: ValueDisplay,
+) -> Result<(), AppError> {
+    match display {
+        ValueDisplay::Hex => {
+            println!("{}", format!("Value (hex, {} bytes):", bytes.len()).bold());
+            println!("{}", hex_dump(bytes));
+        }
+        ValueDisplay::Base64 => {
+            println!("{}", "Value (base64):".bold());
+            println!("{}", BASE64.encode(bytes));
+        }
+        ValueDisplay::Escaped | ValueDisplay::Raw => {
+            if is_binary(bytes) {
+                println!(
+                    "{}",
+                    format!(
+                        "Value is binary ({} bytes), unprintable bytes are escaped. Use --hex, --base64 or --raw to see it as-is.",
+                        bytes.len()
+                    )
+                    .yellow()
+                );
+                println!("{}", "Value:".bold());
+                println!("{}", escape_bytes(bytes));
+                return Ok(());
+            }
+
+            let value = String::from_utf8_lossy(bytes);
+            match client.pretty_print_json(&value) {
+                Ok(pretty_value) if pretty => {
+                    println!("{}", "Value (pretty-printed):".bold());
+                    println!("{}", pretty_value);
+                }
+                _ => {
+                    println!("{}", "Value:".bold());
+                    println!("{}", value);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Format a hash field value, list item or stream field for display. JSON
+/// objects and arrays are pretty-printed when `pretty` is set, with the
+/// continuation lines indented to line up with the line they start on.
+fn nested_value(value: &str, indent: usize, pretty: bool) -> String {
+    let trimmed = value.trim_start();
+    if pretty && (trimmed.starts_with('{') || trimmed.starts_with('[')) {
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(value) {
+            if let Ok(text) = serde_json::to_string_pretty(&json) {
+                return text.replace('\n', &format!("\n{}", " ".repeat(indent)));
+            }
+        }
+    }
+    value.to_string()
+}
+
+#[derive(Tabled)]
+struct MultiGetRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Value")]
+    value: String,
+}
+
+#[derive(Tabled)]
+struct PatternGetRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Type")]
+    key_type: String,
+    #[tabled(rename = "Value")]
+    value: String,
+}
+
+pub async fn run(
+    key: String,
+    environment: Option<String>,
+    pretty: bool,
+    nested_pretty: bool,
+    decode: Option<DecodeFormat>,
+    display: ValueDisplay,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let (key_info, contents) = get::run(&mut client, &key).await?;
+
+    // Raw output is meant for piping, so write only the value bytes
+    if let (ValueDisplay::Raw, Contents::String(bytes)) = (display, &contents) {
+        let mut stdout = std::io::stdout();
+        stdout.write_all(bytes)?;
+        stdout.flush()?;
+        return Ok(());
+    }
+
+    println!("{}", format!("Key: {}", key).bold());
+    println!("{}", format!("Type: {}", key_info.key_type).cyan());
+
+    match contents {
+        Contents::String(bytes) if decode.is_some() => {
+            let format = decode.unwrap_or(DecodeFormat::Auto);
+            match decode_value(&bytes, format) {
+                Some((value, name)) => {
+                    println!("{}", format!("Value (decoded from {}):", name).bold());
+                    println!("{}", serde_json::to_string_pretty(&value)?);
+                }
+                None => {
+                    println!(
+                        "{}",
+                        "Value could not be decoded, showing it as text".yellow()
+                    );
+                    println!("{}", "Value:".bold());
+                    println!("{}", escape_bytes(&bytes));
+                }
+            }
+        }
+        Contents::String(bytes) => print_string_value(&client, &bytes, pretty, display)?,
+        Contents::Hash(hash) => {
+            if hash.is_empty() {
+                println!("{}", "Hash is empty".yellow());
+            } else {
+                println!("{}", "Hash fields:".bold());
+                for (field, value) in hash {
+                    println!(
+                        "  {}: {}",
+                        field.cyan(),
+                        nested_value(&value, 2, nested_pretty)
+                    );
+                }
+            }
+        }
+        Contents::List(list) => {
+            if list.is_empty() {
+                println!("{}", "List is empty".yellow());
+            } else {
+                println!("{}", format!("List ({} items):", list.len()).bold());
+                for (i, item) in list.iter().enumerate() {
+                    println!("  [{}]: {}", i, nested_value(item, 2, nested_pretty));
+                }
+            }
+        }
+        Contents::Stream { length, entries } => {
+            if entries.is_empty() {
+                println!("{}", "Stream is empty".yellow());
+            } else {
+                if length > entries.len() {
+                    println!(
+                        "{}",
+                        format!(
+                            "Stream ({} entries, showing the first {}):",
+                            length,
+                            entries.len()
+                        )
+                        .bold()
+                    );
+                } else {
+                    println!("{}", format!("Stream ({} entries):", length).bold());
+                }
+                for entry in entries {
+                    println!("  {}", entry.id.cyan());
+                    for (field, value) in entry.fields {
+                        println!("    {}: {}", field, nested_value(&value, 4, nested_pretty));
+                    }
+                }
+            }
+        }
+        Contents::Set(set) => {
+            if set.is_empty() {
+                println!("{}", "Set is empty".yellow());
+            } else {
+                println!("{}", format!("Set ({} members):", set.len()).bold());
+                for member in set {
+                    println!("  • {}", member);
+                }
+            }
+        }
+        Contents::Zset(zset) => {
+            if zset.is_empty() {
+                println!("{}", "Sorted set is empty".yellow());
+            } else {
+                println!("{}", format!("Sorted set ({} members):", zset.len()).bold());
+                for (member, score) in zset {
+                    println!("  • {} (score: {})", member, score);
+                }
+            }
+        }
+        Contents::Unsupported => {
+            println!(
+                "{}",
+                format!("Unsupported key type: {}", key_info.key_type).red()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn to_file(
+    key: String,
+    path: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let written = get::to_file(&mut client, &key, &path).await?;
+    success(&format!(
+        "Wrote {} ({}) to {}",
+        key,
+        format_bytes(written as u64),
+        path
+    ));
+
+    Ok(())
+}
+
+pub async fn query(
+    key: String,
+    expr: String,
+    decode: Option<DecodeFormat>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    // Reject a malformed path before connecting
+    let path = parse_query(&expr).map_err(AppError::QueryError)?;
+
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let results = get::query(&mut client, &key, &path, decode).await?;
+    for result in results {
+        // Print strings bare, like `jq -r`, so results can be piped on
+        match result {
+            serde_json::Value::String(s) => println!("{}", s),
+            other => println!("{}", serde_json::to_string_pretty(&other)?),
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn multi(keys: Vec<String>, environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    // MGET returns nil for missing keys and for keys that are not strings
+    let values = get::multi(&mut client, keys).await?;
+    let missing = values.iter().filter(|(_, v)| v.is_none()).count();
+
+    let rows: Vec<MultiGetRow> = values
+        .into_iter()
+        .map(|(key, value)| MultiGetRow {
+            key,
+            value: value.unwrap_or_else(|| "(nil)".to_string()),
+        })
+        .collect();
+
+    println!("{}", Table::new(rows));
+    if missing > 0 {
+        println!(
+            "{}",
+            format!("{} keys missing or not strings", missing).yellow()
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn pattern(
+    pattern: String,
+    json: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    const MAX_VALUE_WIDTH: usize = 80;
+
+    let config = AppConfig::load()?;
+    let format = if json {
+        OutputFormat::Json
+    } else {
+        config.output_format.clone()
+    };
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let values = get::pattern(&mut client, &pattern).await?;
+
+    let map: serde_json::Map<String, serde_json::Value> = values
+        .iter()
+        .map(|kv| (kv.key.clone(), kv.value.clone().unwrap_or_default()))
+        .collect();
+    if print_structured(&format, &map)? {
+        return Ok(());
+    }
+
+    if values.is_empty() {
+        println!(
+            "{}",
+            format!("No keys found matching pattern: {}", pattern).yellow()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Found {} keys matching '{}':", values.len(), pattern).bold()
+    );
+
+    let rows: Vec<PatternGetRow> = values
+        .into_iter()
+        .map(|kv| {
+            let value = match kv.value {
+                Some(serde_json::Value::String(s)) => s,
+                Some(other) => other.to_string(),
+                None => format!("(unsupported type: {})", kv.key_type),
+            };
+            let value = if value.chars().count() > MAX_VALUE_WIDTH {
+                let truncated: String = value.chars().take(MAX_VALUE_WIDTH - 3).collect();
+                format!("{}...", truncated)
+            } else {
+                value
+            };
+            PatternGetRow {
+                key: kv.key,
+                key_type: kv.key_type,
+                value,
+            }
+        })
+        .collect();
+
+    println!("{}", Table::new(rows));
+
+    Ok(())
+}
+
+pub async fn get_hash_field(
+    key: String,
+    field: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let value = get::hash_field(&mut client, &key, &field).await?;
+    println!("{}", format!("Field: {}:{}", key, field).bold());
+    println!("{}", "Value:".bold());
+    println!("{}", value);
+
+    Ok(())
+}
+
+pub async fn get_list_range(
+    key: String,
+    start: isize,
+    stop: isize,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let list = get::list_range(&mut client, &key, start, stop).await?;
+
+    if list.is_empty() {
+        println!("{}", "No items found in the specified range".yellow());
+    } else {
+        println!(
+            "{}",
+            format!("List range [{}-{}] ({} items):", start, stop, list.len()).bold()
+        );
+        for (i, item) in list.iter().enumerate() {
+            let actual_index = start + i as isize;
+            println!("  [{}]: {}", actual_index, item);
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn watch(
+    key: String,
+    environment: Option<String>,
+    interval: Duration,
+    pretty: bool,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let mut previous: Option<Vec<String>> = None;
+    loop {
+        let lines = get::value_lines(&mut client, &key, pretty).await?;
+
+        // Clear the screen and redraw from the top
+        clear_screen();
+        println!(
+            "{}",
+            format!(
+                "Watching '{}' every {}s - last refresh {} (Ctrl+C to stop)",
+                key,
+                interval.as_secs_f64(),
+                Local::now().format("%H:%M:%S")
+            )
+            .cyan()
+            .bold()
+        );
+        rule(80);
+
+        for (i, line) in lines.iter().enumerate() {
+            let changed = previous
+                .as_ref()
+                .is_some_and(|prev| prev.get(i) != Some(line));
+            if changed {
+                println!("{}", line.yellow().bold());
+            } else {
+                println!("{}", line);
+            }
+        }
+        if let Some(prev) = &previous {
+            if prev.len() > lines.len() {
+                println!(
+                    "{}",
+                    format!("({} lines removed)", prev.len() - lines.len()).red()
+                );
+            }
+        }
+        previous = Some(lines);
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::glance;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::format::format_seconds;
+use solt::redis_client::RedisClient;
+
+#[derive(Tabled)]
+struct GlanceRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Exists")]
+    exists: String,
+    #[tabled(rename = "Type")]
+    key_type: String,
+    #[tabled(rename = "TTL")]
+    ttl: String,
+    #[tabled(rename = "Length")]
+    length: String,
+    #[tabled(rename = "Last Access")]
+    idle: String,
+}
+
+pub async fn run(environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let redis_config = config.redis_config(&env_name)?;
+
+    if config.favorites.is_empty() {
+        println!("{}", "No favorites configured.".yellow());
+        println!(
+            "{}",
+            format!(
+                "Add keys to the favorites list in {}",
+                AppConfig::config_path()?.display()
+            )
+            .cyan()
+        );
+        return Ok(());
+    }
+
+    let mut client = RedisClient::connect(redis_config).await?;
+    let favorites = glance::run(&mut client, &config.favorites).await?;
+
+    let mut rows = Vec::new();
+    let mut missing = 0;
+    for favorite in favorites {
+        let Some(key_type) = favorite.key_type else {
+            missing += 1;
+            rows.push(GlanceRow {
+                key: favorite.key,
+                exists: "✗".red().to_string(),
+                key_type: "-".to_string(),
+                ttl: "-".to_string(),
+                length: "-".to_string(),
+                idle: "-".to_string(),
+            });
+            continue;
+        };
+
+        rows.push(GlanceRow {
+            key: favorite.key,
+            exists: "✓".green().to_string(),
+            key_type,
+            ttl: match favorite.ttl {
+                Some(-1) => "No expiry".to_string(),
+                Some(t) if t >= 0 => format_seconds(t as u64),
+                _ => "Unknown".to_string(),
+            },
+            length: favorite
+                .length
+                .map(|l| l.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            idle: favorite
+                .idle
+                .map(|i| format!("{} ago", format_seconds(i as u64)))
+                .unwrap_or_else(|| "Unknown".to_string()),
+        });
+    }
+
+    println!(
+        "{}",
+        format!("Favorites at a glance ({})", env_name)
+            .bold()
+            .cyan()
+    );
+    println!("{}", Table::new(rows));
+
+    if missing > 0 {
+        println!(
+            "{}",
+            format!("⚠ {} favorite keys do not exist", missing)
+                .red()
+                .bold()
+        );
+    } else {
+        println!("{}", "✓ All favorite keys exist".green());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use colored::*;
+use std::time::Duration;
+
+use solt::commands::hash;
+use solt::error::AppError;
+use solt::format::format_seconds;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+pub async fn delete_fields(
+    key: String,
+    fields: Vec<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let deleted = hash::delete_fields(&mut client, &key, &fields).await?;
+
+    success(&format!(
+        "Deleted {} of {} fields from '{}'",
+        deleted,
+        fields.len(),
+        key
+    ));
+
+    Ok(())
+}
+
+/// Check a single field, exiting with code 1 when it is missing so the
+/// check can be used in scripts.
+pub async fn exists(
+    key: String,
+    field: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    hash::exists(&mut client, &key, &field).await?;
+
+    success(&format!("Field '{}' exists in '{}'", field, key));
+
+    Ok(())
+}
+
+pub async fn random(
+    key: String,
+    count: i64,
+    with_values: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let fields = hash::random(&mut client, &key, count, with_values).await?;
+
+    println!("{}", format!("Random fields of '{}':", key).bold());
+    for (field, value) in fields {
+        match value {
+            Some(value) => println!("  {}: {}", field.cyan(), value),
+            None => println!("  {}", field.cyan()),
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn expire(
+    key: String,
+    ttl: Duration,
+    fields: Vec<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let codes = hash::expire(&mut client, &key, ttl, &fields).await?;
+
+    let mut missing = 0;
+    for (field, code) in fields.iter().zip(&codes) {
+        match code {
+            1 => println!("  {} {}", "✓".green(), field),
+            2 => println!("  {} {} (deleted)", "✓".green(), field),
+            -2 => {
+                missing += 1;
+                println!("  {} {} (not found)", "✗".red(), field);
+            }
+            code => println!("  {} {} (not set, code {})", "!".yellow(), field, code),
+        }
+    }
+    if missing == fields.len() {
+        return Err(AppError::NotFound(format!(
+            "None of the fields exist in '{}'",
+            key
+        )));
+    }
+
+    success(&format!(
+        "Set a TTL of {} on {} fields of '{}'",
+        format_seconds(ttl.as_secs()),
+        fields.len() - missing,
+        key
+    ));
+
+    Ok(())
+}
+
+pub async fn ttl(
+    key: String,
+    fields: Vec<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let ttls = hash::ttl(&mut client, &key, &fields).await?;
+
+    println!("{}", format!("Field TTLs of '{}':", key).bold());
+    for (field, ttl) in fields.iter().zip(ttls) {
+        let ttl = match ttl {
+            -2 => "not found".red().to_string(),
+            -1 => "no expiry".yellow().to_string(),
+            secs => format_seconds(secs as u64).cyan().to_string(),
+        };
+        println!("  {}: {}", field, ttl);
+    }
+
+    Ok(())
+}
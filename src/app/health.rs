@@ -0,0 +1,48 @@
+use colored::*;
+use std::time::Duration;
+
+use solt::commands::health;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::print_structured;
+
+/// Print a one-line status and fail with `AppError::Unhealthy` when any
+/// check does not pass.
+pub async fn run(
+    max_latency: Duration,
+    min_memory_free: f64,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let mut client = RedisClient::connect(config.redis_config(&env_name)?).await?;
+    let report = health::run(&mut client, max_latency, min_memory_free).await?;
+
+    if !print_structured(&config.output_format, &report)? {
+        let summary: Vec<String> = report
+            .checks
+            .iter()
+            .map(|check| format!("{}: {}", check.name, check.detail))
+            .collect();
+        if report.healthy {
+            println!("{} {}", "OK".green().bold(), summary.join("; "));
+        }
+    }
+
+    if report.healthy {
+        return Ok(());
+    }
+    let failures: Vec<String> = report
+        .checks
+        .iter()
+        .filter(|check| !check.ok)
+        .map(|check| format!("{}: {}", check.name, check.detail))
+        .collect();
+    Err(AppError::Unhealthy(format!(
+        "UNHEALTHY {} ({})",
+        failures.join("; "),
+        env_name
+    )))
+}
@@ -0,0 +1,86 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::import;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::app::dry_run;
+use crate::output::{spinner, success};
+
+#[derive(Tabled)]
+struct CommandRow {
+    #[tabled(rename = "Command")]
+    name: String,
+    #[tabled(rename = "Count")]
+    count: usize,
+}
+
+pub async fn pipe_file(
+    path: String,
+    environment: Option<String>,
+    dry_run: bool,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    if dry_run {
+        return report(&mut client, &path).await;
+    }
+
+    let progress = spinner();
+    let report = import::pipe_file(&mut client, &path, |sent| {
+        progress.set_message(format!("{} commands sent", sent));
+        progress.tick();
+    })
+    .await?;
+    progress.finish_and_clear();
+
+    for (name, error) in &report.errors {
+        println!("{}", format!("{} failed: {}", name, error).red());
+    }
+    success(&format!("Sent {} commands from {}", report.sent, path));
+    if !report.errors.is_empty() {
+        println!(
+            "{}",
+            format!("{} commands returned errors", report.errors.len()).red()
+        );
+    }
+
+    Ok(())
+}
+
+/// Dry run: summarize the commands the file holds and the keys they would
+/// write, including how many of those already exist.
+async fn report(client: &mut RedisClient, path: &str) -> Result<(), AppError> {
+    let plan = import::plan(client, path).await?;
+
+    let total: usize = plan.commands.values().sum();
+    println!(
+        "{}",
+        format!(
+            "Dry run: {} would send {} commands touching {} keys",
+            path, total, plan.keys
+        )
+        .yellow()
+        .bold()
+    );
+    let rows: Vec<CommandRow> = plan
+        .commands
+        .into_iter()
+        .map(|(name, count)| CommandRow { name, count })
+        .collect();
+    println!("{}", Table::new(rows));
+    if plan.existing > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} of those keys already exist and would be modified",
+                plan.existing
+            )
+            .yellow()
+        );
+    }
+
+    dry_run::print_no_changes();
+    Ok(())
+}
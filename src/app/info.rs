@@ -0,0 +1,50 @@
+use colored::*;
+use serde_json::{Map, Value as JsonValue};
+
+use solt::commands::info;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, rule};
+
+pub async fn run(
+    section: Option<String>,
+    all: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let sections = info::run(&mut client, section.as_deref(), all).await?;
+
+    let structured: Map<String, JsonValue> = sections
+        .iter()
+        .map(|section| {
+            let fields: Map<String, JsonValue> = section
+                .fields
+                .iter()
+                .map(|(key, value)| (key.clone(), JsonValue::String(value.clone())))
+                .collect();
+            (section.name.to_lowercase(), JsonValue::Object(fields))
+        })
+        .collect();
+    if print_structured(&config.output_format, &structured)? {
+        return Ok(());
+    }
+
+    for (i, section) in sections.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}", format!("{}:", section.name).bold());
+        rule(50);
+        if section.fields.is_empty() {
+            println!("{}", "(empty)".dimmed());
+        }
+        for (key, value) in &section.fields {
+            println!("{}: {}", key.cyan(), value.yellow());
+        }
+    }
+
+    Ok(())
+}
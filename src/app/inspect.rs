@@ -0,0 +1,165 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::inspect::{self, KeyDetails};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::format::{format_bytes, format_seconds};
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, rule};
+
+#[derive(Tabled)]
+struct InspectRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Type")]
+    key_type: String,
+    #[tabled(rename = "Length")]
+    length: String,
+    #[tabled(rename = "TTL")]
+    ttl: String,
+    #[tabled(rename = "Memory")]
+    memory: String,
+    #[tabled(rename = "Encoding")]
+    encoding: String,
+    #[tabled(rename = "Idle")]
+    idle: String,
+}
+
+/// One key gets a detailed view; several keys, or all keys matching
+/// `pattern`, a combined table.
+pub async fn run(
+    keys: Vec<String>,
+    pattern: Option<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let keys = match &pattern {
+        Some(pattern) => {
+            let keys = client.scan_keys(pattern).await?;
+            if keys.is_empty() {
+                println!(
+                    "{}",
+                    format!("No keys matching pattern '{}'", pattern).yellow()
+                );
+                return Ok(());
+            }
+            keys
+        }
+        None => keys,
+    };
+
+    let mut details = inspect::run(&mut client, &keys).await?;
+
+    if pattern.is_none() && details.len() == 1 {
+        let details = details.remove(0);
+        if details.info.key_type == "none" {
+            return Err(AppError::NotFound(format!(
+                "Key '{}' not found",
+                details.info.key
+            )));
+        }
+        if !print_structured(&config.output_format, &details)? {
+            print_details(details);
+        }
+        return Ok(());
+    }
+
+    let missing = details.iter().filter(|d| d.info.key_type == "none").count();
+
+    if !print_structured(&config.output_format, &details)? {
+        let rows: Vec<InspectRow> = details.into_iter().map(inspect_row).collect();
+        println!("{}", Table::new(rows));
+    }
+
+    if missing > 0 {
+        return Err(AppError::NotFound(format!(
+            "{} of {} keys not found",
+            missing,
+            keys.len()
+        )));
+    }
+
+    Ok(())
+}
+
+fn print_details(details: KeyDetails) {
+    let info = details.info;
+
+    println!("{}", format!("Key: {}", info.key).bold());
+    rule(50);
+    println!("Type: {}", info.key_type.cyan());
+    println!("Encoding: {}", info.encoding.cyan());
+    if let Some(length) = details.length {
+        let unit = if info.key_type == "string" {
+            "bytes"
+        } else {
+            "elements"
+        };
+        println!("Length: {}", format!("{} {}", length, unit).cyan());
+    }
+    println!("TTL: {}", format_ttl(info.ttl).cyan());
+    println!("Memory: {}", format_memory(info.memory_usage).cyan());
+    match (info.idle_time, info.frequency) {
+        (Some(idle), _) => {
+            println!(
+                "Idle: {}",
+                format!("{} ago", format_seconds(idle as u64)).cyan()
+            )
+        }
+        (None, Some(frequency)) => println!(
+            "Access frequency: {} {}",
+            frequency.to_string().cyan(),
+            "(LFU counter)".dimmed()
+        ),
+        (None, None) => println!("Idle: {}", "Unknown".cyan()),
+    }
+}
+
+fn inspect_row(details: KeyDetails) -> InspectRow {
+    let info = details.info;
+    if info.key_type == "none" {
+        return InspectRow {
+            key: info.key,
+            key_type: "missing".red().to_string(),
+            length: "-".to_string(),
+            ttl: "-".to_string(),
+            memory: "-".to_string(),
+            encoding: "-".to_string(),
+            idle: "-".to_string(),
+        };
+    }
+    InspectRow {
+        length: details
+            .length
+            .map(|l| l.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        ttl: format_ttl(info.ttl),
+        memory: format_memory(info.memory_usage),
+        idle: match (info.idle_time, info.frequency) {
+            (Some(idle), _) => format_seconds(idle as u64),
+            (None, Some(frequency)) => format!("freq {}", frequency),
+            (None, None) => "-".to_string(),
+        },
+        key: info.key,
+        key_type: info.key_type,
+        encoding: info.encoding,
+    }
+}
+
+fn format_ttl(ttl: Option<i64>) -> String {
+    match ttl {
+        Some(-1) => "No expiry".to_string(),
+        Some(t) if t >= 0 => format_seconds(t as u64),
+        _ => "Unknown".to_string(),
+    }
+}
+
+fn format_memory(memory: Option<usize>) -> String {
+    memory
+        .map(|m| format_bytes(m as u64))
+        .unwrap_or_else(|| "Unknown".to_string())
+}
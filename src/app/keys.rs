@@ -0,0 +1,380 @@
+use colored::*;
+use serde::Serialize;
+use tabled::{Table, Tabled};
+
+use solt::cli::KeySort;
+use solt::commands::keys::{self, DEFAULT_PAGE_SIZE};
+use solt::config::{AppConfig, RedisConfig};
+use solt::error::AppError;
+use solt::format::format_seconds;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, progress_bar, spinner};
+
+/// One page of a paginated listing, for JSON/YAML output.
+#[derive(Serialize)]
+struct KeyPage<'a, T: Serialize> {
+    keys: &'a [T],
+    /// Cursor to pass to `--cursor` for the next page; 0 when done
+    cursor: u64,
+}
+
+#[derive(Tabled)]
+struct KeyRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Type")]
+    key_type: String,
+    #[tabled(rename = "TTL")]
+    ttl: String,
+    #[tabled(rename = "Memory")]
+    memory: String,
+    #[tabled(rename = "Encoding")]
+    encoding: String,
+    #[tabled(rename = "Idle")]
+    idle: String,
+    #[tabled(rename = "Freq")]
+    frequency: String,
+}
+
+#[derive(Tabled)]
+struct NodeKeyRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Node")]
+    node: String,
+}
+
+#[derive(Tabled)]
+struct DbKeyRow {
+    #[tabled(rename = "DB")]
+    db: u8,
+    #[tabled(rename = "Key")]
+    key: String,
+}
+
+/// The settings for `environment`, or `None` after listing the configured
+/// environments when it does not exist.
+fn environment_config(config: &AppConfig, environment: Option<&str>) -> Option<RedisConfig> {
+    let env_name = config.resolve_environment(environment);
+    if let Some(env) = config.get_environment(&env_name) {
+        return Some(env.config.clone());
+    }
+
+    println!(
+        "{}",
+        format!("Error: Environment '{}' not found", env_name).red()
+    );
+    println!("{}", "Available environments:".yellow());
+    for env_name in config.environments.keys() {
+        println!("  • {}", env_name.cyan());
+    }
+    println!();
+    println!("{}", "To add a new environment, use:".cyan());
+    println!("  solt config --add-env <name>");
+    None
+}
+
+pub async fn run(
+    pattern: Option<String>,
+    environment: Option<String>,
+    detailed: bool,
+    limit: Option<usize>,
+    cursor: Option<u64>,
+    key_type: Option<String>,
+    sort: Option<(KeySort, bool)>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let Some(redis_config) = environment_config(&config, environment.as_deref()) else {
+        return Ok(());
+    };
+    let mut client = RedisClient::connect(redis_config.clone()).await?;
+
+    let pattern = pattern.unwrap_or_else(|| "*".to_string());
+    let (keys, next_cursor) =
+        keys::run(&mut client, &pattern, limit, cursor, key_type.as_deref()).await?;
+
+    if !detailed {
+        let printed = match next_cursor {
+            Some(next) => print_structured(
+                &config.output_format,
+                &KeyPage {
+                    keys: &keys,
+                    cursor: next,
+                },
+            )?,
+            None => print_structured(&config.output_format, &keys)?,
+        };
+        if printed {
+            return Ok(());
+        }
+    }
+
+    let mut heading = if next_cursor.is_some() {
+        format!("Showing {} keys matching pattern '{}'", keys.len(), pattern)
+    } else {
+        format!("Found {} keys matching pattern '{}'", keys.len(), pattern)
+    };
+    if let Some(key_type) = &key_type {
+        heading.push_str(&format!(" of type {}", key_type));
+    }
+    println!("{}", heading.cyan().bold());
+
+    if keys.is_empty() {
+        println!("{}", "No keys found.".yellow());
+        print_next_cursor(next_cursor, &pattern, limit, key_type.as_deref());
+        return Ok(());
+    }
+
+    if detailed {
+        // Get detailed information for each key
+        let progress = progress_bar(keys.len() as u64);
+        progress.set_message("Getting key details...");
+        let (mut key_infos, errors) =
+            keys::details(&redis_config, &keys, |done| progress.inc(done as u64)).await?;
+        progress.finish_with_message("Key details retrieved");
+        for (count, e) in errors {
+            println!(
+                "{}",
+                format!("Error getting info for {} keys: {}", count, e).red()
+            );
+        }
+
+        if let Some((sort, desc)) = sort {
+            keys::sort_key_infos(&mut key_infos, sort, desc);
+        }
+
+        let printed = match next_cursor {
+            Some(next) => print_structured(
+                &config.output_format,
+                &KeyPage {
+                    keys: &key_infos,
+                    cursor: next,
+                },
+            )?,
+            None => print_structured(&config.output_format, &key_infos)?,
+        };
+        if printed {
+            return Ok(());
+        }
+
+        // Display as table
+        let rows: Vec<KeyRow> = key_infos
+            .into_iter()
+            .map(|info| KeyRow {
+                key: info.key,
+                key_type: info.key_type,
+                ttl: info
+                    .ttl
+                    .map(|t| {
+                        if t == -1 {
+                            "No expiry".to_string()
+                        } else if t == -2 {
+                            "Key doesn't exist".to_string()
+                        } else {
+                            format!("{}s", t)
+                        }
+                    })
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                memory: info
+                    .memory_usage
+                    .map(|m| format!("{} bytes", m))
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                encoding: info.encoding,
+                idle: info
+                    .idle_time
+                    .map(|i| format_seconds(i as u64))
+                    .unwrap_or_else(|| "-".to_string()),
+                frequency: info
+                    .frequency
+                    .map(|f| f.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            })
+            .collect();
+
+        let table = Table::new(rows).to_string();
+        println!("{}", table);
+    } else {
+        // Simple list
+        for key in keys {
+            println!("• {}", key.cyan());
+        }
+    }
+
+    print_next_cursor(next_cursor, &pattern, limit, key_type.as_deref());
+
+    Ok(())
+}
+
+/// List matching keys across a whole Redis Cluster, each annotated with
+/// the master that owns it.
+pub async fn cluster_wide(
+    pattern: String,
+    key_type: Option<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let redis_config = config.redis_config(&env_name)?;
+
+    let mut client = RedisClient::connect(redis_config.clone()).await?;
+    if !client.cluster_enabled().await? {
+        return Err(AppError::ConfigError(format!(
+            "Environment '{}' is not a Redis Cluster; drop --cluster-wide",
+            env_name
+        )));
+    }
+
+    let masters = keys::cluster_masters(&mut client, &redis_config).await?;
+
+    let progress = spinner();
+    progress.set_message(format!("Scanning {} masters...", masters.len()));
+    let result = keys::cluster_wide(&masters, &pattern, key_type.as_deref()).await;
+    progress.finish_and_clear();
+    let keys = result?;
+
+    if print_structured(&config.output_format, &keys)? {
+        return Ok(());
+    }
+
+    let mut heading = format!(
+        "Found {} keys matching pattern '{}' across {} masters",
+        keys.len(),
+        pattern,
+        masters.len()
+    );
+    if let Some(key_type) = &key_type {
+        heading.push_str(&format!(" of type {}", key_type));
+    }
+    println!("{}", heading.cyan().bold());
+
+    if keys.is_empty() {
+        println!("{}", "No keys found.".yellow());
+    } else {
+        let rows = keys.into_iter().map(|k| NodeKeyRow {
+            key: k.key,
+            node: k.node,
+        });
+        println!("{}", Table::new(rows));
+    }
+
+    Ok(())
+}
+
+/// List matching keys in every logical database of the instance, with a
+/// per-database summary.
+pub async fn all_dbs(
+    pattern: String,
+    key_type: Option<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let redis_config = config.redis_config(&config.resolve_environment(environment.as_deref()))?;
+
+    let mut client = RedisClient::connect(redis_config.clone()).await?;
+    let databases = keys::databases(&mut client).await?;
+
+    let progress = spinner();
+    progress.set_message(format!("Scanning {} databases...", databases.len()));
+    let result = keys::all_dbs(&redis_config, &databases, &pattern, key_type.as_deref()).await;
+    progress.finish_and_clear();
+    let keys = result?;
+
+    if print_structured(&config.output_format, &keys)? {
+        return Ok(());
+    }
+
+    let mut per_db: Vec<(u8, usize)> = Vec::new();
+    for key in &keys {
+        match per_db.last_mut() {
+            Some((db, count)) if *db == key.db => *count += 1,
+            _ => per_db.push((key.db, 1)),
+        }
+    }
+
+    let mut heading = format!(
+        "Found {} keys matching pattern '{}' in {} of {} databases",
+        keys.len(),
+        pattern,
+        per_db.len(),
+        databases.len()
+    );
+    if let Some(key_type) = &key_type {
+        heading.push_str(&format!(" of type {}", key_type));
+    }
+    println!("{}", heading.cyan().bold());
+
+    if keys.is_empty() {
+        println!("{}", "No keys found.".yellow());
+        return Ok(());
+    }
+
+    let summary: Vec<String> = per_db
+        .iter()
+        .map(|(db, count)| format!("db{}: {}", db, count))
+        .collect();
+    println!("{}", summary.join(", "));
+    let rows = keys.into_iter().map(|k| DbKeyRow {
+        db: k.db,
+        key: k.key,
+    });
+    println!("{}", Table::new(rows));
+
+    Ok(())
+}
+
+fn print_next_cursor(
+    next_cursor: Option<u64>,
+    pattern: &str,
+    limit: Option<usize>,
+    key_type: Option<&str>,
+) {
+    match next_cursor {
+        Some(0) => println!("\n{}", "End of keyspace reached.".dimmed()),
+        Some(next) => {
+            println!("\n{} {}", "Next cursor:".bold(), next.to_string().yellow());
+            let type_arg = key_type
+                .map(|t| format!(" --type {}", t))
+                .unwrap_or_default();
+            println!(
+                "  solt keys '{}'{} --cursor {} --limit {}",
+                pattern,
+                type_arg,
+                next,
+                limit.unwrap_or(DEFAULT_PAGE_SIZE)
+            );
+        }
+        None => {}
+    }
+}
+
+pub async fn count_keys(
+    pattern: Option<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let Some(redis_config) = environment_config(&config, environment.as_deref()) else {
+        return Ok(());
+    };
+    let mut client = RedisClient::connect(redis_config).await?;
+
+    let pattern = pattern.unwrap_or_else(|| "*".to_string());
+    let count = keys::count(&mut client, &pattern).await?;
+
+    println!(
+        "{}",
+        format!("Found {} keys matching pattern '{}'", count.total, pattern)
+            .green()
+            .bold()
+    );
+
+    // Group by type if we have keys
+    if count.total > 0 {
+        println!("\n{}", "Breakdown by type:".bold());
+        for (key_type, count) in count.by_type {
+            println!("• {}: {}", key_type.cyan(), count.to_string().yellow());
+        }
+    }
+
+    Ok(())
+}
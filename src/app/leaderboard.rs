@@ -0,0 +1,91 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::leaderboard::{self, LeaderboardOptions};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, rule};
+
+#[derive(Tabled)]
+struct EntryRow {
+    #[tabled(rename = "#")]
+    rank: String,
+    #[tabled(rename = "Member")]
+    member: String,
+    #[tabled(rename = "Score")]
+    score: String,
+}
+
+/// Render a sorted set as a ranked leaderboard.
+pub async fn run(
+    key: String,
+    options: LeaderboardOptions,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let report = leaderboard::run(&mut client, &key, &options).await?;
+    if print_structured(&config.output_format, &report)? {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Leaderboard '{}' ({} members):", report.key, report.members).bold()
+    );
+    rule(50);
+    if report.entries.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "Page {} is past the end ({} pages)",
+                options.page, report.pages
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+
+    let rows: Vec<EntryRow> = report
+        .entries
+        .iter()
+        .map(|entry| {
+            let highlight = options.around.as_deref() == Some(entry.member.as_str());
+            let row = EntryRow {
+                rank: entry.rank.to_string(),
+                member: entry.member.clone(),
+                score: entry.score.to_string(),
+            };
+            if highlight {
+                EntryRow {
+                    rank: row.rank.yellow().bold().to_string(),
+                    member: row.member.yellow().bold().to_string(),
+                    score: row.score.yellow().bold().to_string(),
+                }
+            } else {
+                row
+            }
+        })
+        .collect();
+    println!("{}", Table::new(rows));
+
+    if options.around.is_none() {
+        println!("Page {} of {}", report.page, report.pages);
+        if report.page < report.pages {
+            let mut next = format!(
+                "solt leaderboard {} --top {} --page {}",
+                report.key,
+                options.top.max(1),
+                report.page + 1
+            );
+            if options.ascending {
+                next.push_str(" --asc");
+            }
+            println!("{}", format!("Next page: {}", next).dimmed());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use colored::*;
+
+use solt::commands::list;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+/// Pop elements and print them in the order they were popped.
+pub async fn pop(
+    key: String,
+    right: bool,
+    count: Option<u64>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let popped = list::pop(&mut client, &key, right, count).await?;
+
+    let end = if right { "tail" } else { "head" };
+    println!(
+        "{}",
+        format!("Popped {} from the {} of '{}':", popped.len(), end, key).bold()
+    );
+    for (i, element) in popped.iter().enumerate() {
+        println!("{}. {}", i + 1, element);
+    }
+
+    Ok(())
+}
+
+pub async fn trim(
+    key: String,
+    start: isize,
+    stop: isize,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let report = list::trim(&mut client, &key, start, stop).await?;
+
+    success(&format!(
+        "Trimmed '{}' to [{}..{}]: removed {}, kept {}",
+        key, start, stop, report.removed, report.kept
+    ));
+    if report.kept == 0 {
+        println!(
+            "{}",
+            "The range was empty, so the list was deleted".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn insert(
+    key: String,
+    before: bool,
+    pivot: String,
+    value: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let len = list::insert(&mut client, &key, before, &pivot, &value).await?;
+
+    success(&format!(
+        "Inserted into '{}' {} '{}' (new length: {})",
+        key,
+        if before { "before" } else { "after" },
+        pivot,
+        len
+    ));
+
+    Ok(())
+}
+
+pub async fn set(
+    key: String,
+    index: isize,
+    value: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    list::set(&mut client, &key, index, &value).await?;
+    success(&format!("Set index {} of '{}'", index, key));
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use colored::*;
+
+use solt::commands::load::{self, SqlLoadOptions};
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{spinner, success};
+
+pub async fn load_sql(
+    options: SqlLoadOptions,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    println!("{}", "Streaming query results into Redis...".cyan());
+
+    let progress = spinner();
+    progress.set_message("Loading rows...");
+    let report = load::load_sql(&mut client, &options, |loaded| {
+        progress.set_message(format!("{} rows loaded", loaded));
+        progress.tick();
+    })
+    .await?;
+    progress.finish_with_message(format!("{} rows loaded", report.loaded));
+
+    for warning in &report.warnings {
+        println!("{}", warning.yellow());
+    }
+    success(&format!("Loaded {} keys from query results", report.loaded));
+    if report.skipped > 0 {
+        println!("{}", format!("Skipped {} rows", report.skipped).yellow());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use colored::*;
+use log::info;
+use tabled::{Table, Tabled};
+
+use solt::commands::migrate;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::app::dry_run;
+use crate::output::{spinner, success};
+
+#[derive(Tabled)]
+struct SummaryRow {
+    #[tabled(rename = "Type")]
+    key_type: String,
+    #[tabled(rename = "Migrated")]
+    migrated: usize,
+    #[tabled(rename = "Skipped")]
+    skipped: usize,
+    #[tabled(rename = "Failed")]
+    failed: usize,
+    #[tabled(rename = "Verify Failed")]
+    verify_failed: usize,
+}
+
+pub async fn run(
+    source: String,
+    dest: String,
+    pattern: String,
+    replace: bool,
+    dry_run: bool,
+) -> Result<(), AppError> {
+    info!("Migrating '{}' from '{}' to '{}'", pattern, source, dest);
+
+    if source == dest {
+        return Err(AppError::ConfigError(
+            "Source and destination environments must differ".to_string(),
+        ));
+    }
+
+    let config = AppConfig::load()?;
+    let source_config = config
+        .get_environment(&source)
+        .ok_or_else(|| AppError::ConfigError(format!("Source environment '{}' not found", source)))?
+        .config
+        .clone();
+    let dest_config = config
+        .get_environment(&dest)
+        .ok_or_else(|| {
+            AppError::ConfigError(format!("Destination environment '{}' not found", dest))
+        })?
+        .config
+        .clone();
+
+    let mut source_client = RedisClient::connect(source_config).await?;
+    let mut dest_client = RedisClient::connect(dest_config).await?;
+
+    println!(
+        "{}",
+        format!(
+            "Migrating keys matching '{}' from '{}' to '{}'",
+            pattern, source, dest
+        )
+        .cyan()
+        .bold()
+    );
+
+    if dry_run {
+        let plan = migrate::plan(&mut source_client, &mut dest_client, &pattern).await?;
+        if plan.keys.is_empty() {
+            print_no_keys(&pattern);
+            return Ok(());
+        }
+
+        // Only the source is measured; report how the destination would
+        // treat keys it already has
+        dry_run::summarize(
+            &mut source_client,
+            &plan.keys,
+            &format!("migrating to '{}'", dest),
+        )
+        .await?;
+        if plan.existing > 0 {
+            let outcome = if replace { "overwritten" } else { "skipped" };
+            println!(
+                "{}",
+                format!(
+                    "{} keys already exist on '{}' and would be {}",
+                    plan.existing, dest, outcome
+                )
+                .yellow()
+            );
+        }
+        dry_run::print_no_changes();
+        return Ok(());
+    }
+
+    let progress = spinner();
+    let report = migrate::run(
+        &mut source_client,
+        &mut dest_client,
+        &pattern,
+        replace,
+        |scanned| progress.set_message(format!("Migrating keys... {} scanned", scanned)),
+    )
+    .await?;
+    progress.finish_and_clear();
+
+    for (key, error) in &report.errors {
+        println!("{}", format!("Error migrating '{}': {}", key, error).red());
+    }
+    for key in &report.unverified {
+        println!("{}", format!("Verification failed for '{}'", key).red());
+    }
+
+    if report.scanned == 0 {
+        print_no_keys(&pattern);
+        return Ok(());
+    }
+
+    let totals = report.totals();
+    let rows: Vec<SummaryRow> = report
+        .by_type
+        .into_iter()
+        .map(|(key_type, stats)| SummaryRow {
+            key_type,
+            migrated: stats.migrated,
+            skipped: stats.skipped,
+            failed: stats.failed,
+            verify_failed: stats.verify_failed,
+        })
+        .collect();
+
+    println!("{}", "Migration Summary:".bold());
+    println!("{}", Table::new(rows));
+
+    if totals.failed > 0 || totals.verify_failed > 0 {
+        println!(
+            "{}",
+            format!(
+                "⚠ {} keys failed and {} failed verification",
+                totals.failed, totals.verify_failed
+            )
+            .red()
+            .bold()
+        );
+    } else {
+        success(&format!("Migrated and verified {} keys", totals.migrated));
+    }
+    if totals.skipped > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} keys already existed on the destination (use --replace to overwrite)",
+                totals.skipped
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+fn print_no_keys(pattern: &str) {
+    println!(
+        "{}",
+        format!("No keys found matching pattern: {}", pattern).yellow()
+    );
+}
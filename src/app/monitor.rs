@@ -0,0 +1,248 @@
+use chrono::{DateTime, Local};
+use colored::*;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tabled::{Table, Tabled};
+
+use solt::commands::monitor::{self, Capture, CaptureOptions, MonitorEvent, MonitorFilter};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::format::format_micros;
+use solt::redis_client::{RedisClient, SlowLogEntry};
+
+use crate::output::{print_structured, rule, spinner, success, thin_rule};
+
+/// Print every matching command the server processes until Ctrl+C.
+pub async fn run(
+    filter: MonitorFilter,
+    capture: Option<CaptureOptions>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let capture = capture.map(Capture::open).transpose()?;
+    let capture_path = capture.as_ref().map(|c| c.path().to_string());
+
+    let client = RedisClient::for_environment(environment.as_deref()).await?;
+    let summary = monitor::run(
+        client,
+        &filter,
+        capture,
+        || {
+            println!("{}", "Starting Redis MONITOR...".yellow().bold());
+            println!(
+                "{}",
+                "MONITOR can noticeably slow down a busy server; keep sessions short".yellow()
+            );
+            if let Some(path) = &capture_path {
+                println!("{}", format!("Capturing to {}", path).cyan());
+            }
+            println!("{}", "Press Ctrl+C to stop".cyan());
+            rule(80);
+        },
+        print_event,
+    )
+    .await?;
+
+    println!(
+        "{}",
+        format!(
+            "Monitor stopped ({} of {} commands shown)",
+            summary.shown, summary.seen
+        )
+        .green()
+    );
+    Ok(())
+}
+
+#[derive(Tabled)]
+struct TrafficRow {
+    #[tabled(rename = "#")]
+    rank: usize,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Count")]
+    count: u64,
+    #[tabled(rename = "Share")]
+    share: String,
+    #[tabled(rename = "Ops/sec")]
+    rate: String,
+}
+
+/// Sample MONITOR traffic and print the busiest commands, keys and clients.
+pub async fn traffic_stats(
+    filter: MonitorFilter,
+    duration: Duration,
+    top: usize,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let progress = spinner();
+    let result = monitor::traffic_stats(client, &filter, duration, top, |total, elapsed| {
+        progress.set_message(format!(
+            "Sampling traffic: {} commands in {:.0}s",
+            total, elapsed
+        ))
+    })
+    .await;
+    progress.finish_and_clear();
+    let report = result?;
+    if print_structured(&config.output_format, &report)? {
+        return Ok(());
+    }
+
+    let (seconds, total) = (report.seconds, report.commands);
+    println!("{}", "Traffic Statistics:".bold());
+    rule(50);
+    println!("Sampled: {}", format!("{:.1}s", seconds).cyan());
+    println!("Commands: {}", report.commands.to_string().cyan());
+    println!(
+        "Throughput: {}",
+        format!("{:.1} ops/sec", report.ops_per_sec).cyan()
+    );
+    if total == 0 {
+        println!("{}", "No matching commands were seen".yellow());
+        return Ok(());
+    }
+
+    let table = |title: &str, counts: &[(String, u64)]| {
+        let rows: Vec<TrafficRow> = counts
+            .iter()
+            .enumerate()
+            .map(|(i, (name, count))| TrafficRow {
+                rank: i + 1,
+                name: name.clone(),
+                count: *count,
+                share: format!("{:.1}%", *count as f64 / total as f64 * 100.0),
+                rate: format!("{:.1}", *count as f64 / seconds),
+            })
+            .collect();
+        if !rows.is_empty() {
+            println!("\n{}", title.bold());
+            println!("{}", Table::new(rows));
+        }
+    };
+    table("Top commands:", &report.top_commands);
+    table("Top keys:", &report.top_keys);
+    table("Top clients:", &report.top_clients);
+
+    Ok(())
+}
+
+fn print_event(event: &MonitorEvent) {
+    let time = DateTime::from_timestamp(
+        event.timestamp.trunc() as i64,
+        (event.timestamp.fract() * 1e9) as u32,
+    )
+    .map(|t| t.with_timezone(&Local).format("%H:%M:%S%.6f").to_string())
+    .unwrap_or_else(|| event.timestamp.to_string());
+    let args: Vec<String> = event.args.iter().map(|arg| format!("{:?}", arg)).collect();
+    println!(
+        "{} {} {} {}",
+        time.dimmed(),
+        format!("[{} {}]", event.db, event.client).dimmed(),
+        event.command.cyan().bold(),
+        args.join(" ")
+    );
+}
+
+pub async fn slowlog_get(
+    count: Option<usize>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let entries = monitor::slowlog_get(&mut client, count.unwrap_or(10)).await?;
+
+    if entries.is_empty() {
+        println!("{}", "No slow log entries found".yellow());
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Slow Log Entries (showing {}):", entries.len()).bold()
+    );
+    rule(80);
+
+    for entry in entries {
+        print_slowlog_entry(&entry);
+    }
+
+    Ok(())
+}
+
+pub async fn slowlog_follow(
+    interval: Duration,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    println!("{}", "Following slow log...".yellow().bold());
+    println!("{}", "Press Ctrl+C to stop".cyan());
+    rule(80);
+
+    monitor::slowlog_follow(&mut client, interval, print_slowlog_entry).await?;
+
+    println!("{}", "Stopped following slow log".green());
+    Ok(())
+}
+
+pub async fn slowlog_reset(environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    monitor::slowlog_reset(&mut client).await?;
+
+    success("Slow log reset");
+    Ok(())
+}
+
+fn print_slowlog_entry(entry: &SlowLogEntry) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+        - entry.timestamp;
+
+    let time_str = if timestamp < 60 {
+        format!("{}s ago", timestamp)
+    } else if timestamp < 3600 {
+        format!("{}m ago", timestamp / 60)
+    } else {
+        format!("{}h ago", timestamp / 3600)
+    };
+
+    println!("{}", format!("ID: {}", entry.id).cyan());
+    println!("  Time: {}", time_str.yellow());
+    // SLOWLOG reports execution time in microseconds
+    println!("  Duration: {}", format_micros(entry.duration).red());
+    println!("  Command: {}", entry.command);
+    thin_rule(40);
+}
+
+pub async fn client_list(environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let clients = monitor::client_list(&mut client).await?;
+
+    if clients.is_empty() {
+        println!("{}", "No clients found".yellow());
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Connected Clients ({}):", clients.len()).bold()
+    );
+    rule(80);
+
+    for client_info in clients {
+        println!("{}", format!("Client ID: {}", client_info.id).cyan().bold());
+        println!("  Address: {}", client_info.addr);
+        println!("  Database: {}", client_info.db);
+        println!("  Age: {}", client_info.age);
+        println!("  Idle: {}", client_info.idle);
+        println!("  Flags: {}", client_info.flags);
+        println!("  Command: {}", client_info.cmd);
+        println!("  Memory: {} bytes", client_info.omem);
+        thin_rule(40);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use chrono::Local;
+use colored::*;
+use futures::StreamExt;
+use std::io::{self, Write};
+
+use solt::commands::notify::{self, NotifyOptions};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{rule, success};
+
+pub async fn run(options: NotifyOptions, environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let redis_config = config.redis_config(&env_name)?;
+    let (patterns, required) = notify::channels(&options, redis_config.db);
+
+    let mut client = RedisClient::connect(redis_config).await?;
+
+    let current = notify::current_flags(&mut client).await?;
+    if !notify::covers_events(&current, &required) {
+        println!(
+            "{}",
+            format!(
+                "⚠ notify-keyspace-events is '{}', which does not publish the requested events",
+                current
+            )
+            .yellow()
+            .bold()
+        );
+
+        let enable = options.enable || {
+            print!("Enable notify-keyspace-events '{}'? [y/N]: ", required);
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            input.trim().eq_ignore_ascii_case("y")
+        };
+
+        if enable {
+            let merged = notify::enable(&mut client, &current, &required).await?;
+            success(&format!("notify-keyspace-events set to '{}'", merged));
+        } else {
+            println!(
+                "{}",
+                "Continuing without enabling notifications; no events may arrive".yellow()
+            );
+        }
+    }
+
+    let mut pubsub = notify::subscribe(client, &patterns).await?;
+
+    println!(
+        "{}",
+        format!("Listening on: {}", patterns.join(", "))
+            .cyan()
+            .bold()
+    );
+    println!("{}", "Press Ctrl+C to stop".cyan());
+    rule(80);
+
+    let mut messages = pubsub.on_message();
+    loop {
+        tokio::select! {
+            message = messages.next() => {
+                let message = match message {
+                    Some(message) => message,
+                    None => break,
+                };
+                let channel = message.get_channel_name().to_string();
+                let payload: String = message.get_payload().unwrap_or_default();
+                print_event(&channel, &payload);
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    println!("{}", "Stopped listening".green());
+    Ok(())
+}
+
+fn print_event(channel: &str, payload: &str) {
+    let time = Local::now().format("%H:%M:%S%.3f");
+    let (event, key) = notify::parse_event(channel, payload);
+
+    let event = match event.as_str() {
+        "expired" => event.yellow(),
+        "evicted" | "del" | "unlink" => event.red(),
+        _ => event.green(),
+    };
+    println!("[{}] {} {}", time, event.bold(), key.cyan());
+}
@@ -0,0 +1,101 @@
+use colored::*;
+use std::time::Duration;
+
+use solt::commands::ping::{self, PingSummary};
+use solt::config::{AppConfig, OutputFormat};
+use solt::error::AppError;
+use solt::format::format_micros;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, rule};
+
+/// Send `count` PINGs `interval` apart and report round-trip times. Failed
+/// PINGs are counted as lost rather than aborting the run; Ctrl+C stops
+/// early and still prints the summary.
+pub async fn run(
+    count: u64,
+    interval: Duration,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let redis_config = config.redis_config(&env_name)?;
+    let address = format!("{}:{}", redis_config.host, redis_config.port);
+
+    let mut client = RedisClient::connect(redis_config).await?;
+    let structured = matches!(
+        config.output_format,
+        OutputFormat::Json | OutputFormat::Yaml
+    );
+
+    if !structured {
+        println!("{}", format!("PING {} ({})", address, env_name).bold());
+    }
+
+    let mut times: Vec<u64> = Vec::with_capacity(count as usize);
+    let mut sent = 0;
+    for seq in 1..=count {
+        let reply = ping::ping(&mut client).await;
+        sent += 1;
+
+        match reply {
+            Ok(elapsed) => {
+                times.push(elapsed);
+                if !structured {
+                    println!(
+                        "reply from {}: seq={} time={}",
+                        address,
+                        seq,
+                        format_micros(elapsed as i64).cyan()
+                    );
+                }
+            }
+            Err(e) if !structured => println!("{}", format!("seq={} failed: {}", seq, e).red()),
+            Err(_) => {}
+        }
+
+        if seq < count {
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+    }
+
+    let summary = PingSummary::new(sent, times);
+
+    if structured {
+        print_structured(&config.output_format, &summary)?;
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", "Round-trip statistics:".bold());
+    rule(50);
+    let loss = summary.lost as f64 / summary.sent.max(1) as f64 * 100.0;
+    let loss = format!("{:.1}% loss", loss);
+    println!(
+        "{} sent, {} received, {}",
+        summary.sent,
+        summary.received,
+        if summary.lost == 0 {
+            loss.green()
+        } else {
+            loss.red()
+        }
+    );
+    let time = |micros: Option<u64>| {
+        micros
+            .map(|m| format_micros(m as i64))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    println!(
+        "min/avg/p99/max = {}/{}/{}/{}",
+        time(summary.min).cyan(),
+        time(summary.avg).cyan(),
+        time(summary.p99).cyan(),
+        time(summary.max).cyan()
+    );
+
+    Ok(())
+}
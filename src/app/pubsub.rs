@@ -0,0 +1,177 @@
+use chrono::Local;
+use colored::*;
+use std::time::Duration;
+use tabled::{Table, Tabled};
+
+use solt::commands::pubsub::{self, ChannelCount};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::format::display_key;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, progress_bar, rule, success};
+
+#[derive(Tabled)]
+struct ChannelRow {
+    #[tabled(rename = "Channel")]
+    channel: String,
+    #[tabled(rename = "Subscribers")]
+    subscribers: u64,
+}
+
+/// Publish messages and print how many subscribers received them.
+pub async fn publish(
+    channel: String,
+    messages: Vec<Vec<u8>>,
+    repeat: u64,
+    interval: Duration,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let total = messages.len() as u64 * repeat;
+    if total == 0 {
+        println!("{}", "No messages to publish".yellow());
+        return Ok(());
+    }
+    if total == 1 {
+        let report = pubsub::publish(&mut client, &channel, &messages, 1, interval, || {}).await?;
+        success(&format!(
+            "Published to '{}' ({} subscribers received it)",
+            channel, report.receivers
+        ));
+        if report.receivers == 0 {
+            println!("{}", "No one is subscribed to this channel".yellow());
+        }
+        return Ok(());
+    }
+
+    let progress = progress_bar(total);
+    let result = pubsub::publish(&mut client, &channel, &messages, repeat, interval, || {
+        progress.inc(1)
+    })
+    .await;
+    progress.finish_and_clear();
+    let report = result?;
+
+    success(&format!(
+        "Published {} messages to '{}' in {:.1}s ({:.0} msg/sec)",
+        report.published,
+        channel,
+        report.seconds,
+        report.published as f64 / report.seconds
+    ));
+    println!(
+        "Deliveries: {} (avg {:.1} subscribers per message)",
+        report.receivers.to_string().cyan(),
+        report.receivers as f64 / report.published.max(1) as f64
+    );
+
+    Ok(())
+}
+
+/// Print messages arriving on `channels` until Ctrl+C.
+pub async fn subscribe(channels: Vec<String>, environment: Option<String>) -> Result<(), AppError> {
+    let client = RedisClient::for_environment(environment.as_deref()).await?;
+    pubsub::subscribe(
+        client,
+        &channels,
+        || {
+            println!(
+                "{}",
+                format!("Subscribed to: {}", channels.join(", "))
+                    .cyan()
+                    .bold()
+            );
+            println!("{}", "Press Ctrl+C to stop".cyan());
+            rule(80);
+        },
+        |channel, payload| {
+            println!(
+                "[{}] {} {}",
+                Local::now().format("%H:%M:%S%.3f"),
+                channel.cyan().bold(),
+                display_key(payload)
+            );
+        },
+    )
+    .await?;
+
+    println!("{}", "Unsubscribed".green());
+    Ok(())
+}
+
+/// List active channels matching `pattern` with their subscriber counts.
+pub async fn channels(pattern: String, environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let active = pubsub::channels(&mut client, &pattern).await?;
+
+    if print_structured(&config.output_format, &active.channels)? {
+        return Ok(());
+    }
+
+    if active.channels.is_empty() {
+        println!(
+            "{}",
+            format!("No active channels matching '{}'", pattern).yellow()
+        );
+    } else {
+        println!("{}", Table::new(channel_rows(&active.channels)));
+        println!(
+            "Total: {} channels",
+            active.channels.len().to_string().cyan()
+        );
+    }
+    if active.patterns > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} pattern subscriptions are active and not counted above",
+                active.patterns
+            )
+            .dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Show how many clients are subscribed to each of `channels`.
+pub async fn numsub(channels: Vec<String>, environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let counts = pubsub::numsub(&mut client, &channels).await?;
+    if print_structured(&config.output_format, &counts)? {
+        return Ok(());
+    }
+
+    println!("{}", Table::new(channel_rows(&counts)));
+    let silent: Vec<&str> = counts
+        .iter()
+        .filter(|count| count.subscribers == 0)
+        .map(|count| count.channel.as_str())
+        .collect();
+    if !silent.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "No subscribers on: {} (messages published there are dropped)",
+                silent.join(", ")
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+fn channel_rows(counts: &[ChannelCount]) -> Vec<ChannelRow> {
+    counts
+        .iter()
+        .map(|count| ChannelRow {
+            channel: count.channel.clone(),
+            subscribers: count.subscribers,
+        })
+        .collect()
+}
@@ -0,0 +1,142 @@
+use colored::*;
+use serde_json::Value as JsonValue;
+
+use solt::commands::queue::{self, QueueItem, QueueOptions};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::format::format_seconds;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, progress_bar, rule, spinner, success};
+
+/// Print a list or stream as a job queue: its length, the oldest and newest
+/// items, and how fast it grows or drains.
+pub async fn run(
+    key: String,
+    options: QueueOptions,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let mut sampling = None;
+    let result = queue::run(&mut client, key, &options, || {
+        let spinner = spinner();
+        spinner.set_message(format!(
+            "Sampling length for {}s...",
+            options.sample.as_secs_f64()
+        ));
+        sampling = Some(spinner);
+    })
+    .await;
+    if let Some(spinner) = sampling {
+        spinner.finish_and_clear();
+    }
+    let report = result?;
+    if print_structured(&config.output_format, &report)? {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Queue: {} ({})", report.key, report.kind).bold()
+    );
+    rule(50);
+    println!("Length: {}", report.length.to_string().cyan());
+    if let Some(age) = report.oldest.first().and_then(|item| item.age_seconds) {
+        println!("Oldest item age: {}", format_seconds(age).cyan());
+    }
+    if let Some(rate) = report.growth_per_second {
+        let trend = if rate > 0.0 {
+            format!("+{:.2}/s (growing)", rate).red()
+        } else if rate < 0.0 {
+            let eta = report.length as f64 / -rate;
+            format!(
+                "{:.2}/s (draining, empty in ~{})",
+                rate,
+                format_seconds(eta as u64)
+            )
+            .green()
+        } else {
+            "0/s (steady)".normal()
+        };
+        println!("Growth: {}", trend);
+    }
+
+    if report.length == 0 {
+        println!("{}", "Queue is empty".yellow());
+        return Ok(());
+    }
+
+    println!("\n{}", "Oldest:".bold());
+    print_items(&report.oldest);
+    // A short queue would repeat the same items under both headings
+    if report.length > report.oldest.len() {
+        println!("\n{}", "Newest:".bold());
+        print_items(&report.newest);
+    }
+
+    Ok(())
+}
+
+/// Move the oldest items of a list onto another queue.
+pub async fn requeue(
+    key: String,
+    dest: String,
+    count: Option<usize>,
+    rpush: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let total = queue::pending(&mut client, &key, count).await?;
+
+    let progress = progress_bar(total as u64);
+    let moved = queue::requeue(&mut client, &key, &dest, total, rpush, |done| {
+        progress.inc(done as u64)
+    })
+    .await?;
+    progress.finish_and_clear();
+
+    success(&format!(
+        "Requeued {} items from '{}' onto '{}'",
+        moved, key, dest
+    ));
+    Ok(())
+}
+
+/// Pop the oldest items of a list and print each payload on its own line.
+pub async fn drain(
+    key: String,
+    count: Option<usize>,
+    rpush: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let total = queue::pending(&mut client, &key, count).await?;
+
+    let drained =
+        queue::drain(&mut client, &key, total, rpush, |item| println!("{}", item)).await?;
+
+    success(&format!("Drained {} items from '{}'", drained, key));
+    Ok(())
+}
+
+fn print_items(items: &[QueueItem]) {
+    for (i, item) in items.iter().enumerate() {
+        let mut label = format!("{}.", i + 1);
+        if let Some(id) = &item.id {
+            label.push_str(&format!(" {}", id));
+        }
+        if let Some(age) = item.age_seconds {
+            label.push_str(&format!(" ({} ago)", format_seconds(age)));
+        }
+        println!("{}", label.cyan());
+        match &item.payload {
+            JsonValue::String(raw) => println!("{}", raw),
+            json => println!(
+                "{}",
+                serde_json::to_string_pretty(json).unwrap_or_else(|_| json.to_string())
+            ),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use log::info;
+
+use solt::error::AppError;
+use solt::format::format_resp;
+use solt::redis_client::RedisClient;
+
+pub async fn run(args: Vec<String>, environment: Option<String>) -> Result<(), AppError> {
+    info!("Running raw command: {:?}", args);
+
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let reply = client.raw_command(&args).await?;
+    println!("{}", format_resp(&reply));
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use solt::commands::rename;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+pub async fn run(
+    old: String,
+    new: String,
+    force: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    rename::run(&mut client, &old, &new, force).await?;
+    success(&format!("Renamed '{}' to '{}'", old, new));
+
+    Ok(())
+}
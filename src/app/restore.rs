@@ -0,0 +1,92 @@
+use colored::*;
+use log::info;
+use std::fs;
+
+use solt::cli::ConflictMode;
+use solt::commands::export::ExportFile;
+use solt::commands::restore;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{progress_bar, success};
+
+pub async fn run(
+    file: String,
+    pattern: Option<String>,
+    prefix: Option<String>,
+    on_conflict: ConflictMode,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    info!("Restoring keys from {}", file);
+
+    let export: ExportFile = serde_json::from_str(&fs::read_to_string(&file)?)?;
+
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let mut client = RedisClient::connect(config.redis_config(&env_name)?).await?;
+
+    println!(
+        "{}",
+        format!(
+            "Restoring {} keys exported from '{}' at {} into '{}'",
+            export.keys.len(),
+            export.environment,
+            export.exported_at.format("%Y-%m-%d %H:%M:%S"),
+            env_name
+        )
+        .cyan()
+        .bold()
+    );
+
+    let plan = restore::plan(
+        &mut client,
+        export.keys,
+        pattern.as_deref(),
+        prefix.as_deref(),
+    )
+    .await?;
+
+    if plan.keys.is_empty() {
+        println!("{}", "No keys to restore".yellow());
+        return Ok(());
+    }
+
+    if let Err(e) = plan.check(on_conflict) {
+        for name in plan.conflicts().into_iter().take(10) {
+            println!("  • {}", name);
+        }
+        return Err(e);
+    }
+
+    let progress = progress_bar(plan.keys.len() as u64);
+    progress.set_message("Restoring keys...");
+    let report = restore::apply(&mut client, &plan, on_conflict, || progress.inc(1)).await?;
+    progress.finish_and_clear();
+
+    for (key, key_type) in &report.failed {
+        println!(
+            "{}",
+            format!(
+                "Cannot restore '{}': unsupported or malformed {} value",
+                key, key_type
+            )
+            .red()
+        );
+    }
+    success(&format!("Restored {} keys", report.restored));
+    if report.skipped > 0 {
+        println!(
+            "{}",
+            format!("Skipped {} existing keys", report.skipped).yellow()
+        );
+    }
+    if !report.failed.is_empty() {
+        println!(
+            "{}",
+            format!("Failed to restore {} keys", report.failed.len()).red()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::scripting;
+use solt::error::AppError;
+use solt::format::format_resp;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+#[derive(Tabled)]
+struct FunctionRow {
+    #[tabled(rename = "Library")]
+    library: String,
+    #[tabled(rename = "Engine")]
+    engine: String,
+    #[tabled(rename = "Function")]
+    function: String,
+    #[tabled(rename = "Description")]
+    description: String,
+    #[tabled(rename = "Flags")]
+    flags: String,
+}
+
+pub async fn eval(
+    script: Option<String>,
+    sha: Option<String>,
+    keys: Vec<String>,
+    args: Vec<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let reply =
+        scripting::eval(&mut client, script.as_deref(), sha.as_deref(), &keys, &args).await?;
+
+    println!("{}", "Result:".bold());
+    println!("{}", format_resp(&reply));
+
+    Ok(())
+}
+
+pub async fn script_load(path: String, environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let sha = scripting::script_load(&mut client, &path).await?;
+
+    success(&format!("Loaded script '{}'", path));
+    println!("SHA1: {}", sha.cyan());
+    println!("{}", format!("Run it with: solt eval --sha {}", sha).cyan());
+
+    Ok(())
+}
+
+pub async fn script_exists(shas: Vec<String>, environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let exists = scripting::script_exists(&mut client, &shas).await?;
+
+    for (sha, exists) in shas.iter().zip(exists) {
+        if exists {
+            println!("{} {}", "✓".green(), sha);
+        } else {
+            println!("{} {}", "✗".red(), sha);
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn script_flush(environment: Option<String>, confirm: bool) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    if !confirm {
+        println!(
+            "{}",
+            "WARNING: This will remove ALL cached Lua scripts!"
+                .red()
+                .bold()
+        );
+        println!("{}", "Use --confirm to proceed".red());
+        return Ok(());
+    }
+
+    scripting::script_flush(&mut client).await?;
+
+    success("Script cache flushed");
+    Ok(())
+}
+
+pub async fn function_list(
+    pattern: Option<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let libraries = scripting::function_list(&mut client, pattern.as_deref()).await?;
+
+    if libraries.is_empty() {
+        println!("{}", "No function libraries loaded".yellow());
+        return Ok(());
+    }
+
+    let mut rows = Vec::new();
+    for library in &libraries {
+        for function in &library.functions {
+            rows.push(FunctionRow {
+                library: library.name.clone(),
+                engine: library.engine.clone(),
+                function: function.name.clone(),
+                description: function.description.clone().unwrap_or_default(),
+                flags: function.flags.join(", "),
+            });
+        }
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Function libraries ({}), {} functions:",
+            libraries.len(),
+            rows.len()
+        )
+        .bold()
+    );
+    println!("{}", Table::new(rows));
+
+    Ok(())
+}
+
+pub async fn function_load(
+    path: String,
+    replace: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let library = scripting::function_load(&mut client, &path, replace).await?;
+
+    success(&format!("Loaded library '{}' from '{}'", library, path));
+
+    Ok(())
+}
+
+pub async fn function_delete(library: String, environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    scripting::function_delete(&mut client, &library).await?;
+
+    success(&format!("Deleted library '{}'", library));
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use colored::*;
+
+use solt::commands::search;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, spinner};
+
+/// Longest value shown next to a hash hit before it is truncated
+const VALUE_PREVIEW: usize = 60;
+
+pub async fn run(
+    pattern: String,
+    count_only: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let progress = spinner();
+    let keys = search::run(&mut client, &pattern, |matches| {
+        progress.set_message(format!("{} matches so far", matches));
+    })
+    .await?;
+    progress.finish_and_clear();
+
+    if count_only {
+        println!("{}", keys.len());
+        return Ok(());
+    }
+
+    if print_structured(&config.output_format, &keys)? {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Found {} keys matching '{}'", keys.len(), pattern)
+            .cyan()
+            .bold()
+    );
+    if keys.is_empty() {
+        println!("{}", "No keys found.".yellow());
+    }
+    for key in keys {
+        println!("• {}", key.cyan());
+    }
+
+    Ok(())
+}
+
+/// Report each hash field matching `pattern` as `key -> field`.
+pub async fn in_hashes(
+    pattern: String,
+    key_pattern: String,
+    count_only: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let progress = spinner();
+    let result = search::in_hashes(&mut client, &pattern, &key_pattern, |searched, hits| {
+        progress.set_message(format!("Searched {} hashes, {} hits", searched, hits));
+    })
+    .await?;
+    progress.finish_and_clear();
+    let hits = result.hits;
+
+    if count_only {
+        println!("{}", hits.len());
+        return Ok(());
+    }
+
+    if print_structured(&config.output_format, &hits)? {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Found {} fields matching '{}' in {} hashes",
+            hits.len(),
+            pattern,
+            result.hashes_searched
+        )
+        .cyan()
+        .bold()
+    );
+    if hits.is_empty() {
+        println!("{}", "No matches found.".yellow());
+    }
+    for hit in hits {
+        let value = if hit.value.chars().count() > VALUE_PREVIEW {
+            let preview: String = hit.value.chars().take(VALUE_PREVIEW).collect();
+            format!("{}...", preview)
+        } else {
+            hit.value
+        };
+        println!("• {} -> {} = {}", hit.key.cyan(), hit.field.yellow(), value);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use chrono::Local;
+use colored::*;
+use futures::StreamExt;
+use log::info;
+use std::time::Duration;
+
+use solt::commands::sentinel;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{rule, success};
+
+pub async fn run() -> Result<(), AppError> {
+    info!("Sentinel command - placeholder");
+    println!("{}", "Sentinel command - not yet implemented".yellow());
+    Ok(())
+}
+
+/// Trigger a failover on the sentinel of this environment. With `wait`,
+/// poll until the sentinel announces a different master address or the
+/// timeout passes.
+pub async fn failover(
+    master: String,
+    wait: Option<Duration>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let before = sentinel::master_addr(&mut client, &master).await?;
+    println!(
+        "Current master of '{}': {}",
+        master,
+        format!("{}:{}", before.0, before.1).cyan()
+    );
+
+    sentinel::failover(&mut client, &master).await?;
+    success(&format!("Failover of '{}' started", master));
+
+    let Some(timeout) = wait else {
+        println!("{}", "Follow progress with: solt sentinel events".cyan());
+        return Ok(());
+    };
+
+    let (after, elapsed) =
+        sentinel::wait_for_new_master(&mut client, &master, &before, timeout).await?;
+    success(&format!(
+        "New master of '{}': {}:{} (after {:.1}s)",
+        master,
+        after.0,
+        after.1,
+        elapsed.as_secs_f64()
+    ));
+
+    Ok(())
+}
+
+/// Print sentinel events until Ctrl+C, optionally limited to `filter`.
+pub async fn events(filter: Vec<String>, environment: Option<String>) -> Result<(), AppError> {
+    let client = RedisClient::for_environment(environment.as_deref()).await?;
+    let mut pubsub = sentinel::subscribe(client, &filter).await?;
+
+    println!(
+        "{}",
+        format!(
+            "Listening for sentinel events: {}",
+            if filter.is_empty() {
+                "all".to_string()
+            } else {
+                filter.join(", ")
+            }
+        )
+        .cyan()
+        .bold()
+    );
+    println!("{}", "Press Ctrl+C to stop".cyan());
+    rule(80);
+
+    let mut messages = pubsub.on_message();
+    loop {
+        tokio::select! {
+            message = messages.next() => {
+                let message = match message {
+                    Some(message) => message,
+                    None => break,
+                };
+                let event = message.get_channel_name().to_string();
+                let payload: String = message.get_payload().unwrap_or_default();
+                print_event(&event, &payload);
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    println!("{}", "Stopped listening".green());
+    Ok(())
+}
+
+fn print_event(event: &str, payload: &str) {
+    let time = Local::now().format("%H:%M:%S%.3f");
+    let colored = match event {
+        "+switch-master" | "+failover-end" => event.yellow().bold(),
+        "+sdown" | "+odown" | "-failover-abort-not-elected" => event.red().bold(),
+        "-sdown" | "-odown" => event.green().bold(),
+        e if e.starts_with("+failover") || e == "+try-failover" => event.yellow(),
+        _ => event.normal(),
+    };
+    println!("[{}] {} {}", time, colored, payload);
+}
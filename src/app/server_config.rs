@@ -0,0 +1,70 @@
+use colored::*;
+use tabled::{Table, Tabled};
+
+use solt::commands::server_config;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+#[derive(Tabled)]
+struct ParameterRow {
+    #[tabled(rename = "Parameter")]
+    name: String,
+    #[tabled(rename = "Value")]
+    value: String,
+}
+
+pub async fn get(pattern: String, environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let params = server_config::get(&mut client, &pattern).await?;
+
+    if params.is_empty() {
+        println!(
+            "{}",
+            format!("No parameters matching '{}'", pattern).yellow()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Server parameters matching '{}':", pattern).bold()
+    );
+    let rows: Vec<ParameterRow> = params
+        .into_iter()
+        .map(|(name, value)| ParameterRow { name, value })
+        .collect();
+    println!("{}", Table::new(rows));
+
+    Ok(())
+}
+
+pub async fn set(
+    param: String,
+    value: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let previous = server_config::set(&mut client, &param, &value).await?;
+
+    success(&format!("Set '{}' to '{}'", param, value));
+    if let Some(previous) = previous {
+        println!("Previous value: {}", previous.yellow());
+    }
+    println!(
+        "{}",
+        "Run 'solt server-config rewrite' to persist the change to redis.conf".cyan()
+    );
+
+    Ok(())
+}
+
+pub async fn rewrite(environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    server_config::rewrite(&mut client).await?;
+
+    success("Configuration file rewritten (CONFIG REWRITE)");
+
+    Ok(())
+}
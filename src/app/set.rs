@@ -0,0 +1,216 @@
+use colored::*;
+use std::io::Read;
+
+use solt::cli::BulkFormat;
+use solt::commands::set;
+use solt::error::AppError;
+use solt::format::escape_bytes;
+use solt::redis_client::{RedisClient, SetOptions};
+
+use crate::output::{spinner, success};
+
+/// Resolve the value of a write: inline, piped through stdin or read from a
+/// file. Stdin and file contents are used as-is, including any trailing
+/// newline, so binary data survives unchanged.
+pub fn read_value(
+    value: Option<String>,
+    stdin: bool,
+    file: Option<String>,
+) -> Result<Vec<u8>, AppError> {
+    if stdin {
+        let mut buffer = Vec::new();
+        std::io::stdin().read_to_end(&mut buffer)?;
+        Ok(buffer)
+    } else if let Some(path) = file {
+        std::fs::read(&path).map_err(|e| {
+            AppError::Io(std::io::Error::new(
+                e.kind(),
+                format!("Cannot read '{}': {}", path, e),
+            ))
+        })
+    } else {
+        Ok(value.unwrap_or_default().into_bytes())
+    }
+}
+
+pub async fn run(
+    key: String,
+    value: Vec<u8>,
+    environment: Option<String>,
+    options: SetOptions,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let outcome = set::run(&mut client, &key, &value, &options).await?;
+
+    if options.get {
+        match &outcome.previous {
+            Some(previous) => println!("Previous value: {}", escape_bytes(previous)),
+            None => println!("Previous value: {}", "(none)".dimmed()),
+        }
+    }
+
+    if !outcome.written {
+        return match options.condition {
+            Some("XX") => Err(AppError::NotFound(format!(
+                "Key '{}' does not exist; not set (--xx)",
+                key
+            ))),
+            _ => Err(AppError::Aborted(format!(
+                "Key '{}' already exists; not set (--nx)",
+                key
+            ))),
+        };
+    }
+
+    success(&format!(
+        "Successfully set key '{}' ({} bytes)",
+        key,
+        value.len()
+    ));
+    if let Some(ttl) = options.ttl {
+        println!("TTL: {} seconds", ttl.as_secs().to_string().cyan());
+    } else if options.keep_ttl {
+        println!("TTL: {}", "kept".cyan());
+    }
+
+    Ok(())
+}
+
+pub async fn multi(
+    pairs: Vec<String>,
+    environment: Option<String>,
+    ttl: Option<u64>,
+) -> Result<(), AppError> {
+    let parsed = set::parse_pairs(&pairs)?;
+
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    set::multi(&mut client, &parsed, ttl).await?;
+
+    success(&format!("Successfully set {} keys", parsed.len()));
+    if let Some(ttl) = ttl {
+        println!("TTL: {} seconds", ttl.to_string().cyan());
+    }
+
+    Ok(())
+}
+
+pub async fn set_hash_field(
+    key: String,
+    field: String,
+    value: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    set::set_hash_field(&mut client, &key, &field, &value).await?;
+
+    success(&format!("Successfully set hash field '{}:{}'", key, field));
+
+    Ok(())
+}
+
+pub async fn push_list(
+    key: String,
+    value: Vec<u8>,
+    environment: Option<String>,
+    left: bool,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let new_length = set::push_list(&mut client, &key, &value, left).await?;
+
+    let direction = if left { "left" } else { "right" };
+    success(&format!(
+        "Successfully pushed to {} of list '{}' (new length: {})",
+        direction, key, new_length
+    ));
+
+    Ok(())
+}
+
+pub async fn add_to_set(
+    key: String,
+    member: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let was_new = set::add_to_set(&mut client, &key, &member).await?;
+
+    if was_new {
+        success(&format!(
+            "Successfully added new member '{}' to set '{}'",
+            member, key
+        ));
+    } else {
+        println!(
+            "{}",
+            format!("Member '{}' already exists in set '{}'", member, key).yellow()
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn add_to_sorted_set(
+    key: String,
+    member: String,
+    score: f64,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let was_new = set::add_to_sorted_set(&mut client, &key, &member, score).await?;
+
+    if was_new {
+        success(&format!(
+            "Successfully added new member '{}' to sorted set '{}' with score {}",
+            member, key, score
+        ));
+    } else {
+        println!(
+            "{}",
+            format!(
+                "Updated member '{}' in sorted set '{}' with score {}",
+                member, key, score
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Seed keys from JSON lines or CSV on stdin, reporting records that were
+/// skipped.
+pub async fn bulk_stdin(
+    format: BulkFormat,
+    environment: Option<String>,
+    default_ttl: Option<u64>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let progress = spinner();
+    let result = set::bulk(
+        &mut client,
+        std::io::stdin().lock(),
+        format,
+        default_ttl,
+        |written| {
+            progress.set_message(format!("{} keys written", written));
+            progress.tick();
+        },
+    )
+    .await;
+    progress.finish_and_clear();
+    let report = result?;
+
+    for (line, e) in &report.skipped {
+        println!("{}", format!("Skipping line {}: {}", line, e).yellow());
+    }
+    success(&format!("Successfully set {} keys", report.written));
+    if !report.skipped.is_empty() {
+        println!(
+            "{}",
+            format!("{} records skipped", report.skipped.len()).yellow()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use colored::*;
+
+use solt::cli::SetAlgebra;
+use solt::commands::set_ops;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, rule, success};
+
+/// Combine sets and print the members, only their number with `count`, or
+/// write them to `store`.
+pub async fn run(
+    operation: SetAlgebra,
+    keys: Vec<String>,
+    store: Option<String>,
+    count: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let title = match operation {
+        SetAlgebra::Union => "Union",
+        SetAlgebra::Inter => "Intersection",
+        SetAlgebra::Diff => "Difference",
+    };
+
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    for key in set_ops::missing_keys(&mut client, &keys).await? {
+        eprintln!(
+            "{}",
+            format!("⚠ Key '{}' does not exist, treating it as empty", key).yellow()
+        );
+    }
+
+    if let Some(destination) = store {
+        let stored = set_ops::store(&mut client, &operation, &keys, &destination).await?;
+        success(&format!(
+            "Stored the {} of {} keys in '{}' ({} members)",
+            title.to_lowercase(),
+            keys.len(),
+            destination,
+            stored
+        ));
+        if stored == 0 {
+            println!(
+                "{}",
+                format!("The result was empty, so '{}' was deleted", destination).yellow()
+            );
+        }
+        return Ok(());
+    }
+
+    let result = set_ops::run(&mut client, &operation, keys, count).await?;
+    if print_structured(&config.output_format, &result)? {
+        return Ok(());
+    }
+
+    if count {
+        println!("{}", result.count);
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "{} of {} ({} members):",
+            title,
+            result.keys.join(", "),
+            result.count
+        )
+        .bold()
+    );
+    rule(50);
+    for member in result.members.unwrap_or_default() {
+        println!("{}", member);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use colored::*;
+use indicatif::ProgressBar;
+use tabled::{Table, Tabled};
+
+use solt::commands::snapshot::{self, Snapshot};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{progress_bar, rule, success};
+
+#[derive(Tabled)]
+struct ChangeRow {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Change")]
+    change: String,
+    #[tabled(rename = "Before")]
+    before: String,
+    #[tabled(rename = "After")]
+    after: String,
+}
+
+pub async fn save(
+    file: String,
+    pattern: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let mut client = RedisClient::connect(config.redis_config(&env_name)?).await?;
+
+    let mut progress: Option<ProgressBar> = None;
+    let snapshot = Snapshot::take(&mut client, &env_name, &pattern, |done, total| {
+        progress
+            .get_or_insert_with(|| {
+                let bar = progress_bar(total);
+                bar.set_message("Capturing keys...");
+                bar
+            })
+            .set_position(done);
+    })
+    .await?;
+    if let Some(progress) = progress {
+        progress.finish_and_clear();
+    }
+
+    snapshot.save(&file)?;
+
+    success(&format!(
+        "Saved snapshot of {} keys to {}",
+        snapshot.keys.len(),
+        file
+    ));
+
+    Ok(())
+}
+
+pub async fn diff(before_file: String, after_file: String) -> Result<(), AppError> {
+    let before = Snapshot::load(&before_file)?;
+    let after = Snapshot::load(&after_file)?;
+
+    println!("{}", "Snapshot Diff:".bold());
+    rule(50);
+    println!(
+        "Before: {} ({} keys, {} at {})",
+        before_file.cyan(),
+        before.keys.len(),
+        before.environment,
+        before.taken_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    println!(
+        "After:  {} ({} keys, {} at {})",
+        after_file.cyan(),
+        after.keys.len(),
+        after.environment,
+        after.taken_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    if before.environment != after.environment {
+        println!(
+            "{}",
+            "Warning: snapshots were taken from different environments".yellow()
+        );
+    }
+    if before.pattern != after.pattern {
+        println!(
+            "{}",
+            format!(
+                "Warning: snapshots cover different patterns ('{}' vs '{}')",
+                before.pattern, after.pattern
+            )
+            .yellow()
+        );
+    }
+    println!();
+
+    let diff = snapshot::diff(&before, &after);
+    if diff.changes.is_empty() {
+        success("No changes between snapshots");
+        return Ok(());
+    }
+
+    let rows: Vec<ChangeRow> = diff
+        .changes
+        .into_iter()
+        .map(|change| ChangeRow {
+            key: change.key,
+            change: change.kind.as_str().to_string(),
+            before: change.before,
+            after: change.after,
+        })
+        .collect();
+    println!("{}", Table::new(rows));
+
+    println!("{}", "Summary:".bold());
+    rule(50);
+    println!("Added:   {}", diff.added.to_string().green());
+    println!("Removed: {}", diff.removed.to_string().red());
+    println!("Changed: {}", diff.changed.to_string().yellow());
+
+    Ok(())
+}
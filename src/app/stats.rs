@@ -0,0 +1,562 @@
+use chrono::Local;
+use colored::*;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+use tabled::{Table, Tabled};
+
+use solt::cli::CommandSort;
+use solt::commands::stats;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::format::{format_bytes, format_micros, format_seconds};
+use solt::redis_client::RedisClient;
+
+use crate::output::{clear_screen, print_structured, rule};
+
+/// Print the headline INFO metrics once, or redraw them every `watch`
+/// interval with rates since the previous refresh.
+pub async fn run(environment: Option<String>, watch: Option<Duration>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let mut client = RedisClient::connect(config.redis_config(&env_name)?).await?;
+
+    let interval = match watch {
+        Some(interval) => interval,
+        None => {
+            let info = stats::run(&mut client).await?;
+            let sorted: BTreeMap<&String, &String> = info.iter().collect();
+            if !print_structured(&config.output_format, &sorted)? {
+                print_overview(&info, None);
+            }
+            return Ok(());
+        }
+    };
+
+    let mut previous: Option<(HashMap<String, String>, Instant)> = None;
+    loop {
+        let info = stats::run(&mut client).await?;
+        let now = Instant::now();
+
+        // Clear the screen and redraw from the top
+        clear_screen();
+        println!(
+            "{}",
+            format!(
+                "Redis stats ({}) every {}s - last refresh {} (Ctrl+C to stop)",
+                env_name,
+                interval.as_secs_f64(),
+                Local::now().format("%H:%M:%S")
+            )
+            .cyan()
+            .bold()
+        );
+        let delta = previous
+            .as_ref()
+            .map(|(prev, at)| (prev, now.duration_since(*at)));
+        print_overview(&info, delta);
+        previous = Some((info, now));
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the headline INFO metrics. When a previous snapshot is given, rates
+/// are computed from the difference between the two snapshots.
+fn print_overview(
+    info: &HashMap<String, String>,
+    previous: Option<(&HashMap<String, String>, Duration)>,
+) {
+    let text = |name: &str| info.get(name).cloned().unwrap_or_else(|| "-".to_string());
+    let number = |map: &HashMap<String, String>, name: &str| {
+        map.get(name).and_then(|v| v.trim().parse::<f64>().ok())
+    };
+    let rate = |name: &str| {
+        let (prev, elapsed) = previous?;
+        let delta = number(info, name)? - number(prev, name)?;
+        Some(delta / elapsed.as_secs_f64().max(0.001))
+    };
+
+    println!("\n{}", "Memory:".bold());
+    rule(50);
+    println!("Used: {}", text("used_memory_human").cyan());
+    println!("Peak: {}", text("used_memory_peak_human").cyan());
+    println!("RSS: {}", text("used_memory_rss_human").cyan());
+    if let Some(ratio) = number(info, "mem_fragmentation_ratio") {
+        println!("Fragmentation ratio: {}", color_ratio(ratio));
+    }
+    println!("Max memory: {}", text("maxmemory_human").cyan());
+
+    println!("\n{}", "Clients:".bold());
+    rule(50);
+    println!("Connected: {}", text("connected_clients").cyan());
+    println!("Blocked: {}", text("blocked_clients").cyan());
+
+    println!("\n{}", "Throughput:".bold());
+    rule(50);
+    match rate("total_commands_processed") {
+        Some(ops) => println!("Ops/sec: {}", format!("{:.1}", ops).cyan()),
+        None => println!("Ops/sec: {}", text("instantaneous_ops_per_sec").cyan()),
+    }
+    if let (Some(input), Some(output)) = (
+        rate("total_net_input_bytes"),
+        rate("total_net_output_bytes"),
+    ) {
+        println!(
+            "Network: {} in / {} out per sec",
+            format_bytes(input as u64).cyan(),
+            format_bytes(output as u64).cyan()
+        );
+    }
+    println!(
+        "Total commands: {}",
+        text("total_commands_processed").cyan()
+    );
+
+    // Prefer the hit ratio since the previous refresh, which reflects
+    // current behaviour rather than the whole uptime
+    let hit_ratio = match (rate("keyspace_hits"), rate("keyspace_misses")) {
+        (Some(hits), Some(misses)) if hits + misses > 0.0 => Some(hits / (hits + misses)),
+        _ => match (
+            number(info, "keyspace_hits"),
+            number(info, "keyspace_misses"),
+        ) {
+            (Some(hits), Some(misses)) if hits + misses > 0.0 => Some(hits / (hits + misses)),
+            _ => None,
+        },
+    };
+    match hit_ratio {
+        Some(ratio) => {
+            let value = format!("{:.1}%", ratio * 100.0);
+            let value = if ratio < 0.5 {
+                value.red()
+            } else if ratio < 0.8 {
+                value.yellow()
+            } else {
+                value.green()
+            };
+            println!("Hit ratio: {}", value);
+        }
+        None => println!("Hit ratio: {}", "-".cyan()),
+    }
+    println!("Evicted keys: {}", text("evicted_keys").cyan());
+    println!("Expired keys: {}", text("expired_keys").cyan());
+
+    println!("\n{}", "Replication:".bold());
+    rule(50);
+    println!("Role: {}", text("role").cyan());
+    println!("Connected replicas: {}", text("connected_slaves").cyan());
+    if info.get("role").map(|r| r.trim()) == Some("slave") {
+        let link = text("master_link_status");
+        let link = if link.trim() == "up" {
+            link.green()
+        } else {
+            link.red()
+        };
+        println!("Master link: {}", link);
+    }
+    println!("Replication offset: {}", text("master_repl_offset").cyan());
+}
+
+pub async fn memory_stats(environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let stats = stats::memory_stats(&mut client).await?;
+
+    println!("{}", "Memory Statistics:".bold());
+    rule(50);
+
+    let bytes = |name: &str| {
+        stats
+            .get_u64(name)
+            .map(format_bytes)
+            .unwrap_or_else(|| "Unknown".to_string())
+    };
+
+    println!("Total allocated: {}", bytes("total.allocated").cyan());
+    println!("Startup allocated: {}", bytes("startup.allocated").cyan());
+    println!("Dataset: {}", bytes("dataset.bytes").cyan());
+    if let Some(percentage) = stats.get_f64("dataset.percentage") {
+        println!(
+            "Dataset percentage: {}",
+            format!("{:.2}%", percentage).cyan()
+        );
+    }
+    if let Some(keys) = stats.get_u64("keys.count") {
+        println!("Keys: {}", keys.to_string().cyan());
+    }
+    if let Some(per_key) = stats.get_u64("keys.bytes-per-key") {
+        println!("Bytes per key: {}", format_bytes(per_key).cyan());
+    }
+
+    // Peak usage
+    println!("\n{}", "Peak Usage:".bold());
+    rule(50);
+    println!("Peak allocated: {}", bytes("peak.allocated").cyan());
+    if let Some(percentage) = stats.get_f64("peak.percentage") {
+        // Current usage far below the peak means memory was freed but may not
+        // have been returned to the OS yet
+        let value = format!("{:.2}%", percentage);
+        let value = if percentage < 50.0 {
+            value.yellow()
+        } else {
+            value.green()
+        };
+        println!("Current / peak: {}", value);
+    }
+
+    // Overhead breakdown
+    println!("\n{}", "Overhead:".bold());
+    rule(50);
+    println!("Overhead total: {}", bytes("overhead.total").cyan());
+    println!("Normal clients: {}", bytes("clients.normal").cyan());
+    println!("Replica clients: {}", bytes("clients.slaves").cyan());
+    println!(
+        "Replication backlog: {}",
+        bytes("replication.backlog").cyan()
+    );
+    println!("AOF buffer: {}", bytes("aof.buffer").cyan());
+    println!("Lua caches: {}", bytes("lua.caches").cyan());
+
+    // Allocator
+    println!("\n{}", "Allocator:".bold());
+    rule(50);
+    println!("Allocated: {}", bytes("allocator.allocated").cyan());
+    println!("Active: {}", bytes("allocator.active").cyan());
+    println!("Resident: {}", bytes("allocator.resident").cyan());
+    if let Some(ratio) = stats.get_f64("allocator-fragmentation.ratio") {
+        println!(
+            "Allocator fragmentation: {} ({})",
+            color_ratio(ratio),
+            bytes("allocator-fragmentation.bytes")
+        );
+    }
+    if let Some(ratio) = stats.get_f64("rss-overhead.ratio") {
+        println!(
+            "RSS overhead: {} ({})",
+            color_ratio(ratio),
+            bytes("rss-overhead.bytes")
+        );
+    }
+
+    // Fragmentation
+    println!("\n{}", "Fragmentation:".bold());
+    rule(50);
+    match stats.get_f64("fragmentation") {
+        Some(ratio) => {
+            println!(
+                "Fragmentation ratio: {} ({})",
+                color_ratio(ratio),
+                bytes("fragmentation.bytes")
+            );
+            if ratio > 1.5 {
+                println!(
+                    "{}",
+                    "⚠ High fragmentation: consider enabling activedefrag or restarting the instance"
+                        .red()
+                );
+            } else if ratio < 1.0 {
+                println!(
+                    "{}",
+                    "⚠ Fragmentation below 1.0: the OS may be swapping Redis memory".red()
+                );
+            }
+        }
+        None => println!("Fragmentation ratio: {}", "Unknown".yellow()),
+    }
+
+    // Per-database overhead
+    if !stats.databases.is_empty() {
+        println!("\n{}", "Databases:".bold());
+        rule(50);
+        for (db, fields) in &stats.databases {
+            println!("{}", db.cyan());
+            for (field, value) in fields {
+                let value = value
+                    .parse::<u64>()
+                    .map(format_bytes)
+                    .unwrap_or_else(|_| value.clone());
+                println!("  {}: {}", field, value);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Tabled)]
+struct CommandRow {
+    #[tabled(rename = "Command")]
+    command: String,
+    #[tabled(rename = "Calls")]
+    calls: u64,
+    #[tabled(rename = "Total Time")]
+    total: String,
+    #[tabled(rename = "Avg Latency")]
+    average: String,
+    #[tabled(rename = "Rejected")]
+    rejected: u64,
+    #[tabled(rename = "Failed")]
+    failed: u64,
+}
+
+pub async fn command_stats(sort: CommandSort, environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let stats = stats::command_stats(&mut client, sort).await?;
+
+    if print_structured(&config.output_format, &stats)? {
+        return Ok(());
+    }
+
+    println!("{}", "Command Statistics:".bold());
+    rule(50);
+    if stats.is_empty() {
+        println!(
+            "{}",
+            "No commands recorded since the last CONFIG RESETSTAT".yellow()
+        );
+        return Ok(());
+    }
+
+    let rows: Vec<CommandRow> = stats
+        .into_iter()
+        .map(|stat| CommandRow {
+            command: stat.command,
+            calls: stat.calls,
+            total: format_micros(stat.usec as i64),
+            average: format_micros(stat.usec_per_call.round() as i64),
+            rejected: stat.rejected_calls,
+            failed: stat.failed_calls,
+        })
+        .collect();
+    println!("{}", Table::new(rows));
+
+    Ok(())
+}
+
+#[derive(Tabled)]
+struct ReplicaRow {
+    #[tabled(rename = "Replica")]
+    address: String,
+    #[tabled(rename = "State")]
+    state: String,
+    #[tabled(rename = "Offset")]
+    offset: u64,
+    #[tabled(rename = "Lag (bytes)")]
+    lag_bytes: String,
+    #[tabled(rename = "Lag (s)")]
+    lag_seconds: String,
+}
+
+/// Show this instance's replication role and, on a master, each replica,
+/// highlighting replicas that are not online or lag more than
+/// `lag_threshold` seconds.
+pub async fn replication(lag_threshold: u64, environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let stats::Replication {
+        info,
+        master_offset,
+        replicas,
+    } = stats::replication(&mut client).await?;
+
+    let text = |name: &str| {
+        info.get(name)
+            .map(|v| v.trim().to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let sorted: BTreeMap<&String, &String> = info.iter().collect();
+    let report = serde_json::json!({ "info": sorted, "replicas": replicas });
+    if print_structured(&config.output_format, &report)? {
+        return Ok(());
+    }
+
+    println!("{}", "Replication:".bold());
+    rule(50);
+    let role = text("role");
+    println!("Role: {}", role.cyan());
+
+    if role == "slave" {
+        let link = text("master_link_status");
+        let link = if link == "up" {
+            link.green()
+        } else {
+            link.red()
+        };
+        println!(
+            "Master: {}",
+            format!("{}:{}", text("master_host"), text("master_port")).cyan()
+        );
+        println!("Master link: {}", link);
+        println!(
+            "Last I/O with master: {}s ago",
+            text("master_last_io_seconds_ago").cyan()
+        );
+        println!("Replica offset: {}", text("slave_repl_offset").cyan());
+        if text("master_sync_in_progress") == "1" {
+            println!("{}", "Full sync in progress".yellow());
+        }
+        return Ok(());
+    }
+
+    println!("Master offset: {}", master_offset.to_string().cyan());
+    println!("Connected replicas: {}", text("connected_slaves").cyan());
+    if text("repl_backlog_active") == "1" {
+        let backlog = info
+            .get("repl_backlog_size")
+            .and_then(|v| v.trim().parse().ok())
+            .map(format_bytes)
+            .unwrap_or_else(|| "-".to_string());
+        println!("Backlog size: {}", backlog.cyan());
+    }
+
+    if replicas.is_empty() {
+        println!("{}", "No replicas connected".yellow());
+        return Ok(());
+    }
+
+    let mut lagging = 0;
+    let rows: Vec<ReplicaRow> = replicas
+        .into_iter()
+        .map(|replica| {
+            let behind = replica.state != "online"
+                || replica.lag_seconds.is_some_and(|lag| lag > lag_threshold);
+            if behind {
+                lagging += 1;
+            }
+            let highlight = |value: String| {
+                if behind {
+                    value.red().to_string()
+                } else {
+                    value
+                }
+            };
+            ReplicaRow {
+                address: highlight(replica.address),
+                state: highlight(replica.state),
+                offset: replica.offset,
+                lag_bytes: highlight(replica.lag_bytes.to_string()),
+                lag_seconds: highlight(
+                    replica
+                        .lag_seconds
+                        .map(|lag| lag.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+            }
+        })
+        .collect();
+    println!("\n{}", Table::new(rows));
+
+    if lagging > 0 {
+        println!(
+            "{}",
+            format!(
+                "⚠ {} replicas offline or lagging more than {}s",
+                lagging, lag_threshold
+            )
+            .red()
+            .bold()
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Tabled)]
+struct DatabaseRow {
+    #[tabled(rename = "DB")]
+    db: String,
+    #[tabled(rename = "Keys")]
+    keys: u64,
+    #[tabled(rename = "Expires")]
+    expires: u64,
+    #[tabled(rename = "Avg TTL")]
+    avg_ttl: String,
+}
+
+/// Show keyspace statistics for every database that holds data, marking
+/// the one this environment uses.
+pub async fn keyspace(environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let redis_config = config.redis_config(&config.resolve_environment(environment.as_deref()))?;
+    let current_db = u32::from(redis_config.db);
+
+    let mut client = RedisClient::connect(redis_config).await?;
+    let stats::Keyspace { databases, total } = stats::keyspace(&mut client).await?;
+    let (total_keys, total_expires, total_avg_ttl) = (total.keys, total.expires, total.avg_ttl);
+
+    let report = serde_json::json!({
+        "databases": databases,
+        "total": { "keys": total_keys, "expires": total_expires, "avg_ttl": total_avg_ttl },
+    });
+    if print_structured(&config.output_format, &report)? {
+        return Ok(());
+    }
+
+    println!("{}", "Keyspace:".bold());
+    rule(50);
+
+    if databases.is_empty() {
+        println!("{}", "No database holds any keys".yellow());
+        return Ok(());
+    }
+
+    let avg_ttl = |millis: u64| {
+        if millis == 0 {
+            "-".to_string()
+        } else {
+            format_seconds(millis / 1000)
+        }
+    };
+    let mut rows: Vec<DatabaseRow> = databases
+        .iter()
+        .map(|database| DatabaseRow {
+            db: if database.db == current_db {
+                format!("db{} (current)", database.db)
+            } else {
+                format!("db{}", database.db)
+            },
+            keys: database.keys,
+            expires: database.expires,
+            avg_ttl: avg_ttl(database.avg_ttl),
+        })
+        .collect();
+    rows.push(DatabaseRow {
+        db: "Total".to_string(),
+        keys: total_keys,
+        expires: total_expires,
+        avg_ttl: avg_ttl(total_avg_ttl),
+    });
+    println!("{}", Table::new(rows));
+
+    Ok(())
+}
+
+pub async fn memory_doctor(environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let report = stats::memory_doctor(&mut client).await?;
+
+    println!("{}", "Memory Doctor Report:".bold());
+    rule(50);
+    println!("{}", report);
+
+    Ok(())
+}
+
+/// Color a memory ratio: green when healthy, yellow when elevated and red
+/// when it indicates heavy fragmentation or swapping.
+fn color_ratio(ratio: f64) -> ColoredString {
+    let value = format!("{:.2}", ratio);
+    if !(1.0..=1.5).contains(&ratio) {
+        value.red()
+    } else if ratio > 1.2 {
+        value.yellow()
+    } else {
+        value.green()
+    }
+}
@@ -0,0 +1,80 @@
+use colored::*;
+
+use solt::commands::string;
+use solt::error::AppError;
+use solt::format::format_bytes;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+pub async fn getrange(
+    key: String,
+    start: i64,
+    end: i64,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let value = string::getrange(&mut client, &key, start, end).await?;
+
+    println!(
+        "{}",
+        format!(
+            "Range [{}..{}] of '{}' ({}):",
+            start,
+            end,
+            key,
+            format_bytes(value.len() as u64)
+        )
+        .bold()
+    );
+    println!("{}", String::from_utf8_lossy(&value));
+
+    Ok(())
+}
+
+pub async fn setrange(
+    key: String,
+    offset: u64,
+    value: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let new_length = string::setrange(&mut client, &key, offset, value.as_bytes()).await?;
+
+    success(&format!(
+        "Wrote {} at offset {} of '{}' (new length: {})",
+        format_bytes(value.len() as u64),
+        offset,
+        key,
+        format_bytes(new_length as u64)
+    ));
+
+    Ok(())
+}
+
+pub async fn append(
+    key: String,
+    value: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let new_length = string::append(&mut client, &key, value.as_bytes()).await?;
+
+    success(&format!(
+        "Appended {} to '{}' (new length: {})",
+        format_bytes(value.len() as u64),
+        key,
+        format_bytes(new_length as u64)
+    ));
+
+    Ok(())
+}
+
+pub async fn strlen(key: String, environment: Option<String>) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let length = string::strlen(&mut client, &key).await?;
+
+    println!("{} ({} bytes)", format_bytes(length as u64).cyan(), length);
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use colored::*;
+use std::io::{self, Write};
+
+use solt::commands::swapdb;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+/// Swap two databases after showing their sizes, asking first unless `yes`.
+pub async fn run(
+    first: u8,
+    second: u8,
+    yes: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    if first == second {
+        return Err(AppError::ConfigError(format!(
+            "Cannot swap db {} with itself",
+            first
+        )));
+    }
+
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let redis_config = config.redis_config(&env_name)?;
+
+    let sizes = swapdb::sizes(&redis_config, first, second).await?;
+
+    println!(
+        "{}",
+        format!(
+            "SWAPDB on '{}': db {} ({} keys) ⇄ db {} ({} keys)",
+            env_name, first, sizes.0, second, sizes.1
+        )
+        .cyan()
+        .bold()
+    );
+    println!(
+        "{}",
+        "Clients of either database will see the other's data immediately".yellow()
+    );
+
+    if !yes {
+        print!("Proceed? [y/N]: ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("{}", "Swap cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    let mut client = RedisClient::connect(redis_config).await?;
+    swapdb::run(&mut client, first, second).await?;
+
+    success(&format!(
+        "Swapped db {} and db {} (db {} now has {} keys, db {} has {})",
+        first, second, first, sizes.1, second, sizes.0
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use chrono::Local;
+use colored::*;
+use log::info;
+use std::time::{Duration, Instant};
+
+use solt::commands::sync;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::rule;
+
+pub async fn run(
+    from: String,
+    to: String,
+    pattern: String,
+    interval: Duration,
+    delete_extra: bool,
+    once: bool,
+) -> Result<(), AppError> {
+    info!("Syncing '{}' from '{}' to '{}'", pattern, from, to);
+
+    if from == to {
+        return Err(AppError::ConfigError(
+            "Source and destination environments must differ".to_string(),
+        ));
+    }
+
+    let config = AppConfig::load()?;
+    let source_config = config
+        .get_environment(&from)
+        .ok_or_else(|| AppError::ConfigError(format!("Source environment '{}' not found", from)))?
+        .config
+        .clone();
+    let dest_config = config
+        .get_environment(&to)
+        .ok_or_else(|| {
+            AppError::ConfigError(format!("Destination environment '{}' not found", to))
+        })?
+        .config
+        .clone();
+
+    let mut source_client = RedisClient::connect(source_config).await?;
+    let mut dest_client = RedisClient::connect(dest_config).await?;
+
+    println!(
+        "{}",
+        format!(
+            "Syncing keys matching '{}' from '{}' to '{}'",
+            pattern, from, to
+        )
+        .cyan()
+        .bold()
+    );
+    if !once {
+        println!(
+            "{}",
+            format!(
+                "Interval: {}s - press Ctrl+C to stop",
+                interval.as_secs_f64()
+            )
+            .cyan()
+        );
+    }
+    rule(80);
+
+    let mut cycle_number = 0;
+    loop {
+        cycle_number += 1;
+        let started = Instant::now();
+        let cycle =
+            sync::cycle(&mut source_client, &mut dest_client, &pattern, delete_extra).await?;
+        for (key, error) in &cycle.errors {
+            println!("{}", format!("Error syncing '{}': {}", key, error).red());
+        }
+
+        let summary = format!(
+            "[{}] cycle {}: {} scanned, {} updated, {} unchanged, {} deleted, {} failed ({}ms)",
+            Local::now().format("%H:%M:%S"),
+            cycle_number,
+            cycle.scanned,
+            cycle.updated,
+            cycle.unchanged,
+            cycle.deleted,
+            cycle.errors.len(),
+            started.elapsed().as_millis()
+        );
+        if !cycle.errors.is_empty() {
+            println!("{}", summary.red());
+        } else if cycle.updated > 0 || cycle.deleted > 0 {
+            println!("{}", summary.yellow());
+        } else {
+            println!("{}", summary.green());
+        }
+
+        if once {
+            if !cycle.errors.is_empty() {
+                return Err(AppError::Aborted(format!(
+                    "{} keys could not be synced",
+                    cycle.errors.len()
+                )));
+            }
+            break;
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("{}", "Sync stopped".green());
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
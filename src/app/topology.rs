@@ -0,0 +1,112 @@
+use colored::*;
+
+use solt::commands::topology::{self, TopologyNode};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, rule};
+
+/// Draw the primary→replica tree of the configured instance.
+pub async fn run(environment: Option<String>) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let redis_config = config.redis_config(&config.resolve_environment(environment.as_deref()))?;
+
+    let mut client = RedisClient::connect(redis_config.clone()).await?;
+    let topology::Topology { root, upstream } = topology::run(&mut client, &redis_config).await?;
+
+    if print_structured(&config.output_format, &root)? {
+        return Ok(());
+    }
+
+    println!("{}", "Replication topology:".bold());
+    rule(50);
+    if let Some(master) = upstream {
+        println!(
+            "{}",
+            format!(
+                "{} is a replica of {}; run against the primary for the full tree",
+                root.address, master
+            )
+            .yellow()
+        );
+    }
+    println!("{}", describe(&root));
+    print_children(&root.replicas, "");
+
+    let (total, unhealthy) = topology::count(&root);
+    println!();
+    if total == 1 {
+        println!("{}", "No replicas found".yellow());
+    } else if unhealthy == 0 {
+        println!(
+            "{}",
+            format!("{} nodes, all replicas linked", total).green()
+        );
+    } else {
+        println!(
+            "{}",
+            format!(
+                "⚠ {} of {} nodes unreachable or not in sync",
+                unhealthy, total
+            )
+            .red()
+            .bold()
+        );
+    }
+
+    Ok(())
+}
+
+fn describe(node: &TopologyNode) -> String {
+    let mut parts = vec![
+        node.address.bold().to_string(),
+        if node.role == "master" {
+            node.role.cyan().to_string()
+        } else {
+            node.role.clone()
+        },
+    ];
+    if let Some(offset) = node.offset {
+        parts.push(format!("offset {}", offset));
+    }
+    if let Some(state) = &node.state {
+        parts.push(if state == "online" {
+            state.green().to_string()
+        } else {
+            state.red().to_string()
+        });
+    }
+    if let Some(link) = &node.link {
+        let link = format!("link {}", link);
+        parts.push(if node.link.as_deref() == Some("up") {
+            link.green().to_string()
+        } else {
+            link.red().to_string()
+        });
+    }
+    if let Some(lag) = node.lag_bytes {
+        let lag_seconds = node
+            .lag_seconds
+            .map(|s| format!(", {}s", s))
+            .unwrap_or_default();
+        parts.push(format!("lag {}B{}", lag, lag_seconds));
+    }
+    if let Some(error) = &node.error {
+        parts.push(format!("unreachable: {}", error).red().to_string());
+    }
+    parts.join("  ")
+}
+
+fn print_children(nodes: &[TopologyNode], prefix: &str) {
+    for (i, node) in nodes.iter().enumerate() {
+        let last = i + 1 == nodes.len();
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        println!("{}{}{}", prefix, branch, describe(node));
+        print_children(&node.replicas, &format!("{}{}", prefix, indent));
+    }
+}
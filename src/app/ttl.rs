@@ -0,0 +1,56 @@
+use colored::*;
+
+use solt::commands::ttl;
+use solt::error::AppError;
+use solt::format::format_seconds;
+use solt::redis_client::RedisClient;
+
+use crate::output::success;
+
+pub async fn run(
+    key: String,
+    set: Option<u64>,
+    persist: bool,
+    condition: Option<&str>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let report = ttl::run(&mut client, &key, set, persist, condition).await?;
+
+    match (report.changed, set) {
+        (Some(true), Some(seconds)) => success(&format!("TTL of '{}' set to {}s", key, seconds)),
+        (Some(false), Some(_)) => {
+            if let Some(condition) = condition {
+                println!(
+                    "{}",
+                    format!(
+                        "TTL of '{}' not changed: key missing or {} condition not met",
+                        key, condition
+                    )
+                    .yellow()
+                );
+            }
+        }
+        (Some(true), None) => success(&format!("Removed expiry from '{}'", key)),
+        (Some(false), None) => println!(
+            "{}",
+            format!("Key '{}' not found or has no expiry", key).yellow()
+        ),
+        (None, _) => {}
+    }
+
+    match report.pttl {
+        -2 => return Err(AppError::NotFound(format!("Key '{}' does not exist", key))),
+        -1 => println!("TTL: {}", "No expiry".cyan()),
+        ms => {
+            let seconds = ms as f64 / 1000.0;
+            println!(
+                "TTL: {} ({})",
+                format!("{:.3}s", seconds).cyan(),
+                format_seconds(seconds as u64).yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use colored::*;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use solt::commands::txn;
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, rule, success};
+
+/// Run a transaction whose commands come from `commands`, one per line of
+/// `file`, or are typed in one at a time when neither is given.
+pub async fn run(
+    commands: Vec<String>,
+    file: Option<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let lines = if let Some(path) = &file {
+        fs::read_to_string(path)?
+            .lines()
+            .map(str::to_string)
+            .collect()
+    } else if !commands.is_empty() {
+        commands
+    } else {
+        match read_interactive()? {
+            Some(lines) => lines,
+            None => {
+                println!("{}", "Transaction discarded.".yellow());
+                return Ok(());
+            }
+        }
+    };
+    let parsed = txn::parse(&lines)?;
+
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment.as_deref());
+    let mut client = RedisClient::connect(config.redis_config(&env_name)?).await?;
+
+    let results = txn::run(&mut client, &parsed).await?;
+    let failed = results.iter().filter(|result| !result.applied).count();
+    if !print_structured(&config.output_format, &results)? {
+        println!(
+            "{}",
+            format!(
+                "Transaction on '{}' ({} commands):",
+                env_name,
+                results.len()
+            )
+            .bold()
+        );
+        rule(50);
+        let width = results.len().to_string().len();
+        for (i, result) in results.iter().enumerate() {
+            println!(
+                "{:>width$}. {}",
+                i + 1,
+                result.command.cyan(),
+                width = width
+            );
+            if let Some(reply) = &result.reply {
+                for line in reply.lines() {
+                    println!("{:width$}  {}", "", line, width = width);
+                }
+            }
+            if let Some(error) = &result.error {
+                println!(
+                    "{:width$}  {}",
+                    "",
+                    format!("(error, not applied) {}", error).red(),
+                    width = width
+                );
+            }
+        }
+        if failed == 0 {
+            success(&format!(
+                "Transaction committed ({} commands)",
+                results.len()
+            ));
+        }
+    }
+    if failed > 0 {
+        return Err(AppError::Aborted(format!(
+            "{} of {} commands failed inside the transaction. Redis does not roll back, so the rest were applied",
+            failed,
+            results.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Read commands from stdin until EXEC, an empty line or end of input.
+/// Returns `None` when the user types DISCARD.
+fn read_interactive() -> Result<Option<Vec<String>>, AppError> {
+    let interactive = io::stdin().is_terminal();
+    if interactive {
+        println!(
+            "{}",
+            "Enter one command per line. EXEC or an empty line submits, DISCARD cancels.".cyan()
+        );
+    }
+
+    let mut lines = Vec::new();
+    let mut stdin = io::stdin().lock();
+    loop {
+        if interactive {
+            print!("txn({})> ", lines.len());
+            io::stdout().flush()?;
+        }
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim();
+        if line.eq_ignore_ascii_case("DISCARD") {
+            return Ok(None);
+        }
+        if line.eq_ignore_ascii_case("EXEC") || (interactive && line.is_empty()) {
+            break;
+        }
+        lines.push(line.to_string());
+    }
+
+    Ok(Some(lines))
+}
@@ -0,0 +1,62 @@
+use colored::*;
+use std::time::Duration;
+
+use solt::commands::wait_for::{self, WaitOutcome};
+use solt::config::AppConfig;
+use solt::error::AppError;
+
+use crate::output::success;
+
+pub async fn run(
+    key: String,
+    gone: bool,
+    timeout: Duration,
+    interval: Duration,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let redis_config = config.redis_config(&config.resolve_environment(environment.as_deref()))?;
+    let what = if gone { "be deleted" } else { "appear" };
+
+    let outcome = wait_for::run(
+        &redis_config,
+        &key,
+        gone,
+        timeout,
+        interval,
+        |notifications| {
+            println!(
+                "{}",
+                format!(
+                    "Waiting up to {}s for '{}' to {} ({})",
+                    timeout.as_secs(),
+                    key,
+                    what,
+                    if notifications {
+                        "keyspace notifications"
+                    } else {
+                        "polling"
+                    }
+                )
+                .cyan()
+            );
+        },
+    )
+    .await?;
+
+    match outcome {
+        WaitOutcome::Immediate => success(&format!(
+            "'{}' {}",
+            key,
+            if gone { "does not exist" } else { "exists" }
+        )),
+        WaitOutcome::After(elapsed) => success(&format!(
+            "'{}' {} after {:.1}s",
+            key,
+            if gone { "is gone" } else { "exists" },
+            elapsed.as_secs_f64()
+        )),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use chrono::Local;
+use colored::*;
+use log::info;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+use solt::error::AppError;
+
+use crate::output::{clear_screen, rule};
+
+/// Re-run another solt subcommand on an interval and redraw its output,
+/// highlighting lines that changed since the previous run. The subcommand
+/// runs as a child process, so anything solt can print can be watched.
+pub async fn run(
+    interval: Duration,
+    args: Vec<String>,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    info!("Watching '{}' every {:?}", args.join(" "), interval);
+
+    if args.first().map(String::as_str) == Some("watch") {
+        return Err(AppError::Aborted(
+            "Cannot watch the watch command".to_string(),
+        ));
+    }
+
+    let exe = std::env::current_exe()?;
+    let mut child_args = Vec::with_capacity(args.len() + 3);
+    if let Some(env) = &environment {
+        child_args.push("-e".to_string());
+        child_args.push(env.clone());
+    }
+    // One history entry for the watch, not one per refresh
+    child_args.push("--no-history".to_string());
+    child_args.extend(args.iter().cloned());
+
+    let title = format!("solt {}", args.join(" "));
+    let mut previous: Option<Vec<String>> = None;
+    loop {
+        let mut command = Command::new(&exe);
+        command
+            .args(&child_args)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        let output = tokio::select! {
+            output = command.output() => output?,
+            _ = tokio::signal::ctrl_c() => break,
+        };
+
+        let lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let errors: Vec<String> = String::from_utf8_lossy(&output.stderr)
+            .lines()
+            .map(str::to_string)
+            .collect();
+
+        clear_screen();
+        println!(
+            "{}",
+            format!(
+                "Every {}s: {} - last refresh {} (Ctrl+C to stop)",
+                interval.as_secs_f64(),
+                title,
+                Local::now().format("%H:%M:%S")
+            )
+            .cyan()
+            .bold()
+        );
+        rule(80);
+
+        for (i, line) in lines.iter().enumerate() {
+            let changed = previous
+                .as_ref()
+                .is_some_and(|prev| prev.get(i) != Some(line));
+            if changed {
+                println!("{}", line.yellow().bold());
+            } else {
+                println!("{}", line);
+            }
+        }
+        if let Some(prev) = &previous {
+            if prev.len() > lines.len() {
+                println!(
+                    "{}",
+                    format!("({} lines removed)", prev.len() - lines.len()).red()
+                );
+            }
+        }
+        for line in &errors {
+            println!("{}", line.red());
+        }
+        if !output.status.success() {
+            let code = output
+                .status
+                .code()
+                .map_or("signal".to_string(), |code| code.to_string());
+            println!("{}", format!("(exited with {})", code).red());
+        }
+
+        previous = Some(lines);
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    Ok(())
+}
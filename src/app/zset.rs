@@ -0,0 +1,124 @@
+use colored::*;
+
+use solt::commands::zset::{self, ScoredMember};
+use solt::config::AppConfig;
+use solt::error::AppError;
+use solt::redis_client::RedisClient;
+
+use crate::output::{print_structured, rule, success};
+
+pub async fn rank(
+    key: String,
+    member: String,
+    rev: bool,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let (rank, score) = zset::rank(&mut client, &key, &member, rev).await?;
+
+    let order = if rev { "highest" } else { "lowest" };
+    println!(
+        "Rank: {} (0-based, from the {} score)",
+        rank.to_string().cyan(),
+        order
+    );
+    println!("Score: {}", score.to_string().cyan());
+
+    Ok(())
+}
+
+pub async fn score(
+    key: String,
+    member: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    println!("{}", zset::score(&mut client, &key, &member).await?);
+
+    Ok(())
+}
+
+pub async fn range(
+    key: String,
+    min: String,
+    max: String,
+    rev: bool,
+    limit: Option<usize>,
+    offset: usize,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+
+    let members = zset::range(&mut client, &key, &min, &max, rev, limit, offset).await?;
+    if print_structured(&config.output_format, &members)? {
+        return Ok(());
+    }
+
+    if members.is_empty() {
+        println!(
+            "{}",
+            format!("No members of '{}' scored between {} and {}", key, min, max).yellow()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Members of '{}' scored {} to {} ({}):",
+            key,
+            min,
+            max,
+            members.len()
+        )
+        .bold()
+    );
+    rule(50);
+    for ScoredMember { member, score } in members {
+        println!("  • {} (score: {})", member, score);
+    }
+
+    Ok(())
+}
+
+pub async fn incr(
+    key: String,
+    member: String,
+    by: f64,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let score = zset::incr(&mut client, &key, &member, by).await?;
+
+    success(&format!(
+        "Changed the score of '{}' in '{}' by {}",
+        member, key, by
+    ));
+    println!("{}", score.to_string().cyan());
+
+    Ok(())
+}
+
+pub async fn remove(
+    key: String,
+    min: String,
+    max: String,
+    environment: Option<String>,
+) -> Result<(), AppError> {
+    let mut client = RedisClient::for_environment(environment.as_deref()).await?;
+    let report = zset::remove(&mut client, &key, &min, &max).await?;
+
+    success(&format!(
+        "Removed {} members of '{}' scored {} to {}",
+        report.removed, key, min, max
+    ));
+    if report.key_deleted {
+        println!(
+            "{}",
+            "Every member was removed, so the key was deleted".yellow()
+        );
+    }
+
+    Ok(())
+}
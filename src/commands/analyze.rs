@@ -0,0 +1,178 @@
+use log::info;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Keys per pipeline when collecting key details
+const ANALYZE_BATCH: usize = 500;
+
+/// Upper bounds (in seconds) of the TTL distribution buckets
+const TTL_BUCKETS: &[(u64, &str)] = &[
+    (60, "< 1m"),
+    (3600, "1m – 1h"),
+    (86_400, "1h – 1d"),
+    (7 * 86_400, "1d – 7d"),
+    (30 * 86_400, "7d – 30d"),
+    (u64::MAX, "> 30d"),
+];
+
+#[derive(Debug, Serialize)]
+pub struct TtlBucket {
+    pub range: String,
+    pub sessions: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionSample {
+    pub key: String,
+    pub memory: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionReport {
+    pub pattern: String,
+    pub sessions: usize,
+    pub total_memory: u64,
+    pub average_memory: u64,
+    /// Average value length of string sessions (the serialized payload)
+    pub average_payload: Option<u64>,
+    pub ttl_distribution: Vec<TtlBucket>,
+    pub without_expiry: usize,
+    pub without_expiry_memory: u64,
+    /// Largest sessions that never expire
+    pub largest_without_expiry: Vec<SessionSample>,
+}
+
+/// A lock-style key and who holds it.
+#[derive(Debug, Serialize)]
+pub struct LockInfo {
+    pub key: String,
+    pub key_type: String,
+    /// Owner token(s): the string value, or the fields of a hash lock
+    /// (Redisson stores `owner:thread` fields with a re-entry count)
+    pub owners: Vec<String>,
+    /// Remaining time to live in milliseconds; None without expiry
+    pub ttl_ms: Option<i64>,
+    /// No expiry, so a crashed holder keeps the lock forever
+    pub suspicious: bool,
+}
+
+/// Report on a session store: how many of `keys` (found with `pattern`)
+/// there are, how their TTLs are distributed, how big they are on average,
+/// and which ones never expire. `progress` is called after each batch with
+/// the number of keys in it.
+pub async fn sessions(
+    client: &mut RedisClient,
+    pattern: String,
+    keys: &[String],
+    top: usize,
+    mut progress: impl FnMut(usize),
+) -> Result<SessionReport, AppError> {
+    info!("Analyzing sessions matching {}", pattern);
+
+    let mut sessions = 0;
+    let mut total_memory = 0;
+    let mut payload_total = 0;
+    let mut payload_count = 0;
+    let mut buckets = vec![0; TTL_BUCKETS.len()];
+    let mut without_expiry = Vec::new();
+    for batch in keys.chunks(ANALYZE_BATCH) {
+        let infos = client.key_infos(batch).await?;
+        let lengths = client.key_lengths(&infos).await?;
+        for (info, length) in infos.into_iter().zip(lengths) {
+            // Sessions can expire between SCAN and the pipeline
+            if info.key_type == "none" {
+                continue;
+            }
+            sessions += 1;
+            let memory = info.memory_usage.map(|m| m as u64);
+            total_memory += memory.unwrap_or(0);
+            if let (Some(length), "string") = (length, info.key_type.as_str()) {
+                payload_total += length as u64;
+                payload_count += 1;
+            }
+            match info.ttl {
+                Some(ttl) if ttl >= 0 => {
+                    let bucket = TTL_BUCKETS
+                        .iter()
+                        .position(|(limit, _)| (ttl as u64) < *limit)
+                        .unwrap_or(TTL_BUCKETS.len() - 1);
+                    buckets[bucket] += 1;
+                }
+                Some(-1) => without_expiry.push(SessionSample {
+                    key: info.key,
+                    memory,
+                }),
+                _ => {}
+            }
+        }
+        progress(batch.len());
+    }
+
+    without_expiry.sort_by_key(|sample| std::cmp::Reverse(sample.memory));
+    let without_expiry_count = without_expiry.len();
+    let without_expiry_memory = without_expiry.iter().filter_map(|s| s.memory).sum();
+    without_expiry.truncate(top);
+
+    Ok(SessionReport {
+        pattern,
+        sessions,
+        total_memory,
+        average_memory: total_memory / sessions.max(1) as u64,
+        average_payload: (payload_count > 0).then(|| payload_total / payload_count),
+        ttl_distribution: TTL_BUCKETS
+            .iter()
+            .zip(&buckets)
+            .map(|((_, range), sessions)| TtlBucket {
+                range: range.to_string(),
+                sessions: *sessions,
+            })
+            .collect(),
+        without_expiry: without_expiry_count,
+        without_expiry_memory,
+        largest_without_expiry: without_expiry,
+    })
+}
+
+/// Lock-style keys with their owner tokens and remaining TTL, suspicious
+/// ones (without an expiry) first, then the ones about to be released.
+pub async fn locks(client: &mut RedisClient, keys: &[String]) -> Result<Vec<LockInfo>, AppError> {
+    info!("Analyzing {} locks", keys.len());
+
+    let mut locks = Vec::with_capacity(keys.len());
+    for batch in keys.chunks(ANALYZE_BATCH) {
+        let values = client.fetch_values(batch).await?;
+        let pttls = client.pttl_many(batch).await?;
+        for (value, pttl) in values.into_iter().zip(pttls) {
+            // Released between SCAN and the pipeline
+            if value.key_type == "none" || pttl == -2 {
+                continue;
+            }
+            let owners = match value.value {
+                Some(JsonValue::String(token)) => vec![token],
+                Some(JsonValue::Object(fields)) => fields
+                    .into_iter()
+                    .map(|(owner, count)| match count {
+                        JsonValue::String(count) => format!("{} (x{})", owner, count),
+                        count => format!("{} (x{})", owner, count),
+                    })
+                    .collect(),
+                _ => vec![format!("<{}>", value.key_type)],
+            };
+            locks.push(LockInfo {
+                key: value.key,
+                key_type: value.key_type,
+                owners,
+                ttl_ms: (pttl >= 0).then_some(pttl),
+                suspicious: pttl == -1,
+            });
+        }
+    }
+
+    // Suspicious locks first, then the ones about to be released
+    locks.sort_by_key(|lock| (!lock.suspicious, lock.ttl_ms));
+
+    Ok(locks)
+}
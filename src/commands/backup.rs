@@ -1,57 +1,183 @@
-use colored::*;
 use log::info;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
 
-use crate::config::AppConfig;
+use crate::config::RedisConfig;
 use crate::error::AppError;
 use crate::redis_client::RedisClient;
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Backup command invoked");
-    let config = AppConfig::load()?;
-    let env = config
-        .default_environment
-        .clone()
-        .unwrap_or_else(|| "dev".to_string());
-    let redis_config = config
-        .get_environment(&env)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env)))?
-        .config
-        .clone();
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    println!(
-        "{}",
-        "Choose backup operation: [1] SAVE, [2] BGSAVE, [3] BGREWRITEAOF".cyan()
-    );
-    println!("{}", "1. SAVE (synchronous save, blocks Redis)".yellow());
-    println!("{}", "2. BGSAVE (background save, non-blocking)".yellow());
-    println!("{}", "3. BGREWRITEAOF (background AOF rewrite)".yellow());
-    print!("Enter choice (1/2/3): ");
-    use std::io::{self, Write};
-    io::stdout().flush().unwrap();
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    let choice = input.trim();
-
-    match choice {
-        "1" => {
-            println!("{}", "Running SAVE...".cyan());
-            client.save(false).await?;
-            println!("{}", "SAVE completed successfully.".green());
+/// The ways to make the server persist its dataset.
+#[derive(Debug, Clone, Copy)]
+pub enum Persistence {
+    /// SAVE: synchronous, blocks the server until done
+    Save,
+    /// BGSAVE: forks and writes the RDB in the background
+    Bgsave,
+    /// BGREWRITEAOF: rewrites the append-only file in the background
+    Bgrewriteaof,
+}
+
+/// Run SAVE, or start BGSAVE or BGREWRITEAOF.
+pub async fn persist(client: &mut RedisClient, kind: Persistence) -> Result<(), AppError> {
+    info!("Persisting with {:?}", kind);
+
+    match kind {
+        Persistence::Save => client.save(false).await?,
+        Persistence::Bgsave => client.save(true).await?,
+        Persistence::Bgrewriteaof => client.bgrewriteaof().await?,
+    }
+    Ok(())
+}
+
+/// Download an RDB file from the server the way a replica would, by sending
+/// SYNC on a plain TCP connection and streaming the payload to `path`.
+/// `progress` is called with the bytes received so far and, when the
+/// server announced it, the total size. Returns the size of the file.
+pub async fn download_rdb(
+    config: &RedisConfig,
+    path: &str,
+    mut progress: impl FnMut(u64, Option<u64>),
+) -> Result<u64, AppError> {
+    info!("Downloading RDB to {}", path);
+
+    if config.tls {
+        return Err(AppError::ConfigError(
+            "RDB download is not supported over TLS connections".to_string(),
+        ));
+    }
+
+    let address = format!("{}:{}", config.host, config.port);
+    let stream = TcpStream::connect(&address)
+        .await
+        .map_err(|e| AppError::ConnectionError(format!("{}: {}", address, e)))?;
+    let mut stream = BufReader::new(stream);
+
+    if let Some(password) = &config.password {
+        send_command(&mut stream, &["AUTH", password]).await?;
+        let reply = read_line(&mut stream).await?;
+        if let Some(error) = reply.strip_prefix('-') {
+            return Err(AppError::ConnectionError(format!(
+                "Authentication failed: {}",
+                error
+            )));
+        }
+    }
+
+    // Ask Redis 7+ not to follow the RDB with the replication stream. Older
+    // servers reply with an error, which is harmless.
+    send_command(&mut stream, &["REPLCONF", "rdb-only", "1"]).await?;
+    read_line(&mut stream).await?;
+
+    send_command(&mut stream, &["SYNC"]).await?;
+
+    // The server sends bare newlines as keepalives while it prepares the RDB
+    let header = loop {
+        let line = read_line(&mut stream).await?;
+        if !line.is_empty() {
+            break line;
+        }
+    };
+    if let Some(error) = header.strip_prefix('-') {
+        return Err(AppError::ConnectionError(format!("SYNC failed: {}", error)));
+    }
+    let size = header
+        .strip_prefix('$')
+        .ok_or_else(|| anyhow::anyhow!("Unexpected SYNC reply: {}", header))?;
+
+    let mut file = File::create(path).await?;
+    let written = if let Some(mark) = size.strip_prefix("EOF:") {
+        // Diskless replication: the payload length is unknown and the end is
+        // signalled by repeating a 40 byte mark
+        copy_until_mark(&mut stream, &mut file, mark.as_bytes(), &mut progress).await?
+    } else {
+        let size: u64 = size
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid RDB size: {}", size))?;
+        copy_exact(&mut stream, &mut file, size, &mut progress).await?
+    };
+    file.flush().await?;
+
+    Ok(written)
+}
+
+async fn send_command(stream: &mut BufReader<TcpStream>, args: &[&str]) -> Result<(), AppError> {
+    let mut command = format!("*{}\r\n", args.len());
+    for arg in args {
+        command.push_str(&format!("${}\r\n{}\r\n", arg.len(), arg));
+    }
+    stream.get_mut().write_all(command.as_bytes()).await?;
+    Ok(())
+}
+
+async fn read_line(stream: &mut BufReader<TcpStream>) -> Result<String, AppError> {
+    let mut line = String::new();
+    if stream.read_line(&mut line).await? == 0 {
+        return Err(AppError::ConnectionError(
+            "Connection closed by server".to_string(),
+        ));
+    }
+    Ok(line.trim_end().to_string())
+}
+
+async fn copy_exact(
+    stream: &mut BufReader<TcpStream>,
+    file: &mut File,
+    size: u64,
+    progress: &mut impl FnMut(u64, Option<u64>),
+) -> Result<u64, AppError> {
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut remaining = size;
+    while remaining > 0 {
+        let want = remaining.min(buffer.len() as u64) as usize;
+        let read = stream.read(&mut buffer[..want]).await?;
+        if read == 0 {
+            return Err(AppError::ConnectionError(format!(
+                "Connection closed with {} bytes remaining",
+                remaining
+            )));
         }
-        "2" => {
-            println!("{}", "Running BGSAVE...".cyan());
-            client.save(true).await?;
-            println!("{}", "BGSAVE triggered successfully.".green());
+        file.write_all(&buffer[..read]).await?;
+        remaining -= read as u64;
+        progress(size - remaining, Some(size));
+    }
+
+    Ok(size)
+}
+
+async fn copy_until_mark(
+    stream: &mut BufReader<TcpStream>,
+    file: &mut File,
+    mark: &[u8],
+    progress: &mut impl FnMut(u64, Option<u64>),
+) -> Result<u64, AppError> {
+    // Hold back the last mark-length bytes until we know they are not the mark
+    let mut pending: Vec<u8> = Vec::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut written = 0u64;
+    loop {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            return Err(AppError::ConnectionError(
+                "Connection closed before the end of the RDB".to_string(),
+            ));
         }
-        "3" => {
-            println!("{}", "Running BGREWRITEAOF...".cyan());
-            client.bgrewriteaof().await?;
-            println!("{}", "BGREWRITEAOF triggered successfully.".green());
+        pending.extend_from_slice(&buffer[..read]);
+
+        if pending.ends_with(mark) {
+            let data = &pending[..pending.len() - mark.len()];
+            file.write_all(data).await?;
+            written += data.len() as u64;
+            break;
         }
-        _ => {
-            println!("{}", "Invalid choice. Aborting.".red());
+        if pending.len() > mark.len() {
+            let flush = pending.len() - mark.len();
+            file.write_all(&pending[..flush]).await?;
+            written += flush as u64;
+            pending.drain(..flush);
         }
+        progress(written, None);
     }
-    Ok(())
+
+    Ok(written)
 }
@@ -1,10 +1,119 @@
-use colored::*;
 use log::info;
+use std::collections::BTreeMap;
 
+use super::migrate::is_busy_key;
+use crate::cli::TtlPolicy;
 use crate::error::AppError;
+use crate::redis_client::RedisClient;
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Bulk command - placeholder");
-    println!("{}", "Bulk command - not yet implemented".yellow());
-    Ok(())
+const BATCH_SIZE: usize = 200;
+
+#[derive(Debug, Default)]
+pub struct TypeSummary {
+    pub copied: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct BulkCopyReport {
+    /// Counts by key type, in type order
+    pub by_type: BTreeMap<String, TypeSummary>,
+    /// Keys that could not be restored: (source, destination, error)
+    pub errors: Vec<(String, String, String)>,
+}
+
+impl BulkCopyReport {
+    pub fn totals(&self) -> TypeSummary {
+        let mut totals = TypeSummary::default();
+        for stats in self.by_type.values() {
+            totals.copied += stats.copied;
+            totals.skipped += stats.skipped;
+            totals.failed += stats.failed;
+        }
+        totals
+    }
+}
+
+/// The part of a glob pattern before its first wildcard or escape.
+pub fn literal_prefix(pattern: &str) -> &str {
+    let end = pattern.find(['*', '?', '[', '\\']).unwrap_or(pattern.len());
+    &pattern[..end]
+}
+
+/// The name `key` is copied to: the literal prefix of `pattern` is replaced
+/// with `to_prefix`.
+pub fn destination(key: &str, pattern: &str, to_prefix: &str) -> String {
+    format!(
+        "{}{}",
+        to_prefix,
+        key.strip_prefix(literal_prefix(pattern)).unwrap_or(key)
+    )
+}
+
+/// How many of the destination keys already exist.
+pub async fn existing_targets(
+    client: &mut RedisClient,
+    keys: &[String],
+    pattern: &str,
+    to_prefix: &str,
+) -> Result<usize, AppError> {
+    let targets: Vec<String> = keys
+        .iter()
+        .map(|key| destination(key, pattern, to_prefix))
+        .collect();
+    let mut existing = 0;
+    for batch in targets.chunks(BATCH_SIZE) {
+        existing += client
+            .exists_many(batch)
+            .await?
+            .into_iter()
+            .filter(|e| *e)
+            .count();
+    }
+    Ok(existing)
+}
+
+/// Copy `keys`, found with `pattern`, to new names under `to_prefix` with
+/// DUMP/RESTORE, so values of every type arrive intact. Existing destination
+/// keys are kept unless `replace`. `progress` is called once per key.
+pub async fn copy(
+    client: &mut RedisClient,
+    keys: &[String],
+    pattern: &str,
+    to_prefix: &str,
+    ttl: &TtlPolicy,
+    replace: bool,
+    mut progress: impl FnMut(),
+) -> Result<BulkCopyReport, AppError> {
+    info!("Bulk copying {} keys to prefix '{}'", keys.len(), to_prefix);
+
+    let mut report = BulkCopyReport::default();
+    for batch in keys.chunks(BATCH_SIZE) {
+        for entry in client.dump_batch(batch).await? {
+            progress();
+            let Some(payload) = entry.payload else {
+                // Expired or deleted between SCAN and DUMP
+                continue;
+            };
+            let stats = report.by_type.entry(entry.key_type).or_default();
+
+            let pttl = match ttl {
+                TtlPolicy::Keep => entry.pttl,
+                TtlPolicy::None => 0,
+                TtlPolicy::Seconds(seconds) => *seconds as i64 * 1000,
+            };
+            let target = destination(&entry.key, pattern, to_prefix);
+            match client.restore(&target, pttl, &payload, replace).await {
+                Ok(_) => stats.copied += 1,
+                Err(e) if is_busy_key(&e) => stats.skipped += 1,
+                Err(e) => {
+                    stats.failed += 1;
+                    report.errors.push((entry.key, target, e.to_string()));
+                }
+            }
+        }
+    }
+
+    Ok(report)
 }
@@ -0,0 +1,136 @@
+use log::info;
+use std::time::{Duration, Instant};
+
+use super::migrate::is_busy_key;
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+const BATCH_SIZE: usize = 200;
+
+#[derive(Debug, Default)]
+pub struct CloneCounts {
+    pub copied: u64,
+    pub skipped: u64,
+    /// Keys that could not be restored, with the error
+    pub errors: Vec<(String, String)>,
+}
+
+/// Keys that did not survive a clone intact.
+#[derive(Debug, Default)]
+pub struct Verification {
+    pub checked: u64,
+    pub missing: Vec<String>,
+    pub different: Vec<String>,
+}
+
+impl Verification {
+    pub fn passed(&self) -> bool {
+        self.missing.is_empty() && self.different.is_empty()
+    }
+}
+
+/// Empty the destination database with FLUSHDB. Returns the number of keys
+/// it held.
+pub async fn flush(dest: &mut RedisClient) -> Result<u64, AppError> {
+    let flushed = dest.dbsize().await?;
+    redis::cmd("FLUSHDB")
+        .query_async::<_, ()>(&mut dest.connection)
+        .await?;
+    Ok(flushed)
+}
+
+/// Copy every key of one database to another with DUMP/RESTORE, keeping
+/// types and TTLs. Existing destination keys are kept unless `replace`.
+/// `rate` limits the keys per second, 0 for unlimited. `progress` is called
+/// once per key with the number done so far.
+pub async fn run(
+    source: &mut RedisClient,
+    dest: &mut RedisClient,
+    replace: bool,
+    rate: u64,
+    mut progress: impl FnMut(u64),
+) -> Result<CloneCounts, AppError> {
+    info!("Cloning database");
+
+    let started = Instant::now();
+    let mut counts = CloneCounts::default();
+    let mut done = 0;
+
+    let mut cursor = 0;
+    loop {
+        let (next, keys) = source.scan_page(cursor, "*", BATCH_SIZE, None).await?;
+
+        for entry in source.dump_batch(&keys).await? {
+            let Some(payload) = entry.payload else {
+                // Expired or deleted between SCAN and DUMP
+                progress(done);
+                continue;
+            };
+            match dest
+                .restore(&entry.key, entry.pttl, &payload, replace)
+                .await
+            {
+                Ok(_) => counts.copied += 1,
+                Err(e) if is_busy_key(&e) => counts.skipped += 1,
+                Err(e) => counts.errors.push((entry.key, e.to_string())),
+            }
+            done += 1;
+            progress(done);
+        }
+
+        // Rate limit by sleeping until the batch is within budget
+        if rate > 0 {
+            let expected = Duration::from_secs_f64(done as f64 / rate as f64);
+            if let Some(wait) = expected.checked_sub(started.elapsed()) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Walk the source again and compare each key's type and value digest with
+/// the destination's. DUMP payloads are not compared because they differ
+/// between Redis versions and encodings even when the values are equal.
+/// `progress` is called once per key.
+pub async fn verify(
+    source: &mut RedisClient,
+    dest: &mut RedisClient,
+    mut progress: impl FnMut(),
+) -> Result<Verification, AppError> {
+    let mut verification = Verification::default();
+
+    let mut cursor = 0;
+    loop {
+        let (next, keys) = source.scan_page(cursor, "*", BATCH_SIZE, None).await?;
+        let expected = source.fetch_values(&keys).await?;
+        let actual = dest.fetch_values(&keys).await?;
+
+        for (expected, actual) in expected.into_iter().zip(actual) {
+            progress();
+            if expected.key_type == "none" {
+                // Expired or deleted since the copy
+                continue;
+            }
+            verification.checked += 1;
+            if actual.key_type == "none" {
+                verification.missing.push(expected.key);
+            } else if actual.key_type != expected.key_type || actual.digest() != expected.digest() {
+                verification.different.push(expected.key);
+            }
+        }
+
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    Ok(verification)
+}
@@ -1,10 +1,620 @@
-use colored::*;
+use futures::future::join_all;
 use log::info;
+use serde::Serialize;
+use std::collections::BTreeSet;
 
+use crate::config::RedisConfig;
 use crate::error::AppError;
+use crate::redis_client::{ClusterNode, RedisClient};
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Cluster command - placeholder");
-    println!("{}", "Cluster command - not yet implemented".yellow());
+/// Number of hash slots in a Redis Cluster
+const SLOT_COUNT: usize = 16384;
+
+/// What one node reported, or why it could not be reached.
+#[derive(Debug, Serialize)]
+pub struct NodeStats {
+    pub address: String,
+    pub role: String,
+    pub failing: bool,
+    pub slots: usize,
+    pub keys: Option<u64>,
+    pub memory: Option<u64>,
+    pub ops_per_sec: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClusterTotals {
+    pub masters: usize,
+    pub replicas: usize,
+    pub keys: u64,
+    pub memory: u64,
+    pub ops_per_sec: u64,
+    pub unreachable: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClusterStats {
+    /// Masters first, then by address
+    pub nodes: Vec<NodeStats>,
+    pub total: ClusterTotals,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SlotLocation {
+    pub key: String,
+    pub slot: u16,
+    pub hash_tag: Option<String>,
+    pub owner: Option<String>,
+    pub replicas: Vec<String>,
+    pub migrating_to: Option<String>,
+    pub importing_into: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClusterCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckReport {
+    pub passed: bool,
+    pub masters: usize,
+    pub nodes: usize,
+    pub checks: Vec<ClusterCheck>,
+}
+
+/// How `cluster reshard` moves slots.
+pub struct ReshardOptions {
+    pub from: String,
+    pub to: String,
+    pub slots: usize,
+    /// Keys per MIGRATE call
+    pub batch: usize,
+    /// MIGRATE timeout in milliseconds
+    pub timeout_ms: u64,
+    /// Only finish slots left open by an interrupted run
+    pub resume: bool,
+    pub dry_run: bool,
+}
+
+/// Where a reshard is in its plan.
+pub enum SlotProgress {
+    Started(u16),
+    Moved(u16),
+}
+
+/// The slots a reshard will move, with connections to both masters.
+pub struct ReshardPlan {
+    pub source_address: String,
+    pub target_address: String,
+    /// Slots left open by an earlier run, finished first
+    pub open: Vec<u16>,
+    /// Every slot to move, in order
+    pub slots: Vec<u16>,
+    nodes: Vec<ClusterNode>,
+    source_id: String,
+    target_id: String,
+    target_config: RedisConfig,
+    source_client: RedisClient,
+    target_client: RedisClient,
+    migrating: BTreeSet<u16>,
+    importing: BTreeSet<u16>,
+}
+
+/// Fail with `AppError::ConfigError` unless `client` is connected to a node
+/// of a Redis Cluster.
+pub async fn ensure_cluster(client: &mut RedisClient, env_name: &str) -> Result<(), AppError> {
+    if !client.cluster_enabled().await? {
+        return Err(AppError::ConfigError(format!(
+            "Environment '{}' is not a Redis Cluster",
+            env_name
+        )));
+    }
     Ok(())
 }
+
+fn address(node: &ClusterNode) -> String {
+    node.host_port()
+        .map(|(host, port)| format!("{}:{}", host, port))
+        .unwrap_or_else(|| node.addr.clone())
+}
+
+/// Connect to every node listed by CLUSTER NODES and collect memory, keys,
+/// throughput and role per node, with totals. Keys are totalled over
+/// masters only, since replicas hold copies of the same keys. `base` holds
+/// the credentials used for every node.
+pub async fn stats(client: &mut RedisClient, base: &RedisConfig) -> Result<ClusterStats, AppError> {
+    info!("Getting cluster stats");
+
+    let mut nodes = client.cluster_nodes().await?;
+    nodes.sort_by(|a, b| {
+        b.is_master()
+            .cmp(&a.is_master())
+            .then_with(|| a.addr.cmp(&b.addr))
+    });
+    let stats: Vec<NodeStats> = join_all(nodes.iter().map(|node| node_stats(base, node))).await;
+
+    let masters = || stats.iter().filter(|node| node.role == "master");
+    let total = ClusterTotals {
+        masters: masters().count(),
+        replicas: stats.len() - masters().count(),
+        keys: masters().filter_map(|node| node.keys).sum(),
+        memory: stats.iter().filter_map(|node| node.memory).sum(),
+        ops_per_sec: stats.iter().filter_map(|node| node.ops_per_sec).sum(),
+        unreachable: stats.iter().filter(|node| node.error.is_some()).count(),
+    };
+
+    Ok(ClusterStats {
+        nodes: stats,
+        total,
+    })
+}
+
+/// Which slot `key` hashes to, the node that owns it and whether the slot
+/// is being moved, to explain MOVED/ASK redirects and CROSSSLOT errors.
+pub async fn locate(client: &mut RedisClient, key: String) -> Result<SlotLocation, AppError> {
+    info!("Locating key: {}", key);
+
+    let slot = client.cluster_keyslot(&key).await?;
+    let nodes = client.cluster_nodes().await?;
+    let by_id = |id: &str| nodes.iter().find(|node| node.id == id).map(address);
+
+    let owner = nodes.iter().find(|node| node.serves_slot(slot));
+    Ok(SlotLocation {
+        hash_tag: hash_tag(&key).map(str::to_string),
+        owner: owner.map(address),
+        replicas: owner
+            .map(|owner| {
+                nodes
+                    .iter()
+                    .filter(|node| node.master == owner.id)
+                    .map(address)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        migrating_to: nodes
+            .iter()
+            .find_map(|node| node.migrating_to(slot))
+            .map(|id| by_id(id).unwrap_or_else(|| id.to_string())),
+        importing_into: nodes
+            .iter()
+            .find(|node| node.importing_from(slot).is_some())
+            .map(address),
+        key,
+        slot,
+    })
+}
+
+/// The `{tag}` part of a key that Redis Cluster hashes instead of the whole
+/// key: the text between the first `{` and the next `}`, if not empty.
+fn hash_tag(key: &str) -> Option<&str> {
+    let start = key.find('{')?;
+    let end = key[start + 1..].find('}')?;
+    (end > 0).then(|| &key[start + 1..start + 1 + end])
+}
+
+/// Validate the cluster the way `redis-cli --cluster check` does: every
+/// slot served exactly once, no open (migrating/importing) slots, unique
+/// config epochs, all nodes agreeing on the configuration and none failing.
+pub async fn check(client: &mut RedisClient, base: &RedisConfig) -> Result<CheckReport, AppError> {
+    info!("Checking cluster health");
+
+    let nodes = client.cluster_nodes().await?;
+    let masters: Vec<&ClusterNode> = nodes.iter().filter(|node| node.is_master()).collect();
+
+    // Slot coverage: each slot owned by exactly one master
+    let mut owners = vec![0u8; SLOT_COUNT];
+    for master in &masters {
+        for (slot, owners) in owners.iter_mut().enumerate() {
+            if master.serves_slot(slot as u16) {
+                *owners = owners.saturating_add(1);
+            }
+        }
+    }
+    let mut coverage = Vec::new();
+    let uncovered = slot_ranges(owners.iter().map(|count| *count == 0));
+    if !uncovered.is_empty() {
+        coverage.push(format!("slots not served: {}", uncovered.join(", ")));
+    }
+    let shared = slot_ranges(owners.iter().map(|count| *count > 1));
+    if !shared.is_empty() {
+        coverage.push(format!("slots served twice: {}", shared.join(", ")));
+    }
+
+    let mut epochs: Vec<(&str, String)> = masters
+        .iter()
+        .map(|node| (node.config_epoch.as_str(), address(node)))
+        .collect();
+    epochs.sort();
+    let clashing: Vec<String> = epochs
+        .windows(2)
+        .filter(|pair| pair[0].0 == pair[1].0)
+        .map(|pair| format!("{} and {} share epoch {}", pair[0].1, pair[1].1, pair[0].0))
+        .collect();
+
+    let failing: Vec<String> = nodes
+        .iter()
+        .filter(|node| node.is_failing() || node.link_state != "connected")
+        .map(|node| format!("{} ({}, {})", address(node), node.flags, node.link_state))
+        .collect();
+
+    // Every reachable node should report the same slot assignment
+    let expected = slot_signature(&nodes);
+    let views = join_all(
+        nodes
+            .iter()
+            .filter(|node| !node.is_failing())
+            .map(|node| async move {
+                let view = match node_config(base, node) {
+                    Some(node_config) => match RedisClient::connect(node_config).await {
+                        Ok(mut client) => client.cluster_nodes().await,
+                        Err(e) => Err(e),
+                    },
+                    None => Err(anyhow::anyhow!("node has no address")),
+                };
+                (address(node), view)
+            }),
+    )
+    .await;
+
+    // Each node reports its own migrating/importing markers only on its
+    // `myself` line, so open slots come from every node's own view
+    let open: Vec<String> = views
+        .iter()
+        .filter_map(|(address, view)| {
+            let myself = view.as_ref().ok()?.iter().find(|node| node.is_myself())?;
+            Some((address, myself))
+        })
+        .flat_map(|(address, myself)| {
+            myself
+                .slots
+                .iter()
+                .filter(|range| range.starts_with('['))
+                .map(move |marker| format!("{} {}", address, marker))
+        })
+        .collect();
+
+    let disagreeing: Vec<String> = views
+        .into_iter()
+        .filter_map(|(address, view)| match view {
+            Ok(view) if slot_signature(&view) == expected => None,
+            Ok(_) => Some(format!(
+                "{} has a different view of slot ownership",
+                address
+            )),
+            Err(e) => Some(format!("{} unreachable: {}", address, e)),
+        })
+        .collect();
+
+    let checks = vec![
+        ClusterCheck {
+            name: "slot coverage",
+            ok: coverage.is_empty(),
+            problems: coverage,
+        },
+        ClusterCheck {
+            name: "open slots",
+            ok: open.is_empty(),
+            problems: open,
+        },
+        ClusterCheck {
+            name: "config epochs",
+            ok: clashing.is_empty(),
+            problems: clashing,
+        },
+        ClusterCheck {
+            name: "node state",
+            ok: failing.is_empty(),
+            problems: failing,
+        },
+        ClusterCheck {
+            name: "config consistency",
+            ok: disagreeing.is_empty(),
+            problems: disagreeing,
+        },
+    ];
+
+    Ok(CheckReport {
+        passed: checks.iter().all(|check| check.ok),
+        masters: masters.len(),
+        nodes: nodes.len(),
+        checks,
+    })
+}
+
+/// Collapse per-slot flags into `start-end` ranges of the slots that are set.
+fn slot_ranges(flags: impl Iterator<Item = bool>) -> Vec<String> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (slot, set) in flags.chain(std::iter::once(false)).enumerate() {
+        match (set, start) {
+            (true, None) => start = Some(slot),
+            (false, Some(first)) => {
+                ranges.push(if first == slot - 1 {
+                    first.to_string()
+                } else {
+                    format!("{}-{}", first, slot - 1)
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    ranges
+}
+
+/// Which master serves which slots, in a form that can be compared between
+/// the views of different nodes.
+fn slot_signature(nodes: &[ClusterNode]) -> Vec<(String, Vec<String>)> {
+    let mut signature: Vec<(String, Vec<String>)> = nodes
+        .iter()
+        .filter(|node| node.is_master())
+        .map(|node| {
+            let mut slots: Vec<String> = node
+                .slots
+                .iter()
+                .filter(|range| !range.starts_with('['))
+                .cloned()
+                .collect();
+            slots.sort();
+            (node.id.clone(), slots)
+        })
+        .collect();
+    signature.sort();
+    signature
+}
+
+impl ReshardPlan {
+    /// Work out which slots to move between two masters. Slots already open
+    /// between the two nodes (from an interrupted run) are always included
+    /// and count towards `options.slots`; `options.resume` plans only those.
+    pub async fn new(
+        client: &mut RedisClient,
+        base: &RedisConfig,
+        options: &ReshardOptions,
+    ) -> Result<Self, AppError> {
+        info!("Resharding from {} to {}", options.from, options.to);
+
+        let nodes = client.cluster_nodes().await?;
+        let source = find_master(&nodes, &options.from)?;
+        let target = find_master(&nodes, &options.to)?;
+        if source.id == target.id {
+            return Err(AppError::ConfigError(
+                "--from and --to are the same node".to_string(),
+            ));
+        }
+
+        let source_config = node_config(base, source)
+            .ok_or_else(|| AppError::ConfigError("Source node has no address".to_string()))?;
+        let target_config = node_config(base, target)
+            .ok_or_else(|| AppError::ConfigError("Target node has no address".to_string()))?;
+        let source_address = format!("{}:{}", source_config.host, source_config.port);
+        let target_address = format!("{}:{}", target_config.host, target_config.port);
+        let mut source_client = RedisClient::connect(source_config).await?;
+        let mut target_client = RedisClient::connect(target_config.clone()).await?;
+
+        // Slots an earlier run left half-moved between these two nodes. A node
+        // only reports its own migrating/importing markers, so ask each one.
+        let source_view = source_client.cluster_myself().await?;
+        let target_view = target_client.cluster_myself().await?;
+        let migrating: BTreeSet<u16> = (0..SLOT_COUNT as u16)
+            .filter(|slot| source_view.migrating_to(*slot) == Some(target.id.as_str()))
+            .collect();
+        let importing: BTreeSet<u16> = (0..SLOT_COUNT as u16)
+            .filter(|slot| target_view.importing_from(*slot) == Some(source.id.as_str()))
+            .collect();
+        let open: Vec<u16> = migrating.union(&importing).copied().collect();
+        let mut slots = open.clone();
+        if !options.resume {
+            slots.extend(
+                (0..SLOT_COUNT as u16)
+                    .filter(|slot| source.serves_slot(*slot) && !open.contains(slot))
+                    .take(options.slots.saturating_sub(open.len())),
+            );
+        }
+        slots.sort_unstable();
+
+        Ok(Self {
+            source_address,
+            target_address,
+            open,
+            slots,
+            source_id: source.id.clone(),
+            target_id: target.id.clone(),
+            nodes,
+            target_config,
+            source_client,
+            target_client,
+            migrating,
+            importing,
+        })
+    }
+
+    /// The planned slots as `start-end` ranges.
+    pub fn slot_ranges(&self) -> Vec<String> {
+        slot_ranges((0..SLOT_COUNT as u16).map(|slot| self.slots.binary_search(&slot).is_ok()))
+    }
+
+    /// How many keys the planned slots hold on the source.
+    pub async fn count_keys(&mut self) -> Result<u64, AppError> {
+        let mut keys = 0;
+        for slot in &self.slots {
+            keys += self.source_client.cluster_countkeysinslot(*slot).await?;
+        }
+        Ok(keys)
+    }
+
+    /// Move the planned slots: each slot is marked IMPORTING on the target
+    /// and MIGRATING on the source, its keys are moved in MIGRATE batches of
+    /// `batch`, and ownership is then assigned to the target on every master.
+    /// Returns the number of keys moved.
+    pub async fn execute(
+        mut self,
+        base: &RedisConfig,
+        batch: usize,
+        timeout_ms: u64,
+        mut progress: impl FnMut(SlotProgress),
+    ) -> Result<usize, AppError> {
+        let source_id = self.source_id.as_str();
+        let target_id = self.target_id.as_str();
+
+        let mut moved_keys = 0usize;
+        for slot in &self.slots {
+            progress(SlotProgress::Started(*slot));
+            // A resumed slot may have only one half of the pair set
+            if !self.importing.contains(slot) {
+                self.target_client
+                    .cluster_setslot(*slot, "IMPORTING", Some(source_id))
+                    .await?;
+            }
+            if !self.migrating.contains(slot) {
+                self.source_client
+                    .cluster_setslot(*slot, "MIGRATING", Some(target_id))
+                    .await?;
+            }
+
+            loop {
+                let keys = self
+                    .source_client
+                    .cluster_getkeysinslot(*slot, batch)
+                    .await?;
+                if keys.is_empty() {
+                    break;
+                }
+                self.source_client
+                    .migrate_keys(
+                        &self.target_config.host,
+                        self.target_config.port,
+                        &keys,
+                        timeout_ms,
+                        self.target_config.password.as_deref(),
+                    )
+                    .await
+                    .map_err(|e| {
+                        AppError::Aborted(format!(
+                            "MIGRATE failed for slot {}: {}. Fix the cause and re-run with --resume",
+                            slot, e
+                        ))
+                    })?;
+                moved_keys += keys.len();
+            }
+
+            // The target first, so it owns the slot before the source lets go
+            self.target_client
+                .cluster_setslot(*slot, "NODE", Some(target_id))
+                .await?;
+            self.source_client
+                .cluster_setslot(*slot, "NODE", Some(target_id))
+                .await?;
+            progress(SlotProgress::Moved(*slot));
+        }
+
+        // Tell the remaining masters straight away rather than waiting for gossip
+        for node in self
+            .nodes
+            .iter()
+            .filter(|node| node.is_master() && node.id != source_id && node.id != target_id)
+        {
+            let Some(node_config) = node_config(base, node) else {
+                continue;
+            };
+            if let Ok(mut other) = RedisClient::connect(node_config).await {
+                for slot in &self.slots {
+                    if let Err(e) = other.cluster_setslot(*slot, "NODE", Some(target_id)).await {
+                        info!("Could not update {} about slot {}: {}", node.addr, slot, e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(moved_keys)
+    }
+}
+
+/// A master given by node id, unique id prefix, or `host:port`.
+fn find_master<'a>(nodes: &'a [ClusterNode], spec: &str) -> Result<&'a ClusterNode, AppError> {
+    let matches: Vec<&ClusterNode> = nodes
+        .iter()
+        .filter(|node| {
+            node.id.starts_with(spec)
+                || node
+                    .host_port()
+                    .is_some_and(|(host, port)| format!("{}:{}", host, port) == spec)
+        })
+        .collect();
+    match matches.as_slice() {
+        [node] if node.is_master() => Ok(node),
+        [node] => Err(AppError::ConfigError(format!(
+            "Node '{}' ({}) is not a master",
+            spec, node.addr
+        ))),
+        [] => Err(AppError::NotFound(format!("No cluster node '{}'", spec))),
+        _ => Err(AppError::ConfigError(format!(
+            "Node '{}' is ambiguous; give more of the node id",
+            spec
+        ))),
+    }
+}
+
+/// Connection settings for another node of the cluster, keeping the
+/// environment's credentials, timeout and TLS settings.
+pub fn node_config(base: &RedisConfig, node: &ClusterNode) -> Option<RedisConfig> {
+    let (host, port) = node.host_port()?;
+    Some(RedisConfig {
+        host,
+        port,
+        db: 0,
+        ..base.clone()
+    })
+}
+
+async fn node_stats(base: &RedisConfig, node: &ClusterNode) -> NodeStats {
+    let mut stats = NodeStats {
+        address: address(node),
+        role: if node.is_master() {
+            "master".to_string()
+        } else {
+            "replica".to_string()
+        },
+        failing: node.is_failing(),
+        slots: node.slot_count(),
+        keys: None,
+        memory: None,
+        ops_per_sec: None,
+        error: None,
+    };
+
+    let config = match node_config(base, node) {
+        Some(config) => config,
+        None => {
+            stats.error = Some("node has no address".to_string());
+            return stats;
+        }
+    };
+    let info = match RedisClient::connect(config).await {
+        Ok(mut client) => client.info().await,
+        Err(e) => Err(e),
+    };
+    match info {
+        Ok(info) => {
+            let number = |name: &str| info.get(name).and_then(|v| v.trim().parse().ok());
+            stats.memory = number("used_memory");
+            stats.ops_per_sec = number("instantaneous_ops_per_sec");
+            stats.keys = Some(
+                info.get("db0")
+                    .and_then(|db| {
+                        db.split(',')
+                            .find_map(|field| field.strip_prefix("keys="))
+                            .and_then(|count| count.trim().parse().ok())
+                    })
+                    .unwrap_or(0),
+            );
+        }
+        Err(e) => stats.error = Some(e.to_string()),
+    }
+    stats
+}
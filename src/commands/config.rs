@@ -1,206 +1,451 @@
-use colored::*;
 use log::info;
-use tabled::{Table, Tabled};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-use crate::config::{AppConfig, OutputFormat, RedisConfig};
+use crate::config::{AppConfig, Environment, OutputFormat, RedisConfig};
 use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Stands in for passwords in output and exports. Importing an environment
+/// with this password keeps the password already configured locally.
+const REDACTED_PASSWORD: &str = "********";
+
+/// The shareable part of the config file: environment definitions only,
+/// never favorites, history size or output preferences.
+#[derive(Serialize, Deserialize)]
+struct EnvironmentBundle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_environment: Option<String>,
+    environments: BTreeMap<String, Environment>,
+}
 
-#[derive(Tabled)]
-struct EnvironmentRow {
-    #[tabled(rename = "Name")]
-    name: String,
-    #[tabled(rename = "Host")]
-    host: String,
-    #[tabled(rename = "Port")]
-    port: String,
-    #[tabled(rename = "Database")]
-    db: String,
-    #[tabled(rename = "TLS")]
-    tls: String,
-    #[tabled(rename = "Default")]
-    default: String,
-}
-
-pub async fn run() -> Result<(), AppError> {
-    info!("Running config command");
-
-    let config = AppConfig::load()?;
-
-    println!("{}", "Current Configuration:".bold());
-    println!("{}", "=".repeat(50));
-
-    // Show default environment
-    if let Some(default_env) = &config.default_environment {
-        println!("Default Environment: {}", default_env.cyan().bold());
-    }
+/// What `export` wrote.
+#[derive(Debug)]
+pub struct ExportSummary {
+    pub environments: usize,
+    /// Passwords replaced by a placeholder
+    pub redacted: usize,
+}
 
-    // Show output format
-    let format_str = match config.output_format {
-        OutputFormat::Json => "JSON",
-        OutputFormat::Table => "Table",
-        OutputFormat::Csv => "CSV",
-        OutputFormat::Plain => "Plain",
-    };
-    println!("Output Format: {}", format_str.cyan());
-    println!("History Size: {}", config.history_size.to_string().cyan());
+/// What `import` changed, by environment name.
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    /// Imported with a redacted password and no local one to keep
+    pub need_password: Vec<String>,
+}
 
-    // Show environments
-    println!("\n{}", "Environments:".bold());
-    println!("{}", "=".repeat(50));
+/// Operator annotations to change; `None` leaves one as it is and an empty
+/// value clears it.
+#[derive(Debug, Default)]
+pub struct Annotations {
+    pub description: Option<String>,
+    pub owner: Option<String>,
+    pub dashboard_url: Option<String>,
+    pub runbook_url: Option<String>,
+    pub banner_color: Option<String>,
+}
 
-    let mut rows = Vec::new();
-    for (name, env) in &config.environments {
-        let is_default = config
-            .default_environment
-            .as_ref()
-            .map_or(false, |d| d == name);
-        rows.push(EnvironmentRow {
-            name: name.clone(),
-            host: env.config.host.clone(),
-            port: env.config.port.to_string(),
-            db: env.config.db.to_string(),
-            tls: if env.config.tls {
-                "Yes".green().to_string()
-            } else {
-                "No".red().to_string()
-            },
-            default: if is_default {
-                "✓".green().to_string()
-            } else {
-                "".to_string()
-            },
-        });
-    }
+#[derive(Debug, Serialize)]
+pub struct SettingDiff {
+    pub setting: String,
+    pub left: String,
+    pub right: String,
+    pub differs: bool,
+}
 
-    let table = Table::new(rows).to_string();
-    println!("{}", table);
+#[derive(Debug)]
+pub struct ConfigDiff {
+    pub settings: Vec<SettingDiff>,
+    /// Why the server settings of each side could not be read
+    pub left_error: Option<String>,
+    pub right_error: Option<String>,
+}
 
-    // Show favorites
-    if !config.favorites.is_empty() {
-        println!("\n{}", "Favorites:".bold());
-        println!("{}", "=".repeat(50));
-        for favorite in &config.favorites {
-            println!("• {}", favorite.cyan());
+/// The configuration with every password replaced by a placeholder, for
+/// machine-readable output.
+pub fn redacted(config: &AppConfig) -> AppConfig {
+    let mut redacted = config.clone();
+    for env in redacted.environments.values_mut() {
+        if env.config.password.is_some() {
+            env.config.password = Some(REDACTED_PASSWORD.to_string());
         }
     }
-
-    Ok(())
+    redacted
 }
 
-pub async fn add_environment(
-    name: String,
-    host: String,
-    port: u16,
-    password: Option<String>,
-    db: u8,
-    timeout: Option<u64>,
-    tls: bool,
-) -> Result<(), AppError> {
+pub fn add_environment(name: &str, redis_config: RedisConfig) -> Result<(), AppError> {
     info!("Adding environment: {}", name);
 
     let mut config = AppConfig::load()?;
-
-    let redis_config = RedisConfig {
-        host,
-        port,
-        password,
-        db,
-        timeout,
-        tls,
-    };
-
-    config.add_environment(name.clone(), redis_config);
+    config.add_environment(name.to_string(), redis_config);
     config.save()?;
-
-    println!(
-        "{}",
-        format!("✓ Environment '{}' added successfully!", name)
-            .green()
-            .bold()
-    );
     Ok(())
 }
 
-pub async fn remove_environment(name: &str) -> Result<(), AppError> {
+pub fn remove_environment(name: &str) -> Result<(), AppError> {
     info!("Removing environment: {}", name);
 
     let mut config = AppConfig::load()?;
-
-    if config.remove_environment(name) {
-        config.save()?;
-        println!(
-            "{}",
-            format!("✓ Environment '{}' removed successfully!", name)
-                .green()
-                .bold()
-        );
-    } else {
-        println!(
-            "{}",
-            format!("✗ Environment '{}' not found!", name).red().bold()
-        );
+    if !config.remove_environment(name) {
+        return Err(AppError::ConfigError(format!(
+            "Environment '{}' not found",
+            name
+        )));
     }
-
+    config.save()?;
     Ok(())
 }
 
-pub async fn set_default_environment(name: &str) -> Result<(), AppError> {
+pub fn set_default_environment(name: &str) -> Result<(), AppError> {
     info!("Setting default environment: {}", name);
 
     let mut config = AppConfig::load()?;
-
-    if config.get_environment(name).is_some() {
-        config.default_environment = Some(name.to_string());
-        config.save()?;
-        println!(
-            "{}",
-            format!("✓ Default environment set to '{}'", name)
-                .green()
-                .bold()
-        );
-    } else {
-        println!(
-            "{}",
-            format!("✗ Environment '{}' not found!", name).red().bold()
-        );
+    if config.get_environment(name).is_none() {
+        return Err(AppError::ConfigError(format!(
+            "Environment '{}' not found",
+            name
+        )));
     }
-
+    config.default_environment = Some(name.to_string());
+    config.save()?;
     Ok(())
 }
 
-pub async fn set_output_format(format: OutputFormat) -> Result<(), AppError> {
+pub fn set_output_format(format: OutputFormat) -> Result<(), AppError> {
     info!("Setting output format");
 
     let mut config = AppConfig::load()?;
     config.output_format = format;
     config.save()?;
-
-    let format_str = match config.output_format {
-        OutputFormat::Json => "JSON",
-        OutputFormat::Table => "Table",
-        OutputFormat::Csv => "CSV",
-        OutputFormat::Plain => "Plain",
-    };
-
-    println!(
-        "{}",
-        format!("✓ Output format set to {}", format_str)
-            .green()
-            .bold()
-    );
     Ok(())
 }
 
-pub async fn set_history_size(size: usize) -> Result<(), AppError> {
+pub fn set_history_size(size: usize) -> Result<(), AppError> {
     info!("Setting history size: {}", size);
 
     let mut config = AppConfig::load()?;
     config.history_size = size;
     config.save()?;
+    Ok(())
+}
 
-    println!(
-        "{}",
-        format!("✓ History size set to {}", size).green().bold()
-    );
+/// Load the config, change one environment with `update` and save it.
+fn update_environment(
+    name: &str,
+    update: impl FnOnce(&mut Environment),
+) -> Result<Environment, AppError> {
+    let mut config = AppConfig::load()?;
+    let env = config
+        .environments
+        .get_mut(name)
+        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", name)))?;
+    update(env);
+    let env = env.clone();
+    config.save()?;
+    Ok(env)
+}
+
+pub fn set_protected(name: &str, protected: bool) -> Result<(), AppError> {
+    info!("Setting protected={} on environment: {}", protected, name);
+
+    update_environment(name, |env| env.protected = protected)?;
     Ok(())
 }
+
+pub fn set_read_only(name: &str, read_only: bool) -> Result<(), AppError> {
+    info!("Setting read_only={} on environment: {}", read_only, name);
+
+    update_environment(name, |env| env.read_only = read_only)?;
+    Ok(())
+}
+
+/// Change the operator annotations of an environment and return it as
+/// saved.
+pub fn annotate_environment(name: &str, annotations: Annotations) -> Result<Environment, AppError> {
+    info!("Annotating environment: {}", name);
+
+    // An empty value clears the annotation
+    let normalize = |value: String| {
+        if value.trim().is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    };
+    update_environment(name, |env| {
+        if let Some(description) = annotations.description {
+            env.description = normalize(description);
+        }
+        if let Some(owner) = annotations.owner {
+            env.owner = normalize(owner);
+        }
+        if let Some(dashboard_url) = annotations.dashboard_url {
+            env.dashboard_url = normalize(dashboard_url);
+        }
+        if let Some(runbook_url) = annotations.runbook_url {
+            env.runbook_url = normalize(runbook_url);
+        }
+        if let Some(banner_color) = annotations.banner_color {
+            env.banner_color = Some(banner_color);
+        }
+    })
+}
+
+/// Write environment definitions to a TOML file that teammates can
+/// import. Passwords are redacted unless `include_passwords` is set.
+pub fn export(
+    path: &str,
+    environments: &[String],
+    include_passwords: bool,
+) -> Result<ExportSummary, AppError> {
+    info!("Exporting environments to {}", path);
+
+    let config = AppConfig::load()?;
+    let mut bundle = EnvironmentBundle {
+        default_environment: None,
+        environments: BTreeMap::new(),
+    };
+    for (name, env) in &config.environments {
+        if environments.is_empty() || environments.contains(name) {
+            bundle.environments.insert(name.clone(), env.clone());
+        }
+    }
+    if let Some(missing) = environments
+        .iter()
+        .find(|name| !config.environments.contains_key(*name))
+    {
+        return Err(AppError::ConfigError(format!(
+            "Environment '{}' not found",
+            missing
+        )));
+    }
+    bundle.default_environment = config
+        .default_environment
+        .clone()
+        .filter(|name| bundle.environments.contains_key(name));
+
+    let mut redacted = 0;
+    if !include_passwords {
+        for env in bundle.environments.values_mut() {
+            if env.config.password.is_some() {
+                env.config.password = Some(REDACTED_PASSWORD.to_string());
+                redacted += 1;
+            }
+        }
+    }
+
+    let content = toml::to_string_pretty(&bundle).map_err(|e| anyhow::anyhow!(e))?;
+    std::fs::write(path, content)
+        .map_err(|e| AppError::ConfigError(format!("Cannot write '{}': {}", path, e)))?;
+
+    Ok(ExportSummary {
+        environments: bundle.environments.len(),
+        redacted,
+    })
+}
+
+/// Load environment definitions exported by [`export`]. By default the
+/// imported environments replace the configured ones; with `merge` they are
+/// added alongside them, replacing only environments of the same name.
+/// Redacted passwords keep the local password of that environment.
+pub fn import(path: &str, merge: bool) -> Result<ImportSummary, AppError> {
+    info!("Importing environments from {} (merge: {})", path, merge);
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::ConfigError(format!("Cannot read '{}': {}", path, e)))?;
+    let bundle: EnvironmentBundle = toml::from_str(&content)
+        .map_err(|e| AppError::ConfigError(format!("Cannot parse '{}': {}", path, e)))?;
+    if bundle.environments.is_empty() {
+        return Err(AppError::ConfigError(format!(
+            "'{}' defines no environments",
+            path
+        )));
+    }
+
+    let mut config = AppConfig::load()?;
+    let mut added = Vec::new();
+    let mut updated = Vec::new();
+    let mut need_password = Vec::new();
+    let mut imported = std::collections::HashMap::new();
+    for (name, mut env) in bundle.environments {
+        env.name = name.clone();
+        let existing = config.environments.get(&name);
+        if env.config.password.as_deref() == Some(REDACTED_PASSWORD) {
+            env.config.password = existing.and_then(|e| e.config.password.clone());
+            if env.config.password.is_none() {
+                need_password.push(name.clone());
+            }
+        }
+        if existing.is_some() {
+            updated.push(name.clone());
+        } else {
+            added.push(name.clone());
+        }
+        imported.insert(name, env);
+    }
+
+    let mut removed: Vec<String> = Vec::new();
+    if merge {
+        config.environments.extend(imported);
+    } else {
+        removed = config
+            .environments
+            .keys()
+            .filter(|name| !imported.contains_key(*name))
+            .cloned()
+            .collect();
+        removed.sort();
+        config.environments = imported;
+        if bundle.default_environment.is_some() {
+            config.default_environment = bundle.default_environment;
+        }
+    }
+    // Never leave the default pointing at an environment that is gone
+    if let Some(default) = &config.default_environment {
+        if !config.environments.contains_key(default) {
+            config.default_environment = None;
+        }
+    }
+    config.save()?;
+
+    Ok(ImportSummary {
+        added,
+        updated,
+        removed,
+        need_password,
+    })
+}
+
+/// Server parameters compared by `config diff`: the ones behind most
+/// "works in dev" surprises (eviction, persistence, limits, notifications).
+const DIFF_PARAMS: &[&str] = &[
+    "maxmemory",
+    "maxmemory-policy",
+    "maxclients",
+    "timeout",
+    "save",
+    "appendonly",
+    "appendfsync",
+    "databases",
+    "notify-keyspace-events",
+    "lazyfree-lazy-eviction",
+    "slowlog-log-slower-than",
+];
+
+/// Server parameters holding credentials; diffs only show whether they are
+/// set
+const SECRET_PARAMS: &[&str] = &["requirepass", "masterauth"];
+
+/// Compare two environments: their connection settings from the config
+/// file, the server version, and live CONFIG GET values. With `all`, every
+/// server parameter that differs is listed, not just the usual suspects.
+pub async fn diff(left: &str, right: &str, all: bool) -> Result<ConfigDiff, AppError> {
+    info!("Diffing configuration of '{}' and '{}'", left, right);
+
+    let config = AppConfig::load()?;
+    let left_env = config
+        .get_environment(left)
+        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", left)))?
+        .clone();
+    let right_env = config
+        .get_environment(right)
+        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", right)))?
+        .clone();
+
+    let mut settings = Vec::new();
+    let mut push = |setting: &str, left: String, right: String| {
+        settings.push(SettingDiff {
+            setting: setting.to_string(),
+            differs: left != right,
+            left,
+            right,
+        });
+    };
+    let (l, r) = (&left_env.config, &right_env.config);
+    push("host", l.host.clone(), r.host.clone());
+    push("port", l.port.to_string(), r.port.to_string());
+    push("db", l.db.to_string(), r.db.to_string());
+    push("tls", l.tls.to_string(), r.tls.to_string());
+    let timeout = |t: Option<u64>| t.map_or("default".to_string(), |t| format!("{}s", t));
+    push("client timeout", timeout(l.timeout), timeout(r.timeout));
+    // Only whether a password is set, never the password itself
+    let password = |p: &Option<String>| if p.is_some() { "set" } else { "not set" }.to_string();
+    push("password", password(&l.password), password(&r.password));
+    push(
+        "protected",
+        left_env.protected.to_string(),
+        right_env.protected.to_string(),
+    );
+    push(
+        "read-only",
+        left_env.read_only.to_string(),
+        right_env.read_only.to_string(),
+    );
+
+    let left_server = server_settings(&left_env.config).await;
+    let right_server = server_settings(&right_env.config).await;
+    let mut names: Vec<String> = if all {
+        let mut names: Vec<String> = left_server
+            .iter()
+            .chain(&right_server)
+            .flat_map(|settings| settings.keys().cloned())
+            .filter(|name| name != "redis_version")
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    } else {
+        DIFF_PARAMS.iter().map(|name| name.to_string()).collect()
+    };
+    names.insert(0, "redis_version".to_string());
+    let lookup = |server: &Result<BTreeMap<String, String>, String>, name: &str| match server {
+        Ok(settings) => settings
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| "-".to_string()),
+        Err(_) => "?".to_string(),
+    };
+    for name in names {
+        let left_value = lookup(&left_server, &name);
+        let right_value = lookup(&right_server, &name);
+        // --all lists only the parameters that differ
+        if all && left_value == right_value && name != "redis_version" {
+            continue;
+        }
+        push(&name, left_value, right_value);
+    }
+
+    Ok(ConfigDiff {
+        settings,
+        left_error: left_server.err(),
+        right_error: right_server.err(),
+    })
+}
+
+/// Server version and CONFIG GET values of one environment, or why they
+/// could not be read (unreachable, or CONFIG disabled by the provider).
+async fn server_settings(config: &RedisConfig) -> Result<BTreeMap<String, String>, String> {
+    let mut client = RedisClient::connect(config.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut settings: BTreeMap<String, String> = client
+        .config_get("*")
+        .await
+        .map_err(|e| format!("CONFIG GET failed: {}", e))?
+        .into_iter()
+        .collect();
+    for name in SECRET_PARAMS {
+        if let Some(value) = settings.get_mut(*name) {
+            *value = if value.is_empty() { "not set" } else { "set" }.to_string();
+        }
+    }
+    if let Ok(info) = client.info().await {
+        if let Some(version) = info.get("redis_version") {
+            settings.insert("redis_version".to_string(), version.clone());
+        }
+    }
+    Ok(settings)
+}
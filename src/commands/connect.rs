@@ -1,158 +1,165 @@
-use colored::*;
+use futures::future::join_all;
 use log::info;
+use serde::Serialize;
+use std::time::{Duration, Instant};
 
 use crate::config::{AppConfig, RedisConfig};
 use crate::error::AppError;
 use crate::redis_client::RedisClient;
 
-pub async fn run(
-    host: Option<String>,
-    port: Option<u16>,
-    password: Option<String>,
-    db: Option<u8>,
-    environment: Option<String>,
-    timeout: Option<u64>,
-    tls: bool,
-) -> Result<(), AppError> {
-    info!("Running connect command");
-
-    let mut config = AppConfig::load()?;
+/// How long `test_all` waits for an environment without its own timeout
+const DEFAULT_TEST_TIMEOUT_SECS: u64 = 5;
+
+/// INFO fields shown after connecting
+const SERVER_FIELDS: &[&str] = &[
+    "redis_version",
+    "os",
+    "arch_bits",
+    "process_id",
+    "uptime_in_seconds",
+    "uptime_in_days",
+    "connected_clients",
+    "used_memory_human",
+    "used_memory_peak_human",
+];
+
+/// Connection settings given on the command line, each overriding the
+/// environment's own.
+#[derive(Debug, Default)]
+pub struct ConnectOptions {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub password: Option<String>,
+    pub db: Option<u8>,
+    pub timeout: Option<u64>,
+    pub tls: bool,
+}
 
-    // Determine which environment to use
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
+#[derive(Debug, Serialize)]
+pub struct EnvironmentStatus {
+    pub environment: String,
+    pub address: String,
+    pub reachable: bool,
+    pub latency_ms: Option<f64>,
+    pub version: Option<String>,
+    pub error: Option<String>,
+}
 
-    let mut redis_config = if let Some(env) = config.get_environment(&env_name) {
-        env.config.clone()
-    } else {
-        // Create new environment with provided parameters
-        RedisConfig {
-            host: host.clone().unwrap_or_else(|| "localhost".to_string()),
-            port: port.unwrap_or(6379),
-            password: password.clone(),
-            db: db.unwrap_or(0),
-            timeout,
-            tls,
-        }
+/// The settings to connect with: those of `env_name` if it is configured,
+/// otherwise localhost:6379, with `options` applied on top.
+pub fn resolve(config: &AppConfig, env_name: &str, options: &ConnectOptions) -> RedisConfig {
+    let mut redis_config = match config.get_environment(env_name) {
+        Some(env) => env.config.clone(),
+        None => RedisConfig {
+            host: "localhost".to_string(),
+            port: 6379,
+            password: None,
+            db: 0,
+            timeout: None,
+            tls: false,
+        },
     };
 
-    // Override with command line parameters if provided
-    if let Some(host) = &host {
+    if let Some(host) = &options.host {
         redis_config.host = host.clone();
     }
-    if let Some(port) = port {
+    if let Some(port) = options.port {
         redis_config.port = port;
     }
-    if let Some(password) = &password {
+    if let Some(password) = &options.password {
         redis_config.password = Some(password.clone());
     }
-    if let Some(db) = db {
+    if let Some(db) = options.db {
         redis_config.db = db;
     }
-    if let Some(timeout) = timeout {
+    if let Some(timeout) = options.timeout {
         redis_config.timeout = Some(timeout);
     }
-    redis_config.tls = tls;
-
-    println!("{}", "Connecting to Redis...".yellow());
-    println!("Host: {}", redis_config.host.cyan());
-    println!("Port: {}", redis_config.port.to_string().cyan());
-    println!("Database: {}", redis_config.db.to_string().cyan());
-    println!(
-        "TLS: {}",
-        if redis_config.tls {
-            "Yes".green()
-        } else {
-            "No".red()
-        }
-    );
-
-    // Test connection
-    let test_config = redis_config.clone();
-    match RedisClient::connect(test_config).await {
-        Ok(mut client) => {
-            println!("{}", "✓ Connected successfully!".green().bold());
-
-            // Test ping
-            match client.ping().await {
-                Ok(response) => println!("Ping: {}", response.green()),
-                Err(e) => println!("{}", format!("Ping failed: {}", e).red()),
-            }
-
-            // Show INFO
-            match client.info().await {
-                Ok(info) => {
-                    println!("\n{}", "Redis Server Information:".bold());
-                    println!("{}", "=".repeat(50));
-
-                    let important_keys = [
-                        "redis_version",
-                        "os",
-                        "arch_bits",
-                        "process_id",
-                        "uptime_in_seconds",
-                        "uptime_in_days",
-                        "connected_clients",
-                        "used_memory_human",
-                        "used_memory_peak_human",
-                    ];
-
-                    for key in &important_keys {
-                        if let Some(value) = info.get(*key) {
-                            println!("{}: {}", key.cyan(), value.yellow());
-                        }
-                    }
-                }
-                Err(e) => println!("{}", format!("Failed to get INFO: {}", e).red()),
-            }
+    redis_config.tls = options.tls;
+    redis_config
+}
 
-            // Save environment if it's new
-            if config.get_environment(&env_name).is_none() {
-                config.add_environment(env_name.clone(), redis_config);
-                config.default_environment = Some(env_name);
-                config.save()?;
-                println!("{}", "Environment saved to config".green());
-            }
+/// The INFO fields worth showing right after connecting, in a fixed order.
+pub async fn server_summary(
+    client: &mut RedisClient,
+) -> Result<Vec<(&'static str, String)>, AppError> {
+    let info = client.info().await?;
+    Ok(SERVER_FIELDS
+        .iter()
+        .filter_map(|field| info.get(*field).map(|value| (*field, value.clone())))
+        .collect())
+}
 
-            Ok(())
-        }
-        Err(e) => {
-            println!("{}", format!("✗ Connection failed: {}", e).red().bold());
-            Err(AppError::ConnectionError(e.to_string()))
-        }
+/// Save `redis_config` as a new environment and make it the default, unless
+/// `env_name` is already configured. Returns whether it was saved.
+pub fn remember(
+    config: &mut AppConfig,
+    env_name: &str,
+    redis_config: RedisConfig,
+) -> Result<bool, AppError> {
+    if config.get_environment(env_name).is_some() {
+        return Ok(false);
     }
+    config.add_environment(env_name.to_string(), redis_config);
+    config.default_environment = Some(env_name.to_string());
+    config.save()?;
+    Ok(true)
 }
 
-pub async fn test_connection(environment: &str) -> Result<(), AppError> {
-    let config = AppConfig::load()?;
+/// Connect to and PING every configured environment at once, sorted by
+/// name. `timeout` overrides each environment's own timeout.
+pub async fn test_all(config: &AppConfig, timeout: Option<u64>) -> Vec<EnvironmentStatus> {
+    info!("Testing every environment");
+
+    let mut environments: Vec<_> = config.environments.values().collect();
+    environments.sort_by(|a, b| a.name.cmp(&b.name));
+
+    join_all(environments.iter().map(|env| {
+        let limit = timeout
+            .or(env.config.timeout)
+            .unwrap_or(DEFAULT_TEST_TIMEOUT_SECS);
+        check_environment(&env.name, &env.config, Duration::from_secs(limit))
+    }))
+    .await
+}
 
-    let redis_config = config
-        .get_environment(environment)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", environment)))?
-        .config
-        .clone();
+async fn check_environment(
+    name: &str,
+    redis_config: &RedisConfig,
+    limit: Duration,
+) -> EnvironmentStatus {
+    let mut status = EnvironmentStatus {
+        environment: name.to_string(),
+        address: format!(
+            "{}:{}/{}",
+            redis_config.host, redis_config.port, redis_config.db
+        ),
+        reachable: false,
+        latency_ms: None,
+        version: None,
+        error: None,
+    };
 
-    match RedisClient::connect(redis_config).await {
-        Ok(mut client) => match client.ping().await {
-            Ok(_) => {
-                println!("{}", "✓ Connection test successful!".green().bold());
-                Ok(())
-            }
-            Err(e) => {
-                println!(
-                    "{}",
-                    format!("✗ Connection test failed: {}", e).red().bold()
-                );
-                Err(AppError::ConnectionError(e.to_string()))
-            }
-        },
-        Err(e) => {
-            println!("{}", format!("✗ Connection failed: {}", e).red().bold());
-            Err(AppError::ConnectionError(e.to_string()))
+    let check = async {
+        let mut client = RedisClient::connect(redis_config.clone()).await?;
+        let started = Instant::now();
+        client.ping().await?;
+        let latency = started.elapsed();
+        let version = client
+            .info()
+            .await
+            .ok()
+            .and_then(|info| info.get("redis_version").cloned());
+        Ok::<_, anyhow::Error>((latency, version))
+    };
+    match tokio::time::timeout(limit, check).await {
+        Ok(Ok((latency, version))) => {
+            status.reachable = true;
+            status.latency_ms = Some(latency.as_secs_f64() * 1000.0);
+            status.version = version;
         }
+        Ok(Err(e)) => status.error = Some(e.to_string()),
+        Err(_) => status.error = Some(format!("timed out after {}s", limit.as_secs())),
     }
+    status
 }
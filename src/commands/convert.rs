@@ -0,0 +1,129 @@
+use chrono::Local;
+use log::info;
+use std::collections::HashSet;
+
+use crate::cli::CollectionType;
+use crate::error::AppError;
+use crate::format::{display_key, key_bytes};
+use crate::redis_client::RedisClient;
+
+/// A collection read back and ready to be rewritten as another type.
+pub struct Conversion {
+    pub from: String,
+    pub to: CollectionType,
+    /// The elements in order, with a score for each one. Lists and sets use
+    /// their position as score when converted to a sorted set.
+    pub elements: Vec<(String, f64)>,
+    /// Elements left after the conversion; sets and sorted sets cannot
+    /// hold duplicates
+    pub unique: usize,
+}
+
+impl Conversion {
+    /// Whether the key already has the target type.
+    pub fn is_noop(&self) -> bool {
+        self.from == self.to.as_str()
+    }
+}
+
+/// Read a list, set or sorted set in full for converting it to `to`.
+pub async fn plan(
+    client: &mut RedisClient,
+    key: &str,
+    to: CollectionType,
+) -> Result<Conversion, AppError> {
+    info!("Converting key: {}", key);
+
+    let key_info = client.key_info(key).await?;
+    let elements: Vec<(String, f64)> = match key_info.key_type.as_str() {
+        "list" => client
+            .get_list(key, 0, -1)
+            .await?
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (v, i as f64))
+            .collect(),
+        "set" => {
+            let mut members = client.get_set(key).await?;
+            members.sort();
+            members
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (v, i as f64))
+                .collect()
+        }
+        "zset" => client.zrange_withscores(key, 0, -1).await?,
+        "none" => return Err(AppError::NotFound(format!("Key '{}' not found", key))),
+        other => {
+            return Err(AppError::ConfigError(format!(
+                "Cannot convert a {} (supported: list, set, zset)",
+                other
+            )))
+        }
+    };
+
+    let unique = match to {
+        CollectionType::List => elements.len(),
+        CollectionType::Set | CollectionType::Zset => elements
+            .iter()
+            .map(|(v, _)| v)
+            .collect::<HashSet<_>>()
+            .len(),
+    };
+
+    Ok(Conversion {
+        from: key_info.key_type,
+        to,
+        elements,
+        unique,
+    })
+}
+
+/// Rewrite `key` as described by `conversion`, keeping its TTL. The
+/// original is moved aside in the same transaction; returns the name of
+/// that backup key.
+pub async fn apply(
+    client: &mut RedisClient,
+    key: &str,
+    conversion: &Conversion,
+) -> Result<String, AppError> {
+    let pttl = client.pttl(key).await?;
+    let name = key_bytes(key);
+    let backup_name = [
+        name.as_slice(),
+        format!(":solt-backup:{}", Local::now().format("%Y%m%d%H%M%S")).as_bytes(),
+    ]
+    .concat();
+
+    // Move the original aside and write the new key in one transaction
+    let mut pipe = redis::pipe();
+    pipe.atomic()
+        .cmd("RENAME")
+        .arg(&name)
+        .arg(&backup_name)
+        .ignore();
+    match conversion.to {
+        CollectionType::List => {
+            let values: Vec<&String> = conversion.elements.iter().map(|(v, _)| v).collect();
+            pipe.cmd("RPUSH").arg(&name).arg(values).ignore();
+        }
+        CollectionType::Set => {
+            let values: Vec<&String> = conversion.elements.iter().map(|(v, _)| v).collect();
+            pipe.cmd("SADD").arg(&name).arg(values).ignore();
+        }
+        CollectionType::Zset => {
+            let mut cmd = redis::cmd("ZADD");
+            cmd.arg(&name);
+            for (member, score) in &conversion.elements {
+                cmd.arg(*score).arg(member);
+            }
+            pipe.add_command(cmd).ignore();
+        }
+    }
+    if pttl > 0 {
+        pipe.cmd("PEXPIRE").arg(&name).arg(pttl).ignore();
+    }
+    pipe.query_async::<_, ()>(&mut client.connection).await?;
+
+    Ok(display_key(&backup_name))
+}
@@ -1,193 +1,266 @@
-use colored::*;
+use chrono::Local;
 use log::info;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::Duration;
 
-use crate::config::AppConfig;
+use super::migrate::is_busy_key;
+use crate::config::RedisConfig;
 use crate::error::AppError;
 use crate::redis_client::RedisClient;
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Copy command invoked");
-    let config = AppConfig::load()?;
-    let env = config
-        .default_environment
-        .clone()
-        .unwrap_or_else(|| "dev".to_string());
-    let redis_config = config
-        .get_environment(&env)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env)))?
-        .config
-        .clone();
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    println!("{}", "Copy keys between databases or environments".cyan());
-    println!("{}", "1. Copy within same environment".yellow());
-    println!("{}", "2. Copy between different environments".yellow());
-    print!("Enter choice (1/2): ");
-    use std::io::{self, Write};
-    io::stdout().flush().unwrap();
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    let choice = input.trim();
-
-    match choice {
-        "1" => {
-            copy_within_environment(&mut client).await?;
-        }
-        "2" => {
-            copy_between_environments(&config).await?;
-        }
-        _ => {
-            println!("{}", "Invalid choice. Aborting.".red());
-        }
-    }
-    Ok(())
+/// Delay before the first retry; doubled on every further attempt
+const RETRY_BASE_DELAY_MS: u64 = 200;
+
+/// Longest delay between retries, however many there are
+const RETRY_MAX_DELAY_MS: u64 = 30_000;
+
+/// The keys a cross-environment copy gave up on, as written to the file
+/// that `copy --retry-failed` accepts.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CopyFailures {
+    pub source_env: String,
+    pub dest_env: String,
+    pub keys: Vec<FailedKey>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FailedKey {
+    pub key: String,
+    pub error: String,
 }
 
-async fn copy_within_environment(client: &mut RedisClient) -> Result<(), AppError> {
-    println!("{}", "Copying within same environment".cyan());
-    print!("Enter source key pattern (e.g., 'user:*'): ");
-    use std::io::{self, Write};
-    io::stdout().flush().unwrap();
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    let source_pattern = input.trim();
-
-    print!("Enter destination prefix (e.g., 'backup:'): ");
-    io::stdout().flush().unwrap();
-    let mut input2 = String::new();
-    io::stdin().read_line(&mut input2).unwrap();
-    let dest_prefix = input2.trim();
-
-    println!(
-        "{}",
-        format!(
-            "Copying keys matching '{}' to '{}*'",
-            source_pattern, dest_prefix
-        )
-        .cyan()
-    );
-    let keys = client.keys(source_pattern).await?;
-    if keys.is_empty() {
-        println!("{}", "No keys found matching the pattern.".yellow());
-        return Ok(());
+impl CopyFailures {
+    pub fn load(path: &str) -> Result<Self, AppError> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
     }
 
-    println!("{}", format!("Found {} keys to copy", keys.len()).green());
-    let mut copied = 0;
-    for key in keys {
-        let dest_key = format!("{}{}", dest_prefix, key);
-        // Get value from source key
-        if let Some(value) = client.get_string(&key).await? {
-            // Set value in destination key
-            match client.set_string(&dest_key, &value, None).await {
-                Ok(_) => {
-                    println!("{}", format!("Copied '{}' -> '{}'", key, dest_key).green());
-                    copied += 1;
-                }
-                Err(e) => {
-                    println!("{}", format!("Error copying '{}': {}", key, e).red());
-                }
+    /// Write the failures to a timestamped file in the current directory and
+    /// return its path.
+    pub fn save(&self) -> Result<String, AppError> {
+        let path = format!(
+            "solt-copy-failures-{}.json",
+            Local::now().format("%Y%m%d-%H%M%S")
+        );
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+/// What happened to one key of a cross-environment copy.
+pub enum CopyEvent<'a> {
+    Copied(&'a str),
+    /// Missing on the source or not a string
+    Skipped(&'a str),
+    /// Failed on a network error or timeout, and will be tried again after
+    /// `backoff`
+    Retrying {
+        key: &'a str,
+        error: &'a anyhow::Error,
+        attempt: u32,
+        backoff: Duration,
+    },
+    /// Failed for the last time
+    Failed(&'a str, &'a anyhow::Error),
+}
+
+#[derive(Debug, Default)]
+pub struct CopyReport {
+    pub copied: u64,
+    pub skipped: u64,
+    pub failed: Vec<FailedKey>,
+}
+
+/// Copy a key into another database of the same instance with COPY, falling
+/// back to DUMP and RESTORE through a connection to that database on servers
+/// older than Redis 6.2.
+pub async fn to_db(
+    client: &mut RedisClient,
+    config: &RedisConfig,
+    source: &str,
+    destination: &str,
+    db: u8,
+    replace: bool,
+) -> Result<(), AppError> {
+    let exists_error = || {
+        AppError::Aborted(format!(
+            "Key '{}' already exists in db {} (use --replace to overwrite)",
+            destination, db
+        ))
+    };
+
+    match client.copy_to_db(source, destination, db, replace).await {
+        Ok(true) => return Ok(()),
+        Ok(false) => {
+            // COPY also returns 0 when the source is missing
+            if !client.exists(source).await? {
+                return Err(AppError::NotFound(format!("Key '{}' not found", source)));
             }
-        } else {
-            println!(
-                "{}",
-                format!("Key '{}' not found or is not a string", key).yellow()
-            );
+            return Err(exists_error());
         }
+        Err(e) if is_unsupported(&e) => {
+            info!("COPY not supported ({}), using DUMP/RESTORE", e);
+        }
+        Err(e) => return Err(e.into()),
     }
-    println!(
-        "{}",
-        format!("Copy operation completed. {} keys copied.", copied).green()
-    );
-    Ok(())
-}
 
-async fn copy_between_environments(config: &AppConfig) -> Result<(), AppError> {
-    println!("{}", "Copying between environments".cyan());
-    println!("Available environments:");
-    for (name, _) in &config.environments {
-        println!("  - {}", name);
+    let (payload, pttl) = dump_key(client, source, &format!("db {}", config.db)).await?;
+    let mut dest_config = config.clone();
+    dest_config.db = db;
+    let mut dest_client = RedisClient::connect(dest_config).await?;
+    match dest_client
+        .restore(destination, pttl, &payload, replace)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) if is_busy_key(&e) => Err(exists_error()),
+        Err(e) => Err(e.into()),
     }
+}
 
-    print!("Enter source environment: ");
-    use std::io::{self, Write};
-    io::stdout().flush().unwrap();
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    let source_env = input.trim();
-
-    print!("Enter destination environment: ");
-    io::stdout().flush().unwrap();
-    let mut input2 = String::new();
-    io::stdin().read_line(&mut input2).unwrap();
-    let dest_env = input2.trim();
-
-    let source_config = config
-        .get_environment(source_env)
-        .ok_or_else(|| {
-            AppError::ConfigError(format!("Source environment '{}' not found", source_env))
-        })?
-        .config
-        .clone();
-    let dest_config = config
-        .get_environment(dest_env)
-        .ok_or_else(|| {
-            AppError::ConfigError(format!("Destination environment '{}' not found", dest_env))
-        })?
-        .config
-        .clone();
-
-    print!("Enter source key pattern (e.g., 'user:*'): ");
-    io::stdout().flush().unwrap();
-    let mut input3 = String::new();
-    io::stdin().read_line(&mut input3).unwrap();
-    let source_pattern = input3.trim();
-
-    println!(
-        "{}",
-        format!(
-            "Copying from '{}' to '{}' with pattern '{}'",
-            source_env, dest_env, source_pattern
-        )
-        .cyan()
-    );
-
-    // Connect to source environment
-    let mut source_client = RedisClient::connect(source_config).await?;
-    let keys = source_client.keys(source_pattern).await?;
-    if keys.is_empty() {
-        println!("{}", "No keys found matching the pattern.".yellow());
-        return Ok(());
+/// Copy a key of any type to another instance with DUMP/RESTORE, keeping its
+/// TTL. The environment names only appear in error messages.
+pub async fn to_environment(
+    source_client: &mut RedisClient,
+    dest_client: &mut RedisClient,
+    source: &str,
+    destination: &str,
+    replace: bool,
+    source_env: &str,
+    dest_env: &str,
+) -> Result<(), AppError> {
+    let (payload, pttl) = dump_key(source_client, source, source_env).await?;
+    match dest_client
+        .restore(destination, pttl, &payload, replace)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) if is_busy_key(&e) => Err(AppError::Aborted(format!(
+            "Key '{}' already exists in '{}' (use --replace to overwrite)",
+            destination, dest_env
+        ))),
+        Err(e) => Err(e.into()),
     }
+}
 
-    println!("{}", format!("Found {} keys to copy", keys.len()).green());
+async fn dump_key(
+    client: &mut RedisClient,
+    key: &str,
+    location: &str,
+) -> Result<(Vec<u8>, i64), AppError> {
+    let dumped = client.dump_batch(&[key.to_string()]).await?.pop();
+    dumped
+        .and_then(|d| d.payload.map(|p| (p, d.pttl)))
+        .ok_or_else(|| AppError::NotFound(format!("Key '{}' not found in '{}'", key, location)))
+}
 
-    // Connect to destination environment
-    let mut dest_client = RedisClient::connect(dest_config).await?;
-    let mut copied = 0;
+/// Whether the server rejected a command it does not know.
+fn is_unsupported(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<redis::RedisError>()
+        .is_some_and(|e| e.kind() == redis::ErrorKind::ResponseError)
+}
+
+/// Copy string keys one by one. Network errors and timeouts are retried with
+/// exponential backoff on a fresh connection; any other error fails the key
+/// at once, since trying again would fail the same way. `on_event` is told
+/// about every key as it goes.
+pub async fn copy_keys_between(
+    source_config: RedisConfig,
+    dest_config: RedisConfig,
+    keys: Vec<String>,
+    max_retries: u32,
+    mut on_event: impl FnMut(CopyEvent),
+) -> Result<CopyReport, AppError> {
+    let mut source_client = RedisClient::connect(source_config.clone()).await?;
+    let mut dest_client = RedisClient::connect(dest_config.clone()).await?;
+
+    let mut report = CopyReport::default();
     for key in keys {
-        // Get value from source
-        if let Some(value) = source_client.get_string(&key).await? {
-            // Set value in destination
-            match dest_client.set_string(&key, &value, None).await {
-                Ok(_) => {
-                    println!("{}", format!("Copied '{}'", key).green());
-                    copied += 1;
+        let mut attempt = 0;
+        loop {
+            let result = copy_string_key(&mut source_client, &mut dest_client, &key).await;
+            match result {
+                Ok(true) => {
+                    on_event(CopyEvent::Copied(&key));
+                    report.copied += 1;
+                    break;
+                }
+                Ok(false) => {
+                    on_event(CopyEvent::Skipped(&key));
+                    report.skipped += 1;
+                    break;
+                }
+                Err(e) if attempt < max_retries && is_transient(&e) => {
+                    let backoff = Duration::from_millis(
+                        2u64.checked_pow(attempt)
+                            .map_or(RETRY_MAX_DELAY_MS, |factor| {
+                                RETRY_BASE_DELAY_MS.saturating_mul(factor)
+                            })
+                            .min(RETRY_MAX_DELAY_MS),
+                    );
+                    attempt += 1;
+                    on_event(CopyEvent::Retrying {
+                        key: &key,
+                        error: &e,
+                        attempt,
+                        backoff,
+                    });
+                    tokio::time::sleep(backoff).await;
+
+                    // The connection may not survive a network blip, so start fresh
+                    if let Ok(client) = RedisClient::connect(source_config.clone()).await {
+                        source_client = client;
+                    }
+                    if let Ok(client) = RedisClient::connect(dest_config.clone()).await {
+                        dest_client = client;
+                    }
                 }
                 Err(e) => {
-                    println!("{}", format!("Error copying '{}': {}", key, e).red());
+                    on_event(CopyEvent::Failed(&key, &e));
+                    report.failed.push(FailedKey {
+                        key: key.clone(),
+                        error: e.to_string(),
+                    });
+                    break;
                 }
             }
-        } else {
-            println!(
-                "{}",
-                format!("Key '{}' not found or is not a string", key).yellow()
-            );
         }
     }
-    println!(
-        "{}",
-        format!("Cross-environment copy completed. {} keys copied.", copied).green()
-    );
-    Ok(())
+
+    Ok(report)
+}
+
+/// Copy a single string key as raw bytes, returning `false` when the source
+/// key is missing or holds another type.
+async fn copy_string_key(
+    source_client: &mut RedisClient,
+    dest_client: &mut RedisClient,
+    key: &str,
+) -> anyhow::Result<bool> {
+    let value = match source_client.get_bytes(key).await {
+        Ok(Some(value)) => value,
+        Ok(None) => return Ok(false),
+        Err(e) if is_wrong_type(&e) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    dest_client.set_bytes(key, &value, None).await?;
+    Ok(true)
+}
+
+/// Whether an error came from the network rather than the command, so the
+/// same command may succeed on a later attempt.
+fn is_transient(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<redis::RedisError>()
+        .is_some_and(|e| e.is_io_error() || e.is_timeout())
+}
+
+/// Whether the server refused the command because the key holds another type.
+fn is_wrong_type(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<redis::RedisError>()
+        .and_then(|e| e.code())
+        == Some("WRONGTYPE")
 }
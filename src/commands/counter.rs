@@ -0,0 +1,24 @@
+use log::info;
+
+use crate::error::AppError;
+use crate::format::Amount;
+use crate::redis_client::RedisClient;
+
+/// Add `by` to a counter (or a hash field with `field`) in a single
+/// INCRBY / HINCRBY, so concurrent writers never lose an update the way a
+/// GET followed by SET would. Fractional amounts use the BYFLOAT variants.
+/// Returns the new value as Redis formats it.
+pub async fn run(
+    client: &mut RedisClient,
+    key: &str,
+    field: Option<&str>,
+    by: Amount,
+) -> Result<String, AppError> {
+    info!("Incrementing {} by {}", key, by);
+
+    let value = match by {
+        Amount::Integer(n) => client.incr_by(key, field, n).await?.to_string(),
+        Amount::Float(f) => client.incr_by_float(key, field, f).await?,
+    };
+    Ok(value)
+}
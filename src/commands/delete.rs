@@ -1,192 +1,114 @@
-use colored::*;
 use log::info;
 
-use crate::config::AppConfig;
 use crate::error::AppError;
 use crate::redis_client::RedisClient;
 
-pub async fn run(key: String, environment: Option<String>) -> Result<(), AppError> {
+/// Keys fetched per SCAN step and removed per UNLINK when deleting by pattern
+const BATCH_SIZE: usize = 500;
+
+/// What FLUSHDB or FLUSHALL would remove.
+#[derive(Debug)]
+pub struct FlushImpact {
+    pub affected: u64,
+    /// Key counts of every database with keys, from INFO keyspace
+    pub databases: Vec<(String, u64)>,
+    /// `used_memory_dataset` (or `used_memory`) of the whole instance, as reported
+    pub memory: Option<String>,
+}
+
+/// Delete a single key. With `lazy` the key is removed with UNLINK, so a
+/// huge collection is freed in the background instead of blocking the
+/// server for the duration of a DEL.
+pub async fn run(client: &mut RedisClient, key: &str, lazy: bool) -> Result<(), AppError> {
     info!("Deleting key: {}", key);
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    let deleted = client.delete_key(&key).await?;
-
-    if deleted {
-        println!(
-            "{}",
-            format!("✓ Successfully deleted key '{}'", key)
-                .green()
-                .bold()
-        );
+    let deleted = if lazy {
+        client.unlink(&[key.to_string()]).await? > 0
     } else {
-        println!("{}", format!("Key '{}' not found", key).yellow());
-    }
+        client.delete_key(key).await?
+    };
 
+    if !deleted {
+        return Err(AppError::NotFound(format!("Key '{}' not found", key)));
+    }
     Ok(())
 }
 
-pub async fn delete_by_pattern(
-    pattern: String,
-    environment: Option<String>,
-    confirm: bool,
-) -> Result<(), AppError> {
+/// Delete every key matching `pattern` one SCAN page at a time with
+/// UNLINK, so the server is never blocked by a single huge KEYS or DEL.
+/// `progress` is called after each page with the number deleted so far.
+pub async fn unlink_matching(
+    client: &mut RedisClient,
+    pattern: &str,
+    mut progress: impl FnMut(usize),
+) -> Result<usize, AppError> {
     info!("Deleting keys by pattern: {}", pattern);
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    // First, get the keys that match the pattern
-    let keys = client.keys(&pattern).await?;
-
-    if keys.is_empty() {
-        println!(
-            "{}",
-            format!("No keys found matching pattern '{}'", pattern).yellow()
-        );
-        return Ok(());
-    }
-
-    println!(
-        "{}",
-        format!("Found {} keys matching pattern '{}'", keys.len(), pattern)
-            .cyan()
-            .bold()
-    );
+    let mut deleted = 0usize;
+    let mut cursor = 0;
+    loop {
+        let (next, keys) = client.scan_page(cursor, pattern, BATCH_SIZE, None).await?;
+        for batch in keys.chunks(BATCH_SIZE) {
+            deleted += client.unlink(batch).await?;
+        }
+        progress(deleted);
 
-    if !confirm {
-        println!("{}", "Keys to be deleted:".yellow());
-        for key in &keys {
-            println!("  • {}", key);
+        cursor = next;
+        if cursor == 0 {
+            break;
         }
-        println!("{}", "Use --confirm to proceed with deletion".red().bold());
-        return Ok(());
     }
 
-    // Delete the keys
-    let deleted_count = client.delete_keys_by_pattern(&pattern).await?;
-
-    println!(
-        "{}",
-        format!("✓ Successfully deleted {} keys", deleted_count)
-            .green()
-            .bold()
-    );
-
-    Ok(())
+    Ok(deleted)
 }
 
-pub async fn flush_db(environment: Option<String>, confirm: bool) -> Result<(), AppError> {
-    info!("Flushing database");
-
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    if !confirm {
-        println!(
-            "{}",
-            "WARNING: This will delete ALL keys in the current database!"
-                .red()
-                .bold()
-        );
-        println!("{}", "Use --confirm to proceed".red());
-        return Ok(());
-    }
-
-    // Use FLUSHDB command
-    let result: String = redis::cmd("FLUSHDB")
-        .query_async(&mut client.connection)
-        .await?;
-
-    println!(
-        "{}",
-        format!("✓ Database flushed: {}", result).green().bold()
+/// Run FLUSHDB, or FLUSHALL with `all`, and return the server's reply.
+pub async fn flush(client: &mut RedisClient, all: bool) -> Result<String, AppError> {
+    info!(
+        "Flushing {}",
+        if all { "all databases" } else { "database" }
     );
 
-    Ok(())
-}
-
-pub async fn flush_all(environment: Option<String>, confirm: bool) -> Result<(), AppError> {
-    info!("Flushing all databases");
-
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    if !confirm {
-        println!(
-            "{}",
-            "WARNING: This will delete ALL keys in ALL databases!"
-                .red()
-                .bold()
-        );
-        println!("{}", "Use --confirm to proceed".red());
-        return Ok(());
-    }
-
-    // Use FLUSHALL command
-    let result: String = redis::cmd("FLUSHALL")
+    let command = if all { "FLUSHALL" } else { "FLUSHDB" };
+    Ok(redis::cmd(command)
         .query_async(&mut client.connection)
-        .await?;
+        .await?)
+}
 
-    println!(
-        "{}",
-        format!("✓ All databases flushed: {}", result)
-            .green()
-            .bold()
-    );
+/// Key counts per database from INFO keyspace and the memory the dataset
+/// currently uses, for a dry run of FLUSHDB or FLUSHALL.
+pub async fn flush_impact(client: &mut RedisClient, all: bool) -> Result<FlushImpact, AppError> {
+    let info = client.info().await?;
+    let mut databases: Vec<(&String, &String)> = info
+        .iter()
+        .filter(|(name, _)| name.starts_with("db") && name[2..].parse::<u32>().is_ok())
+        .collect();
+    databases.sort_by_key(|(name, _)| name[2..].parse::<u32>().unwrap_or(0));
+    let databases: Vec<(String, u64)> = databases
+        .into_iter()
+        .map(|(name, stats)| (name.clone(), keyspace_keys(stats).unwrap_or(0)))
+        .collect();
+
+    let affected = if all {
+        databases.iter().map(|(_, keys)| keys).sum()
+    } else {
+        client.dbsize().await?
+    };
+
+    Ok(FlushImpact {
+        affected,
+        databases,
+        memory: info
+            .get("used_memory_dataset")
+            .or(info.get("used_memory"))
+            .cloned(),
+    })
+}
 
-    Ok(())
+/// Key count from an INFO keyspace line such as `keys=10,expires=2,avg_ttl=0`.
+fn keyspace_keys(stats: &str) -> Option<u64> {
+    stats
+        .split(',')
+        .find_map(|field| field.strip_prefix("keys="))
+        .and_then(|count| count.trim().parse().ok())
 }
@@ -0,0 +1,203 @@
+use log::info;
+use std::collections::BTreeMap;
+
+use crate::error::AppError;
+use crate::format::format_seconds;
+use crate::redis_client::RedisClient;
+
+const BATCH_SIZE: usize = 500;
+
+/// Values longer than this are shown as hashes instead of inline
+const MAX_INLINE_VALUE: usize = 40;
+
+/// TTLs drift by the time spent between reads, so allow a small tolerance.
+const TTL_TOLERANCE_MS: i64 = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    MissingInDest,
+    ExtraInDest,
+    Type,
+    Value,
+    Ttl,
+}
+
+impl DiffKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiffKind::MissingInDest => "missing in dest",
+            DiffKind::ExtraInDest => "extra in dest",
+            DiffKind::Type => "type",
+            DiffKind::Value => "value",
+            DiffKind::Ttl => "ttl",
+        }
+    }
+}
+
+/// One difference of a key, with both sides described for display ("-"
+/// for a side where the key is missing).
+#[derive(Debug)]
+pub struct Difference {
+    pub key: String,
+    pub kind: DiffKind,
+    pub source: String,
+    pub dest: String,
+}
+
+#[derive(Debug, Default)]
+pub struct DiffReport {
+    /// Sorted by key
+    pub differences: Vec<Difference>,
+    pub identical: usize,
+    pub differing: usize,
+    pub missing: usize,
+    pub extra: usize,
+}
+
+struct KeySnapshot {
+    key_type: String,
+    value: String,
+    digest: String,
+    pttl: i64,
+}
+
+/// Compare type, value and TTL of the keys matching `pattern` on both
+/// servers.
+pub async fn run(
+    source: &mut RedisClient,
+    dest: &mut RedisClient,
+    pattern: &str,
+) -> Result<DiffReport, AppError> {
+    info!("Diffing '{}'", pattern);
+
+    let source_keys = snapshot(source, pattern).await?;
+    let dest_keys = snapshot(dest, pattern).await?;
+
+    let mut report = DiffReport::default();
+    let differences = &mut report.differences;
+
+    for (key, src) in &source_keys {
+        let Some(dst) = dest_keys.get(key) else {
+            report.missing += 1;
+            differences.push(Difference {
+                key: key.clone(),
+                kind: DiffKind::MissingInDest,
+                source: src.key_type.clone(),
+                dest: "-".to_string(),
+            });
+            continue;
+        };
+
+        let mut found = false;
+        if src.key_type != dst.key_type {
+            differences.push(Difference {
+                key: key.clone(),
+                kind: DiffKind::Type,
+                source: src.key_type.clone(),
+                dest: dst.key_type.clone(),
+            });
+            found = true;
+        } else if src.digest != dst.digest {
+            let (source_shown, dest_shown) =
+                if src.value.len() > MAX_INLINE_VALUE || dst.value.len() > MAX_INLINE_VALUE {
+                    (describe_digest(src), describe_digest(dst))
+                } else {
+                    (src.value.clone(), dst.value.clone())
+                };
+            differences.push(Difference {
+                key: key.clone(),
+                kind: DiffKind::Value,
+                source: source_shown,
+                dest: dest_shown,
+            });
+            found = true;
+        }
+
+        if !ttl_matches(src.pttl, dst.pttl) {
+            differences.push(Difference {
+                key: key.clone(),
+                kind: DiffKind::Ttl,
+                source: describe_pttl(src.pttl),
+                dest: describe_pttl(dst.pttl),
+            });
+            found = true;
+        }
+
+        if found {
+            report.differing += 1;
+        } else {
+            report.identical += 1;
+        }
+    }
+
+    for (key, dst) in &dest_keys {
+        if !source_keys.contains_key(key) {
+            report.extra += 1;
+            differences.push(Difference {
+                key: key.clone(),
+                kind: DiffKind::ExtraInDest,
+                source: "-".to_string(),
+                dest: dst.key_type.clone(),
+            });
+        }
+    }
+
+    differences.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(report)
+}
+
+/// Scan the pattern and capture each key's type, canonical value, digest and
+/// TTL.
+async fn snapshot(
+    client: &mut RedisClient,
+    pattern: &str,
+) -> Result<BTreeMap<String, KeySnapshot>, AppError> {
+    let keys = client.scan_keys(pattern).await?;
+
+    let mut snapshots = BTreeMap::new();
+    for batch in keys.chunks(BATCH_SIZE) {
+        let values = client.fetch_values(batch).await?;
+        let pttls = client.pttl_many(batch).await?;
+
+        for (kv, pttl) in values.into_iter().zip(pttls) {
+            // Expired or deleted since the scan
+            if kv.key_type == "none" {
+                continue;
+            }
+            let value = kv.canonical_value();
+            let digest = kv.digest();
+            snapshots.insert(
+                kv.key,
+                KeySnapshot {
+                    key_type: kv.key_type,
+                    value,
+                    digest,
+                    pttl,
+                },
+            );
+        }
+    }
+
+    Ok(snapshots)
+}
+
+fn describe_digest(snapshot: &KeySnapshot) -> String {
+    format!("#{} ({} bytes)", snapshot.digest, snapshot.value.len())
+}
+
+/// Whether two PTTL replies agree, allowing for the drift between reads.
+/// Negative replies (no expiry, missing key) must match exactly.
+pub fn ttl_matches(source_pttl: i64, dest_pttl: i64) -> bool {
+    if source_pttl < 0 || dest_pttl < 0 {
+        return source_pttl == dest_pttl;
+    }
+    (source_pttl - dest_pttl).abs() <= TTL_TOLERANCE_MS
+}
+
+fn describe_pttl(pttl: i64) -> String {
+    if pttl < 0 {
+        "no expiry".to_string()
+    } else {
+        format_seconds((pttl / 1000) as u64)
+    }
+}
@@ -0,0 +1,48 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Keys sampled per pipeline when measuring an operation's impact
+const BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Default, Serialize)]
+pub struct TypeImpact {
+    pub keys: usize,
+    pub memory: u64,
+}
+
+/// What an operation would touch, broken down by key type.
+#[derive(Debug, Default, Serialize)]
+pub struct Impact {
+    pub by_type: BTreeMap<String, TypeImpact>,
+}
+
+impl Impact {
+    pub fn total_keys(&self) -> usize {
+        self.by_type.values().map(|t| t.keys).sum()
+    }
+
+    pub fn total_memory(&self) -> u64 {
+        self.by_type.values().map(|t| t.memory).sum()
+    }
+}
+
+/// Measure the count and memory of `keys` per type without changing
+/// anything, for reporting what an operation would do to them.
+pub async fn measure(client: &mut RedisClient, keys: &[String]) -> Result<Impact, AppError> {
+    let mut impact = Impact::default();
+    for batch in keys.chunks(BATCH_SIZE) {
+        for (key_type, memory) in client.type_and_memory(batch).await? {
+            // Gone since the scan, so the operation would not touch it
+            if key_type == "none" {
+                continue;
+            }
+            let entry = impact.by_type.entry(key_type).or_default();
+            entry.keys += 1;
+            entry.memory += memory.unwrap_or(0);
+        }
+    }
+    Ok(impact)
+}
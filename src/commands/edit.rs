@@ -1,10 +1,321 @@
-use colored::*;
 use log::info;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::error::AppError;
+use crate::format::key_bytes;
+use crate::redis_client::RedisClient;
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Edit command - placeholder");
-    println!("{}", "Edit command - not yet implemented".yellow());
+/// A hash, list, set or sorted set as it is edited: hashes as TOML or
+/// JSON, the others one element per line
+#[derive(Clone, PartialEq)]
+pub enum Collection {
+    Hash(BTreeMap<String, String>),
+    List(Vec<String>),
+    Set(BTreeSet<String>),
+    Zset(BTreeMap<String, f64>),
+}
+
+/// How many entries an edit adds, changes and removes.
+#[derive(Debug, Default)]
+pub struct Changes {
+    pub added: usize,
+    pub changed: usize,
+    pub removed: usize,
+}
+
+/// Replace the value of an existing string key, keeping its expiry.
+pub async fn run(client: &mut RedisClient, key: &str, value: &[u8]) -> Result<(), AppError> {
+    info!("Editing key: {}", key);
+
+    let key_type = client.key_type(key).await?;
+    match key_type.as_str() {
+        "string" => {}
+        "none" => return Err(AppError::NotFound(format!("Key '{}' not found", key))),
+        other => {
+            return Err(AppError::Anyhow(anyhow::anyhow!(
+                "Key '{}' is a {}; run 'solt edit {}' without a value to edit it in $EDITOR",
+                key,
+                other,
+                key
+            )))
+        }
+    }
+
+    if !client.replace_bytes(key, value).await? {
+        return Err(AppError::NotFound(format!("Key '{}' not found", key)));
+    }
     Ok(())
 }
+
+impl Collection {
+    pub async fn read(
+        client: &mut RedisClient,
+        key: &str,
+        key_type: &str,
+    ) -> Result<Collection, AppError> {
+        Ok(match key_type {
+            "hash" => Collection::Hash(client.get_hash(key).await?.into_iter().collect()),
+            "list" => Collection::List(client.get_list(key, 0, -1).await?),
+            "set" => Collection::Set(client.get_set(key).await?.into_iter().collect()),
+            _ => Collection::Zset(
+                client
+                    .zrange_withscores(key, 0, -1)
+                    .await?
+                    .into_iter()
+                    .collect(),
+            ),
+        })
+    }
+
+    /// The collection as editable text: hashes as TOML (or JSON with
+    /// `json`), the others one element per line, sorted sets as
+    /// `score member`.
+    pub fn render(&self, key: &str, json: bool) -> Result<String, AppError> {
+        let lines: Vec<String> = match self {
+            Collection::Hash(hash) if json => return Ok(serde_json::to_string_pretty(hash)? + "\n"),
+            Collection::Hash(hash) => {
+                return toml::to_string(hash).map_err(|e| AppError::Anyhow(e.into()))
+            }
+            Collection::List(list) => list.clone(),
+            Collection::Set(set) => set.iter().cloned().collect(),
+            Collection::Zset(zset) => {
+                let mut members: Vec<(&String, &f64)> = zset.iter().collect();
+                members.sort_by(|a, b| a.1.total_cmp(b.1).then_with(|| a.0.cmp(b.0)));
+                members
+                    .into_iter()
+                    .map(|(member, score)| format!("{} {}", score, member))
+                    .collect()
+            }
+        };
+
+        let unsafe_element = match self {
+            Collection::Zset(zset) => zset
+                .keys()
+                .any(|m| m.is_empty() || m.contains(['\n', '\r'])),
+            _ => lines
+                .iter()
+                .any(|l| l.is_empty() || l.contains(['\n', '\r'])),
+        };
+        if unsafe_element {
+            return Err(AppError::Aborted(format!(
+                "'{}' has empty or multi-line elements, which cannot be edited one per line",
+                key
+            )));
+        }
+
+        Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+    }
+
+    /// Parse edited text back into a collection of the same kind.
+    pub fn parse(&self, text: &str, json: bool) -> Result<Collection, String> {
+        let lines = text.lines().filter(|line| !line.trim().is_empty());
+        match self {
+            Collection::Hash(_) => {
+                let fields: BTreeMap<String, serde_json::Value> = if json {
+                    serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?
+                } else {
+                    toml::from_str(text).map_err(|e| format!("Invalid TOML: {}", e))?
+                };
+                fields
+                    .into_iter()
+                    .map(|(field, value)| match value {
+                        serde_json::Value::String(s) => Ok((field, s)),
+                        serde_json::Value::Number(n) => Ok((field, n.to_string())),
+                        serde_json::Value::Bool(b) => Ok((field, b.to_string())),
+                        _ => Err(format!("Field '{}' must be a string or a number", field)),
+                    })
+                    .collect::<Result<_, _>>()
+                    .map(Collection::Hash)
+            }
+            Collection::List(_) => Ok(Collection::List(lines.map(str::to_string).collect())),
+            Collection::Set(_) => Ok(Collection::Set(lines.map(str::to_string).collect())),
+            Collection::Zset(_) => {
+                let mut zset = BTreeMap::new();
+                for (number, line) in text.lines().enumerate() {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let (score, member) = line
+                        .trim_start()
+                        .split_once(char::is_whitespace)
+                        .ok_or_else(|| {
+                            format!(
+                                "Line {}: expected 'score member', found '{}'",
+                                number + 1,
+                                line
+                            )
+                        })?;
+                    let score: f64 = score
+                        .parse()
+                        .ok()
+                        .filter(|s: &f64| !s.is_nan())
+                        .ok_or_else(|| format!("Line {}: invalid score '{}'", number + 1, score))?;
+                    if zset.insert(member.to_string(), score).is_some() {
+                        return Err(format!(
+                            "Line {}: member '{}' appears more than once",
+                            number + 1,
+                            member
+                        ));
+                    }
+                }
+                Ok(Collection::Zset(zset))
+            }
+        }
+    }
+
+    /// Whether the collection has no entries, in which case writing it
+    /// deletes the key.
+    pub fn is_empty(&self) -> bool {
+        entries(self).is_empty()
+    }
+
+    /// What changes between this collection and `draft`.
+    pub fn changes(&self, draft: &Collection) -> Changes {
+        let (before, after) = (entries(self), entries(draft));
+        let mut changes = Changes::default();
+        for name in before.keys().chain(after.keys()).collect::<BTreeSet<_>>() {
+            match (before.get(name), after.get(name)) {
+                (None, Some(_)) => changes.added += 1,
+                (Some(_), None) => changes.removed += 1,
+                (Some(a), Some(b)) if a != b => changes.changed += 1,
+                _ => {}
+            }
+        }
+        changes
+    }
+}
+
+/// Entries keyed by field, member or (for lists) index, so any two
+/// collections of the same kind can be compared entry by entry.
+fn entries(collection: &Collection) -> BTreeMap<String, String> {
+    match collection {
+        Collection::Hash(hash) => hash.clone(),
+        Collection::List(list) => list
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (i.to_string(), value.clone()))
+            .collect(),
+        Collection::Set(set) => set.iter().map(|m| (m.clone(), String::new())).collect(),
+        Collection::Zset(zset) => zset
+            .iter()
+            .map(|(member, score)| (member.clone(), score.to_string()))
+            .collect(),
+    }
+}
+
+/// Names of the entries that differ between two collections.
+fn changed(a: &BTreeMap<String, String>, b: &BTreeMap<String, String>) -> BTreeSet<String> {
+    a.keys()
+        .chain(b.keys())
+        .filter(|name| a.get(*name) != b.get(*name))
+        .cloned()
+        .collect()
+}
+
+/// Entries both the user and someone else changed. Any concurrent change
+/// conflicts for a list, since positions shift.
+pub fn conflicts(base: &Collection, current: &Collection, draft: &Collection) -> Vec<String> {
+    let base_entries = entries(base);
+    let theirs = changed(&base_entries, &entries(current));
+    if theirs.is_empty() {
+        return Vec::new();
+    }
+    if matches!(base, Collection::List(_)) {
+        return vec!["the list".to_string()];
+    }
+    changed(&base_entries, &entries(draft))
+        .intersection(&theirs)
+        .map(|name| format!("'{}'", name))
+        .collect()
+}
+
+/// Write the edit from `base` to `draft` in a MULTI/EXEC transaction, touching
+/// only what changed. Returns `false` when the WATCHed key changed in the
+/// meantime and nothing was written.
+pub async fn apply(
+    client: &mut RedisClient,
+    key: &str,
+    base: &Collection,
+    draft: &Collection,
+) -> Result<bool, AppError> {
+    // Read right before EXEC so a list rewrite restores the expiry the
+    // key has now, not the one it had when the editor opened
+    let ttl = client.pttl(key).await?;
+    Ok(client
+        .exec_if_unchanged(plan(key, base, draft, ttl))
+        .await?)
+}
+
+/// Commands that turn `base` into `draft`, touching only what changed.
+fn plan(key: &str, base: &Collection, draft: &Collection, ttl: i64) -> Vec<redis::Cmd> {
+    let key = key_bytes(key);
+    let mut commands = Vec::new();
+
+    match (base, draft) {
+        (Collection::List(old), Collection::List(new)) => {
+            if new.is_empty() {
+                commands.push(key_command("DEL", &key));
+            } else if new.len() == old.len() {
+                for (i, (a, b)) in old.iter().zip(new).enumerate() {
+                    if a != b {
+                        let mut cmd = key_command("LSET", &key);
+                        cmd.arg(i).arg(b);
+                        commands.push(cmd);
+                    }
+                }
+            } else if new.starts_with(old) {
+                commands.push(batch("RPUSH", &key, new[old.len()..].to_vec()));
+            } else {
+                // Anything else is a rewrite; DEL drops the TTL, so restore it
+                commands.push(key_command("DEL", &key));
+                commands.push(batch("RPUSH", &key, new.clone()));
+                if ttl > 0 {
+                    let mut cmd = key_command("PEXPIRE", &key);
+                    cmd.arg(ttl);
+                    commands.push(cmd);
+                }
+            }
+        }
+        _ => {
+            let (before, after) = (entries(base), entries(draft));
+            let (updated, removed): (Vec<String>, Vec<String>) = changed(&before, &after)
+                .into_iter()
+                .partition(|name| after.contains_key(name));
+            let (add, remove) = match draft {
+                Collection::Hash(_) => ("HSET", "HDEL"),
+                Collection::Set(_) => ("SADD", "SREM"),
+                _ => ("ZADD", "ZREM"),
+            };
+            if !updated.is_empty() {
+                let args = updated
+                    .into_iter()
+                    .flat_map(|name| match draft {
+                        Collection::Set(_) => vec![name],
+                        Collection::Zset(_) => vec![after[&name].clone(), name],
+                        _ => {
+                            let value = after[&name].clone();
+                            vec![name, value]
+                        }
+                    })
+                    .collect();
+                commands.push(batch(add, &key, args));
+            }
+            if !removed.is_empty() {
+                commands.push(batch(remove, &key, removed));
+            }
+        }
+    }
+    commands
+}
+
+fn key_command(name: &str, key: &[u8]) -> redis::Cmd {
+    let mut cmd = redis::cmd(name);
+    cmd.arg(key);
+    cmd
+}
+
+fn batch(name: &str, key: &[u8], args: Vec<String>) -> redis::Cmd {
+    let mut cmd = key_command(name, key);
+    cmd.arg(args);
+    cmd
+}
@@ -0,0 +1,35 @@
+use log::info;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Whether each of `keys` exists, in the order given. With `touch` their
+/// last access time is refreshed as well.
+pub async fn run(
+    client: &mut RedisClient,
+    keys: &[String],
+    touch: bool,
+) -> Result<Vec<bool>, AppError> {
+    info!("Checking existence of keys: {:?}", keys);
+
+    let exists = client.exists_many(keys).await?;
+    if touch {
+        client.touch(keys).await?;
+    }
+    Ok(exists)
+}
+
+/// The keys matching `pattern`, touched as well with `touch`.
+pub async fn matching(
+    client: &mut RedisClient,
+    pattern: &str,
+    touch: bool,
+) -> Result<Vec<String>, AppError> {
+    info!("Checking existence of keys matching: {}", pattern);
+
+    let matches = client.scan_keys(pattern).await?;
+    if touch && !matches.is_empty() {
+        client.touch(&matches).await?;
+    }
+    Ok(matches)
+}
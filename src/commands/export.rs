@@ -1,10 +1,257 @@
-use colored::*;
+use chrono::{DateTime, Local};
 use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 
+use crate::cli::ExportFormat;
 use crate::error::AppError;
+use crate::format::key_bytes;
+use crate::redis_client::RedisClient;
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Export command - placeholder");
-    println!("{}", "Export command - not yet implemented".yellow());
-    Ok(())
+/// SCAN COUNT hint, and so roughly the number of keys held in memory at once
+const BATCH_SIZE: usize = 500;
+
+/// A JSON export file, as read back by `solt restore`.
+#[derive(Serialize, Deserialize)]
+pub struct ExportFile {
+    pub environment: String,
+    pub pattern: String,
+    pub exported_at: DateTime<Local>,
+    pub keys: Vec<ExportedKey>,
+}
+
+/// One exported key. Strings are stored as strings, hashes as objects,
+/// lists and sets as arrays and sorted sets as member to score objects.
+#[derive(Serialize, Deserialize)]
+pub struct ExportedKey {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub key_type: String,
+    /// Remaining TTL in seconds at export time, rounded up so a key about
+    /// to expire is not restored without an expiry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<i64>,
+    pub value: JsonValue,
+}
+
+/// Build the commands that recreate a key, or `None` if the value does not
+/// match its declared type.
+pub fn restore_commands(key: &ExportedKey) -> Option<Vec<redis::Cmd>> {
+    let name = key_bytes(&key.key);
+    let create = match (key.key_type.as_str(), &key.value) {
+        ("string", JsonValue::String(value)) => {
+            let mut cmd = redis::cmd("SET");
+            cmd.arg(&name).arg(value);
+            cmd
+        }
+        ("hash", JsonValue::Object(fields)) if !fields.is_empty() => {
+            let mut cmd = redis::cmd("HSET");
+            cmd.arg(&name);
+            for (field, value) in fields {
+                cmd.arg(field).arg(json_scalar(value));
+            }
+            cmd
+        }
+        ("list", JsonValue::Array(items)) if !items.is_empty() => {
+            let mut cmd = redis::cmd("RPUSH");
+            cmd.arg(&name);
+            for item in items {
+                cmd.arg(json_scalar(item));
+            }
+            cmd
+        }
+        ("set", JsonValue::Array(members)) if !members.is_empty() => {
+            let mut cmd = redis::cmd("SADD");
+            cmd.arg(&name);
+            for member in members {
+                cmd.arg(json_scalar(member));
+            }
+            cmd
+        }
+        ("zset", JsonValue::Object(members)) if !members.is_empty() => {
+            let mut cmd = redis::cmd("ZADD");
+            cmd.arg(&name);
+            for (member, score) in members {
+                cmd.arg(score.as_f64()?).arg(member);
+            }
+            cmd
+        }
+        _ => return None,
+    };
+
+    let mut commands = Vec::with_capacity(3);
+    // SET replaces on its own, but collections must not merge into an
+    // existing key
+    if key.key_type != "string" {
+        let mut del = redis::cmd("DEL");
+        del.arg(&name);
+        commands.push(del);
+    }
+    commands.push(create);
+    if let Some(ttl) = key.ttl.filter(|t| *t > 0) {
+        let mut expire = redis::cmd("EXPIRE");
+        expire.arg(&name).arg(ttl);
+        commands.push(expire);
+    }
+
+    Some(commands)
+}
+
+fn json_scalar(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Writes keys to the output file as they are fetched.
+enum ExportWriter {
+    Json {
+        out: BufWriter<File>,
+        first: bool,
+    },
+    Csv(Box<csv::Writer<BufWriter<File>>>),
+    /// Raw RESP commands for `redis-cli --pipe` or `solt import --pipe-file`
+    Resp(BufWriter<File>),
+}
+
+impl ExportWriter {
+    fn create(
+        format: &ExportFormat,
+        output: &str,
+        environment: &str,
+        pattern: &str,
+    ) -> Result<Self, AppError> {
+        let mut out = BufWriter::new(File::create(output)?);
+        match format {
+            ExportFormat::Json => {
+                // Write the envelope by hand so keys can be streamed into the
+                // array; the result still deserializes as an ExportFile
+                write!(
+                    out,
+                    "{{\n  \"environment\": {},\n  \"pattern\": {},\n  \"exported_at\": {},\n  \"keys\": [",
+                    serde_json::to_string(environment)?,
+                    serde_json::to_string(pattern)?,
+                    serde_json::to_string(&Local::now())?
+                )?;
+                Ok(ExportWriter::Json { out, first: true })
+            }
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(out);
+                writer
+                    .write_record(["key", "type", "ttl", "value"])
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                Ok(ExportWriter::Csv(Box::new(writer)))
+            }
+            ExportFormat::Resp => Ok(ExportWriter::Resp(out)),
+        }
+    }
+
+    fn write(&mut self, key: &ExportedKey) -> Result<(), AppError> {
+        match self {
+            ExportWriter::Json { out, first } => {
+                if !*first {
+                    out.write_all(b",")?;
+                }
+                *first = false;
+                write!(out, "\n    {}", serde_json::to_string(key)?)?;
+            }
+            ExportWriter::Csv(writer) => {
+                let value = match &key.value {
+                    JsonValue::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let ttl = key.ttl.map(|t| t.to_string()).unwrap_or_default();
+                writer
+                    .write_record([&key.key, &key.key_type, &ttl, &value])
+                    .map_err(|e| anyhow::anyhow!(e))?;
+            }
+            ExportWriter::Resp(out) => {
+                let commands = restore_commands(key).ok_or_else(|| {
+                    anyhow::anyhow!("Malformed {} value for '{}'", key.key_type, key.key)
+                })?;
+                for cmd in commands {
+                    out.write_all(&cmd.get_packed_command())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), AppError> {
+        match self {
+            ExportWriter::Json { mut out, .. } => {
+                out.write_all(b"\n  ]\n}\n")?;
+                out.flush()?;
+            }
+            ExportWriter::Csv(mut writer) => writer.flush()?,
+            ExportWriter::Resp(mut out) => out.flush()?,
+        }
+        Ok(())
+    }
+}
+
+/// What an export wrote.
+#[derive(Debug, Default)]
+pub struct ExportSummary {
+    pub exported: u64,
+    /// Keys skipped because their type cannot be exported
+    pub unsupported: u64,
+}
+
+/// Export the keys matching `pattern` to the file `output`, recording
+/// `environment` as their origin. `progress` is called after each SCAN
+/// step with the number of keys exported so far.
+pub async fn run(
+    client: &mut RedisClient,
+    format: &ExportFormat,
+    output: &str,
+    environment: &str,
+    pattern: &str,
+    mut progress: impl FnMut(u64),
+) -> Result<ExportSummary, AppError> {
+    info!("Exporting '{}' to {}", pattern, output);
+
+    let mut writer = ExportWriter::create(format, output, environment, pattern)?;
+    let mut summary = ExportSummary::default();
+
+    // SCAN one page at a time and write it out before fetching the next, so
+    // memory stays bounded by the batch size. Keys moved by a rehash during
+    // the scan can be exported twice; restore writes each key only once.
+    let mut cursor = 0;
+    loop {
+        let (next, keys) = client.scan_page(cursor, pattern, BATCH_SIZE, None).await?;
+
+        if !keys.is_empty() {
+            let values = client.fetch_values(&keys).await?;
+            let ttls = client.pttl_many(&keys).await?;
+
+            for (kv, pttl) in values.into_iter().zip(ttls) {
+                match kv.value {
+                    Some(value) => {
+                        writer.write(&ExportedKey {
+                            key: kv.key,
+                            key_type: kv.key_type,
+                            ttl: (pttl >= 0).then(|| ((pttl + 999) / 1000).max(1)),
+                            value,
+                        })?;
+                        summary.exported += 1;
+                    }
+                    None if kv.key_type == "none" => {}
+                    None => summary.unsupported += 1,
+                }
+            }
+        }
+        progress(summary.exported);
+
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    writer.finish()?;
+    Ok(summary)
 }
@@ -1,10 +1,86 @@
-use colored::*;
 use log::info;
 
 use crate::error::AppError;
+use crate::format::Bounds;
+use crate::redis_client::{KeyInfo, RedisClient};
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Filter command - placeholder");
-    println!("{}", "Filter command - not yet implemented".yellow());
-    Ok(())
+/// Keys fetched per SCAN step and inspected per pipeline
+const BATCH_SIZE: usize = 500;
+
+/// What a key must satisfy to be listed. Unset criteria match everything.
+pub struct Criteria {
+    pub ttl: Option<Bounds>,
+    pub size: Option<Bounds>,
+    pub key_type: Option<String>,
+    pub encodings: Vec<String>,
+}
+
+impl Criteria {
+    pub fn matches(&self, info: &KeyInfo) -> bool {
+        if info.key_type == "none" {
+            return false;
+        }
+        if let Some(ttl) = &self.ttl {
+            // Keys without an expiry have no TTL to compare
+            if !info.ttl.is_some_and(|t| t >= 0 && ttl.contains(t as u64)) {
+                return false;
+            }
+        }
+        if let Some(size) = &self.size {
+            if !info.memory_usage.is_some_and(|m| size.contains(m as u64)) {
+                return false;
+            }
+        }
+        if let Some(key_type) = &self.key_type {
+            if !info.key_type.eq_ignore_ascii_case(key_type) {
+                return false;
+            }
+        }
+        self.encodings.is_empty()
+            || self
+                .encodings
+                .iter()
+                .any(|e| info.encoding.eq_ignore_ascii_case(e))
+    }
+}
+
+/// The keys matching `pattern` that satisfy `criteria`, sorted by name.
+/// `progress` is called after each batch with the number of keys scanned
+/// and matched so far.
+pub async fn run(
+    client: &mut RedisClient,
+    pattern: &str,
+    criteria: &Criteria,
+    mut progress: impl FnMut(u64, usize),
+) -> Result<Vec<KeyInfo>, AppError> {
+    info!("Filtering keys matching {}", pattern);
+
+    // SCAN in batches so large keyspaces are never fetched in one go, and
+    // inspect each batch with a single pipeline
+    let mut scanned = 0u64;
+    let mut matches = Vec::new();
+    let mut cursor = 0;
+    loop {
+        let (next, keys) = client.scan_page(cursor, pattern, BATCH_SIZE, None).await?;
+        scanned += keys.len() as u64;
+        matches.extend(
+            client
+                .key_infos(&keys)
+                .await?
+                .into_iter()
+                .filter(|info| criteria.matches(info)),
+        );
+        progress(scanned, matches.len());
+
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    // SCAN may return a key more than once
+    matches.sort_by(|a, b| a.key.cmp(&b.key));
+    matches.dedup_by(|a, b| a.key == b.key);
+
+    Ok(matches)
 }
@@ -1,190 +1,269 @@
-use colored::*;
 use log::info;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 
-use crate::config::AppConfig;
+use crate::cli::DecodeFormat;
+use crate::decode::decode_value;
 use crate::error::AppError;
-use crate::redis_client::RedisClient;
+use crate::format::{escape_bytes, is_binary};
+use crate::query::{apply_query, Segment};
+use crate::redis_client::{KeyInfo, KeyValue, RedisClient, StreamEntry};
 
-pub async fn run(key: String, environment: Option<String>, pretty: bool) -> Result<(), AppError> {
+/// Number of stream entries returned by `run`
+const STREAM_ENTRIES: usize = 100;
+
+/// Keys per pipeline when fetching values by pattern
+const BATCH_SIZE: usize = 500;
+
+/// The value of a key, by type.
+pub enum Contents {
+    String(Vec<u8>),
+    Hash(HashMap<String, String>),
+    List(Vec<String>),
+    /// The first entries of a stream and its full length
+    Stream {
+        length: usize,
+        entries: Vec<StreamEntry>,
+    },
+    Set(Vec<String>),
+    Zset(Vec<(String, f64)>),
+    Unsupported,
+}
+
+/// Read a key of any type with its metadata.
+pub async fn run(client: &mut RedisClient, key: &str) -> Result<(KeyInfo, Contents), AppError> {
     info!("Getting value for key: {}", key);
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
+    let key_info = client.key_info(key).await?;
+    let not_found = || AppError::NotFound(format!("Key '{}' not found", key));
+    let contents = match key_info.key_type.as_str() {
+        "none" => return Err(not_found()),
+        "string" => Contents::String(client.get_bytes(key).await?.ok_or_else(not_found)?),
+        "hash" => Contents::Hash(client.get_hash(key).await?),
+        "list" => Contents::List(client.get_list(key, 0, -1).await?),
+        "stream" => Contents::Stream {
+            length: client.key_length(key, "stream").await?.unwrap_or(0),
+            entries: client.get_stream(key, STREAM_ENTRIES).await?,
+        },
+        "set" => Contents::Set(client.get_set(key).await?),
+        "zset" => Contents::Zset(client.get_sorted_set(key, 0, -1, true).await?),
+        _ => Contents::Unsupported,
+    };
+    Ok((key_info, contents))
+}
 
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
+/// Write the value of `key` to `path`: strings as their raw bytes, other
+/// types as pretty-printed JSON. Returns the number of bytes written.
+pub async fn to_file(client: &mut RedisClient, key: &str, path: &str) -> Result<usize, AppError> {
+    info!("Writing value of {} to {}", key, path);
 
-    let mut client = RedisClient::connect(redis_config).await?;
+    let key_type = client.key_type(key).await?;
+    let bytes = match key_type.as_str() {
+        "none" => return Err(AppError::NotFound(format!("Key '{}' not found", key))),
+        "string" => client
+            .get_bytes(key)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Key '{}' not found", key)))?,
+        _ => {
+            let value = client
+                .fetch_values(&[key.to_string()])
+                .await?
+                .pop()
+                .and_then(|kv| kv.value)
+                .ok_or_else(|| {
+                    AppError::Anyhow(anyhow::anyhow!(
+                        "Cannot write a {} value to a file",
+                        key_type
+                    ))
+                })?;
+            let mut json = serde_json::to_vec_pretty(&value)?;
+            json.push(b'\n');
+            json
+        }
+    };
 
-    // First get key info to determine type
-    let key_info = client.key_info(&key).await?;
+    std::fs::write(path, &bytes)?;
+    Ok(bytes.len())
+}
 
-    println!("{}", format!("Key: {}", key).bold());
-    println!("{}", format!("Type: {}", key_info.key_type).cyan());
+/// Evaluate a query path against the value of `key`. Strings must hold
+/// JSON, or another format with `decode`; JSON held in the fields of a hash
+/// or the items of a list is parsed so the path can reach into it.
+pub async fn query(
+    client: &mut RedisClient,
+    key: &str,
+    path: &[Segment],
+    decode: Option<DecodeFormat>,
+) -> Result<Vec<JsonValue>, AppError> {
+    info!("Querying key {}", key);
 
-    match key_info.key_type.as_str() {
+    let key_info = client.key_info(key).await?;
+    let value = match key_info.key_type.as_str() {
+        "none" => return Err(AppError::NotFound(format!("Key '{}' not found", key))),
         "string" => {
-            if let Some(value) = client.get_string(&key).await? {
-                if pretty {
-                    match client.pretty_print_json(&value) {
-                        Ok(pretty_value) => {
-                            println!("{}", "Value (pretty-printed):".bold());
-                            println!("{}", pretty_value);
-                        }
-                        Err(_) => {
-                            println!("{}", "Value:".bold());
-                            println!("{}", value);
-                        }
-                    }
-                } else {
-                    println!("{}", "Value:".bold());
-                    println!("{}", value);
-                }
-            } else {
-                println!("{}", "Key not found or value is nil".red());
-            }
-        }
-        "hash" => {
-            let hash = client.get_hash(&key).await?;
-            if hash.is_empty() {
-                println!("{}", "Hash is empty".yellow());
-            } else {
-                println!("{}", "Hash fields:".bold());
-                for (field, value) in hash {
-                    println!("  {}: {}", field.cyan(), value);
-                }
-            }
-        }
-        "list" => {
-            let list = client.get_list(&key, 0, -1).await?;
-            if list.is_empty() {
-                println!("{}", "List is empty".yellow());
-            } else {
-                println!("{}", format!("List ({} items):", list.len()).bold());
-                for (i, item) in list.iter().enumerate() {
-                    println!("  [{}]: {}", i, item);
+            let bytes = client.get_bytes(key).await?.unwrap_or_default();
+            match decode_value(&bytes, decode.unwrap_or(DecodeFormat::Json)) {
+                Some((value, _)) => value,
+                None => {
+                    return Err(AppError::QueryError(format!(
+                        "value of '{}' is not JSON (use --decode for other formats)",
+                        key
+                    )))
                 }
             }
         }
-        "set" => {
-            let set = client.get_set(&key).await?;
-            if set.is_empty() {
-                println!("{}", "Set is empty".yellow());
-            } else {
-                println!("{}", format!("Set ({} members):", set.len()).bold());
-                for member in set {
-                    println!("  • {}", member);
-                }
-            }
+        _ => {
+            let value = client
+                .fetch_values(&[key.to_string()])
+                .await?
+                .pop()
+                .and_then(|kv| kv.value)
+                .ok_or_else(|| {
+                    AppError::QueryError(format!("cannot query a {} value", key_info.key_type))
+                })?;
+            expand_nested(value)
         }
-        "zset" => {
-            let zset = client.get_sorted_set(&key, 0, -1, true).await?;
-            if zset.is_empty() {
-                println!("{}", "Sorted set is empty".yellow());
-            } else {
-                println!("{}", format!("Sorted set ({} members):", zset.len()).bold());
-                for (member, score) in zset {
-                    println!("  • {} (score: {})", member, score);
-                }
-            }
+    };
+
+    apply_query(&value, path).map_err(AppError::QueryError)
+}
+
+/// Parse JSON held in the elements of a hash or list so a path can reach
+/// into it, e.g. `.profile.address` on a hash whose `profile` field is JSON.
+fn expand_nested(value: JsonValue) -> JsonValue {
+    let parse = |item: JsonValue| match &item {
+        JsonValue::String(s) if s.trim_start().starts_with(['{', '[']) => {
+            serde_json::from_str(s).unwrap_or(item)
         }
-        _ => {
-            println!(
-                "{}",
-                format!("Unsupported key type: {}", key_info.key_type).red()
-            );
+        _ => item,
+    };
+    match value {
+        JsonValue::Object(map) => {
+            JsonValue::Object(map.into_iter().map(|(k, v)| (k, parse(v))).collect())
         }
+        JsonValue::Array(items) => JsonValue::Array(items.into_iter().map(parse).collect()),
+        other => other,
     }
-
-    Ok(())
 }
 
-pub async fn get_hash_field(
-    key: String,
-    field: String,
-    environment: Option<String>,
-) -> Result<(), AppError> {
-    info!("Getting hash field: {}:{}", key, field);
+/// Values of string keys with MGET; `None` for keys that are missing or
+/// not strings.
+pub async fn multi(
+    client: &mut RedisClient,
+    keys: Vec<String>,
+) -> Result<Vec<(String, Option<String>)>, AppError> {
+    info!("Getting {} keys with MGET", keys.len());
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
+    let values = client.mget(&keys).await?;
+    Ok(keys.into_iter().zip(values).collect())
+}
 
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
+/// Values of every key matching `pattern`, of any type.
+pub async fn pattern(client: &mut RedisClient, pattern: &str) -> Result<Vec<KeyValue>, AppError> {
+    info!("Getting values for pattern: {}", pattern);
 
-    let mut client = RedisClient::connect(redis_config).await?;
+    let keys = client.scan_keys(pattern).await?;
 
-    // Get the entire hash and find the specific field
-    let hash = client.get_hash(&key).await?;
-
-    if let Some(value) = hash.get(&field) {
-        println!("{}", format!("Field: {}:{}", key, field).bold());
-        println!("{}", "Value:".bold());
-        println!("{}", value);
-    } else {
-        println!(
-            "{}",
-            format!("Field '{}' not found in hash '{}'", field, key).red()
-        );
+    let mut values = Vec::with_capacity(keys.len());
+    for batch in keys.chunks(BATCH_SIZE) {
+        values.extend(client.fetch_values(batch).await?);
     }
+    // Drop keys that expired or were deleted since the scan
+    values.retain(|kv| kv.key_type != "none");
+    Ok(values)
+}
+
+pub async fn hash_field(
+    client: &mut RedisClient,
+    key: &str,
+    field: &str,
+) -> Result<String, AppError> {
+    info!("Getting hash field: {}:{}", key, field);
 
-    Ok(())
+    // Get the entire hash and find the specific field
+    let mut hash = client.get_hash(key).await?;
+    hash.remove(field)
+        .ok_or_else(|| AppError::NotFound(format!("Field '{}' not found in hash '{}'", field, key)))
 }
 
-pub async fn get_list_range(
-    key: String,
+pub async fn list_range(
+    client: &mut RedisClient,
+    key: &str,
     start: isize,
     stop: isize,
-    environment: Option<String>,
-) -> Result<(), AppError> {
+) -> Result<Vec<String>, AppError> {
     info!("Getting list range: {} [{}-{}]", key, start, stop);
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    let list = client.get_list(&key, start, stop).await?;
-
-    if list.is_empty() {
-        println!("{}", "No items found in the specified range".yellow());
-    } else {
-        println!(
-            "{}",
-            format!("List range [{}-{}] ({} items):", start, stop, list.len()).bold()
-        );
-        for (i, item) in list.iter().enumerate() {
-            let actual_index = start + i as isize;
-            println!("  [{}]: {}", actual_index, item);
+    Ok(client.get_list(key, start, stop).await?)
+}
+
+/// Render a key's value as plain lines in a stable order so successive
+/// renders can be compared line by line.
+pub async fn value_lines(
+    client: &mut RedisClient,
+    key: &str,
+    pretty: bool,
+) -> Result<Vec<String>, AppError> {
+    let key_info = client.key_info(key).await?;
+    let mut lines = vec![format!("Type: {}", key_info.key_type)];
+
+    match key_info.key_type.as_str() {
+        "none" => lines.push("Key does not exist".to_string()),
+        "string" => {
+            if let Some(bytes) = client.get_bytes(key).await? {
+                let value = if is_binary(&bytes) {
+                    escape_bytes(&bytes)
+                } else {
+                    String::from_utf8_lossy(&bytes).to_string()
+                };
+                let value = if pretty {
+                    client.pretty_print_json(&value)?
+                } else {
+                    value
+                };
+                lines.extend(value.lines().map(|l| l.to_string()));
+            }
+        }
+        "hash" => {
+            let mut fields: Vec<(String, String)> =
+                client.get_hash(key).await?.into_iter().collect();
+            fields.sort();
+            lines.push(format!("Hash ({} fields):", fields.len()));
+            lines.extend(
+                fields
+                    .into_iter()
+                    .map(|(field, value)| format!("  {}: {}", field, value)),
+            );
+        }
+        "list" => {
+            let list = client.get_list(key, 0, -1).await?;
+            lines.push(format!("List ({} items):", list.len()));
+            lines.extend(
+                list.iter()
+                    .enumerate()
+                    .map(|(i, item)| format!("  [{}]: {}", i, item)),
+            );
         }
+        "set" => {
+            let mut set = client.get_set(key).await?;
+            set.sort();
+            lines.push(format!("Set ({} members):", set.len()));
+            lines.extend(set.into_iter().map(|member| format!("  • {}", member)));
+        }
+        "zset" => {
+            let zset = client.get_sorted_set(key, 0, -1, true).await?;
+            lines.push(format!("Sorted set ({} members):", zset.len()));
+            lines.extend(
+                zset.into_iter()
+                    .map(|(member, score)| format!("  • {} (score: {})", member, score)),
+            );
+        }
+        other => lines.push(format!("Unsupported key type: {}", other)),
+    }
+
+    if let Some(ttl) = key_info.ttl.filter(|t| *t >= 0) {
+        lines.push(format!("TTL: {}s", ttl));
     }
 
-    Ok(())
+    Ok(lines)
 }
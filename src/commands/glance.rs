@@ -0,0 +1,66 @@
+use log::info;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// The state of one favorite key.
+#[derive(Debug)]
+pub struct Favorite {
+    pub key: String,
+    /// `None` when the key does not exist
+    pub key_type: Option<String>,
+    pub ttl: Option<i64>,
+    pub length: Option<usize>,
+    /// Seconds since last access
+    pub idle: Option<i64>,
+}
+
+/// Look up every favorite. Favorites may be glob patterns; they are expanded
+/// into the keys they match, or kept as-is when they match nothing.
+pub async fn run(
+    client: &mut RedisClient,
+    favorites: &[String],
+) -> Result<Vec<Favorite>, AppError> {
+    info!("Running glance command");
+
+    let mut keys = Vec::new();
+    for favorite in favorites {
+        if favorite.contains(['*', '?', '[']) {
+            let matches = client.scan_keys(favorite).await?;
+            if matches.is_empty() {
+                keys.push(favorite.clone());
+            } else {
+                keys.extend(matches);
+            }
+        } else {
+            keys.push(favorite.clone());
+        }
+    }
+
+    let mut entries = Vec::with_capacity(keys.len());
+    for key in keys {
+        let info = client.key_info(&key).await?;
+        if info.key_type == "none" {
+            entries.push(Favorite {
+                key,
+                key_type: None,
+                ttl: None,
+                length: None,
+                idle: None,
+            });
+            continue;
+        }
+
+        let length = client.key_length(&key, &info.key_type).await?;
+        let idle = client.idle_time(&key).await?;
+        entries.push(Favorite {
+            key,
+            key_type: Some(info.key_type),
+            ttl: info.ttl,
+            length,
+            idle,
+        });
+    }
+
+    Ok(entries)
+}
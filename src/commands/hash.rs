@@ -0,0 +1,105 @@
+use log::info;
+use std::time::Duration;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Delete `fields` from a hash. Returns how many existed.
+pub async fn delete_fields(
+    client: &mut RedisClient,
+    key: &str,
+    fields: &[String],
+) -> Result<usize, AppError> {
+    info!("Deleting {} fields from {}", fields.len(), key);
+
+    let deleted = client.delete_hash_fields(key, fields).await?;
+    if deleted == 0 {
+        return Err(AppError::NotFound(format!(
+            "None of the fields exist in '{}'",
+            key
+        )));
+    }
+    Ok(deleted)
+}
+
+/// Check a single field, failing with `NotFound` when it is missing.
+pub async fn exists(client: &mut RedisClient, key: &str, field: &str) -> Result<(), AppError> {
+    info!("Checking field {} of {}", field, key);
+
+    if !client.hash_field_exists(key, field).await? {
+        return Err(AppError::NotFound(format!(
+            "Field '{}' not found in '{}'",
+            field, key
+        )));
+    }
+    Ok(())
+}
+
+/// `count` random fields (HRANDFIELD semantics, so a negative count may
+/// repeat fields), with their values when `with_values` is set.
+pub async fn random(
+    client: &mut RedisClient,
+    key: &str,
+    count: i64,
+    with_values: bool,
+) -> Result<Vec<(String, Option<String>)>, AppError> {
+    info!("Sampling {} random fields from {}", count, key);
+
+    let fields = client.random_hash_fields(key, count, with_values).await?;
+    if fields.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "Hash '{}' is empty or does not exist",
+            key
+        )));
+    }
+    Ok(fields)
+}
+
+/// Give individual fields a TTL, leaving the rest of the hash untouched.
+/// Returns HEXPIRE's code for each field: 1 when set, 2 when the field was
+/// deleted straight away, -2 when it does not exist, and 0 when the
+/// expiry was not set.
+pub async fn expire(
+    client: &mut RedisClient,
+    key: &str,
+    ttl: Duration,
+    fields: &[String],
+) -> Result<Vec<i64>, AppError> {
+    info!("Expiring {} fields of {} in {:?}", fields.len(), key, ttl);
+
+    client
+        .expire_hash_fields(key, ttl, fields)
+        .await
+        .map_err(field_ttl_error)
+}
+
+/// The remaining TTL in seconds of each field: -1 without an expiry and -2
+/// when the field does not exist.
+pub async fn ttl(
+    client: &mut RedisClient,
+    key: &str,
+    fields: &[String],
+) -> Result<Vec<i64>, AppError> {
+    info!("Getting field TTLs of {}", key);
+
+    client
+        .hash_field_ttls(key, fields)
+        .await
+        .map_err(field_ttl_error)
+}
+
+/// Hash-field TTL commands only exist since Redis 7.4; say so instead of
+/// surfacing a bare "unknown command".
+fn field_ttl_error(error: anyhow::Error) -> AppError {
+    let unsupported = error.downcast_ref::<redis::RedisError>().is_some_and(|e| {
+        e.kind() == redis::ErrorKind::ResponseError && e.to_string().contains("unknown command")
+    });
+    if unsupported {
+        AppError::Aborted(format!(
+            "Hash field TTLs need Redis 7.4 or newer ({})",
+            error
+        ))
+    } else {
+        error.into()
+    }
+}
@@ -0,0 +1,121 @@
+use log::info;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+use crate::format::format_micros;
+use crate::redis_client::RedisClient;
+
+/// Number of PINGs sent; the fastest one is compared against the limit so a
+/// single scheduling hiccup does not fail a probe.
+const PING_ATTEMPTS: usize = 3;
+
+#[derive(Debug, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub checks: Vec<Check>,
+}
+
+/// Check latency, replication and free memory. An unhealthy server is
+/// reported with `healthy: false` rather than as an error.
+pub async fn run(
+    client: &mut RedisClient,
+    max_latency: Duration,
+    min_memory_free: f64,
+) -> Result<HealthReport, AppError> {
+    info!("Running health check");
+
+    let mut latency = Duration::MAX;
+    for _ in 0..PING_ATTEMPTS {
+        let start = Instant::now();
+        client.ping().await?;
+        latency = latency.min(start.elapsed());
+    }
+    let info = client.info().await?;
+
+    let checks = vec![
+        Check {
+            name: "latency",
+            ok: latency <= max_latency,
+            detail: format!(
+                "{} (max {})",
+                format_micros(latency.as_micros() as i64),
+                format_micros(max_latency.as_micros() as i64)
+            ),
+        },
+        check_replication(&info),
+        check_memory(&info, min_memory_free),
+    ];
+    Ok(HealthReport {
+        healthy: checks.iter().all(|check| check.ok),
+        checks,
+    })
+}
+
+/// A replica must have a live link to its master; a master must not have
+/// replicas that have dropped out of the `online` state.
+fn check_replication(info: &HashMap<String, String>) -> Check {
+    let field = |name: &str| info.get(name).map(|v| v.trim()).unwrap_or("");
+    if field("role") == "slave" {
+        let link = field("master_link_status");
+        return Check {
+            name: "replication",
+            ok: link == "up",
+            detail: format!("replica, master link {}", link),
+        };
+    }
+
+    let replicas: Vec<&String> = info
+        .iter()
+        .filter(|(name, _)| {
+            name.strip_prefix("slave")
+                .is_some_and(|n| n.parse::<u32>().is_ok())
+        })
+        .map(|(_, value)| value)
+        .collect();
+    let offline = replicas
+        .iter()
+        .filter(|value| !value.contains("state=online"))
+        .count();
+    Check {
+        name: "replication",
+        ok: offline == 0,
+        detail: if offline == 0 {
+            format!("master, {} replicas online", replicas.len())
+        } else {
+            format!("master, {} of {} replicas offline", offline, replicas.len())
+        },
+    }
+}
+
+/// Free memory relative to `maxmemory`. Without a limit Redis can grow
+/// until the host runs out, so the check passes but says so.
+fn check_memory(info: &HashMap<String, String>, min_free: f64) -> Check {
+    let number = |name: &str| {
+        info.get(name)
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+    let max = number("maxmemory");
+    if max == 0 {
+        return Check {
+            name: "memory",
+            ok: true,
+            detail: "no maxmemory limit".to_string(),
+        };
+    }
+    let free = max.saturating_sub(number("used_memory")) as f64 / max as f64 * 100.0;
+    Check {
+        name: "memory",
+        ok: free >= min_free,
+        detail: format!("{:.1}% free (min {}%)", free, min_free),
+    }
+}
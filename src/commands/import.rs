@@ -0,0 +1,160 @@
+use log::info;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use crate::error::AppError;
+use crate::format::display_key;
+use crate::redis_client::RedisClient;
+
+const BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub sent: usize,
+    /// Commands that returned an error, by name, with the error
+    pub errors: Vec<(String, String)>,
+}
+
+/// What replaying a file would do.
+#[derive(Debug, Default)]
+pub struct ImportPlan {
+    /// Number of commands per command name
+    pub commands: BTreeMap<String, usize>,
+    /// Distinct keys the commands write
+    pub keys: usize,
+    /// How many of those keys already exist
+    pub existing: usize,
+}
+
+/// Replay a file of RESP-encoded commands, like `redis-cli --pipe`. Every
+/// command runs exactly once; one that returns an error does not stop the
+/// rest. `progress` is called after each batch with the number of commands
+/// sent so far.
+pub async fn pipe_file(
+    client: &mut RedisClient,
+    path: &str,
+    mut progress: impl FnMut(usize),
+) -> Result<ImportReport, AppError> {
+    info!("Importing commands from {}", path);
+
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut report = ImportReport::default();
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+
+    loop {
+        let command = read_command(&mut reader)?;
+        let done = command.is_none();
+        if let Some(command) = command {
+            batch.push(command);
+        }
+
+        if batch.len() >= BATCH_SIZE || (done && !batch.is_empty()) {
+            send_batch(client, &batch, &mut report.errors).await?;
+            report.sent += batch.len();
+            batch.clear();
+            progress(report.sent);
+        }
+
+        if done {
+            break;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Parse the whole file and summarize the commands it holds and the keys
+/// they would write, including how many of those already exist.
+pub async fn plan(client: &mut RedisClient, path: &str) -> Result<ImportPlan, AppError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut plan = ImportPlan::default();
+    let mut keys = BTreeSet::new();
+    while let Some(args) = read_command(&mut reader)? {
+        let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+        *plan.commands.entry(name).or_default() += 1;
+        // The first argument is the key for the commands an export contains
+        if let Some(key) = args.get(1) {
+            keys.insert(display_key(key));
+        }
+    }
+
+    let keys: Vec<String> = keys.into_iter().collect();
+    for batch in keys.chunks(BATCH_SIZE) {
+        plan.existing += client
+            .exists_many(batch)
+            .await?
+            .into_iter()
+            .filter(|e| *e)
+            .count();
+    }
+    plan.keys = keys.len();
+
+    Ok(plan)
+}
+
+/// Send a batch as one pipeline and record the commands that returned
+/// errors.
+async fn send_batch(
+    client: &mut RedisClient,
+    batch: &[Vec<Vec<u8>>],
+    errors: &mut Vec<(String, String)>,
+) -> Result<(), AppError> {
+    let commands: Vec<redis::Cmd> = batch.iter().map(|args| to_cmd(args)).collect();
+    let replies = client.pipeline_each(&commands).await?;
+
+    for (args, reply) in batch.iter().zip(replies) {
+        if let Err(e) = reply {
+            let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+            errors.push((name, e.to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn to_cmd(args: &[Vec<u8>]) -> redis::Cmd {
+    let mut cmd = redis::cmd(&String::from_utf8_lossy(&args[0]));
+    for arg in &args[1..] {
+        cmd.arg(arg.as_slice());
+    }
+    cmd
+}
+
+/// Read one `*<n>` array of bulk strings, or `None` at end of file.
+fn read_command(reader: &mut impl BufRead) -> Result<Option<Vec<Vec<u8>>>, AppError> {
+    let header = loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end().to_string();
+        if !line.is_empty() {
+            break line;
+        }
+    };
+
+    let count: usize = header
+        .strip_prefix('*')
+        .and_then(|n| n.parse().ok())
+        .filter(|n| *n > 0)
+        .ok_or_else(|| anyhow::anyhow!("Expected a RESP array, found '{}'", header))?;
+
+    let mut args = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let len: usize = line
+            .trim_end()
+            .strip_prefix('$')
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| anyhow::anyhow!("Expected a RESP bulk string, found '{}'", line))?;
+
+        // Payload plus trailing CRLF
+        let mut data = vec![0u8; len + 2];
+        reader.read_exact(&mut data)?;
+        data.truncate(len);
+        args.push(data);
+    }
+
+    Ok(Some(args))
+}
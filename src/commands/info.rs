@@ -0,0 +1,40 @@
+use log::info;
+
+use crate::error::AppError;
+use crate::redis_client::{InfoSection, RedisClient};
+
+/// INFO grouped by section: the default sections, every section with
+/// `all`, or just `section`. An unknown section name is reported along with
+/// the sections the server actually has.
+pub async fn run(
+    client: &mut RedisClient,
+    section: Option<&str>,
+    all: bool,
+) -> Result<Vec<InfoSection>, AppError> {
+    info!("Getting INFO");
+
+    let requested = match (section, all) {
+        (Some(section), _) => section.to_lowercase(),
+        (None, true) => "all".to_string(),
+        (None, false) => "default".to_string(),
+    };
+    let sections = client.info_sections(&requested).await?;
+
+    // Redis answers an unknown section with an empty reply rather than an
+    // error, so compare against what the server offers
+    if sections.is_empty() {
+        let available: Vec<String> = client
+            .info_sections("all")
+            .await?
+            .into_iter()
+            .map(|section| section.name.to_lowercase())
+            .collect();
+        return Err(AppError::NotFound(format!(
+            "Unknown INFO section '{}'. Available: {}",
+            requested,
+            available.join(", ")
+        )));
+    }
+
+    Ok(sections)
+}
@@ -1,10 +1,37 @@
-use colored::*;
 use log::info;
+use serde::Serialize;
 
 use crate::error::AppError;
+use crate::redis_client::{KeyInfo, RedisClient};
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Inspect command - placeholder");
-    println!("{}", "Inspect command - not yet implemented".yellow());
-    Ok(())
+/// Keys per pipeline when inspecting many keys
+const INSPECT_BATCH: usize = 500;
+
+/// Everything `inspect` reports about a key.
+#[derive(Debug, Serialize)]
+pub struct KeyDetails {
+    #[serde(flatten)]
+    pub info: KeyInfo,
+    /// Elements held by the key, or bytes for strings
+    pub length: Option<usize>,
+}
+
+/// The metadata of keys without their values: type, encoding, length, TTL,
+/// memory usage and how recently (or, under LFU, how often) they were
+/// accessed. Missing keys are included with type "none".
+pub async fn run(client: &mut RedisClient, keys: &[String]) -> Result<Vec<KeyDetails>, AppError> {
+    info!("Inspecting keys: {:?}", keys);
+
+    let mut details = Vec::with_capacity(keys.len());
+    for batch in keys.chunks(INSPECT_BATCH) {
+        let infos = client.key_infos(batch).await?;
+        let lengths = client.key_lengths(&infos).await?;
+        details.extend(
+            infos
+                .into_iter()
+                .zip(lengths)
+                .map(|(info, length)| KeyDetails { info, length }),
+        );
+    }
+    Ok(details)
 }
@@ -1,197 +1,358 @@
-use colored::*;
+use futures::future::try_join_all;
 use log::info;
-use tabled::{Table, Tabled};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
-use crate::config::AppConfig;
+use super::cluster;
+use crate::cli::KeySort;
+use crate::config::RedisConfig;
 use crate::error::AppError;
-use crate::redis_client::RedisClient;
-
-#[derive(Tabled)]
-struct KeyRow {
-    #[tabled(rename = "Key")]
-    key: String,
-    #[tabled(rename = "Type")]
-    key_type: String,
-    #[tabled(rename = "TTL")]
-    ttl: String,
-    #[tabled(rename = "Memory")]
-    memory: String,
-    #[tabled(rename = "Encoding")]
-    encoding: String,
+use crate::redis_client::{KeyInfo, RedisClient};
+
+/// Page size used when a cursor is given without a limit
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Keys per pipeline when fetching details
+const KEY_INFO_BATCH: usize = 500;
+
+/// Connections used side by side when fetching details
+const KEY_INFO_CONNECTIONS: usize = 4;
+
+/// A key found by a cluster-wide scan and the master that owns it.
+#[derive(Debug, Serialize)]
+pub struct NodeKey {
+    pub key: String,
+    pub node: String,
+}
+
+/// A key found by a scan of every database.
+#[derive(Debug, Serialize)]
+pub struct DbKey {
+    pub db: u8,
+    pub key: String,
+}
+
+#[derive(Debug)]
+pub struct KeyCount {
+    pub total: usize,
+    /// Keys whose type could not be read count as "unknown"
+    pub by_type: HashMap<String, usize>,
 }
 
+/// Keys matching `pattern`. With `limit` or `cursor`, the keyspace is
+/// walked with SCAN one page at a time instead of fetching every match with
+/// KEYS, and the cursor for the next page is returned too. A type filter
+/// also needs SCAN so the server can apply it.
 pub async fn run(
-    pattern: Option<String>,
-    environment: Option<String>,
-    detailed: bool,
-) -> Result<(), AppError> {
-    info!("Running keys command with pattern: {:?}", pattern);
-
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
+    client: &mut RedisClient,
+    pattern: &str,
+    limit: Option<usize>,
+    cursor: Option<u64>,
+    key_type: Option<&str>,
+) -> Result<(Vec<String>, Option<u64>), AppError> {
+    info!("Running keys command with pattern: {}", pattern);
+
+    let mut scanner = TypedScan::new(key_type);
+    if limit.is_some() || cursor.is_some() {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        let (next, keys) = scanner
+            .collect(client, pattern, cursor.unwrap_or(0), limit)
+            .await?;
+        Ok((keys, Some(next)))
+    } else if key_type.is_some() {
+        let (_, mut keys) = scanner.collect(client, pattern, 0, usize::MAX).await?;
+        // SCAN may return a key more than once
+        keys.sort();
+        keys.dedup();
+        Ok((keys, None))
+    } else {
+        Ok((client.keys(pattern).await?, None))
+    }
+}
+
+/// The healthy masters of a Redis Cluster, by address, with the settings to
+/// connect to each.
+pub async fn cluster_masters(
+    client: &mut RedisClient,
+    base: &RedisConfig,
+) -> Result<Vec<(String, RedisConfig)>, AppError> {
+    Ok(client
+        .cluster_nodes()
+        .await?
+        .iter()
+        .filter(|node| node.is_master() && !node.is_failing())
+        .filter_map(|node| {
+            let node_config = cluster::node_config(base, node)?;
+            Some((
+                format!("{}:{}", node_config.host, node_config.port),
+                node_config,
+            ))
+        })
+        .collect())
+}
+
+/// List matching keys across a whole Redis Cluster: SCAN runs on every
+/// master in parallel and the results are merged and sorted, each key
+/// annotated with the node that owns it.
+pub async fn cluster_wide(
+    masters: &[(String, RedisConfig)],
+    pattern: &str,
+    key_type: Option<&str>,
+) -> Result<Vec<NodeKey>, AppError> {
+    info!("Scanning all cluster masters for pattern: {}", pattern);
+
+    let scans = masters.iter().map(|(address, node_config)| async move {
+        let mut client = RedisClient::connect(node_config.clone()).await?;
+        let (_, keys) = TypedScan::new(key_type)
+            .collect(&mut client, pattern, 0, usize::MAX)
+            .await?;
+        Ok::<_, AppError>(
+            keys.into_iter()
+                .map(|key| NodeKey {
+                    key,
+                    node: address.clone(),
+                })
+                .collect::<Vec<_>>(),
+        )
     });
 
-    let redis_config = match config.get_environment(&env_name) {
-        Some(env) => env.config.clone(),
-        None => {
-            println!(
-                "{}",
-                format!("Error: Environment '{}' not found", env_name).red()
-            );
-            println!("{}", "Available environments:".yellow());
-            for env_name in config.environments.keys() {
-                println!("  • {}", env_name.cyan());
+    let mut keys: Vec<NodeKey> = try_join_all(scans).await?.into_iter().flatten().collect();
+    // SCAN may return a key more than once
+    keys.sort_by(|a, b| a.key.cmp(&b.key));
+    keys.dedup_by(|a, b| a.key == b.key);
+    Ok(keys)
+}
+
+/// The logical databases of the instance. The count comes from CONFIG GET
+/// databases; where CONFIG is disabled (as on many managed services) the
+/// databases INFO keyspace reports are used instead.
+pub async fn databases(client: &mut RedisClient) -> Result<Vec<u8>, AppError> {
+    Ok(match client.config_get("databases").await {
+        Ok(params) => {
+            let count = params
+                .iter()
+                .find(|(name, _)| name == "databases")
+                .and_then(|(_, value)| value.parse::<u32>().ok())
+                .unwrap_or(16);
+            (0..count).filter_map(|db| u8::try_from(db).ok()).collect()
+        }
+        Err(e) => {
+            info!("CONFIG GET databases failed ({}), using INFO keyspace", e);
+            let mut databases: Vec<u8> = client
+                .info()
+                .await?
+                .keys()
+                .filter_map(|name| name.strip_prefix("db")?.parse().ok())
+                .collect();
+            databases.sort_unstable();
+            databases
+        }
+    })
+}
+
+/// List matching keys in every database in `databases`, not just the
+/// configured one, so data written to a forgotten `SELECT 3` turns up.
+/// Keys come back grouped by database.
+pub async fn all_dbs(
+    base: &RedisConfig,
+    databases: &[u8],
+    pattern: &str,
+    key_type: Option<&str>,
+) -> Result<Vec<DbKey>, AppError> {
+    info!("Scanning all databases for pattern: {}", pattern);
+
+    let scans = databases.iter().map(|&db| {
+        let mut db_config = base.clone();
+        db_config.db = db;
+        async move {
+            let mut client = RedisClient::connect(db_config).await?;
+            let (_, mut keys) = TypedScan::new(key_type)
+                .collect(&mut client, pattern, 0, usize::MAX)
+                .await?;
+            // SCAN may return a key more than once
+            keys.sort();
+            keys.dedup();
+            Ok::<_, AppError>(
+                keys.into_iter()
+                    .map(|key| DbKey { db, key })
+                    .collect::<Vec<_>>(),
+            )
+        }
+    });
+    Ok(try_join_all(scans).await?.into_iter().flatten().collect())
+}
+
+/// Fetch type, TTL, memory and encoding for every key. Keys are split
+/// across up to `KEY_INFO_CONNECTIONS` connections working concurrently,
+/// each sending pipelines of `KEY_INFO_BATCH` keys. Results keep the order
+/// of `keys`; a batch that fails is skipped and returned with its size and
+/// error. `progress` is called after each batch with the number of keys in
+/// it.
+pub async fn details(
+    redis_config: &RedisConfig,
+    keys: &[String],
+    progress: impl Fn(usize),
+) -> Result<(Vec<KeyInfo>, Vec<(usize, String)>), AppError> {
+    let progress = &progress;
+    let per_connection = keys.len().div_ceil(KEY_INFO_CONNECTIONS).max(1);
+    let workers = keys.chunks(per_connection).map(|share| async move {
+        let mut client = RedisClient::connect(redis_config.clone()).await?;
+        let mut infos = Vec::with_capacity(share.len());
+        let mut errors = Vec::new();
+        for batch in share.chunks(KEY_INFO_BATCH) {
+            match client.key_infos(batch).await {
+                Ok(batch_infos) => infos.extend(batch_infos),
+                Err(e) => errors.push((batch.len(), e.to_string())),
             }
-            println!();
-            println!("{}", "To add a new environment, use:".cyan());
-            println!("  solt config --add-env <name>");
-            return Ok(());
+            progress(batch.len());
         }
-    };
+        Ok::<_, AppError>((infos, errors))
+    });
+
+    let mut infos = Vec::with_capacity(keys.len());
+    let mut errors = Vec::new();
+    for (share_infos, share_errors) in try_join_all(workers).await? {
+        infos.extend(share_infos);
+        errors.extend(share_errors);
+    }
+    Ok((infos, errors))
+}
 
-    let mut client = RedisClient::connect(redis_config).await?;
+/// Order detailed results by the chosen column. Unknown memory and keys
+/// without an expiry always sort last, whichever direction is chosen, so
+/// `--sort ttl` surfaces the soonest-expiring keys and `--sort memory
+/// --desc` the biggest.
+pub fn sort_key_infos(key_infos: &mut [KeyInfo], sort: KeySort, desc: bool) {
+    let directed = |ordering: Ordering| if desc { ordering.reverse() } else { ordering };
+    key_infos.sort_by(|a, b| match sort {
+        KeySort::Name => directed(a.key.cmp(&b.key)),
+        KeySort::Type => directed(a.key_type.cmp(&b.key_type).then(a.key.cmp(&b.key))),
+        KeySort::Memory => missing_last(a.memory_usage, b.memory_usage, directed),
+        KeySort::Ttl => {
+            let expiry = |info: &KeyInfo| info.ttl.filter(|t| *t >= 0);
+            missing_last(expiry(a), expiry(b), directed)
+        }
+    });
+}
 
-    let pattern = pattern.unwrap_or_else(|| "*".to_string());
-    let keys = client.keys(&pattern).await?;
+fn missing_last<T: Ord>(
+    a: Option<T>,
+    b: Option<T>,
+    directed: impl Fn(Ordering) -> Ordering,
+) -> Ordering {
+    match (a, b) {
+        (Some(x), Some(y)) => directed(x.cmp(&y)),
+        (x, y) => y.is_some().cmp(&x.is_some()),
+    }
+}
 
-    println!(
-        "{}",
-        format!("Found {} keys matching pattern '{}'", keys.len(), pattern)
-            .cyan()
-            .bold()
-    );
+/// SCAN that optionally filters by type. The TYPE option is tried first so
+/// the server does the filtering; servers older than Redis 6 reject it,
+/// after which each page is filtered here with pipelined TYPE calls.
+pub struct TypedScan<'a> {
+    key_type: Option<&'a str>,
+    server_side: bool,
+}
 
-    if keys.is_empty() {
-        println!("{}", "No keys found.".yellow());
-        return Ok(());
+impl<'a> TypedScan<'a> {
+    pub fn new(key_type: Option<&'a str>) -> Self {
+        Self {
+            key_type,
+            server_side: true,
+        }
     }
 
-    if detailed {
-        // Get detailed information for each key
-        let mut key_infos = Vec::new();
-        let progress = indicatif::ProgressBar::new(keys.len() as u64);
-        progress.set_message("Getting key details...");
-
-        for key in &keys {
-            match client.key_info(key).await {
-                Ok(info) => key_infos.push(info),
-                Err(e) => {
-                    println!(
-                        "{}",
-                        format!("Error getting info for key '{}': {}", key, e).red()
-                    );
+    pub async fn page(
+        &mut self,
+        client: &mut RedisClient,
+        pattern: &str,
+        cursor: u64,
+        count: usize,
+    ) -> Result<(u64, Vec<String>), AppError> {
+        let Some(key_type) = self.key_type else {
+            return Ok(client.scan_page(cursor, pattern, count, None).await?);
+        };
+
+        if self.server_side {
+            match client
+                .scan_page(cursor, pattern, count, Some(key_type))
+                .await
+            {
+                Ok(page) => return Ok(page),
+                Err(e) if is_rejected(&e) => {
+                    info!("SCAN TYPE not supported ({}), filtering client-side", e);
+                    self.server_side = false;
                 }
+                Err(e) => return Err(e.into()),
             }
-            progress.inc(1);
         }
-        progress.finish_with_message("Key details retrieved");
 
-        // Display as table
-        let rows: Vec<KeyRow> = key_infos
+        let (next, keys) = client.scan_page(cursor, pattern, count, None).await?;
+        let types = client.type_and_memory(&keys).await?;
+        let keys = keys
             .into_iter()
-            .map(|info| KeyRow {
-                key: info.key,
-                key_type: info.key_type,
-                ttl: info
-                    .ttl
-                    .map(|t| {
-                        if t == -1 {
-                            "No expiry".to_string()
-                        } else if t == -2 {
-                            "Key doesn't exist".to_string()
-                        } else {
-                            format!("{}s", t)
-                        }
-                    })
-                    .unwrap_or_else(|| "Unknown".to_string()),
-                memory: info
-                    .memory_usage
-                    .map(|m| format!("{} bytes", m))
-                    .unwrap_or_else(|| "Unknown".to_string()),
-                encoding: info.encoding,
-            })
+            .zip(types)
+            .filter(|(_, (t, _))| t == key_type)
+            .map(|(key, _)| key)
             .collect();
+        Ok((next, keys))
+    }
 
-        let table = Table::new(rows).to_string();
-        println!("{}", table);
-    } else {
-        // Simple list
-        for key in keys {
-            println!("• {}", key.cyan());
+    /// Collect pages starting at `cursor` until at least `limit` keys have
+    /// been seen or the scan completes. Pages are never split, so the
+    /// returned cursor resumes exactly after the last key shown; a page may
+    /// take the total slightly over `limit`.
+    pub async fn collect(
+        &mut self,
+        client: &mut RedisClient,
+        pattern: &str,
+        cursor: u64,
+        limit: usize,
+    ) -> Result<(u64, Vec<String>), AppError> {
+        let mut keys = Vec::new();
+        let mut cursor = cursor;
+        loop {
+            let (next, page) = self
+                .page(client, pattern, cursor, limit.clamp(1, 1000))
+                .await?;
+            keys.extend(page);
+            cursor = next;
+            if cursor == 0 || keys.len() >= limit {
+                break;
+            }
         }
+        Ok((cursor, keys))
     }
+}
 
-    Ok(())
+/// Whether the server refused the command itself, as opposed to a
+/// connection failure.
+fn is_rejected(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<redis::RedisError>()
+        .is_some_and(|e| e.kind() == redis::ErrorKind::ResponseError)
 }
 
-pub async fn count_keys(
-    pattern: Option<String>,
-    environment: Option<String>,
-) -> Result<(), AppError> {
-    info!("Counting keys with pattern: {:?}", pattern);
-
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
+/// Count the keys matching `pattern`, broken down by type.
+pub async fn count(client: &mut RedisClient, pattern: &str) -> Result<KeyCount, AppError> {
+    info!("Counting keys with pattern: {}", pattern);
 
-    let redis_config = match config.get_environment(&env_name) {
-        Some(env) => env.config.clone(),
-        None => {
-            println!(
-                "{}",
-                format!("Error: Environment '{}' not found", env_name).red()
-            );
-            println!("{}", "Available environments:".yellow());
-            for env_name in config.environments.keys() {
-                println!("  • {}", env_name.cyan());
+    let keys = client.keys(pattern).await?;
+    let mut by_type = HashMap::new();
+    for key in &keys {
+        match client.key_info(key).await {
+            Ok(info) => {
+                *by_type.entry(info.key_type).or_insert(0) += 1;
             }
-            println!();
-            println!("{}", "To add a new environment, use:".cyan());
-            println!("  solt config --add-env <name>");
-            return Ok(());
-        }
-    };
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    let pattern = pattern.unwrap_or_else(|| "*".to_string());
-    let keys = client.keys(&pattern).await?;
-
-    println!(
-        "{}",
-        format!("Found {} keys matching pattern '{}'", keys.len(), pattern)
-            .green()
-            .bold()
-    );
-
-    // Group by type if we have keys
-    if !keys.is_empty() {
-        let mut type_counts = std::collections::HashMap::new();
-
-        for key in &keys {
-            match client.key_info(key).await {
-                Ok(info) => {
-                    *type_counts.entry(info.key_type).or_insert(0) += 1;
-                }
-                Err(_) => {
-                    *type_counts.entry("unknown".to_string()).or_insert(0) += 1;
-                }
+            Err(_) => {
+                *by_type.entry("unknown".to_string()).or_insert(0) += 1;
             }
         }
-
-        println!("\n{}", "Breakdown by type:".bold());
-        for (key_type, count) in type_counts {
-            println!("• {}: {}", key_type.cyan(), count.to_string().yellow());
-        }
     }
 
-    Ok(())
+    Ok(KeyCount {
+        total: keys.len(),
+        by_type,
+    })
 }
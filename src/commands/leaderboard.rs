@@ -0,0 +1,92 @@
+use log::info;
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+#[derive(Debug, Serialize)]
+pub struct Entry {
+    /// 1-based position, as players see it
+    pub rank: usize,
+    pub member: String,
+    pub score: f64,
+}
+
+/// One page of a leaderboard. `entries` is empty when the requested page
+/// is past the end.
+#[derive(Debug, Serialize)]
+pub struct Page {
+    pub key: String,
+    pub members: usize,
+    pub page: usize,
+    pub pages: usize,
+    pub entries: Vec<Entry>,
+}
+
+pub struct LeaderboardOptions {
+    /// Entries per page
+    pub top: usize,
+    /// 1-based page to show
+    pub page: usize,
+    /// Center the page on this member instead
+    pub around: Option<String>,
+    /// Lowest score first (e.g. fastest times)
+    pub ascending: bool,
+}
+
+/// Read a sorted set as a ranked leaderboard, one page at a time or
+/// centered on a single member.
+pub async fn run(
+    client: &mut RedisClient,
+    key: &str,
+    options: &LeaderboardOptions,
+) -> Result<Page, AppError> {
+    info!("Showing leaderboard {}", key);
+
+    let members = client.zcard(key).await?;
+    if members == 0 {
+        return Err(AppError::NotFound(format!(
+            "Sorted set '{}' is empty or does not exist",
+            key
+        )));
+    }
+
+    let top = options.top.max(1);
+    let start = match &options.around {
+        Some(member) => {
+            let rank = client
+                .zrank(key, member, !options.ascending)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Member '{}' not found in '{}'", member, key))
+                })?;
+            rank.saturating_sub(top / 2)
+                .min(members.saturating_sub(top))
+        }
+        None => (options.page.max(1) - 1) * top,
+    };
+    let stop = (start + top - 1) as isize;
+    let entries = if options.ascending {
+        client.zrange_withscores(key, start as isize, stop).await?
+    } else {
+        client
+            .zrevrange_withscores(key, start as isize, stop)
+            .await?
+    };
+
+    Ok(Page {
+        key: key.to_string(),
+        members,
+        page: start / top + 1,
+        pages: members.div_ceil(top),
+        entries: entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, (member, score))| Entry {
+                rank: start + i + 1,
+                member,
+                score,
+            })
+            .collect(),
+    })
+}
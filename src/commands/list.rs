@@ -0,0 +1,111 @@
+use log::info;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// How many elements a trim removed and kept.
+#[derive(Debug)]
+pub struct TrimReport {
+    pub removed: usize,
+    pub kept: usize,
+}
+
+/// Remove up to `count` elements (one without it) from one end of a list.
+/// Returns them in the order they were popped.
+pub async fn pop(
+    client: &mut RedisClient,
+    key: &str,
+    right: bool,
+    count: Option<u64>,
+) -> Result<Vec<String>, AppError> {
+    info!(
+        "Popping from {} (right: {}, count: {:?})",
+        key, right, count
+    );
+
+    let popped = client
+        .pop_list(key, right, count.map(|c| c as usize))
+        .await?;
+    if popped.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "List '{}' is empty or does not exist",
+            key
+        )));
+    }
+    Ok(popped)
+}
+
+/// Keep only elements `start..=stop` of a list. An empty range deletes it.
+pub async fn trim(
+    client: &mut RedisClient,
+    key: &str,
+    start: isize,
+    stop: isize,
+) -> Result<TrimReport, AppError> {
+    info!("Trimming {} to {}..{}", key, start, stop);
+
+    let before = client.list_length(key).await?;
+    if before == 0 {
+        return Err(AppError::NotFound(format!(
+            "List '{}' is empty or does not exist",
+            key
+        )));
+    }
+    client.trim_list(key, start, stop).await?;
+    let after = client.list_length(key).await?;
+
+    Ok(TrimReport {
+        removed: before - after,
+        kept: after,
+    })
+}
+
+/// Insert `value` before or after the first `pivot`. Returns the new length.
+pub async fn insert(
+    client: &mut RedisClient,
+    key: &str,
+    before: bool,
+    pivot: &str,
+    value: &str,
+) -> Result<usize, AppError> {
+    info!("Inserting into {} next to {}", key, pivot);
+
+    client
+        .insert_list(key, before, pivot, value)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("'{}' not found in list '{}'", pivot, key)))
+}
+
+/// Replace the element at `index`; negative indexes count from the end.
+pub async fn set(
+    client: &mut RedisClient,
+    key: &str,
+    index: isize,
+    value: &str,
+) -> Result<(), AppError> {
+    info!("Setting index {} of {}", index, key);
+
+    // Check up front so a missing key or bad index reads as "not found"
+    // rather than a raw server error
+    let len = client.list_length(key).await?;
+    if len == 0 {
+        return Err(AppError::NotFound(format!(
+            "List '{}' is empty or does not exist",
+            key
+        )));
+    }
+    let in_range = if index < 0 {
+        index.unsigned_abs() <= len
+    } else {
+        (index as usize) < len
+    };
+    if !in_range {
+        return Err(AppError::NotFound(format!(
+            "Index {} is out of range for '{}' (length {})",
+            index, key, len
+        )));
+    }
+
+    client.set_list_index(key, index, value).await?;
+    Ok(())
+}
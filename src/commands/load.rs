@@ -0,0 +1,155 @@
+use futures::{pin_mut, StreamExt};
+use log::info;
+use tokio_postgres::{NoTls, SimpleQueryMessage, SimpleQueryRow};
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+pub struct SqlLoadOptions {
+    pub dsn: String,
+    pub query: String,
+    pub key_template: String,
+    pub value_column: Option<String>,
+    pub ttl: Option<u64>,
+    pub batch_size: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct SqlLoadReport {
+    pub loaded: usize,
+    pub skipped: usize,
+    /// Why rows were skipped, where there is more to say than that all
+    /// their columns were NULL
+    pub warnings: Vec<String>,
+}
+
+/// Stream the rows of a PostgreSQL query into Redis, one key per row named
+/// after `key_template`. A row becomes a string holding `value_column`, or
+/// a hash of all its non-NULL columns. `progress` is called after each row
+/// with the number of keys loaded so far.
+pub async fn load_sql(
+    client: &mut RedisClient,
+    options: &SqlLoadOptions,
+    mut progress: impl FnMut(usize),
+) -> Result<SqlLoadReport, AppError> {
+    info!(
+        "Loading SQL query results with template: {}",
+        options.key_template
+    );
+
+    let (pg_client, pg_connection) = tokio_postgres::connect(&options.dsn, NoTls)
+        .await
+        .map_err(|e| AppError::ConnectionError(format!("PostgreSQL: {}", e)))?;
+    tokio::spawn(async move {
+        if let Err(e) = pg_connection.await {
+            log::error!("PostgreSQL connection error: {}", e);
+        }
+    });
+
+    // The simple query protocol returns every column as text, which is
+    // exactly what ends up in Redis
+    let stream = pg_client
+        .simple_query_raw(&options.query)
+        .await
+        .map_err(|e| anyhow::anyhow!("Query failed: {}", e))?;
+    pin_mut!(stream);
+
+    let batch_size = options.batch_size.max(1);
+    let mut pipe = redis::pipe();
+    let mut pending = 0;
+    let mut report = SqlLoadReport::default();
+
+    while let Some(message) = stream.next().await {
+        let message = message.map_err(|e| anyhow::anyhow!("Query failed: {}", e))?;
+        let row = match message {
+            SimpleQueryMessage::Row(row) => row,
+            _ => continue,
+        };
+
+        let key = match render_key_template(&options.key_template, &row) {
+            Ok(key) => key,
+            Err(e) => {
+                report.warnings.push(format!("Skipping row: {}", e));
+                report.skipped += 1;
+                continue;
+            }
+        };
+
+        match &options.value_column {
+            Some(column) => match row.try_get(column.as_str()) {
+                Ok(Some(value)) => {
+                    pipe.cmd("SET").arg(&key).arg(value).ignore();
+                }
+                _ => {
+                    report.warnings.push(format!(
+                        "Skipping '{}': column '{}' is missing or NULL",
+                        key, column
+                    ));
+                    report.skipped += 1;
+                    continue;
+                }
+            },
+            None => {
+                // Every non-NULL column becomes a hash field
+                pipe.cmd("DEL").arg(&key).ignore();
+                let mut hset = redis::cmd("HSET");
+                hset.arg(&key);
+                let mut fields = 0;
+                for (i, column) in row.columns().iter().enumerate() {
+                    if let Some(value) = row.get(i) {
+                        hset.arg(column.name()).arg(value);
+                        fields += 1;
+                    }
+                }
+                if fields == 0 {
+                    report.skipped += 1;
+                    continue;
+                }
+                pipe.add_command(hset).ignore();
+            }
+        }
+
+        if let Some(ttl) = options.ttl {
+            pipe.cmd("EXPIRE").arg(&key).arg(ttl).ignore();
+        }
+
+        pending += 1;
+        if pending >= batch_size {
+            pipe.query_async::<_, ()>(&mut client.connection).await?;
+            pipe = redis::pipe();
+            report.loaded += pending;
+            pending = 0;
+        }
+        progress(report.loaded);
+    }
+
+    if pending > 0 {
+        pipe.query_async::<_, ()>(&mut client.connection).await?;
+        report.loaded += pending;
+    }
+
+    Ok(report)
+}
+
+/// Substitute `{column}` placeholders in a key template with row values.
+fn render_key_template(template: &str, row: &SimpleQueryRow) -> Result<String, String> {
+    let mut key = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        key.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("unclosed placeholder in template '{}'", template))?;
+        let column = &rest[start + 1..start + end];
+        match row.try_get(column) {
+            Ok(Some(value)) => key.push_str(value),
+            Ok(None) => return Err(format!("column '{}' is NULL", column)),
+            Err(_) => return Err(format!("column '{}' not found in query result", column)),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    key.push_str(rest);
+
+    Ok(key)
+}
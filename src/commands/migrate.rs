@@ -0,0 +1,171 @@
+use log::info;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+const BATCH_SIZE: usize = 200;
+
+#[derive(Debug, Default)]
+pub struct TypeSummary {
+    pub migrated: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub verify_failed: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct MigrateReport {
+    pub scanned: usize,
+    pub by_type: BTreeMap<String, TypeSummary>,
+    /// Keys that could not be restored, with the error
+    pub errors: Vec<(String, String)>,
+    /// Keys whose copy does not match the source
+    pub unverified: Vec<String>,
+}
+
+impl MigrateReport {
+    pub fn totals(&self) -> TypeSummary {
+        let mut totals = TypeSummary::default();
+        for stats in self.by_type.values() {
+            totals.migrated += stats.migrated;
+            totals.skipped += stats.skipped;
+            totals.failed += stats.failed;
+            totals.verify_failed += stats.verify_failed;
+        }
+        totals
+    }
+}
+
+/// What a migration would copy.
+#[derive(Debug)]
+pub struct MigratePlan {
+    pub keys: Vec<String>,
+    /// How many of the keys the destination already has
+    pub existing: usize,
+}
+
+/// Find the keys a migration would copy and how many already exist on the
+/// destination, without changing anything.
+pub async fn plan(
+    source: &mut RedisClient,
+    dest: &mut RedisClient,
+    pattern: &str,
+) -> Result<MigratePlan, AppError> {
+    let keys = source.scan_keys(pattern).await?;
+
+    let mut existing = 0;
+    for batch in keys.chunks(BATCH_SIZE) {
+        existing += dest
+            .exists_many(batch)
+            .await?
+            .into_iter()
+            .filter(|e| *e)
+            .count();
+    }
+
+    Ok(MigratePlan { keys, existing })
+}
+
+/// Copy the keys matching `pattern` with DUMP/RESTORE and verify each copy.
+/// Keys the destination already has are skipped unless `replace`. Works one
+/// SCAN page at a time so the keyspace is never held in memory; `progress`
+/// is called after each page with the number of keys scanned so far.
+pub async fn run(
+    source: &mut RedisClient,
+    dest: &mut RedisClient,
+    pattern: &str,
+    replace: bool,
+    mut progress: impl FnMut(usize),
+) -> Result<MigrateReport, AppError> {
+    info!("Migrating '{}'", pattern);
+
+    let mut report = MigrateReport::default();
+    let mut cursor = 0;
+    loop {
+        let (next, keys) = source.scan_page(cursor, pattern, BATCH_SIZE, None).await?;
+        report.scanned += keys.len();
+        migrate_batch(source, dest, &keys, replace, &mut report).await?;
+        progress(report.scanned);
+
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Restore one page of keys on the destination and verify each copy.
+async fn migrate_batch(
+    source_client: &mut RedisClient,
+    dest_client: &mut RedisClient,
+    keys: &[String],
+    replace: bool,
+    report: &mut MigrateReport,
+) -> Result<(), AppError> {
+    if keys.is_empty() {
+        return Ok(());
+    }
+
+    // Restore each key, remembering which ones need verifying
+    let mut restored: HashMap<String, String> = HashMap::new();
+    for entry in source_client.dump_batch(keys).await? {
+        let Some(payload) = entry.payload else {
+            // Expired or deleted between SCAN and DUMP
+            continue;
+        };
+        let stats = report.by_type.entry(entry.key_type.clone()).or_default();
+
+        match dest_client
+            .restore(&entry.key, entry.pttl, &payload, replace)
+            .await
+        {
+            Ok(_) => {
+                restored.insert(entry.key, entry.key_type);
+            }
+            Err(e) if is_busy_key(&e) => stats.skipped += 1,
+            Err(e) => {
+                report.errors.push((entry.key, e.to_string()));
+                stats.failed += 1;
+            }
+        }
+    }
+
+    if restored.is_empty() {
+        return Ok(());
+    }
+
+    // Compare values rather than DUMP payloads, which differ between Redis
+    // versions and encodings even when the data is the same
+    let restored_keys: Vec<String> = restored.keys().cloned().collect();
+    let expected = source_client.fetch_values(&restored_keys).await?;
+    let actual = dest_client.fetch_values(&restored_keys).await?;
+    for (expected, actual) in expected.into_iter().zip(actual) {
+        let stats = report
+            .by_type
+            .entry(restored[&expected.key].clone())
+            .or_default();
+        // A key that expired on the source since the copy has nothing left
+        // to compare against
+        if expected.key_type == "none"
+            || (actual.key_type == expected.key_type && actual.digest() == expected.digest())
+        {
+            stats.migrated += 1;
+        } else {
+            stats.verify_failed += 1;
+            report.unverified.push(expected.key);
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether RESTORE failed because the target key already exists.
+pub fn is_busy_key(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<redis::RedisError>()
+        .and_then(|e| e.code())
+        == Some("BUSYKEY")
+}
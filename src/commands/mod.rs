@@ -1,15 +1,17 @@
-pub mod version;
-
 // Connection & Config commands
 pub mod config;
 pub mod connect;
+pub mod server_config;
 
 // Key Inspection commands
+pub mod exists;
 pub mod inspect;
 pub mod keys;
+pub mod wait_for;
 
 // Value Viewing commands
 pub mod get;
+pub mod leaderboard;
 pub mod set;
 
 // Search & Filter commands
@@ -17,29 +19,55 @@ pub mod filter;
 pub mod search;
 
 // Editing & Writing commands
+pub mod convert;
+pub mod counter;
 pub mod delete;
 pub mod edit;
+pub mod hash;
+pub mod list;
+pub mod rename;
+pub mod set_ops;
+pub mod string;
+pub mod ttl;
+pub mod txn;
+pub mod zset;
 
 // Bulk Operations commands
 pub mod bulk;
+pub mod clone_db;
 pub mod copy;
+pub mod diff;
+pub mod dry_run;
+pub mod migrate;
+pub mod swapdb;
+pub mod sync;
 
 // Monitoring & Debug commands
-pub mod debug;
+pub mod analyze;
+pub mod health;
+pub mod info;
 pub mod monitor;
+pub mod ping;
+pub mod queue;
+pub mod scripting;
 pub mod stats;
 
 // Backup & Export commands
 pub mod backup;
 pub mod export;
+pub mod import;
+pub mod load;
+pub mod restore;
+pub mod snapshot;
 
 // Pub/Sub commands
+pub mod notify;
 pub mod pubsub;
 
 // Cluster & Sentinel commands
 pub mod cluster;
 pub mod sentinel;
+pub mod topology;
 
 // UX Features commands
-pub mod favorites;
-pub mod history;
+pub mod glance;
@@ -1,154 +1,387 @@
-use colored::*;
+use futures::StreamExt;
 use log::info;
-use std::time::{SystemTime, UNIX_EPOCH};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
 
-use crate::config::AppConfig;
+use crate::cli::CaptureFormat;
 use crate::error::AppError;
-use crate::redis_client::RedisClient;
+use crate::format::glob_match;
+use crate::redis_client::{ClientInfo, RedisClient, SlowLogEntry};
 
-pub async fn run(environment: Option<String>) -> Result<(), AppError> {
-    info!("Starting Redis monitor");
+/// Entries fetched per poll while following the slow log
+const SLOWLOG_FOLLOW_BATCH: usize = 128;
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    println!("{}", "Starting Redis MONITOR...".yellow().bold());
-    println!("{}", "Press Ctrl+C to stop".cyan());
-    println!("{}", "=".repeat(80));
-
-    // Note: This is a simplified monitor. In a real implementation,
-    // you'd want to handle the stream properly with proper error handling
-    // and graceful shutdown on Ctrl+C
-
-    match client.monitor().await {
-        Ok(_) => {
-            println!("{}", "Monitor stopped".green());
-            Ok(())
-        }
-        Err(e) => {
-            println!("{}", format!("Monitor error: {}", e).red());
-            Err(AppError::ConnectionError(format!("Monitor failed: {}", e)))
-        }
+/// One command reported by MONITOR, such as
+/// `1700000000.123456 [0 10.0.0.5:51234] "SET" "user:1" "x"`.
+#[derive(Debug, Serialize)]
+pub struct MonitorEvent {
+    pub timestamp: f64,
+    pub db: u32,
+    pub client: String,
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+impl MonitorEvent {
+    pub fn parse(line: &str) -> Option<Self> {
+        let (timestamp, rest) = line.split_once(' ')?;
+        let (source, rest) = rest.strip_prefix('[')?.split_once("] ")?;
+        let (db, client) = source.split_once(' ')?;
+        let mut words = parse_quoted_words(rest).into_iter();
+        Some(MonitorEvent {
+            timestamp: timestamp.parse().ok()?,
+            db: db.parse().ok()?,
+            client: client.to_string(),
+            command: words.next()?.to_uppercase(),
+            args: words.collect(),
+        })
     }
 }
 
-pub async fn slowlog_get(
-    count: Option<usize>,
-    environment: Option<String>,
-) -> Result<(), AppError> {
-    info!("Getting slow log entries");
+/// Client-side filters for MONITOR traffic; an unset filter matches
+/// everything.
+pub struct MonitorFilter {
+    /// Glob matched against every argument, since key positions vary by command
+    pub key_pattern: Option<String>,
+    /// Upper-cased command names
+    pub commands: Vec<String>,
+    /// `ip:port`, or just `ip` to match every connection from a host
+    pub client_addr: Option<String>,
+}
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
+impl MonitorFilter {
+    pub fn matches(&self, event: &MonitorEvent) -> bool {
+        let command = self.commands.is_empty() || self.commands.contains(&event.command);
+        let key = self
+            .key_pattern
+            .as_ref()
+            .is_none_or(|pattern| event.args.iter().any(|arg| glob_match(pattern, arg)));
+        let client = self.client_addr.as_ref().is_none_or(|addr| {
+            event.client == *addr
+                || event
+                    .client
+                    .rsplit_once(':')
+                    .is_some_and(|(ip, _)| ip == addr)
+        });
+        command && key && client
+    }
+}
 
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
+/// Where and how `monitor --out` records traffic.
+pub struct CaptureOptions {
+    pub path: String,
+    pub format: CaptureFormat,
+    pub max_size: u64,
+    pub keep: usize,
+}
 
-    let mut client = RedisClient::connect(redis_config).await?;
+/// Appends captured commands to a file, rotating it to `FILE.1`, `FILE.2`,
+/// ... once it reaches the size limit.
+pub struct Capture {
+    options: CaptureOptions,
+    writer: BufWriter<File>,
+    written: u64,
+}
 
-    let count = count.unwrap_or(10);
-    let entries = client.slowlog_get(count).await?;
+impl Capture {
+    pub fn open(options: CaptureOptions) -> Result<Self, AppError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&options.path)?;
+        let written = file.metadata()?.len();
+        Ok(Capture {
+            options,
+            writer: BufWriter::new(file),
+            written,
+        })
+    }
 
-    if entries.is_empty() {
-        println!("{}", "No slow log entries found".yellow());
-        return Ok(());
+    pub fn write(&mut self, line: &str, event: &MonitorEvent) -> Result<(), AppError> {
+        let record = match self.options.format {
+            CaptureFormat::Text => line.to_string(),
+            CaptureFormat::Jsonl => serde_json::to_string(event)?,
+        };
+        if self.written > 0 && self.written + record.len() as u64 + 1 > self.options.max_size {
+            self.rotate()?;
+        }
+        writeln!(self.writer, "{}", record)?;
+        self.written += record.len() as u64 + 1;
+        Ok(())
     }
 
-    println!(
-        "{}",
-        format!("Slow Log Entries (showing {}):", entries.len()).bold()
-    );
-    println!("{}", "=".repeat(80));
-
-    for entry in entries {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64
-            - entry.timestamp;
-
-        let time_str = if timestamp < 60 {
-            format!("{}s ago", timestamp)
-        } else if timestamp < 3600 {
-            format!("{}m ago", timestamp / 60)
+    /// The file being written to.
+    pub fn path(&self) -> &str {
+        &self.options.path
+    }
+
+    pub fn flush(&mut self) -> Result<(), AppError> {
+        Ok(self.writer.flush()?)
+    }
+
+    fn rotate(&mut self) -> Result<(), AppError> {
+        self.writer.flush()?;
+        let path = &self.options.path;
+        if self.options.keep == 0 {
+            fs::remove_file(path)?;
         } else {
-            format!("{}h ago", timestamp / 3600)
-        };
+            // Shift FILE.(n-1) -> FILE.n, dropping the oldest
+            for n in (1..self.options.keep).rev() {
+                let from = format!("{}.{}", path, n);
+                if Path::new(&from).exists() {
+                    fs::rename(&from, format!("{}.{}", path, n + 1))?;
+                }
+            }
+            fs::rename(path, format!("{}.1", path))?;
+        }
+        self.writer = BufWriter::new(File::create(path)?);
+        self.written = 0;
+        Ok(())
+    }
+}
 
-        println!("{}", format!("ID: {}", entry.id).cyan());
-        println!("  Time: {}", time_str.yellow());
-        println!("  Duration: {}ms", entry.duration.to_string().red());
-        println!("  Command: {}", entry.command);
-        println!("{}", "-".repeat(40));
+/// How many commands a MONITOR session saw, and how many matched.
+#[derive(Debug, Default)]
+pub struct MonitorSummary {
+    pub seen: u64,
+    pub shown: u64,
+}
+
+/// Stream every command the server processes, filtered client-side, until
+/// Ctrl+C, optionally recording the matching commands to `capture`.
+/// `on_start` is called once MONITOR is running, then `on_event` for each
+/// matching command.
+pub async fn run(
+    client: RedisClient,
+    filter: &MonitorFilter,
+    mut capture: Option<Capture>,
+    on_start: impl FnOnce(),
+    mut on_event: impl FnMut(&MonitorEvent),
+) -> Result<MonitorSummary, AppError> {
+    info!("Starting Redis monitor");
+
+    let mut monitor = client.into_monitor().await?;
+    on_start();
+
+    let mut lines = monitor.on_message::<String>();
+    let mut summary = MonitorSummary::default();
+    loop {
+        tokio::select! {
+            line = lines.next() => {
+                let line = match line {
+                    Some(line) => line,
+                    None => break,
+                };
+                let event = match MonitorEvent::parse(&line) {
+                    Some(event) => event,
+                    None => continue,
+                };
+                summary.seen += 1;
+                if filter.matches(&event) {
+                    summary.shown += 1;
+                    on_event(&event);
+                    if let Some(capture) = &mut capture {
+                        capture.write(&line, &event)?;
+                    }
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
     }
 
-    Ok(())
+    if let Some(capture) = &mut capture {
+        capture.flush()?;
+    }
+    Ok(summary)
 }
 
-pub async fn client_list(environment: Option<String>) -> Result<(), AppError> {
-    info!("Getting client list");
+#[derive(Debug, Serialize)]
+pub struct TrafficReport {
+    pub seconds: f64,
+    pub commands: u64,
+    pub ops_per_sec: f64,
+    pub top_commands: Vec<(String, u64)>,
+    pub top_keys: Vec<(String, u64)>,
+    pub top_clients: Vec<(String, u64)>,
+}
+
+/// Sample MONITOR for `duration` (or until Ctrl+C), then report throughput
+/// and the busiest commands, keys and clients among the commands matching
+/// `filter`. The key of a command is taken to be its first argument.
+/// `progress` is called every hundred commands with the count so far and
+/// the seconds elapsed.
+pub async fn traffic_stats(
+    client: RedisClient,
+    filter: &MonitorFilter,
+    duration: Duration,
+    top: usize,
+    mut progress: impl FnMut(u64, f64),
+) -> Result<TrafficReport, AppError> {
+    info!("Sampling MONITOR traffic");
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
+    let mut monitor = client.into_monitor().await?;
+    let mut lines = monitor.on_message::<String>();
 
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
+    let mut commands: HashMap<String, u64> = HashMap::new();
+    let mut keys: HashMap<String, u64> = HashMap::new();
+    let mut clients: HashMap<String, u64> = HashMap::new();
+    let mut total = 0u64;
 
-    let mut client = RedisClient::connect(redis_config).await?;
+    let started = Instant::now();
+    let deadline = tokio::time::sleep(duration);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            line = lines.next() => {
+                let event = match line.as_deref().map(MonitorEvent::parse) {
+                    Some(Some(event)) => event,
+                    Some(None) => continue,
+                    None => break,
+                };
+                if !filter.matches(&event) {
+                    continue;
+                }
+                total += 1;
+                *commands.entry(event.command).or_default() += 1;
+                if let Some(key) = event.args.into_iter().next() {
+                    *keys.entry(key).or_default() += 1;
+                }
+                *clients.entry(event.client).or_default() += 1;
+                if total % 100 == 1 {
+                    progress(total, started.elapsed().as_secs_f64());
+                }
+            }
+            _ = &mut deadline => break,
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
 
-    let clients = client.client_list().await?;
+    let seconds = started.elapsed().as_secs_f64().max(0.001);
+    let ranked = |counts: HashMap<String, u64>| {
+        let mut counts: Vec<(String, u64)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts.truncate(top);
+        counts
+    };
+    Ok(TrafficReport {
+        seconds,
+        commands: total,
+        ops_per_sec: total as f64 / seconds,
+        top_commands: ranked(commands),
+        top_keys: ranked(keys),
+        top_clients: ranked(clients),
+    })
+}
 
-    if clients.is_empty() {
-        println!("{}", "No clients found".yellow());
-        return Ok(());
+/// Split MONITOR's argument list into words, undoing the escaping Redis
+/// applies inside double quotes (`\"`, `\\`, `\n`, `\xHH`, ...).
+fn parse_quoted_words(input: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let bytes = input.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'"' {
+            i += 1;
+            continue;
+        }
+        i += 1;
+        let mut word = Vec::new();
+        while i < bytes.len() && bytes[i] != b'"' {
+            if bytes[i] == b'\\' && i + 1 < bytes.len() {
+                i += 1;
+                match bytes[i] {
+                    b'n' => word.push(b'\n'),
+                    b'r' => word.push(b'\r'),
+                    b't' => word.push(b'\t'),
+                    b'a' => word.push(0x07),
+                    b'b' => word.push(0x08),
+                    b'x' if i + 2 < bytes.len() => {
+                        let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                        match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                            Some(byte) => {
+                                word.push(byte);
+                                i += 2;
+                            }
+                            None => word.push(b'x'),
+                        }
+                    }
+                    other => word.push(other),
+                }
+            } else {
+                word.push(bytes[i]);
+            }
+            i += 1;
+        }
+        words.push(String::from_utf8_lossy(&word).into_owned());
+        i += 1;
     }
+    words
+}
+
+/// The most recent `count` slow log entries, newest first.
+pub async fn slowlog_get(
+    client: &mut RedisClient,
+    count: usize,
+) -> Result<Vec<SlowLogEntry>, AppError> {
+    info!("Getting slow log entries");
+
+    Ok(client.slowlog_get(count).await?)
+}
 
-    println!(
-        "{}",
-        format!("Connected Clients ({}):", clients.len()).bold()
-    );
-    println!("{}", "=".repeat(80));
-
-    for client_info in clients {
-        println!("{}", format!("Client ID: {}", client_info.id).cyan().bold());
-        println!("  Address: {}", client_info.addr);
-        println!("  Database: {}", client_info.db);
-        println!("  Age: {}", client_info.age);
-        println!("  Idle: {}", client_info.idle);
-        println!("  Flags: {}", client_info.flags);
-        println!("  Command: {}", client_info.cmd);
-        println!("  Memory: {} bytes", client_info.omem);
-        println!("{}", "-".repeat(40));
+/// Poll the slow log every `interval` until Ctrl+C, calling `on_entry` for
+/// each entry logged after following started, oldest first.
+pub async fn slowlog_follow(
+    client: &mut RedisClient,
+    interval: Duration,
+    mut on_entry: impl FnMut(&SlowLogEntry),
+) -> Result<(), AppError> {
+    info!("Following slow log");
+
+    // Only report entries logged after we started following
+    let mut last_id = client
+        .slowlog_get(1)
+        .await?
+        .first()
+        .map(|entry| entry.id)
+        .unwrap_or(-1);
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+
+        let mut entries: Vec<SlowLogEntry> = client
+            .slowlog_get(SLOWLOG_FOLLOW_BATCH)
+            .await?
+            .into_iter()
+            .filter(|entry| entry.id > last_id)
+            .collect();
+        entries.sort_by_key(|entry| entry.id);
+
+        for entry in &entries {
+            on_entry(entry);
+        }
+        if let Some(entry) = entries.last() {
+            last_id = entry.id;
+        }
     }
 
     Ok(())
 }
+
+pub async fn slowlog_reset(client: &mut RedisClient) -> Result<(), AppError> {
+    info!("Resetting slow log");
+
+    Ok(client.slowlog_reset().await?)
+}
+
+pub async fn client_list(client: &mut RedisClient) -> Result<Vec<ClientInfo>, AppError> {
+    info!("Getting client list");
+
+    Ok(client.client_list().await?)
+}
@@ -0,0 +1,113 @@
+use log::info;
+use redis::aio::PubSub;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Event classes covered by the `A` alias in notify-keyspace-events
+const ALL_CLASSES: &str = "g$lshzxetd";
+
+pub struct NotifyOptions {
+    pub patterns: Vec<String>,
+    pub expired: bool,
+    pub evicted: bool,
+    pub set: bool,
+    pub enable: bool,
+}
+
+/// The channel patterns to subscribe to for `options` in database `db`,
+/// and the notify-keyspace-events classes they need.
+pub fn channels(options: &NotifyOptions, db: u8) -> (Vec<String>, String) {
+    let mut patterns = options.patterns.clone();
+    let mut required = String::from("E");
+    if options.expired {
+        patterns.push(format!("__keyevent@{}__:expired", db));
+        required.push('x');
+    }
+    if options.evicted {
+        patterns.push(format!("__keyevent@{}__:evicted", db));
+        required.push('e');
+    }
+    if options.set {
+        patterns.push(format!("__keyevent@{}__:set", db));
+        required.push('$');
+    }
+    if !options.patterns.is_empty() {
+        required = "KEA".to_string();
+    }
+    if patterns.is_empty() {
+        patterns.push(format!("__keyevent@{}__:*", db));
+        required = "EA".to_string();
+    }
+    (patterns, required)
+}
+
+/// The server's current notify-keyspace-events value.
+pub async fn current_flags(client: &mut RedisClient) -> Result<String, AppError> {
+    Ok(client
+        .config_get("notify-keyspace-events")
+        .await?
+        .into_iter()
+        .next()
+        .map(|(_, value)| value)
+        .unwrap_or_default())
+}
+
+/// Add the `required` classes to the current notify-keyspace-events value.
+/// Returns the new value.
+pub async fn enable(
+    client: &mut RedisClient,
+    current: &str,
+    required: &str,
+) -> Result<String, AppError> {
+    let merged = merge_flags(current, required);
+    client.config_set("notify-keyspace-events", &merged).await?;
+    Ok(merged)
+}
+
+/// Turn `client` into a pub/sub connection subscribed to `patterns`.
+pub async fn subscribe(client: RedisClient, patterns: &[String]) -> Result<PubSub, AppError> {
+    info!("Listening for keyspace notifications");
+
+    let mut pubsub = client.into_pubsub();
+    for pattern in patterns {
+        pubsub.psubscribe(pattern).await?;
+    }
+    Ok(pubsub)
+}
+
+/// Split a notification into its event and key. Keyevent channels carry the
+/// key as payload, keyspace channels the event.
+pub fn parse_event(channel: &str, payload: &str) -> (String, String) {
+    if let Some((_, event)) = channel
+        .strip_prefix("__keyevent@")
+        .and_then(|rest| rest.split_once("__:"))
+    {
+        (event.to_string(), payload.to_string())
+    } else if let Some((_, key)) = channel
+        .strip_prefix("__keyspace@")
+        .and_then(|rest| rest.split_once("__:"))
+    {
+        (payload.to_string(), key.to_string())
+    } else {
+        (channel.to_string(), payload.to_string())
+    }
+}
+
+/// Whether the current notify-keyspace-events value publishes every class in
+/// `required`.
+pub fn covers_events(current: &str, required: &str) -> bool {
+    required
+        .chars()
+        .all(|c| current.contains(c) || (current.contains('A') && ALL_CLASSES.contains(c)))
+}
+
+fn merge_flags(current: &str, required: &str) -> String {
+    let mut merged = current.to_string();
+    for c in required.chars() {
+        if !covers_events(&merged, &c.to_string()) {
+            merged.push(c);
+        }
+    }
+    merged
+}
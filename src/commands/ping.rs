@@ -0,0 +1,53 @@
+use log::info;
+use serde::Serialize;
+use std::time::Instant;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Round-trip summary, with times in microseconds.
+#[derive(Debug, Serialize)]
+pub struct PingSummary {
+    pub sent: usize,
+    pub received: usize,
+    pub lost: usize,
+    pub min: Option<u64>,
+    pub avg: Option<u64>,
+    pub p99: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl PingSummary {
+    /// Summarize `sent` PINGs, of which the ones in `times` (microseconds)
+    /// got a reply.
+    pub fn new(sent: usize, mut times: Vec<u64>) -> Self {
+        times.sort_unstable();
+        PingSummary {
+            sent,
+            received: times.len(),
+            lost: sent - times.len(),
+            min: times.first().copied(),
+            avg: (!times.is_empty()).then(|| times.iter().sum::<u64>() / times.len() as u64),
+            p99: percentile(&times, 99.0),
+            max: times.last().copied(),
+        }
+    }
+}
+
+/// Send one PING and return its round-trip time in microseconds.
+pub async fn ping(client: &mut RedisClient) -> Result<u64, AppError> {
+    info!("Pinging Redis");
+
+    let start = Instant::now();
+    client.ping().await?;
+    Ok(start.elapsed().as_micros() as u64)
+}
+
+/// Nearest-rank percentile of already sorted samples.
+fn percentile(sorted: &[u64], pct: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.clamp(1, sorted.len()) - 1).copied()
+}
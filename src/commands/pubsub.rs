@@ -1,10 +1,134 @@
-use colored::*;
+use futures::StreamExt;
 use log::info;
+use serde::Serialize;
+use std::time::{Duration, Instant};
 
 use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+#[derive(Debug, Serialize)]
+pub struct ChannelCount {
+    pub channel: String,
+    pub subscribers: u64,
+}
+
+/// Channels with at least one subscriber. Pattern subscriptions are not
+/// tied to a channel, so only their number is known.
+#[derive(Debug)]
+pub struct ActiveChannels {
+    pub channels: Vec<ChannelCount>,
+    pub patterns: u64,
+}
+
+#[derive(Debug)]
+pub struct PublishReport {
+    pub published: u64,
+    /// Sum of the subscribers that received each message
+    pub receivers: usize,
+    pub seconds: f64,
+}
+
+/// Publish `messages` to `channel`, `repeat` times over, pausing `interval`
+/// between messages. Ctrl+C stops early; the report covers what was sent.
+/// `progress` is called after each message.
+pub async fn publish(
+    client: &mut RedisClient,
+    channel: &str,
+    messages: &[Vec<u8>],
+    repeat: u64,
+    interval: Duration,
+    mut progress: impl FnMut(),
+) -> Result<PublishReport, AppError> {
+    info!("Publishing to channel: {}", channel);
+
+    let total = messages.len() as u64 * repeat;
+    let started = Instant::now();
+    let (mut published, mut receivers) = (0u64, 0usize);
+    'outer: for _ in 0..repeat {
+        for message in messages {
+            receivers += client.publish(channel, message).await?;
+            published += 1;
+            progress();
+            if published == total {
+                break 'outer;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = tokio::signal::ctrl_c() => break 'outer,
+            }
+        }
+    }
+
+    Ok(PublishReport {
+        published,
+        receivers,
+        seconds: started.elapsed().as_secs_f64().max(0.001),
+    })
+}
+
+/// Subscribe to `channels` and call `on_message` with the channel and
+/// payload of every message until Ctrl+C. `on_start` is called once the
+/// subscriptions are in place.
+pub async fn subscribe(
+    client: RedisClient,
+    channels: &[String],
+    on_start: impl FnOnce(),
+    mut on_message: impl FnMut(&str, &[u8]),
+) -> Result<(), AppError> {
+    info!("Subscribing to channels: {:?}", channels);
+
+    let mut pubsub = client.into_pubsub();
+    for channel in channels {
+        pubsub.subscribe(channel).await?;
+    }
+    on_start();
+
+    let mut messages = pubsub.on_message();
+    loop {
+        tokio::select! {
+            message = messages.next() => {
+                let message = match message {
+                    Some(message) => message,
+                    None => break,
+                };
+                let payload: Vec<u8> = message.get_payload().unwrap_or_default();
+                on_message(message.get_channel_name(), &payload);
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
 
-pub async fn run() -> Result<(), AppError> {
-    info!("PubSub command - placeholder");
-    println!("{}", "PubSub command - not yet implemented".yellow());
     Ok(())
 }
+
+/// Active channels matching `pattern`, sorted, with their subscriber counts.
+pub async fn channels(client: &mut RedisClient, pattern: &str) -> Result<ActiveChannels, AppError> {
+    info!("Listing pub/sub channels matching: {}", pattern);
+
+    let mut names = client.pubsub_channels(pattern).await?;
+    names.sort();
+    let channels = channel_counts(client.pubsub_numsub(&names).await?);
+    let patterns = client.pubsub_numpat().await?;
+
+    Ok(ActiveChannels { channels, patterns })
+}
+
+/// How many clients are subscribed to each of `channels`.
+pub async fn numsub(
+    client: &mut RedisClient,
+    channels: &[String],
+) -> Result<Vec<ChannelCount>, AppError> {
+    info!("Counting subscribers for: {:?}", channels);
+
+    Ok(channel_counts(client.pubsub_numsub(channels).await?))
+}
+
+fn channel_counts(counts: Vec<(String, u64)>) -> Vec<ChannelCount> {
+    counts
+        .into_iter()
+        .map(|(channel, subscribers)| ChannelCount {
+            channel,
+            subscribers,
+        })
+        .collect()
+}
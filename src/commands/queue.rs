@@ -0,0 +1,249 @@
+use log::info;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::error::AppError;
+use crate::redis_client::{RedisClient, StreamEntry};
+
+/// Payload fields job libraries use for the enqueue time (Sidekiq uses
+/// `enqueued_at` and `created_at`, in seconds; others use milliseconds)
+const TIMESTAMP_FIELDS: &[&str] = &["enqueued_at", "created_at", "timestamp"];
+
+/// Elements moved per pipeline when requeueing
+const REQUEUE_BATCH: usize = 500;
+
+pub struct QueueOptions {
+    /// Items to show from each end
+    pub show: usize,
+    /// How long to watch the length for the growth rate; zero skips it
+    pub sample: Duration,
+    /// Producers RPUSH, so the oldest item is at the head of the list
+    pub rpush: bool,
+}
+
+/// One queued item: the raw payload decoded as JSON where possible.
+#[derive(Debug, Serialize)]
+pub struct QueueItem {
+    /// Stream entry ID; None for list items
+    pub id: Option<String>,
+    pub payload: JsonValue,
+    /// Seconds since the item was enqueued, when it can be told
+    pub age_seconds: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QueueReport {
+    pub key: String,
+    pub kind: String,
+    pub length: usize,
+    pub oldest: Vec<QueueItem>,
+    pub newest: Vec<QueueItem>,
+    /// Change in length per second over the sampling window
+    pub growth_per_second: Option<f64>,
+}
+
+/// Treat a list or stream as a job queue: its length, the oldest and newest
+/// items with JSON payloads decoded, and how fast it grows or drains.
+/// `on_sample` is called before waiting out the sampling window.
+pub async fn run(
+    client: &mut RedisClient,
+    key: String,
+    options: &QueueOptions,
+    on_sample: impl FnOnce(),
+) -> Result<QueueReport, AppError> {
+    info!("Inspecting queue {}", key);
+
+    let kind = queue_kind(client, &key).await?;
+    let length = queue_length(client, &key, &kind).await?;
+
+    let (oldest, newest) = if kind == "stream" {
+        let oldest = client.get_stream(&key, options.show).await?;
+        let newest = client.get_stream_newest(&key, options.show).await?;
+        (
+            oldest.into_iter().map(stream_item).collect(),
+            newest.into_iter().map(stream_item).collect(),
+        )
+    } else {
+        let show = options.show as isize;
+        let mut head = client.get_list(&key, 0, show - 1).await?;
+        let mut tail = client.get_list(&key, -show, -1).await?;
+        tail.reverse();
+        if options.rpush {
+            std::mem::swap(&mut head, &mut tail);
+        }
+        // Without RPUSH the head holds the newest items and the tail the oldest
+        (
+            tail.into_iter().map(list_item).collect::<Vec<_>>(),
+            head.into_iter().map(list_item).collect::<Vec<_>>(),
+        )
+    };
+
+    let growth_per_second = if options.sample.is_zero() {
+        None
+    } else {
+        on_sample();
+        let started = Instant::now();
+        tokio::time::sleep(options.sample).await;
+        let later = queue_length(client, &key, &kind).await?;
+        Some((later as f64 - length as f64) / started.elapsed().as_secs_f64())
+    };
+
+    Ok(QueueReport {
+        key,
+        kind,
+        length,
+        oldest,
+        newest,
+        growth_per_second,
+    })
+}
+
+/// How many items requeue or drain will move from the list queue `key`:
+/// `count`, or the whole list when it is not given.
+pub async fn pending(
+    client: &mut RedisClient,
+    key: &str,
+    count: Option<usize>,
+) -> Result<usize, AppError> {
+    require_list(client, key).await?;
+    Ok(match count {
+        Some(count) => count,
+        None => client.list_length(key).await?,
+    })
+}
+
+/// Move up to `total` of the oldest items of a list onto the newest end of
+/// `dest`, e.g. to retry jobs from a dead-letter list. `progress` is called
+/// with the number moved by each batch.
+pub async fn requeue(
+    client: &mut RedisClient,
+    key: &str,
+    dest: &str,
+    total: usize,
+    rpush: bool,
+    mut progress: impl FnMut(usize),
+) -> Result<usize, AppError> {
+    info!("Requeueing {} onto {}", key, dest);
+
+    let mut moved = 0;
+    while moved < total {
+        let batch = (total - moved).min(REQUEUE_BATCH);
+        let done = client.move_list(key, dest, !rpush, !rpush, batch).await?;
+        moved += done;
+        progress(done);
+        if done < batch {
+            break;
+        }
+    }
+    Ok(moved)
+}
+
+/// Pop up to `total` of the oldest items of a list, handing each payload to
+/// `on_item` as soon as it is popped, so they can be saved before they are
+/// gone. Returns the number popped.
+pub async fn drain(
+    client: &mut RedisClient,
+    key: &str,
+    total: usize,
+    rpush: bool,
+    mut on_item: impl FnMut(&str),
+) -> Result<usize, AppError> {
+    info!("Draining {}", key);
+
+    let mut drained = 0;
+    while drained < total {
+        let batch = (total - drained).min(REQUEUE_BATCH);
+        let items = client.pop_list(key, !rpush, Some(batch)).await?;
+        for item in &items {
+            on_item(item);
+        }
+        drained += items.len();
+        if items.len() < batch {
+            break;
+        }
+    }
+    Ok(drained)
+}
+
+async fn queue_kind(client: &mut RedisClient, key: &str) -> Result<String, AppError> {
+    let key_type = client.key_type(key).await?;
+    match key_type.as_str() {
+        "list" | "stream" => Ok(key_type),
+        "none" => Err(AppError::NotFound(format!("Queue '{}' not found", key))),
+        other => Err(AppError::Anyhow(anyhow::anyhow!(
+            "Key '{}' is a {}, queues must be lists or streams",
+            key,
+            other
+        ))),
+    }
+}
+
+async fn require_list(client: &mut RedisClient, key: &str) -> Result<(), AppError> {
+    if queue_kind(client, key).await? != "list" {
+        return Err(AppError::Anyhow(anyhow::anyhow!(
+            "Key '{}' is a stream; only list queues can be requeued or drained",
+            key
+        )));
+    }
+    Ok(())
+}
+
+async fn queue_length(client: &mut RedisClient, key: &str, kind: &str) -> Result<usize, AppError> {
+    Ok(client.key_length(key, kind).await?.unwrap_or(0))
+}
+
+fn list_item(raw: String) -> QueueItem {
+    let payload = serde_json::from_str(&raw).unwrap_or(JsonValue::String(raw));
+    QueueItem {
+        id: None,
+        age_seconds: payload_age(&payload),
+        payload,
+    }
+}
+
+fn stream_item(entry: StreamEntry) -> QueueItem {
+    let payload = JsonValue::Object(
+        entry
+            .fields
+            .into_iter()
+            .map(|(field, value)| {
+                let value = serde_json::from_str(&value).unwrap_or(JsonValue::String(value));
+                (field, value)
+            })
+            .collect(),
+    );
+    // Auto-generated stream IDs start with the millisecond timestamp
+    let age_seconds = entry
+        .id
+        .split_once('-')
+        .and_then(|(ms, _)| ms.parse::<u64>().ok())
+        .and_then(|ms| now_seconds().checked_sub(ms / 1000))
+        .or_else(|| payload_age(&payload));
+    QueueItem {
+        id: Some(entry.id),
+        payload,
+        age_seconds,
+    }
+}
+
+/// Age of a job from a timestamp field in its payload, taken as
+/// milliseconds when it is too large to be seconds.
+fn payload_age(payload: &JsonValue) -> Option<u64> {
+    let timestamp = TIMESTAMP_FIELDS
+        .iter()
+        .find_map(|field| payload.get(field)?.as_f64())?;
+    let seconds = if timestamp > 1e11 {
+        timestamp / 1000.0
+    } else {
+        timestamp
+    };
+    now_seconds().checked_sub(seconds as u64)
+}
+
+fn now_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
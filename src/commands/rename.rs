@@ -0,0 +1,40 @@
+use log::info;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Rename `old` to `new`, overwriting an existing `new` only with `force`.
+/// In a cluster both names must hash to the same slot.
+pub async fn run(
+    client: &mut RedisClient,
+    old: &str,
+    new: &str,
+    force: bool,
+) -> Result<(), AppError> {
+    info!("Renaming key: {} -> {}", old, new);
+
+    if !client.exists(old).await? {
+        return Err(AppError::NotFound(format!("Key '{}' not found", old)));
+    }
+
+    if client.cluster_enabled().await? {
+        let old_slot = client.cluster_keyslot(old).await?;
+        let new_slot = client.cluster_keyslot(new).await?;
+        if old_slot != new_slot {
+            return Err(AppError::Aborted(format!(
+                "Cannot rename across cluster slots: '{}' is in slot {}, '{}' is in slot {}. \
+                 Use a hash tag (e.g. 'user:{{123}}:a' and 'user:{{123}}:b') to keep keys in the same slot",
+                old, old_slot, new, new_slot
+            )));
+        }
+    }
+
+    if !client.rename(old, new, force).await? {
+        return Err(AppError::Aborted(format!(
+            "Key '{}' already exists (use --force to overwrite it)",
+            new
+        )));
+    }
+
+    Ok(())
+}
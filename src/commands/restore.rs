@@ -0,0 +1,148 @@
+use log::info;
+use std::collections::HashSet;
+
+use super::export::{restore_commands, ExportedKey};
+use crate::cli::ConflictMode;
+use crate::error::AppError;
+use crate::format::glob_match;
+use crate::redis_client::RedisClient;
+
+const BATCH_SIZE: usize = 200;
+
+/// The keys of an export selected for restoring, and which of them already
+/// exist in the target database.
+pub struct RestorePlan {
+    pub keys: Vec<ExportedKey>,
+    pub existing: Vec<bool>,
+}
+
+impl RestorePlan {
+    /// Names of the keys that already exist.
+    pub fn conflicts(&self) -> Vec<&str> {
+        self.keys
+            .iter()
+            .zip(&self.existing)
+            .filter(|(_, exists)| **exists)
+            .map(|(key, _)| key.key.as_str())
+            .collect()
+    }
+
+    /// Refuse to restore when keys already exist and `on_conflict` is
+    /// `Abort`.
+    pub fn check(&self, on_conflict: ConflictMode) -> Result<(), AppError> {
+        let conflicts = self.existing.iter().filter(|e| **e).count();
+        if conflicts > 0 && matches!(on_conflict, ConflictMode::Abort) {
+            return Err(AppError::Aborted(format!(
+                "{} keys already exist, nothing was restored (use --on-conflict skip or replace)",
+                conflicts
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RestoreReport {
+    pub restored: usize,
+    pub skipped: usize,
+    /// Keys whose value is unsupported or malformed, with their type
+    pub failed: Vec<(String, String)>,
+}
+
+/// Select the exported keys matching `pattern`, rename them with `prefix`
+/// and check which already exist.
+pub async fn plan(
+    client: &mut RedisClient,
+    keys: Vec<ExportedKey>,
+    pattern: Option<&str>,
+    prefix: Option<&str>,
+) -> Result<RestorePlan, AppError> {
+    // Filter on the original names, then rewrite them
+    let keys: Vec<ExportedKey> = keys
+        .into_iter()
+        .filter(|k| pattern.is_none_or(|p| glob_match(p, &k.key)))
+        .map(|mut k| {
+            if let Some(prefix) = prefix {
+                k.key = rewrite_prefix(&k.key, prefix);
+            }
+            k
+        })
+        .collect();
+
+    // A key moved by a rehash during the export's SCAN can appear twice;
+    // keep its last copy so it is only written once
+    let mut seen = HashSet::new();
+    let mut keys: Vec<ExportedKey> = keys
+        .into_iter()
+        .rev()
+        .filter(|k| seen.insert(k.key.clone()))
+        .collect();
+    keys.reverse();
+
+    let names: Vec<String> = keys.iter().map(|k| k.key.clone()).collect();
+    let mut existing = Vec::with_capacity(names.len());
+    for batch in names.chunks(BATCH_SIZE) {
+        existing.extend(client.exists_many(batch).await?);
+    }
+
+    Ok(RestorePlan { keys, existing })
+}
+
+/// Write the planned keys. Conflicts are checked before writing anything,
+/// so `ConflictMode::Abort` leaves the database untouched. `progress` is
+/// called once per key.
+pub async fn apply(
+    client: &mut RedisClient,
+    plan: &RestorePlan,
+    on_conflict: ConflictMode,
+    mut progress: impl FnMut(),
+) -> Result<RestoreReport, AppError> {
+    info!("Restoring {} keys", plan.keys.len());
+
+    plan.check(on_conflict)?;
+
+    let mut report = RestoreReport::default();
+    let mut pipe = redis::pipe();
+    let mut pending = 0;
+    for (key, exists) in plan.keys.iter().zip(&plan.existing) {
+        progress();
+        if *exists && matches!(on_conflict, ConflictMode::Skip) {
+            report.skipped += 1;
+            continue;
+        }
+
+        let Some(commands) = restore_commands(key) else {
+            report.failed.push((key.key.clone(), key.key_type.clone()));
+            continue;
+        };
+        for cmd in commands {
+            pipe.add_command(cmd).ignore();
+        }
+
+        pending += 1;
+        if pending >= BATCH_SIZE {
+            pipe.query_async::<_, ()>(&mut client.connection).await?;
+            pipe = redis::pipe();
+            report.restored += pending;
+            pending = 0;
+        }
+    }
+    if pending > 0 {
+        pipe.query_async::<_, ()>(&mut client.connection).await?;
+        report.restored += pending;
+    }
+
+    Ok(report)
+}
+
+/// Apply a `--prefix` rewrite. `old=new` replaces a leading `old` with
+/// `new`; a bare value is prepended to every key.
+fn rewrite_prefix(key: &str, prefix: &str) -> String {
+    match prefix.split_once('=') {
+        Some((old, new)) => match key.strip_prefix(old) {
+            Some(rest) => format!("{}{}", new, rest),
+            None => key.to_string(),
+        },
+        None => format!("{}{}", prefix, key),
+    }
+}
@@ -0,0 +1,80 @@
+use log::info;
+use redis::Value;
+use std::fs;
+
+use crate::error::AppError;
+use crate::redis_client::{FunctionLibrary, RedisClient};
+
+/// Run a Lua script, read from the file `script` or already cached under
+/// `sha`, and return its reply.
+pub async fn eval(
+    client: &mut RedisClient,
+    script: Option<&str>,
+    sha: Option<&str>,
+    keys: &[String],
+    args: &[String],
+) -> Result<Value, AppError> {
+    info!("Evaluating script with keys: {:?}", keys);
+
+    match (script, sha) {
+        (Some(path), _) => {
+            let source = fs::read_to_string(path)?;
+            Ok(client.eval(&source, keys, args).await?)
+        }
+        (None, Some(sha)) => Ok(client.evalsha(sha, keys, args).await?),
+        (None, None) => Err(AppError::ConfigError(
+            "Either --script or --sha is required".to_string(),
+        )),
+    }
+}
+
+/// Cache the script in the file at `path` and return its SHA1.
+pub async fn script_load(client: &mut RedisClient, path: &str) -> Result<String, AppError> {
+    info!("Loading script: {}", path);
+
+    let source = fs::read_to_string(path)?;
+    Ok(client.script_load(&source).await?)
+}
+
+/// Whether each of `shas` is in the script cache, in the same order.
+pub async fn script_exists(
+    client: &mut RedisClient,
+    shas: &[String],
+) -> Result<Vec<bool>, AppError> {
+    info!("Checking scripts: {:?}", shas);
+
+    Ok(client.script_exists(shas).await?)
+}
+
+pub async fn script_flush(client: &mut RedisClient) -> Result<(), AppError> {
+    info!("Flushing script cache");
+
+    Ok(client.script_flush().await?)
+}
+
+pub async fn function_list(
+    client: &mut RedisClient,
+    pattern: Option<&str>,
+) -> Result<Vec<FunctionLibrary>, AppError> {
+    info!("Listing functions");
+
+    Ok(client.function_list(pattern).await?)
+}
+
+/// Load the function library in the file at `path` and return its name.
+pub async fn function_load(
+    client: &mut RedisClient,
+    path: &str,
+    replace: bool,
+) -> Result<String, AppError> {
+    info!("Loading function library: {}", path);
+
+    let code = fs::read_to_string(path)?;
+    Ok(client.function_load(&code, replace).await?)
+}
+
+pub async fn function_delete(client: &mut RedisClient, library: &str) -> Result<(), AppError> {
+    info!("Deleting function library: {}", library);
+
+    Ok(client.function_delete(library).await?)
+}
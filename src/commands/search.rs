@@ -1,10 +1,129 @@
-use colored::*;
 use log::info;
+use serde::Serialize;
 
 use crate::error::AppError;
+use crate::format::glob_match;
+use crate::redis_client::RedisClient;
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Search command - placeholder");
-    println!("{}", "Search command - not yet implemented".yellow());
-    Ok(())
+/// Keys fetched per SCAN step, and fields per HSCAN step
+const BATCH_SIZE: usize = 500;
+
+/// A hash field whose name or value matched the search pattern.
+#[derive(Debug, Serialize)]
+pub struct HashHit {
+    pub key: String,
+    pub field: String,
+    pub value: String,
+}
+
+/// The result of searching inside hashes.
+#[derive(Debug)]
+pub struct HashSearch {
+    pub hashes_searched: u64,
+    /// Sorted by key, then field
+    pub hits: Vec<HashHit>,
+}
+
+/// Treat a pattern without glob characters as a substring search.
+fn search_pattern(pattern: &str) -> String {
+    if pattern.contains(['*', '?', '[']) {
+        pattern.to_string()
+    } else {
+        format!("*{}*", pattern)
+    }
+}
+
+/// The sorted key names matching `pattern`, or containing it when it has no
+/// glob characters. `progress` is called after each SCAN step with the
+/// number of matches so far.
+pub async fn run(
+    client: &mut RedisClient,
+    pattern: &str,
+    mut progress: impl FnMut(usize),
+) -> Result<Vec<String>, AppError> {
+    info!("Searching keys for {}", pattern);
+
+    let glob = search_pattern(pattern);
+    let mut keys = Vec::new();
+    let mut cursor = 0;
+    loop {
+        let (next, page) = client.scan_page(cursor, &glob, BATCH_SIZE, None).await?;
+        keys.extend(page);
+        progress(keys.len());
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    // SCAN may return a key more than once
+    keys.sort();
+    keys.dedup();
+
+    Ok(keys)
+}
+
+/// Search the field names and values of every hash matching `key_pattern`.
+/// Hashes are read with HSCAN so large ones are never fetched in a single
+/// reply. `progress` is called after each hash with the number of hashes
+/// searched and hits so far.
+pub async fn in_hashes(
+    client: &mut RedisClient,
+    pattern: &str,
+    key_pattern: &str,
+    mut progress: impl FnMut(u64, usize),
+) -> Result<HashSearch, AppError> {
+    info!("Searching hashes matching {} for {}", key_pattern, pattern);
+
+    let glob = search_pattern(pattern);
+    let mut hits = Vec::new();
+    let mut hashes_searched = 0u64;
+    let mut cursor = 0;
+    loop {
+        let (next, keys) = client
+            .scan_page(cursor, key_pattern, BATCH_SIZE, None)
+            .await?;
+        let types = client.type_and_memory(&keys).await?;
+        let hashes = keys
+            .into_iter()
+            .zip(types)
+            .filter(|(_, (key_type, _))| key_type == "hash")
+            .map(|(key, _)| key);
+
+        for key in hashes {
+            hashes_searched += 1;
+            let mut field_cursor = 0;
+            loop {
+                let (next_field, pairs) = client.hscan_page(&key, field_cursor, BATCH_SIZE).await?;
+                for (field, value) in pairs {
+                    if glob_match(&glob, &field) || glob_match(&glob, &value) {
+                        hits.push(HashHit {
+                            key: key.clone(),
+                            field,
+                            value,
+                        });
+                    }
+                }
+                field_cursor = next_field;
+                if field_cursor == 0 {
+                    break;
+                }
+            }
+            progress(hashes_searched, hits.len());
+        }
+
+        cursor = next;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    // SCAN and HSCAN may both return an entry more than once
+    hits.sort_by(|a, b| (&a.key, &a.field).cmp(&(&b.key, &b.field)));
+    hits.dedup_by(|a, b| a.key == b.key && a.field == b.field);
+
+    Ok(HashSearch {
+        hashes_searched,
+        hits,
+    })
 }
@@ -1,10 +1,72 @@
-use colored::*;
 use log::info;
+use redis::aio::PubSub;
+use std::time::{Duration, Instant};
 
 use crate::error::AppError;
+use crate::redis_client::RedisClient;
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Sentinel command - placeholder");
-    println!("{}", "Sentinel command - not yet implemented".yellow());
+/// How often the master address is polled while waiting for a failover
+const FAILOVER_POLL: Duration = Duration::from_secs(1);
+
+/// The address the sentinel currently announces for `master`.
+pub async fn master_addr(
+    client: &mut RedisClient,
+    master: &str,
+) -> Result<(String, u16), AppError> {
+    client
+        .sentinel_master_addr(master)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Sentinel does not monitor '{}'", master)))
+}
+
+/// Trigger SENTINEL FAILOVER for `master`.
+pub async fn failover(client: &mut RedisClient, master: &str) -> Result<(), AppError> {
+    info!("Triggering failover of {}", master);
+
+    client.sentinel_failover(master).await?;
     Ok(())
 }
+
+/// Poll until the sentinel announces a master address other than `before`,
+/// failing once `timeout` passes. Returns the new address and how long it
+/// took.
+pub async fn wait_for_new_master(
+    client: &mut RedisClient,
+    master: &str,
+    before: &(String, u16),
+    timeout: Duration,
+) -> Result<((String, u16), Duration), AppError> {
+    let started = Instant::now();
+    while started.elapsed() < timeout {
+        tokio::time::sleep(FAILOVER_POLL).await;
+        if let Some(after) = client.sentinel_master_addr(master).await? {
+            if &after != before {
+                return Ok((after, started.elapsed()));
+            }
+        }
+    }
+
+    Err(AppError::Aborted(format!(
+        "'{}' still reports {}:{} as master after {}s",
+        master,
+        before.0,
+        before.1,
+        timeout.as_secs()
+    )))
+}
+
+/// Subscribe to the events the sentinel publishes (one channel per event
+/// type), or only to those in `filter`.
+pub async fn subscribe(client: RedisClient, filter: &[String]) -> Result<PubSub, AppError> {
+    info!("Listening for sentinel events");
+
+    let mut pubsub = client.into_pubsub();
+    if filter.is_empty() {
+        pubsub.psubscribe("*").await?;
+    } else {
+        for event in filter {
+            pubsub.subscribe(event).await?;
+        }
+    }
+    Ok(pubsub)
+}
@@ -0,0 +1,42 @@
+use log::info;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Server parameters matching `pattern`, as CONFIG GET returns them.
+pub async fn get(
+    client: &mut RedisClient,
+    pattern: &str,
+) -> Result<Vec<(String, String)>, AppError> {
+    info!("Getting server config: {}", pattern);
+
+    Ok(client.config_get(pattern).await?)
+}
+
+/// Change a server parameter with CONFIG SET. Returns the previous value,
+/// if the server reported one.
+pub async fn set(
+    client: &mut RedisClient,
+    param: &str,
+    value: &str,
+) -> Result<Option<String>, AppError> {
+    info!("Setting server config: {} = {}", param, value);
+
+    let previous = client
+        .config_get(param)
+        .await?
+        .into_iter()
+        .find(|(name, _)| name == param)
+        .map(|(_, value)| value);
+
+    client.config_set(param, value).await?;
+    Ok(previous)
+}
+
+/// Persist the running configuration to redis.conf with CONFIG REWRITE.
+pub async fn rewrite(client: &mut RedisClient) -> Result<(), AppError> {
+    info!("Rewriting server config");
+
+    client.config_rewrite().await?;
+    Ok(())
+}
@@ -1,219 +1,268 @@
-use colored::*;
 use log::info;
+use std::io::BufRead;
 use std::time::Duration;
 
-use crate::config::AppConfig;
+use crate::cli::BulkFormat;
 use crate::error::AppError;
-use crate::redis_client::RedisClient;
+use crate::format::key_bytes;
+use crate::redis_client::{RedisClient, SetOptions, SetOutcome};
 
+/// Write a string value. When the NX/XX condition is not met nothing is
+/// written and the outcome says so.
 pub async fn run(
-    key: String,
-    value: String,
-    environment: Option<String>,
-    ttl: Option<u64>,
-) -> Result<(), AppError> {
+    client: &mut RedisClient,
+    key: &str,
+    value: &[u8],
+    options: &SetOptions,
+) -> Result<SetOutcome, AppError> {
     info!("Setting string value for key: {}", key);
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    let ttl_duration = ttl.map(Duration::from_secs);
-    client.set_string(&key, &value, ttl_duration).await?;
+    Ok(client.set_with_options(key, value, options).await?)
+}
 
-    println!(
-        "{}",
-        format!("✓ Successfully set key '{}'", key).green().bold()
-    );
-    if let Some(ttl) = ttl {
-        println!("TTL: {} seconds", ttl.to_string().cyan());
+/// Split `key=value` arguments for `multi`.
+pub fn parse_pairs(pairs: &[String]) -> Result<Vec<(String, String)>, AppError> {
+    let mut parsed = Vec::with_capacity(pairs.len());
+    for pair in pairs {
+        match pair.split_once('=') {
+            Some((key, value)) if !key.is_empty() => {
+                parsed.push((key.to_string(), value.to_string()))
+            }
+            _ => {
+                return Err(AppError::ConfigError(format!(
+                    "Invalid pair '{}', expected format key=value",
+                    pair
+                )))
+            }
+        }
     }
+    Ok(parsed)
+}
+
+/// Write several keys at once with MSET, all with the same optional TTL.
+pub async fn multi(
+    client: &mut RedisClient,
+    pairs: &[(String, String)],
+    ttl: Option<u64>,
+) -> Result<(), AppError> {
+    info!("Setting {} keys with MSET", pairs.len());
 
-    Ok(())
+    Ok(client.mset(pairs, ttl.map(Duration::from_secs)).await?)
 }
 
 pub async fn set_hash_field(
-    key: String,
-    field: String,
-    value: String,
-    environment: Option<String>,
+    client: &mut RedisClient,
+    key: &str,
+    field: &str,
+    value: &str,
 ) -> Result<(), AppError> {
     info!("Setting hash field: {}:{} = {}", key, field, value);
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    client.set_hash_field(&key, &field, &value).await?;
-
-    println!(
-        "{}",
-        format!("✓ Successfully set hash field '{}:{}'", key, field)
-            .green()
-            .bold()
-    );
-
-    Ok(())
+    Ok(client.set_hash_field(key, field, value).await?)
 }
 
+/// Push onto either end of a list and return its new length.
 pub async fn push_list(
-    key: String,
-    value: String,
-    environment: Option<String>,
+    client: &mut RedisClient,
+    key: &str,
+    value: &[u8],
     left: bool,
-) -> Result<(), AppError> {
+) -> Result<usize, AppError> {
     info!("Pushing to list: {} (left: {})", key, left);
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    let new_length = client.push_list(&key, &value, left).await?;
-
-    let direction = if left { "left" } else { "right" };
-    println!(
-        "{}",
-        format!(
-            "✓ Successfully pushed to {} of list '{}' (new length: {})",
-            direction, key, new_length
-        )
-        .green()
-        .bold()
-    );
-
-    Ok(())
+    Ok(client.push_list(key, value, left).await?)
 }
 
+/// Add a set member; false when it was already there.
 pub async fn add_to_set(
-    key: String,
-    member: String,
-    environment: Option<String>,
-) -> Result<(), AppError> {
+    client: &mut RedisClient,
+    key: &str,
+    member: &str,
+) -> Result<bool, AppError> {
     info!("Adding member to set: {} = {}", key, member);
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    let was_new = client.add_to_set(&key, &member).await?;
-
-    if was_new {
-        println!(
-            "{}",
-            format!(
-                "✓ Successfully added new member '{}' to set '{}'",
-                member, key
-            )
-            .green()
-            .bold()
-        );
-    } else {
-        println!(
-            "{}",
-            format!("Member '{}' already exists in set '{}'", member, key).yellow()
-        );
-    }
-
-    Ok(())
+    Ok(client.add_to_set(key, member).await?)
 }
 
+/// Add a sorted set member or update its score; false when it was already
+/// there.
 pub async fn add_to_sorted_set(
-    key: String,
-    member: String,
+    client: &mut RedisClient,
+    key: &str,
+    member: &str,
     score: f64,
-    environment: Option<String>,
-) -> Result<(), AppError> {
+) -> Result<bool, AppError> {
     info!(
         "Adding member to sorted set: {} = {} (score: {})",
         key, member, score
     );
 
-    let config = AppConfig::load()?;
-    let env_name = environment.unwrap_or_else(|| {
-        config
-            .default_environment
-            .clone()
-            .unwrap_or_else(|| "dev".to_string())
-    });
-
-    let redis_config = config
-        .get_environment(&env_name)
-        .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", env_name)))?
-        .config
-        .clone();
-
-    let mut client = RedisClient::connect(redis_config).await?;
-
-    let was_new = client.add_to_sorted_set(&key, &member, score).await?;
-
-    if was_new {
-        println!(
-            "{}",
-            format!(
-                "✓ Successfully added new member '{}' to sorted set '{}' with score {}",
-                member, key, score
-            )
-            .green()
-            .bold()
-        );
-    } else {
-        println!(
-            "{}",
-            format!(
-                "Updated member '{}' in sorted set '{}' with score {}",
-                member, key, score
-            )
-            .yellow()
-        );
+    Ok(client.add_to_sorted_set(key, member, score).await?)
+}
+
+/// Keys written per pipeline by `bulk`
+const BULK_BATCH_SIZE: usize = 1000;
+
+/// One key read by `bulk`.
+struct BulkRecord {
+    key: String,
+    value: Vec<u8>,
+    ttl: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct BulkReport {
+    pub written: usize,
+    /// Line number and reason for each record that could not be parsed
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// Seed keys from JSON lines or CSV read from `input`. Records that cannot
+/// be parsed are skipped and listed in the report; `default_ttl` applies to
+/// records without their own ttl. `progress` is called with the running
+/// total after each pipeline.
+pub async fn bulk<'a>(
+    client: &mut RedisClient,
+    input: impl BufRead + 'a,
+    format: BulkFormat,
+    default_ttl: Option<u64>,
+    mut progress: impl FnMut(usize),
+) -> Result<BulkReport, AppError> {
+    info!("Bulk loading keys");
+
+    let records: Box<dyn Iterator<Item = (usize, Result<BulkRecord, String>)> + 'a> = match format {
+        BulkFormat::Jsonl => Box::new(
+            input
+                .lines()
+                .enumerate()
+                .map(|(i, line)| (i + 1, line.map_err(|e| e.to_string())))
+                .filter(|(_, line)| line.as_ref().map_or(true, |l| !l.trim().is_empty()))
+                .map(|(n, line)| (n, line.and_then(|l| parse_jsonl(&l)))),
+        ),
+        BulkFormat::Csv => {
+            let mut reader = csv::Reader::from_reader(input);
+            let headers = reader
+                .headers()
+                .map_err(|e| AppError::ConfigError(format!("Invalid CSV header: {}", e)))?
+                .clone();
+            let column = |name: &str| headers.iter().position(|h| h.trim() == name);
+            let (key_col, value_col) = match (column("key"), column("value")) {
+                (Some(key), Some(value)) => (key, value),
+                _ => {
+                    return Err(AppError::ConfigError(
+                        "CSV header must have 'key' and 'value' columns".to_string(),
+                    ))
+                }
+            };
+            let ttl_col = column("ttl");
+            // Line 1 is the header
+            Box::new(reader.into_records().enumerate().map(move |(i, record)| {
+                let record = record
+                    .map_err(|e| e.to_string())
+                    .and_then(|r| parse_csv(&r, key_col, value_col, ttl_col));
+                (i + 2, record)
+            }))
+        }
+    };
+
+    let mut report = BulkReport::default();
+    let mut batch = Vec::with_capacity(BULK_BATCH_SIZE);
+
+    for (line, record) in records {
+        match record {
+            Ok(record) => batch.push(record),
+            Err(e) => report.skipped.push((line, e)),
+        }
+
+        if batch.len() >= BULK_BATCH_SIZE {
+            report.written += write_batch(client, &batch, default_ttl).await?;
+            batch.clear();
+            progress(report.written);
+        }
+    }
+    if !batch.is_empty() {
+        report.written += write_batch(client, &batch, default_ttl).await?;
+    }
+
+    Ok(report)
+}
+
+async fn write_batch(
+    client: &mut RedisClient,
+    batch: &[BulkRecord],
+    default_ttl: Option<u64>,
+) -> Result<usize, AppError> {
+    let mut pipe = redis::pipe();
+    for record in batch {
+        let cmd = pipe
+            .cmd("SET")
+            .arg(key_bytes(&record.key))
+            .arg(&record.value);
+        if let Some(ttl) = record.ttl.or(default_ttl) {
+            cmd.arg("EX").arg(ttl);
+        }
+        cmd.ignore();
     }
+    pipe.query_async::<_, ()>(&mut client.connection).await?;
+    Ok(batch.len())
+}
 
-    Ok(())
+/// Parse a `{"key": ..., "value": ..., "ttl": ...}` line. Values that are
+/// not strings are stored as their JSON text.
+fn parse_jsonl(line: &str) -> Result<BulkRecord, String> {
+    let mut object = match serde_json::from_str::<serde_json::Value>(line) {
+        Ok(serde_json::Value::Object(object)) => object,
+        Ok(_) => return Err("expected a JSON object".to_string()),
+        Err(e) => return Err(format!("invalid JSON: {}", e)),
+    };
+
+    let key = match object.remove("key") {
+        Some(serde_json::Value::String(key)) if !key.is_empty() => key,
+        _ => return Err("missing or empty \"key\"".to_string()),
+    };
+    let value = match object.remove("value") {
+        Some(serde_json::Value::String(value)) => value.into_bytes(),
+        Some(serde_json::Value::Null) | None => return Err("missing \"value\"".to_string()),
+        Some(other) => other.to_string().into_bytes(),
+    };
+    let ttl = match object.remove("ttl") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(ttl) => Some(
+            ttl.as_u64()
+                .filter(|t| *t > 0)
+                .ok_or_else(|| format!("invalid ttl: {}", ttl))?,
+        ),
+    };
+
+    Ok(BulkRecord { key, value, ttl })
+}
+
+fn parse_csv(
+    record: &csv::StringRecord,
+    key_col: usize,
+    value_col: usize,
+    ttl_col: Option<usize>,
+) -> Result<BulkRecord, String> {
+    let key = record.get(key_col).unwrap_or_default();
+    if key.is_empty() {
+        return Err("empty key".to_string());
+    }
+    let value = record
+        .get(value_col)
+        .ok_or_else(|| "missing value".to_string())?;
+    let ttl = match ttl_col.and_then(|col| record.get(col)).map(str::trim) {
+        None | Some("") => None,
+        Some(ttl) => Some(
+            ttl.parse::<u64>()
+                .ok()
+                .filter(|t| *t > 0)
+                .ok_or_else(|| format!("invalid ttl: {}", ttl))?,
+        ),
+    };
+
+    Ok(BulkRecord {
+        key: key.to_string(),
+        value: value.as_bytes().to_vec(),
+        ttl,
+    })
 }
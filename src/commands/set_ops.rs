@@ -0,0 +1,76 @@
+use log::info;
+use serde::Serialize;
+
+use crate::cli::SetAlgebra;
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+#[derive(Debug, Serialize)]
+pub struct SetOpsResult {
+    pub operation: &'static str,
+    pub keys: Vec<String>,
+    pub count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<String>>,
+}
+
+/// The Redis command and short name of a set operation.
+fn command(operation: &SetAlgebra) -> (&'static str, &'static str) {
+    match operation {
+        SetAlgebra::Union => ("SUNION", "union"),
+        SetAlgebra::Inter => ("SINTER", "inter"),
+        SetAlgebra::Diff => ("SDIFF", "diff"),
+    }
+}
+
+/// The keys that do not exist. Redis treats them as empty sets, which
+/// silently hides a typo, so callers should point them out.
+pub async fn missing_keys(
+    client: &mut RedisClient,
+    keys: &[String],
+) -> Result<Vec<String>, AppError> {
+    let exists = client.exists_many(keys).await?;
+    Ok(keys
+        .iter()
+        .zip(exists)
+        .filter(|(_, exists)| !exists)
+        .map(|(key, _)| key.clone())
+        .collect())
+}
+
+/// Combine sets with SUNION, SINTER or SDIFF. The members come back sorted,
+/// or only their number with `count`.
+pub async fn run(
+    client: &mut RedisClient,
+    operation: &SetAlgebra,
+    keys: Vec<String>,
+    count: bool,
+) -> Result<SetOpsResult, AppError> {
+    let (command, name) = command(operation);
+    info!("Computing {} of {}", name, keys.join(", "));
+
+    let mut members = client.set_algebra(command, &keys).await?;
+    members.sort();
+
+    Ok(SetOpsResult {
+        operation: name,
+        count: members.len(),
+        members: (!count).then_some(members),
+        keys,
+    })
+}
+
+/// Write the combination to `destination` with the STORE variant, so large
+/// results never leave the server. Returns the number of members stored;
+/// an empty result deletes `destination`.
+pub async fn store(
+    client: &mut RedisClient,
+    operation: &SetAlgebra,
+    keys: &[String],
+    destination: &str,
+) -> Result<usize, AppError> {
+    let (command, name) = command(operation);
+    info!("Storing {} of {} in {}", name, keys.join(", "), destination);
+
+    Ok(client.set_algebra_store(command, destination, keys).await?)
+}
@@ -0,0 +1,214 @@
+use chrono::{DateTime, Local, TimeZone};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+const BATCH_SIZE: usize = 500;
+
+/// Expiry times shift by the time spent reading, so allow a small tolerance.
+const EXPIRY_TOLERANCE_MS: i64 = 2000;
+
+/// Type, value digest and expiry of every key matching a pattern, for
+/// telling later what changed.
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    pub environment: String,
+    pub pattern: String,
+    pub taken_at: DateTime<Local>,
+    pub keys: BTreeMap<String, SnapshotKey>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapshotKey {
+    #[serde(rename = "type")]
+    pub key_type: String,
+    pub digest: String,
+    /// Absolute expiry in Unix milliseconds, so snapshots taken at different
+    /// times compare equal when the expiry did not move
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Type,
+    Value,
+    Expiry,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Type => "type",
+            ChangeKind::Value => "value",
+            ChangeKind::Expiry => "expiry",
+        }
+    }
+}
+
+/// One change of a key, with both sides described for display ("-" for a
+/// side where the key is missing).
+#[derive(Debug)]
+pub struct Change {
+    pub key: String,
+    pub kind: ChangeKind,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Default)]
+pub struct SnapshotDiff {
+    /// Sorted by key
+    pub changes: Vec<Change>,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+impl Snapshot {
+    /// Capture the keys matching `pattern`, recording `environment` as
+    /// their origin. `progress` is called once per key with the number
+    /// done and the total.
+    pub async fn take(
+        client: &mut RedisClient,
+        environment: &str,
+        pattern: &str,
+        mut progress: impl FnMut(u64, u64),
+    ) -> Result<Self, AppError> {
+        info!("Taking snapshot of '{}'", pattern);
+
+        let taken_at = Local::now();
+        let keys = client.scan_keys(pattern).await?;
+
+        let mut done = 0;
+        let mut snapshot_keys = BTreeMap::new();
+        for batch in keys.chunks(BATCH_SIZE) {
+            let values = client.fetch_values(batch).await?;
+            let pttls = client.pttl_many(batch).await?;
+
+            for (kv, pttl) in values.into_iter().zip(pttls) {
+                done += 1;
+                progress(done, keys.len() as u64);
+                // Expired or deleted since the scan
+                if kv.key_type == "none" {
+                    continue;
+                }
+                let digest = kv.digest();
+                snapshot_keys.insert(
+                    kv.key,
+                    SnapshotKey {
+                        key_type: kv.key_type,
+                        digest,
+                        expires_at: (pttl >= 0).then(|| taken_at.timestamp_millis() + pttl),
+                    },
+                );
+            }
+        }
+
+        Ok(Snapshot {
+            environment: environment.to_string(),
+            pattern: pattern.to_string(),
+            taken_at,
+            keys: snapshot_keys,
+        })
+    }
+
+    pub fn load(path: &str) -> Result<Self, AppError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), AppError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Compare two snapshots key by key.
+pub fn diff(before: &Snapshot, after: &Snapshot) -> SnapshotDiff {
+    let mut diff = SnapshotDiff::default();
+    let changes = &mut diff.changes;
+
+    for (key, old) in &before.keys {
+        let Some(new) = after.keys.get(key) else {
+            diff.removed += 1;
+            changes.push(Change {
+                key: key.clone(),
+                kind: ChangeKind::Removed,
+                before: old.key_type.clone(),
+                after: "-".to_string(),
+            });
+            continue;
+        };
+
+        let mut key_changed = false;
+        if old.key_type != new.key_type {
+            changes.push(Change {
+                key: key.clone(),
+                kind: ChangeKind::Type,
+                before: old.key_type.clone(),
+                after: new.key_type.clone(),
+            });
+            key_changed = true;
+        } else if old.digest != new.digest {
+            changes.push(Change {
+                key: key.clone(),
+                kind: ChangeKind::Value,
+                before: old.digest.clone(),
+                after: new.digest.clone(),
+            });
+            key_changed = true;
+        }
+
+        if !expiry_matches(old.expires_at, new.expires_at) {
+            changes.push(Change {
+                key: key.clone(),
+                kind: ChangeKind::Expiry,
+                before: describe_expiry(old.expires_at),
+                after: describe_expiry(new.expires_at),
+            });
+            key_changed = true;
+        }
+
+        if key_changed {
+            diff.changed += 1;
+        }
+    }
+
+    for (key, new) in &after.keys {
+        if !before.keys.contains_key(key) {
+            diff.added += 1;
+            changes.push(Change {
+                key: key.clone(),
+                kind: ChangeKind::Added,
+                before: "-".to_string(),
+                after: new.key_type.clone(),
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    diff
+}
+
+fn expiry_matches(before: Option<i64>, after: Option<i64>) -> bool {
+    match (before, after) {
+        (Some(a), Some(b)) => (a - b).abs() <= EXPIRY_TOLERANCE_MS,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn describe_expiry(expires_at: Option<i64>) -> String {
+    match expires_at.and_then(|ms| Local.timestamp_millis_opt(ms).single()) {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => "no expiry".to_string(),
+    }
+}
@@ -1,10 +1,220 @@
-use colored::*;
 use log::info;
+use serde::Serialize;
+use std::collections::HashMap;
 
+use crate::cli::CommandSort;
 use crate::error::AppError;
+use crate::redis_client::{MemoryStats, RedisClient};
 
-pub async fn run() -> Result<(), AppError> {
-    info!("Stats command - placeholder");
-    println!("{}", "Stats command - not yet implemented".yellow());
-    Ok(())
+/// One INFO snapshot, every section flattened into a single map.
+pub async fn run(client: &mut RedisClient) -> Result<HashMap<String, String>, AppError> {
+    info!("Running stats command");
+
+    Ok(client.info().await?)
+}
+
+pub async fn memory_stats(client: &mut RedisClient) -> Result<MemoryStats, AppError> {
+    info!("Getting memory stats");
+
+    Ok(client.memory_stats().await?)
+}
+
+/// One `cmdstat_*` line of INFO COMMANDSTATS.
+#[derive(Debug, Serialize)]
+pub struct CommandStat {
+    pub command: String,
+    pub calls: u64,
+    pub usec: u64,
+    pub usec_per_call: f64,
+    pub rejected_calls: u64,
+    pub failed_calls: u64,
+}
+
+/// Per-command call counts and latency from INFO COMMANDSTATS, busiest
+/// first by the chosen measure.
+pub async fn command_stats(
+    client: &mut RedisClient,
+    sort: CommandSort,
+) -> Result<Vec<CommandStat>, AppError> {
+    info!("Getting command stats");
+
+    let info = client.info_section("commandstats").await?;
+    let mut stats: Vec<CommandStat> = info
+        .iter()
+        .filter_map(|(name, fields)| {
+            let command = name.strip_prefix("cmdstat_")?;
+            let fields = info_fields(fields);
+            let int = |wanted: &str| fields.get(wanted).and_then(|v| v.parse().ok()).unwrap_or(0);
+            Some(CommandStat {
+                command: command.to_string(),
+                calls: int("calls"),
+                usec: int("usec"),
+                usec_per_call: fields
+                    .get("usec_per_call")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0.0),
+                // Only reported since Redis 6.2
+                rejected_calls: int("rejected_calls"),
+                failed_calls: int("failed_calls"),
+            })
+        })
+        .collect();
+
+    stats.sort_by(|a, b| match sort {
+        CommandSort::Calls => b.calls.cmp(&a.calls),
+        CommandSort::AvgLatency => b.usec_per_call.total_cmp(&a.usec_per_call),
+        CommandSort::Total => b.usec.cmp(&a.usec),
+    });
+
+    Ok(stats)
+}
+
+/// A replica as listed by its master in INFO REPLICATION.
+#[derive(Debug, Serialize)]
+pub struct ReplicaStatus {
+    pub address: String,
+    pub state: String,
+    pub offset: u64,
+    pub lag_bytes: u64,
+    pub lag_seconds: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct Replication {
+    /// The INFO REPLICATION section
+    pub info: HashMap<String, String>,
+    pub master_offset: u64,
+    pub replicas: Vec<ReplicaStatus>,
+}
+
+/// This instance's replication role. On a master, each replica comes with
+/// its lag behind `master_repl_offset` in bytes and the seconds since its
+/// last acknowledgement.
+pub async fn replication(client: &mut RedisClient) -> Result<Replication, AppError> {
+    info!("Getting replication stats");
+
+    let info = client.info_section("replication").await?;
+    let master_offset: u64 = info
+        .get("master_repl_offset")
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0);
+
+    let mut replica_names: Vec<&String> = info
+        .keys()
+        .filter(|name| {
+            name.strip_prefix("slave")
+                .is_some_and(|n| n.parse::<u32>().is_ok())
+        })
+        .collect();
+    replica_names.sort_by_key(|name| name[5..].parse::<u32>().unwrap_or(0));
+    let replicas: Vec<ReplicaStatus> = replica_names
+        .into_iter()
+        .map(|name| {
+            let fields = info_fields(&info[name]);
+            let offset = fields
+                .get("offset")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            ReplicaStatus {
+                address: format!(
+                    "{}:{}",
+                    fields.get("ip").unwrap_or(&"?"),
+                    fields.get("port").unwrap_or(&"?")
+                ),
+                state: fields.get("state").unwrap_or(&"unknown").to_string(),
+                offset,
+                lag_bytes: master_offset.saturating_sub(offset),
+                lag_seconds: fields.get("lag").and_then(|v| v.parse().ok()),
+            }
+        })
+        .collect();
+
+    Ok(Replication {
+        info,
+        master_offset,
+        replicas,
+    })
+}
+
+/// Keys held by one logical database, from INFO KEYSPACE.
+#[derive(Debug, Serialize)]
+pub struct DatabaseStat {
+    pub db: u32,
+    pub keys: u64,
+    pub expires: u64,
+    /// Average remaining TTL of keys with an expiry, in milliseconds
+    pub avg_ttl: u64,
+}
+
+/// Totals across every database of the instance.
+#[derive(Debug)]
+pub struct KeyspaceTotal {
+    pub keys: u64,
+    pub expires: u64,
+    /// Average TTL weighted by each database's keys with an expiry
+    pub avg_ttl: u64,
+}
+
+#[derive(Debug)]
+pub struct Keyspace {
+    pub databases: Vec<DatabaseStat>,
+    pub total: KeyspaceTotal,
+}
+
+/// Keys, keys with an expiry and average TTL for every database that holds
+/// data, with totals across the instance.
+pub async fn keyspace(client: &mut RedisClient) -> Result<Keyspace, AppError> {
+    info!("Getting keyspace stats");
+
+    let info = client.info_section("keyspace").await?;
+    let mut databases: Vec<DatabaseStat> = info
+        .iter()
+        .filter_map(|(name, stats)| {
+            let db = name.strip_prefix("db")?.parse().ok()?;
+            let fields = info_fields(stats);
+            let int = |wanted: &str| fields.get(wanted).and_then(|v| v.parse().ok()).unwrap_or(0);
+            Some(DatabaseStat {
+                db,
+                keys: int("keys"),
+                expires: int("expires"),
+                avg_ttl: int("avg_ttl"),
+            })
+        })
+        .collect();
+    databases.sort_by_key(|database| database.db);
+
+    let total_keys: u64 = databases.iter().map(|d| d.keys).sum();
+    let total_expires: u64 = databases.iter().map(|d| d.expires).sum();
+    // Weight each database's average by how many keys it has with a TTL
+    let total_avg_ttl = databases
+        .iter()
+        .map(|d| d.avg_ttl * d.expires)
+        .sum::<u64>()
+        .checked_div(total_expires)
+        .unwrap_or(0);
+
+    Ok(Keyspace {
+        databases,
+        total: KeyspaceTotal {
+            keys: total_keys,
+            expires: total_expires,
+            avg_ttl: total_avg_ttl,
+        },
+    })
+}
+
+/// Split an INFO value such as `ip=10.0.0.2,port=6380,lag=0` into fields.
+pub fn info_fields(value: &str) -> HashMap<&str, &str> {
+    value
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect()
+}
+
+/// The advice of MEMORY DOCTOR, as plain text.
+pub async fn memory_doctor(client: &mut RedisClient) -> Result<String, AppError> {
+    info!("Running memory doctor");
+
+    Ok(client.memory_doctor().await?)
 }
@@ -0,0 +1,44 @@
+use log::info;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Bytes `start..=end` of a string; negative offsets count from the end.
+pub async fn getrange(
+    client: &mut RedisClient,
+    key: &str,
+    start: i64,
+    end: i64,
+) -> Result<Vec<u8>, AppError> {
+    info!("Getting range of {}: {}..{}", key, start, end);
+    Ok(client.getrange(key, start, end).await?)
+}
+
+/// Overwrite part of a string starting at `offset`. Returns the new length.
+pub async fn setrange(
+    client: &mut RedisClient,
+    key: &str,
+    offset: u64,
+    value: &[u8],
+) -> Result<usize, AppError> {
+    info!("Setting range of {} at offset {}", key, offset);
+    Ok(client.setrange(key, offset, value).await?)
+}
+
+/// Append to a string, creating it if needed. Returns the new length.
+pub async fn append(client: &mut RedisClient, key: &str, value: &[u8]) -> Result<usize, AppError> {
+    info!("Appending to {}", key);
+    Ok(client.append(key, value).await?)
+}
+
+/// The byte length of a string without fetching it, so multi-megabyte
+/// values can be sized up before slicing them with getrange.
+pub async fn strlen(client: &mut RedisClient, key: &str) -> Result<usize, AppError> {
+    info!("Getting length of {}", key);
+
+    // STRLEN reports 0 for missing keys, which would hide a typo
+    if !client.exists(key).await? {
+        return Err(AppError::NotFound(format!("Key '{}' not found", key)));
+    }
+    Ok(client.strlen(key).await?)
+}
@@ -0,0 +1,36 @@
+use log::info;
+
+use crate::config::RedisConfig;
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Key counts of databases `first` and `second` of the instance behind
+/// `config`, for showing what a swap would exchange.
+pub async fn sizes(config: &RedisConfig, first: u8, second: u8) -> Result<(u64, u64), AppError> {
+    let mut sizes = Vec::with_capacity(2);
+    for db in [first, second] {
+        let mut db_config = config.clone();
+        db_config.db = db;
+        let mut client = RedisClient::connect(db_config).await?;
+        sizes.push(client.dbsize().await?);
+    }
+    Ok((sizes[0], sizes[1]))
+}
+
+/// Swap two logical databases with SWAPDB. The swap is atomic and instant,
+/// which makes it the usual way to publish a dataset prepared in a spare
+/// database (blue/green), but every client of either database sees the
+/// other's data straight away.
+pub async fn run(client: &mut RedisClient, first: u8, second: u8) -> Result<(), AppError> {
+    info!("Swapping databases {} and {}", first, second);
+
+    if first == second {
+        return Err(AppError::ConfigError(format!(
+            "Cannot swap db {} with itself",
+            first
+        )));
+    }
+
+    client.swapdb(first, second).await?;
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use super::diff::ttl_matches;
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+const BATCH_SIZE: usize = 200;
+
+/// What one reconciliation pass did.
+#[derive(Debug, Default)]
+pub struct SyncCycle {
+    pub scanned: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub deleted: usize,
+    /// Keys that could not be written, with the error
+    pub errors: Vec<(String, String)>,
+}
+
+/// Run one reconciliation pass. Keys are compared by type, value digest and
+/// TTL rather than DUMP payload, which differs across server versions and
+/// encodings for equal values, and differing keys are restored on the
+/// destination.
+pub async fn cycle(
+    source_client: &mut RedisClient,
+    dest_client: &mut RedisClient,
+    pattern: &str,
+    delete_extra: bool,
+) -> Result<SyncCycle, AppError> {
+    let mut cycle = SyncCycle::default();
+
+    let keys = source_client.scan_keys(pattern).await?;
+    cycle.scanned = keys.len();
+
+    for batch in keys.chunks(BATCH_SIZE) {
+        let dumped = source_client.dump_batch(batch).await?;
+        let source_values = source_client.fetch_values(batch).await?;
+        let dest_values = dest_client.fetch_values(batch).await?;
+        let dest_pttls = dest_client.pttl_many(batch).await?;
+
+        for (((dumped, source), dest), dest_pttl) in dumped
+            .into_iter()
+            .zip(source_values)
+            .zip(dest_values)
+            .zip(dest_pttls)
+        {
+            // Expired or deleted since the scan
+            let payload = match dumped.payload {
+                Some(payload) => payload,
+                None => continue,
+            };
+
+            if dest.key_type == source.key_type
+                && dest.digest() == source.digest()
+                && ttl_matches(dumped.pttl, dest_pttl)
+            {
+                cycle.unchanged += 1;
+                continue;
+            }
+
+            match dest_client
+                .restore(&dumped.key, dumped.pttl, &payload, true)
+                .await
+            {
+                Ok(_) => cycle.updated += 1,
+                Err(e) => cycle.errors.push((dumped.key, e.to_string())),
+            }
+        }
+    }
+
+    if delete_extra {
+        let source_keys: HashSet<&String> = keys.iter().collect();
+        for key in dest_client.scan_keys(pattern).await? {
+            if !source_keys.contains(&key) && dest_client.delete_key(&key).await? {
+                cycle.deleted += 1;
+            }
+        }
+    }
+
+    Ok(cycle)
+}
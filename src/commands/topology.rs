@@ -0,0 +1,209 @@
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use log::info;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::stats::info_fields;
+use crate::config::RedisConfig;
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// Chained replication deeper than this is almost certainly a loop
+const MAX_DEPTH: usize = 8;
+
+/// One instance in the replication tree, as seen from its own INFO
+/// REPLICATION plus what its master reported about it.
+#[derive(Debug, Serialize)]
+pub struct TopologyNode {
+    pub address: String,
+    pub role: String,
+    /// `online`, `wait_bgsave`, ... as reported by the master
+    pub state: Option<String>,
+    /// `master_link_status` as reported by the replica itself
+    pub link: Option<String>,
+    pub offset: Option<u64>,
+    pub lag_bytes: Option<u64>,
+    pub lag_seconds: Option<u64>,
+    pub error: Option<String>,
+    pub replicas: Vec<TopologyNode>,
+}
+
+/// A replica line (`slaveN:ip=...,port=...`) from its master's INFO.
+struct ListedReplica {
+    host: String,
+    port: u16,
+    state: String,
+    offset: u64,
+    lag_seconds: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct Topology {
+    pub root: TopologyNode,
+    /// The master of the configured instance, when it is itself a replica
+    pub upstream: Option<String>,
+}
+
+/// Walk INFO REPLICATION from the instance `client` is connected to down
+/// through every replica it lists (and their replicas). Replicas are
+/// queried with the credentials of `base`.
+pub async fn run(client: &mut RedisClient, base: &RedisConfig) -> Result<Topology, AppError> {
+    info!("Discovering replication topology");
+
+    // The target itself must answer; unreachable replicas are only reported
+    let info = client.info_section("replication").await?;
+
+    let upstream = (info.get("role").map(|r| r.trim()) == Some("slave")).then(|| {
+        format!(
+            "{}:{}",
+            info.get("master_host").map(|v| v.trim()).unwrap_or("?"),
+            info.get("master_port").map(|v| v.trim()).unwrap_or("?")
+        )
+    });
+
+    let mut root = node_from_info(format!("{}:{}", base.host, base.port), &info);
+    let mut seen = vec![root.address.clone()];
+    root.replicas = discover_replicas(base, &info, 1, &mut seen).await;
+
+    Ok(Topology { root, upstream })
+}
+
+fn node_from_info(address: String, info: &HashMap<String, String>) -> TopologyNode {
+    let text = |name: &str| info.get(name).map(|v| v.trim().to_string());
+    let role = text("role").unwrap_or_else(|| "unknown".to_string());
+    let offset = if role == "slave" {
+        text("slave_repl_offset")
+    } else {
+        text("master_repl_offset")
+    };
+    TopologyNode {
+        address,
+        link: if role == "slave" {
+            text("master_link_status")
+        } else {
+            None
+        },
+        role: if role == "slave" {
+            "replica".to_string()
+        } else {
+            role
+        },
+        state: None,
+        offset: offset.and_then(|v| v.parse().ok()),
+        lag_bytes: None,
+        lag_seconds: None,
+        error: None,
+        replicas: Vec::new(),
+    }
+}
+
+fn listed_replicas(info: &HashMap<String, String>) -> Vec<ListedReplica> {
+    let mut names: Vec<&String> = info
+        .keys()
+        .filter(|name| {
+            name.strip_prefix("slave")
+                .is_some_and(|n| n.parse::<u32>().is_ok())
+        })
+        .collect();
+    names.sort_by_key(|name| name[5..].parse::<u32>().unwrap_or(0));
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let fields = info_fields(&info[name]);
+            Some(ListedReplica {
+                host: fields.get("ip")?.to_string(),
+                port: fields.get("port")?.parse().ok()?,
+                state: fields.get("state").unwrap_or(&"unknown").to_string(),
+                offset: fields
+                    .get("offset")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0),
+                lag_seconds: fields.get("lag").and_then(|v| v.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+/// Visit each replica listed in `info`, querying it directly with the
+/// credentials of `base`. Replicas that cannot be reached still appear,
+/// with what their master knows about them.
+fn discover_replicas<'a>(
+    base: &'a RedisConfig,
+    info: &'a HashMap<String, String>,
+    depth: usize,
+    seen: &'a mut Vec<String>,
+) -> BoxFuture<'a, Vec<TopologyNode>> {
+    async move {
+        let master_offset: u64 = info
+            .get("master_repl_offset")
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0);
+
+        let mut nodes = Vec::new();
+        for listed in listed_replicas(info) {
+            let address = format!("{}:{}", listed.host, listed.port);
+            if seen.contains(&address) || depth > MAX_DEPTH {
+                continue;
+            }
+            seen.push(address.clone());
+
+            let replica_config = RedisConfig {
+                host: listed.host.clone(),
+                port: listed.port,
+                db: 0,
+                ..base.clone()
+            };
+            let replica_info = match RedisClient::connect(replica_config.clone()).await {
+                Ok(mut client) => client
+                    .info_section("replication")
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+
+            let node = match replica_info {
+                Ok(replica_info) => {
+                    let mut node = node_from_info(address, &replica_info);
+                    node.replicas =
+                        discover_replicas(&replica_config, &replica_info, depth + 1, seen).await;
+                    node
+                }
+                Err(error) => TopologyNode {
+                    address,
+                    role: "replica".to_string(),
+                    state: None,
+                    link: None,
+                    offset: Some(listed.offset),
+                    lag_bytes: None,
+                    lag_seconds: None,
+                    error: Some(error),
+                    replicas: Vec::new(),
+                },
+            };
+            let offset = node.offset.unwrap_or(listed.offset);
+            nodes.push(TopologyNode {
+                state: Some(listed.state),
+                lag_bytes: Some(master_offset.saturating_sub(offset)),
+                lag_seconds: listed.lag_seconds,
+                ..node
+            });
+        }
+        nodes
+    }
+    .boxed()
+}
+
+fn is_healthy(node: &TopologyNode) -> bool {
+    node.error.is_none()
+        && node.state.as_deref().is_none_or(|s| s == "online")
+        && node.link.as_deref().is_none_or(|l| l == "up")
+}
+
+/// Total nodes in the tree and how many of them are not healthy.
+pub fn count(node: &TopologyNode) -> (usize, usize) {
+    node.replicas.iter().map(count).fold(
+        (1, usize::from(!is_healthy(node))),
+        |(total, bad), (t, b)| (total + t, bad + b),
+    )
+}
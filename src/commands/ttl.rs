@@ -0,0 +1,41 @@
+use log::info;
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+/// What `run` changed and the key's expiry afterwards.
+#[derive(Debug, Serialize)]
+pub struct TtlReport {
+    /// Whether the requested EXPIRE or PERSIST took effect; `None` when
+    /// nothing was requested.
+    pub changed: Option<bool>,
+    /// PTTL after the change: -1 without an expiry, -2 when the key does
+    /// not exist.
+    pub pttl: i64,
+}
+
+/// Set the key's expiry to `set` seconds (subject to an NX/XX/GT/LT
+/// `condition`), or remove it with `persist`, then read it back.
+pub async fn run(
+    client: &mut RedisClient,
+    key: &str,
+    set: Option<u64>,
+    persist: bool,
+    condition: Option<&str>,
+) -> Result<TtlReport, AppError> {
+    info!("Managing TTL for key: {}", key);
+
+    let changed = if let Some(seconds) = set {
+        Some(client.expire(key, seconds, condition).await?)
+    } else if persist {
+        Some(client.persist(key).await?)
+    } else {
+        None
+    };
+
+    Ok(TtlReport {
+        changed,
+        pttl: client.pttl(key).await?,
+    })
+}
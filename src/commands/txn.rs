@@ -0,0 +1,107 @@
+use log::info;
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::format::{display_key, format_resp, split_command_line};
+use crate::redis_client::{RedisClient, TransactionOutcome};
+
+/// Commands solt sends itself to frame the transaction
+const CONTROL_COMMANDS: [&str; 5] = ["MULTI", "EXEC", "DISCARD", "WATCH", "UNWATCH"];
+
+#[derive(Debug, Serialize)]
+pub struct CommandReply {
+    pub command: String,
+    /// Whether the command took effect. One that fails at run time does
+    /// not undo the others.
+    pub applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Split command lines into arguments, skipping blank lines and `#`
+/// comments. Transaction control commands are refused, since `run` frames
+/// the transaction itself.
+pub fn parse(lines: &[String]) -> Result<Vec<Vec<Vec<u8>>>, AppError> {
+    let mut parsed = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let args = split_command_line(line).map_err(AppError::ConfigError)?;
+        let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+        if CONTROL_COMMANDS.contains(&name.as_str()) {
+            return Err(AppError::ConfigError(format!(
+                "{} cannot be used inside solt txn, which sends MULTI and EXEC itself",
+                name
+            )));
+        }
+        parsed.push(args);
+    }
+    if parsed.is_empty() {
+        return Err(AppError::ConfigError(
+            "No commands given for the transaction".to_string(),
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Submit the commands as a single MULTI/EXEC transaction and return the
+/// reply of each one. A command the server rejects while queueing discards
+/// the whole transaction and is reported as an error.
+pub async fn run(
+    client: &mut RedisClient,
+    commands: &[Vec<Vec<u8>>],
+) -> Result<Vec<CommandReply>, AppError> {
+    info!("Running a transaction");
+
+    let labels: Vec<String> = commands
+        .iter()
+        .map(|args| {
+            args.iter()
+                .map(|arg| quote_arg(arg))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+
+    match client.transaction(commands).await? {
+        TransactionOutcome::Executed(replies) => Ok(labels
+            .into_iter()
+            .zip(replies)
+            .map(|(command, reply)| match reply {
+                Ok(reply) => CommandReply {
+                    command,
+                    applied: true,
+                    reply: Some(format_resp(&reply)),
+                    error: None,
+                },
+                Err(e) => CommandReply {
+                    command,
+                    applied: false,
+                    reply: None,
+                    error: Some(e.to_string()),
+                },
+            })
+            .collect()),
+        TransactionOutcome::Rejected { index, error } => Err(AppError::Aborted(format!(
+            "Command {} ({}) was rejected, so the transaction was discarded and nothing ran: {}",
+            index + 1,
+            labels[index],
+            error
+        ))),
+    }
+}
+
+/// Show an argument so the command reads back the way it was typed.
+fn quote_arg(arg: &[u8]) -> String {
+    let shown = display_key(arg);
+    let ambiguous = shown.is_empty() || shown.contains(|c: char| c.is_whitespace() || c == '\'');
+    if ambiguous && !shown.starts_with('"') {
+        format!("{:?}", shown)
+    } else {
+        shown
+    }
+}
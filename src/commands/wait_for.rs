@@ -0,0 +1,95 @@
+use futures::StreamExt;
+use log::info;
+use std::time::{Duration, Instant};
+
+use crate::config::RedisConfig;
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+use super::notify::covers_events;
+
+/// How a wait ended.
+#[derive(Debug)]
+pub enum WaitOutcome {
+    /// The key was already in the requested state
+    Immediate,
+    /// The key reached the requested state after this long
+    After(Duration),
+}
+
+/// Block until `key` exists (or, with `gone`, no longer exists), giving up
+/// after `timeout`. EXISTS is polled every `interval`; when keyspace
+/// notifications for the relevant events are enabled, a change to the key
+/// also triggers an immediate re-check. `on_wait` is called once the first
+/// check fails, with whether notifications are in use.
+pub async fn run(
+    config: &RedisConfig,
+    key: &str,
+    gone: bool,
+    timeout: Duration,
+    interval: Duration,
+    on_wait: impl FnOnce(bool),
+) -> Result<WaitOutcome, AppError> {
+    info!("Waiting for key '{}' (gone: {})", key, gone);
+
+    let mut client = RedisClient::connect(config.clone()).await?;
+    let started = Instant::now();
+    let what = if gone { "be deleted" } else { "appear" };
+
+    // Deletion can come from DEL, expiry or eviction; creation from any write
+    let required = if gone { "Kgxe" } else { "KA" };
+    let notifications = match client.config_get("notify-keyspace-events").await {
+        Ok(values) => values
+            .into_iter()
+            .next()
+            .is_some_and(|(_, value)| covers_events(&value, required)),
+        // CONFIG is often disabled on managed services; polling still works
+        Err(_) => false,
+    };
+
+    // Subscribe before the first check so no change can slip in between
+    let mut pubsub = None;
+    if notifications {
+        let mut subscriber = RedisClient::connect(config.clone()).await?.into_pubsub();
+        subscriber
+            .subscribe(format!("__keyspace@{}__:{}", config.db, key))
+            .await?;
+        pubsub = Some(subscriber);
+    }
+    let mut events = pubsub.as_mut().map(|pubsub| pubsub.on_message());
+
+    if client.exists(key).await? != gone {
+        return Ok(WaitOutcome::Immediate);
+    }
+
+    on_wait(notifications);
+
+    let deadline = tokio::time::sleep(timeout);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            Some(_) = async {
+                match events.as_mut() {
+                    Some(events) => events.next().await,
+                    None => std::future::pending().await,
+                }
+            } => {}
+            _ = &mut deadline => {
+                return Err(AppError::Aborted(format!(
+                    "Timed out after {}s waiting for '{}' to {}",
+                    timeout.as_secs(),
+                    key,
+                    what
+                )));
+            }
+            _ = tokio::signal::ctrl_c() => {
+                return Err(AppError::Aborted("Interrupted".to_string()));
+            }
+        }
+
+        if client.exists(key).await? != gone {
+            return Ok(WaitOutcome::After(started.elapsed()));
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use log::info;
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::redis_client::RedisClient;
+
+#[derive(Debug, Serialize)]
+pub struct ScoredMember {
+    pub member: String,
+    pub score: f64,
+}
+
+/// How many members a removal took out, and whether that emptied the key.
+#[derive(Debug)]
+pub struct RemoveReport {
+    pub removed: usize,
+    pub key_deleted: bool,
+}
+
+/// The 0-based rank of `member` counted from the lowest score (highest
+/// with `rev`), along with its score.
+pub async fn rank(
+    client: &mut RedisClient,
+    key: &str,
+    member: &str,
+    rev: bool,
+) -> Result<(usize, f64), AppError> {
+    info!("Getting rank of {} in {}", member, key);
+
+    let rank = client.zrank(key, member, rev).await?;
+    let score = client.zscore(key, member).await?;
+    match (rank, score) {
+        (Some(rank), Some(score)) => Ok((rank, score)),
+        _ => Err(member_not_found(key, member)),
+    }
+}
+
+pub async fn score(client: &mut RedisClient, key: &str, member: &str) -> Result<f64, AppError> {
+    info!("Getting score of {} in {}", member, key);
+
+    client
+        .zscore(key, member)
+        .await?
+        .ok_or_else(|| member_not_found(key, member))
+}
+
+/// Members scored between `min` and `max` (ZRANGEBYSCORE syntax, so
+/// "(1" and "+inf" work), optionally one page of `limit` after `offset`.
+pub async fn range(
+    client: &mut RedisClient,
+    key: &str,
+    min: &str,
+    max: &str,
+    rev: bool,
+    limit: Option<usize>,
+    offset: usize,
+) -> Result<Vec<ScoredMember>, AppError> {
+    info!("Getting members of {} scored {}..{}", key, min, max);
+
+    let members = client
+        .zrange_by_score(key, min, max, rev, limit.map(|count| (offset, count)))
+        .await?;
+    Ok(members
+        .into_iter()
+        .map(|(member, score)| ScoredMember { member, score })
+        .collect())
+}
+
+/// Add `by` to the score of `member`. Returns the new score.
+pub async fn incr(
+    client: &mut RedisClient,
+    key: &str,
+    member: &str,
+    by: f64,
+) -> Result<f64, AppError> {
+    info!("Incrementing {} in {} by {}", member, key, by);
+    Ok(client.zincr(key, member, by).await?)
+}
+
+/// Remove the members scored between `min` and `max`.
+pub async fn remove(
+    client: &mut RedisClient,
+    key: &str,
+    min: &str,
+    max: &str,
+) -> Result<RemoveReport, AppError> {
+    info!("Removing members of {} scored {}..{}", key, min, max);
+
+    if !client.exists(key).await? {
+        return Err(AppError::NotFound(format!("Key '{}' not found", key)));
+    }
+    let removed = client.zrem_range_by_score(key, min, max).await?;
+    Ok(RemoveReport {
+        removed,
+        key_deleted: !client.exists(key).await?,
+    })
+}
+
+fn member_not_found(key: &str, member: &str) -> AppError {
+    AppError::NotFound(format!("Member '{}' not found in '{}'", member, key))
+}
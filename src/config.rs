@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-// use std::time::Duration; // Remove unused import
+use std::time::Duration;
+
+use crate::error::AppError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RedisConfig {
@@ -17,6 +19,27 @@ pub struct RedisConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Environment {
     pub name: String,
+    /// Free-form notes about what this environment is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Team or person responsible for this environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Monitoring dashboard for this environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dashboard_url: Option<String>,
+    /// Runbook to follow during incidents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runbook_url: Option<String>,
+    /// Destructive commands must be confirmed by typing the environment name
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub protected: bool,
+    /// Refuse every command that writes to this environment
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
+    /// Color of the environment banner, or "none" to hide it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub banner_color: Option<String>,
     pub config: RedisConfig,
 }
 
@@ -39,6 +62,8 @@ pub enum OutputFormat {
     Csv,
     #[serde(rename = "plain")]
     Plain,
+    #[serde(rename = "yaml")]
+    Yaml,
 }
 
 impl Default for AppConfig {
@@ -48,9 +73,9 @@ impl Default for AppConfig {
         // Default development environment
         environments.insert(
             "dev".to_string(),
-            Environment {
-                name: "dev".to_string(),
-                config: RedisConfig {
+            Environment::new(
+                "dev".to_string(),
+                RedisConfig {
                     host: "localhost".to_string(),
                     port: 6379,
                     password: None,
@@ -58,15 +83,15 @@ impl Default for AppConfig {
                     timeout: Some(30),
                     tls: false,
                 },
-            },
+            ),
         );
 
         // Default staging environment
         environments.insert(
             "staging".to_string(),
-            Environment {
-                name: "staging".to_string(),
-                config: RedisConfig {
+            Environment::new(
+                "staging".to_string(),
+                RedisConfig {
                     host: "localhost".to_string(),
                     port: 6379,
                     password: None,
@@ -74,24 +99,23 @@ impl Default for AppConfig {
                     timeout: Some(30),
                     tls: false,
                 },
-            },
+            ),
         );
 
         // Default production environment
-        environments.insert(
+        let mut prod = Environment::new(
             "prod".to_string(),
-            Environment {
-                name: "prod".to_string(),
-                config: RedisConfig {
-                    host: "localhost".to_string(),
-                    port: 6379,
-                    password: None,
-                    db: 2,
-                    timeout: Some(30),
-                    tls: false,
-                },
+            RedisConfig {
+                host: "localhost".to_string(),
+                port: 6379,
+                password: None,
+                db: 2,
+                timeout: Some(30),
+                tls: false,
             },
         );
+        prod.protected = true;
+        environments.insert("prod".to_string(), prod);
 
         Self {
             environments,
@@ -141,14 +165,68 @@ impl AppConfig {
         self.environments.get(name)
     }
 
+    /// Connection settings of the environment called `name`.
+    pub fn redis_config(&self, name: &str) -> Result<RedisConfig, AppError> {
+        self.get_environment(name)
+            .map(|env| env.config.clone())
+            .ok_or_else(|| AppError::ConfigError(format!("Environment '{}' not found", name)))
+    }
+
     pub fn add_environment(&mut self, name: String, config: RedisConfig) {
         self.environments
-            .insert(name.clone(), Environment { name, config });
+            .insert(name.clone(), Environment::new(name, config));
     }
 
     pub fn remove_environment(&mut self, name: &str) -> bool {
         self.environments.remove(name).is_some()
     }
+
+    /// The environment a command targets: the one given on the command
+    /// line, else the configured default, else "dev".
+    pub fn resolve_environment(&self, environment: Option<&str>) -> String {
+        environment
+            .map(|e| e.to_string())
+            .or_else(|| self.default_environment.clone())
+            .unwrap_or_else(|| "dev".to_string())
+    }
+}
+
+impl Environment {
+    pub fn new(name: String, config: RedisConfig) -> Self {
+        Self {
+            name,
+            description: None,
+            owner: None,
+            dashboard_url: None,
+            runbook_url: None,
+            protected: false,
+            read_only: false,
+            banner_color: None,
+            config,
+        }
+    }
+
+    /// Banner color: the configured one, else red for production and
+    /// protected environments, yellow for staging and green otherwise.
+    /// None when the banner is turned off.
+    pub fn banner_color(&self) -> Option<colored::Color> {
+        match self.banner_color.as_deref() {
+            Some("none") => None,
+            Some(color) => color.parse().ok(),
+            None if self.protected || matches!(self.name.as_str(), "prod" | "production") => {
+                Some(colored::Color::Red)
+            }
+            None if self.name == "staging" => Some(colored::Color::Yellow),
+            None => Some(colored::Color::Green),
+        }
+    }
+
+    pub fn has_annotations(&self) -> bool {
+        self.description.is_some()
+            || self.owner.is_some()
+            || self.dashboard_url.is_some()
+            || self.runbook_url.is_some()
+    }
 }
 
 impl RedisConfig {
@@ -165,4 +243,12 @@ impl RedisConfig {
             protocol, auth, self.host, self.port, self.db
         )
     }
+
+    /// How long to wait for the connection and for each reply. A timeout of
+    /// 0 waits forever.
+    pub fn command_timeout(&self) -> Option<Duration> {
+        self.timeout
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs)
+    }
 }
@@ -0,0 +1,142 @@
+use serde_json::{Map, Number, Value as JsonValue};
+
+use crate::cli::DecodeFormat;
+
+/// Decode a stored value into JSON. Returns the decoded value and the name
+/// of the format that matched, or `None` if the value is not in the
+/// requested format. In auto mode JSON is tried first, and the binary
+/// formats only for values that are not plain text.
+pub fn decode_value(bytes: &[u8], format: DecodeFormat) -> Option<(JsonValue, &'static str)> {
+    match format {
+        DecodeFormat::Json => decode_json(bytes).map(|v| (v, "json")),
+        DecodeFormat::Msgpack => decode_msgpack(bytes).map(|v| (v, "msgpack")),
+        DecodeFormat::Cbor => decode_cbor(bytes).map(|v| (v, "cbor")),
+        DecodeFormat::Auto => {
+            if let Some(value) = decode_json(bytes) {
+                return Some((value, "json"));
+            }
+            // Short text also happens to be valid MessagePack (e.g. "a" is
+            // the integer 97), so only try binary formats on binary data
+            if is_text(bytes) {
+                return None;
+            }
+            decode_msgpack(bytes)
+                .map(|v| (v, "msgpack"))
+                .or_else(|| decode_cbor(bytes).map(|v| (v, "cbor")))
+        }
+    }
+}
+
+fn is_text(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => !text
+            .chars()
+            .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t')),
+        Err(_) => false,
+    }
+}
+
+fn decode_json(bytes: &[u8]) -> Option<JsonValue> {
+    serde_json::from_slice(bytes).ok()
+}
+
+fn decode_msgpack(bytes: &[u8]) -> Option<JsonValue> {
+    let mut reader = bytes;
+    let value = rmpv::decode::read_value(&mut reader).ok()?;
+    // Trailing bytes mean this was not a single MessagePack value
+    reader.is_empty().then(|| msgpack_to_json(value))
+}
+
+fn decode_cbor(bytes: &[u8]) -> Option<JsonValue> {
+    let mut reader = bytes;
+    let value: ciborium::Value = ciborium::de::from_reader(&mut reader).ok()?;
+    reader.is_empty().then(|| cbor_to_json(value))
+}
+
+fn msgpack_to_json(value: rmpv::Value) -> JsonValue {
+    match value {
+        rmpv::Value::Nil => JsonValue::Null,
+        rmpv::Value::Boolean(b) => JsonValue::Bool(b),
+        rmpv::Value::Integer(i) => match (i.as_i64(), i.as_u64()) {
+            (Some(n), _) => JsonValue::from(n),
+            (_, Some(n)) => JsonValue::from(n),
+            _ => JsonValue::Null,
+        },
+        rmpv::Value::F32(f) => float(f as f64),
+        rmpv::Value::F64(f) => float(f),
+        rmpv::Value::String(s) => match s.as_str() {
+            Some(s) => JsonValue::String(s.to_string()),
+            None => JsonValue::String(String::from_utf8_lossy(s.as_bytes()).to_string()),
+        },
+        rmpv::Value::Binary(data) => bytes_to_json(&data),
+        rmpv::Value::Array(items) => {
+            JsonValue::Array(items.into_iter().map(msgpack_to_json).collect())
+        }
+        rmpv::Value::Map(entries) => JsonValue::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| (map_key(msgpack_to_json(k)), msgpack_to_json(v)))
+                .collect::<Map<_, _>>(),
+        ),
+        rmpv::Value::Ext(ext_type, data) => {
+            let mut ext = Map::new();
+            ext.insert("ext_type".to_string(), JsonValue::from(ext_type));
+            ext.insert("data".to_string(), bytes_to_json(&data));
+            JsonValue::Object(ext)
+        }
+    }
+}
+
+fn cbor_to_json(value: ciborium::Value) -> JsonValue {
+    match value {
+        ciborium::Value::Null => JsonValue::Null,
+        ciborium::Value::Bool(b) => JsonValue::Bool(b),
+        ciborium::Value::Integer(i) => {
+            let n = i128::from(i);
+            match (i64::try_from(n), u64::try_from(n)) {
+                (Ok(n), _) => JsonValue::from(n),
+                (_, Ok(n)) => JsonValue::from(n),
+                _ => JsonValue::String(n.to_string()),
+            }
+        }
+        ciborium::Value::Float(f) => float(f),
+        ciborium::Value::Text(s) => JsonValue::String(s),
+        ciborium::Value::Bytes(data) => bytes_to_json(&data),
+        ciborium::Value::Array(items) => {
+            JsonValue::Array(items.into_iter().map(cbor_to_json).collect())
+        }
+        ciborium::Value::Map(entries) => JsonValue::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| (map_key(cbor_to_json(k)), cbor_to_json(v)))
+                .collect::<Map<_, _>>(),
+        ),
+        ciborium::Value::Tag(tag, inner) => {
+            let mut tagged = Map::new();
+            tagged.insert("tag".to_string(), JsonValue::from(tag));
+            tagged.insert("value".to_string(), cbor_to_json(*inner));
+            JsonValue::Object(tagged)
+        }
+        _ => JsonValue::Null,
+    }
+}
+
+fn float(f: f64) -> JsonValue {
+    Number::from_f64(f)
+        .map(JsonValue::Number)
+        .unwrap_or_else(|| JsonValue::String(f.to_string()))
+}
+
+/// JSON has no byte strings, so show them as hex.
+fn bytes_to_json(data: &[u8]) -> JsonValue {
+    let hex: String = data.iter().map(|b| format!("{:02x}", b)).collect();
+    JsonValue::String(format!("0x{}", hex))
+}
+
+/// JSON object keys must be strings.
+fn map_key(key: JsonValue) -> String {
+    match key {
+        JsonValue::String(s) => s,
+        other => other.to_string(),
+    }
+}
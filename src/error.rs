@@ -17,6 +17,41 @@ pub enum AppError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Aborted(String),
+
+    #[error("{0}")]
+    Unhealthy(String),
+
+    #[error("Query error: {0}")]
+    QueryError(String),
+
     #[error("Anyhow error: {0}")]
     Anyhow(#[from] anyhow::Error),
 }
+
+impl AppError {
+    /// Process exit code for this error, so scripts can tell failures apart:
+    /// 1 when a key or field was not found, 2 when Redis could not be
+    /// reached, 3 for configuration problems, 5 when a health or cluster
+    /// check failed and 4 for anything else.
+    pub fn exit_code(&self) -> i32 {
+        let is_connection =
+            |e: &redis::RedisError| e.is_io_error() || e.is_connection_refusal() || e.is_timeout();
+        match self {
+            AppError::NotFound(_) => 1,
+            AppError::ConnectionError(_) => 2,
+            AppError::Redis(e) if is_connection(e) => 2,
+            AppError::Anyhow(e) => match e.downcast_ref::<redis::RedisError>() {
+                Some(e) if is_connection(e) => 2,
+                _ => 4,
+            },
+            AppError::ConfigError(_) => 3,
+            AppError::Unhealthy(_) => 5,
+            _ => 4,
+        }
+    }
+}
@@ -0,0 +1,611 @@
+use redis::Value;
+use std::time::Duration;
+
+/// Format a byte count using binary units (e.g. `1.50 MiB`).
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", size, UNITS[unit])
+}
+
+/// Format a number of seconds compactly (e.g. `45s`, `12m`, `3h`, `2d`).
+pub fn format_seconds(seconds: u64) -> String {
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else if seconds < 3600 {
+        format!("{}m", seconds / 60)
+    } else if seconds < 86400 {
+        format!("{}h", seconds / 3600)
+    } else {
+        format!("{}d", seconds / 86400)
+    }
+}
+
+/// Format a duration given in microseconds using the most readable unit.
+pub fn format_micros(micros: i64) -> String {
+    if micros < 1000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2}s", micros as f64 / 1_000_000.0)
+    }
+}
+
+/// Parse a human-readable duration such as `500ms`, `30s`, `5m` or `1h`.
+/// A bare number is interpreted as seconds.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("Invalid duration '{}'", input))?;
+    let seconds = match unit.trim() {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => {
+            return Err(format!(
+                "Invalid duration unit in '{}' (use ms, s, m or h)",
+                input
+            ))
+        }
+    };
+
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format!("Duration '{}' is out of range", input))
+}
+
+/// Parse a polling interval with [`parse_duration`], rejecting zero so loops
+/// always sleep between iterations.
+pub fn parse_interval(input: &str) -> Result<Duration, String> {
+    let interval = parse_duration(input)?;
+    if interval.is_zero() {
+        return Err(format!(
+            "Interval '{}' must be greater than zero",
+            input.trim()
+        ));
+    }
+    Ok(interval)
+}
+
+/// Parse a byte size such as `512`, `4KB`, `1MiB` or `2g`. Decimal (KB) and
+/// binary (KiB) suffixes are both accepted; single letters are binary.
+pub fn parse_size(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+
+    let value: u64 = number
+        .parse()
+        .map_err(|_| format!("Invalid size '{}'", input))?;
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1000,
+        "mb" => 1000 * 1000,
+        "gb" => 1000 * 1000 * 1000,
+        "k" | "kib" => 1024,
+        "m" | "mib" => 1024 * 1024,
+        "g" | "gib" => 1024 * 1024 * 1024,
+        _ => return Err(format!("Invalid size unit in '{}'", input)),
+    };
+
+    value
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("Size '{}' is too large", input))
+}
+
+/// Parse a string offset, which may be negative to count from the end.
+pub fn parse_offset(input: &str) -> Result<i64, String> {
+    let input = input.trim();
+    let (negative, size) = match input.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, input),
+    };
+    let value =
+        i64::try_from(parse_size(size)?).map_err(|_| format!("Offset '{}' is too large", input))?;
+    Ok(if negative { -value } else { value })
+}
+
+/// Parse a percentage such as `10%` or `12.5`, between 0 and 100.
+pub fn parse_percent(input: &str) -> Result<f64, String> {
+    let input = input.trim();
+    let value: f64 = input
+        .strip_suffix('%')
+        .unwrap_or(input)
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid percentage '{}'", input))?;
+    if !(0.0..=100.0).contains(&value) {
+        return Err(format!("Percentage '{}' must be between 0 and 100", input));
+    }
+    Ok(value)
+}
+
+/// A counter increment: whole numbers use INCRBY, anything else INCRBYFLOAT.
+#[derive(Debug, Clone, Copy)]
+pub enum Amount {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Amount {
+    pub fn negated(self) -> Amount {
+        match self {
+            Amount::Integer(n) => Amount::Integer(-n),
+            Amount::Float(f) => Amount::Float(-f),
+        }
+    }
+}
+
+impl std::fmt::Display for Amount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Amount::Integer(n) => write!(f, "{}", n),
+            Amount::Float(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+/// Parse an increment such as `5`, `-2` or `0.5`.
+pub fn parse_amount(input: &str) -> Result<Amount, String> {
+    let input = input.trim();
+    if let Ok(n) = input.parse::<i64>() {
+        return Ok(Amount::Integer(n));
+    }
+    match input.parse::<f64>() {
+        Ok(f) if f.is_finite() => Ok(Amount::Float(f)),
+        _ => Err(format!("Invalid number '{}'", input)),
+    }
+}
+
+/// An inclusive range where either end may be left open.
+#[derive(Debug, Clone, Copy)]
+pub struct Bounds {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl Bounds {
+    pub fn contains(&self, value: u64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+/// Parse a `min-max` range, where either side may be empty (`1kb-`,
+/// `-10m`) and a single value matches exactly.
+fn parse_bounds(
+    input: &str,
+    parse: impl Fn(&str) -> Result<u64, String>,
+) -> Result<Bounds, String> {
+    let bound = |s: &str| {
+        let s = s.trim();
+        if s.is_empty() {
+            Ok(None)
+        } else {
+            parse(s).map(Some)
+        }
+    };
+    let bounds = match input.split_once('-') {
+        Some((min, max)) => Bounds {
+            min: bound(min)?,
+            max: bound(max)?,
+        },
+        None => {
+            let value = bound(input)?;
+            Bounds {
+                min: value,
+                max: value,
+            }
+        }
+    };
+    if let (Some(min), Some(max)) = (bounds.min, bounds.max) {
+        if min > max {
+            return Err(format!(
+                "Invalid range '{}': minimum is above maximum",
+                input
+            ));
+        }
+    }
+    Ok(bounds)
+}
+
+/// Parse a size range such as `1kb-10mb`.
+pub fn parse_size_range(input: &str) -> Result<Bounds, String> {
+    parse_bounds(input, parse_size)
+}
+
+/// Parse a duration range in whole seconds, such as `1m-1h`.
+pub fn parse_duration_range(input: &str) -> Result<Bounds, String> {
+    parse_bounds(input, |s| parse_duration(s).map(|d| d.as_secs()))
+}
+
+/// Render a RESP reply the way redis-cli does, with numbered and indented
+/// nested arrays.
+pub fn format_resp(value: &Value) -> String {
+    resp_lines(value).join("\n")
+}
+
+fn resp_lines(value: &Value) -> Vec<String> {
+    match value {
+        Value::Nil => vec!["(nil)".to_string()],
+        Value::Int(i) => vec![format!("(integer) {}", i)],
+        Value::Data(data) => vec![format!("{:?}", String::from_utf8_lossy(data))],
+        Value::Status(status) => vec![status.clone()],
+        Value::Okay => vec!["OK".to_string()],
+        Value::Bulk(items) if items.is_empty() => vec!["(empty array)".to_string()],
+        Value::Bulk(items) => {
+            let width = items.len().to_string().len();
+            let mut lines = Vec::new();
+            for (i, item) in items.iter().enumerate() {
+                let prefix = format!("{:>width$}) ", i + 1, width = width);
+                let indent = " ".repeat(prefix.len());
+                for (j, line) in resp_lines(item).into_iter().enumerate() {
+                    if j == 0 {
+                        lines.push(format!("{}{}", prefix, line));
+                    } else {
+                        lines.push(format!("{}{}", indent, line));
+                    }
+                }
+            }
+            lines
+        }
+    }
+}
+
+/// Split a command line the way redis-cli does: arguments are separated by
+/// whitespace, "double quotes" allow `\n`, `\t`, `\"`, `\\` and `\xHH`
+/// escapes, and 'single quotes' only `\'`. Arguments are bytes since
+/// `\xHH` can produce invalid UTF-8.
+pub fn split_command_line(line: &str) -> Result<Vec<Vec<u8>>, String> {
+    fn push(arg: &mut Vec<u8>, c: char) {
+        let mut buf = [0u8; 4];
+        arg.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+
+    let mut args = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&first) = chars.peek() else {
+            return Ok(args);
+        };
+
+        let mut arg = Vec::new();
+        if first == '"' || first == '\'' {
+            chars.next();
+            loop {
+                let c = chars
+                    .next()
+                    .ok_or_else(|| format!("Unbalanced quotes in: {}", line))?;
+                if c == first {
+                    break;
+                }
+                if c != '\\' {
+                    push(&mut arg, c);
+                    continue;
+                }
+                let escaped = chars
+                    .next()
+                    .ok_or_else(|| format!("Unbalanced quotes in: {}", line))?;
+                match (first, escaped) {
+                    ('\'', '\'') => push(&mut arg, '\''),
+                    ('\'', other) => {
+                        push(&mut arg, '\\');
+                        push(&mut arg, other);
+                    }
+                    (_, 'n') => arg.push(b'\n'),
+                    (_, 'r') => arg.push(b'\r'),
+                    (_, 't') => arg.push(b'\t'),
+                    (_, 'a') => arg.push(0x07),
+                    (_, 'b') => arg.push(0x08),
+                    (_, 'x') => {
+                        let hex: String = (0..2).filter_map(|_| chars.next()).collect();
+                        let byte = u8::from_str_radix(&hex, 16)
+                            .map_err(|_| format!("Invalid \\x escape in: {}", line))?;
+                        arg.push(byte);
+                    }
+                    (_, other) => push(&mut arg, other),
+                }
+            }
+            if chars.peek().is_some_and(|c| !c.is_whitespace()) {
+                return Err(format!(
+                    "Closing quote must be followed by a space in: {}",
+                    line
+                ));
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                push(&mut arg, c);
+            }
+        }
+        args.push(arg);
+    }
+}
+
+/// Match text against a Redis-style glob pattern (`*`, `?`, `[abc]`,
+/// `[a-z]`, `[^a]` and `\` escapes), as used by KEYS and SCAN MATCH.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_at(&pattern, &text)
+}
+
+fn glob_match_at(pattern: &[char], text: &[char]) -> bool {
+    let Some((&first, rest)) = pattern.split_first() else {
+        return text.is_empty();
+    };
+
+    match first {
+        '*' => (0..=text.len()).any(|skip| glob_match_at(rest, &text[skip..])),
+        '?' => !text.is_empty() && glob_match_at(rest, &text[1..]),
+        '[' => {
+            let Some(end) = rest.iter().position(|&c| c == ']') else {
+                // Unclosed bracket matches literally
+                return text.first() == Some(&'[') && glob_match_at(rest, &text[1..]);
+            };
+            let Some(&c) = text.first() else {
+                return false;
+            };
+            let (negate, class) = match rest[..end].split_first() {
+                Some(('^', class)) => (true, class),
+                _ => (false, &rest[..end]),
+            };
+            let mut matched = false;
+            let mut i = 0;
+            while i < class.len() {
+                if i + 2 < class.len() && class[i + 1] == '-' {
+                    matched |= class[i] <= c && c <= class[i + 2];
+                    i += 3;
+                } else {
+                    matched |= class[i] == c;
+                    i += 1;
+                }
+            }
+            matched != negate && glob_match_at(&rest[end + 1..], &text[1..])
+        }
+        '\\' if !rest.is_empty() => {
+            text.first() == Some(&rest[0]) && glob_match_at(&rest[1..], &text[1..])
+        }
+        c => text.first() == Some(&c) && glob_match_at(rest, &text[1..]),
+    }
+}
+
+/// How `get` shows string values.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ValueDisplay {
+    /// Text as-is, with unprintable bytes escaped
+    Escaped,
+    /// Bytes written to stdout unmodified
+    Raw,
+    Hex,
+    Base64,
+}
+
+/// Whether a value should be treated as binary rather than text: invalid
+/// UTF-8, or control characters other than common whitespace.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text
+            .chars()
+            .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t')),
+        Err(_) => true,
+    }
+}
+
+/// Render bytes as text that is safe to print, escaping control characters
+/// and invalid UTF-8 as `\xNN` the way redis-cli does. Newlines and tabs are
+/// kept so multi-line values stay readable.
+pub fn escape_bytes(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '\n' | '\t' => out.push(c),
+                c if c.is_control() => {
+                    let mut buf = [0u8; 4];
+                    for b in c.encode_utf8(&mut buf).bytes() {
+                        out.push_str(&format!("\\x{:02x}", b));
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+        for b in chunk.invalid() {
+            out.push_str(&format!("\\x{:02x}", b));
+        }
+    }
+    out
+}
+
+/// Format bytes as a hex dump with offsets and an ASCII column, like `xxd`.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, line)| {
+            let hex: Vec<String> = line.iter().map(|b| format!("{:02x}", b)).collect();
+            let ascii: String = line
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:08x}  {:<47}  {}", i * 16, hex.join(" "), ascii)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format a key name for display. Printable UTF-8 names are shown as-is;
+/// anything else is double-quoted with `\xNN` escapes, like redis-cli. The
+/// quoted form is accepted back by `key_bytes`, so it can be pasted into
+/// later commands.
+pub fn display_key(bytes: &[u8]) -> String {
+    if let Ok(name) = std::str::from_utf8(bytes) {
+        let quoted = name.len() >= 2 && name.starts_with('"') && name.ends_with('"');
+        if !quoted && !name.chars().any(char::is_control) {
+            return name.to_string();
+        }
+    }
+
+    let mut out = String::from("\"");
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() => {
+                    let mut buf = [0u8; 4];
+                    for b in c.encode_utf8(&mut buf).bytes() {
+                        out.push_str(&format!("\\x{:02x}", b));
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+        for b in chunk.invalid() {
+            out.push_str(&format!("\\x{:02x}", b));
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Turn a key argument into the bytes sent to Redis. A double-quoted name
+/// has its escapes (`\xNN`, `\n`, `\r`, `\t`, `\"`, `\\`) decoded, so keys
+/// shown by `display_key` round-trip; anything else is used literally.
+pub fn key_bytes(name: &str) -> Vec<u8> {
+    let inner = match name.strip_prefix('"').and_then(|n| n.strip_suffix('"')) {
+        Some(inner) => inner,
+        None => return name.as_bytes().to_vec(),
+    };
+
+    let mut out = Vec::with_capacity(inner.len());
+    let mut bytes = inner.bytes();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'n') => out.push(b'\n'),
+            Some(b'r') => out.push(b'\r'),
+            Some(b't') => out.push(b'\t'),
+            Some(b'"') => out.push(b'"'),
+            Some(b'\\') => out.push(b'\\'),
+            Some(b'x') => {
+                let hex: Vec<u8> = bytes.by_ref().take(2).collect();
+                match std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+                {
+                    Some(byte) if hex.len() == 2 => out.push(byte),
+                    // Not a valid escape, so this was not a quoted name
+                    _ => return name.as_bytes().to_vec(),
+                }
+            }
+            _ => return name.as_bytes().to_vec(),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(line: &str) -> Vec<String> {
+        split_command_line(line)
+            .unwrap()
+            .into_iter()
+            .map(|arg| String::from_utf8(arg).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("user:*", "user:42"));
+        assert!(glob_match("user:*:name", "user:42:name"));
+        assert!(!glob_match("user:*:name", "user:42:email"));
+        assert!(glob_match("h?llo", "hello"));
+        assert!(!glob_match("h?llo", "hllo"));
+        assert!(glob_match("*é*", "café au lait"));
+    }
+
+    #[test]
+    fn glob_classes() {
+        assert!(glob_match("h[ae]llo", "hallo"));
+        assert!(!glob_match("h[ae]llo", "hillo"));
+        assert!(glob_match("h[^e]llo", "hallo"));
+        assert!(!glob_match("h[^e]llo", "hello"));
+        assert!(glob_match("key[0-9]", "key7"));
+        assert!(!glob_match("key[0-9]", "keyx"));
+        assert!(!glob_match("key[0-9]", "key"));
+        // An unclosed bracket is a literal
+        assert!(glob_match("a[b", "a[b"));
+    }
+
+    #[test]
+    fn glob_escapes() {
+        assert!(glob_match("a\\*", "a*"));
+        assert!(!glob_match("a\\*", "ab"));
+        assert!(glob_match("what\\?", "what?"));
+        assert!(!glob_match("what\\?", "whats"));
+    }
+
+    #[test]
+    fn split_plain_arguments() {
+        assert_eq!(split("  SET  key   value "), ["SET", "key", "value"]);
+        assert!(split("").is_empty());
+        assert!(split("   ").is_empty());
+    }
+
+    #[test]
+    fn split_quoted_arguments() {
+        assert_eq!(split(r#"SET k "hello world""#), ["SET", "k", "hello world"]);
+        assert_eq!(split(r#"SET k """#), ["SET", "k", ""]);
+        assert_eq!(split(r#"ECHO "a\nb\t\"c\"\\""#), ["ECHO", "a\nb\t\"c\"\\"]);
+        assert_eq!(split(r"ECHO 'it\'s \n'"), ["ECHO", "it's \\n"]);
+    }
+
+    #[test]
+    fn split_hex_escapes_to_bytes() {
+        let args = split_command_line(r#"SET k "\xff\x00a""#).unwrap();
+        assert_eq!(args[2], [0xff, 0x00, b'a']);
+        assert!(split_command_line(r#"SET k "\xzz""#).is_err());
+    }
+
+    #[test]
+    fn split_rejects_bad_quoting() {
+        assert!(split_command_line(r#"SET k "open"#).is_err());
+        assert!(split_command_line("SET k 'open").is_err());
+        assert!(split_command_line(r#"SET k "a"b"#).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use colored::*;
+use std::io::{self, IsTerminal, Write};
+
+use crate::output;
+use solt::config::AppConfig;
+use solt::error::AppError;
+
+/// Ask for the environment name to be typed before a destructive command
+/// runs against a protected environment. Unprotected environments pass
+/// straight through, so `--confirm` keeps working there.
+pub fn confirm_protected(environment: Option<&str>, action: &str) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment);
+    let protected = config
+        .get_environment(&env_name)
+        .is_some_and(|env| env.protected);
+    if !protected {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "⚠ '{}' is a protected environment and this will {}.",
+            env_name, action
+        )
+        .red()
+        .bold()
+    );
+    print!("Type the environment name to continue: ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim() != env_name {
+        return Err(AppError::Aborted(format!(
+            "Confirmation did not match '{}', nothing was changed",
+            env_name
+        )));
+    }
+
+    Ok(())
+}
+
+/// Refuse a write command against an environment marked `read_only`.
+pub fn ensure_writable(environment: Option<&str>, command: &str) -> Result<(), AppError> {
+    let config = AppConfig::load()?;
+    let env_name = config.resolve_environment(environment);
+    let read_only = config
+        .get_environment(&env_name)
+        .is_some_and(|env| env.read_only);
+    if read_only {
+        return Err(AppError::ConfigError(format!(
+            "Environment '{}' is read-only, '{}' is not allowed",
+            env_name, command
+        )));
+    }
+    Ok(())
+}
+
+/// Print a colored banner naming the environment a command is about to
+/// use, on stderr so piped output stays clean. Skipped with `--quiet`, when
+/// stderr is not a terminal, and for environments whose banner is "none".
+pub fn banner(environment: Option<&str>) {
+    if output::is_quiet() || !io::stderr().is_terminal() {
+        return;
+    }
+    let Ok(config) = AppConfig::load() else {
+        return;
+    };
+    let env_name = config.resolve_environment(environment);
+    let Some(env) = config.get_environment(&env_name) else {
+        return;
+    };
+    let Some(color) = env.banner_color() else {
+        return;
+    };
+
+    let mut details = format!(
+        "{}:{} db {}",
+        env.config.host, env.config.port, env.config.db
+    );
+    if env.read_only {
+        details.push_str(" · read-only");
+    } else if env.protected {
+        details.push_str(" · protected");
+    }
+    if output::is_plain() {
+        eprintln!("[{}] {}", env.name.to_uppercase(), details);
+    } else {
+        eprintln!(
+            "{} {}",
+            format!(" {} ", env.name.to_uppercase())
+                .bold()
+                .white()
+                .on_color(color),
+            details.color(color)
+        );
+    }
+}
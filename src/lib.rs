@@ -0,0 +1,23 @@
+//! Solt's environment handling and typed Redis helpers, usable from other
+//! Rust tools as well as through the `solt` binary.
+//!
+//! [`AppConfig`] reads the same `~/.solt/config.toml` the CLI uses, and
+//! [`RedisClient::for_environment`] connects to one of its environments
+//! with that environment's credentials, TLS and timeout. The client's
+//! methods, and the subcommands under [`commands`], return typed results
+//! (key lists, [`redis_client::KeyInfo`], reports) and never print; the
+//! `solt` binary renders them to the terminal.
+
+pub mod cli;
+pub mod commands;
+pub mod config;
+pub mod decode;
+pub mod error;
+pub mod format;
+pub mod query;
+pub mod redis_client;
+pub mod trace;
+
+pub use config::{AppConfig, Environment, RedisConfig};
+pub use error::AppError;
+pub use redis_client::RedisClient;
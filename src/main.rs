@@ -1,13 +1,13 @@
 mod app;
-mod cli;
-mod commands;
-mod config;
-mod error;
-mod redis_client;
+mod guard;
+mod output;
 
-use error::AppError;
+use colored::*;
 
 #[tokio::main]
-async fn main() -> Result<(), AppError> {
-    app::run().await
+async fn main() {
+    if let Err(e) = app::run().await {
+        eprintln!("{}", format!("Error: {}", e).red());
+        std::process::exit(e.exit_code());
+    }
 }
@@ -0,0 +1,108 @@
+use colored::*;
+use indicatif::{ProgressBar, ProgressDrawTarget};
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use solt::config::OutputFormat;
+use solt::error::AppError;
+
+/// Print `value` as JSON or YAML if that is the requested output format.
+/// Returns false for the human-readable formats, in which case the caller
+/// prints its own tables.
+pub fn print_structured<T: Serialize + ?Sized>(
+    format: &OutputFormat,
+    value: &T,
+) -> Result<bool, AppError> {
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(value)?);
+            Ok(true)
+        }
+        OutputFormat::Yaml => {
+            let yaml = serde_yaml::to_string(value).map_err(|e| anyhow::anyhow!(e))?;
+            print!("{}", yaml);
+            Ok(true)
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => Ok(false),
+    }
+}
+
+/// Set when output should be free of colors and decoration.
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Set by `--quiet`: only results and errors are printed.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Decide once at startup how output is styled. Colors and decorative
+/// rules are dropped for `--no-color`, when NO_COLOR is set to a non-empty
+/// value, or when stdout is not a terminal. `--quiet` also drops them, along
+/// with confirmations and progress bars.
+pub fn init(no_color: bool, quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let plain = no_color || quiet || no_color_env || !std::io::stdout().is_terminal();
+    if plain {
+        colored::control::set_override(false);
+    }
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Whether informational output should be suppressed.
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Print a confirmation that an operation succeeded, unless quiet.
+pub fn success(message: &str) {
+    if !is_quiet() {
+        println!("{}", format!("✓ {}", message).green().bold());
+    }
+}
+
+/// A progress bar of `len` steps, hidden when quiet.
+pub fn progress_bar(len: u64) -> ProgressBar {
+    let progress = ProgressBar::new(len);
+    if is_quiet() {
+        progress.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    progress
+}
+
+/// A spinner for work of unknown length, hidden when quiet.
+pub fn spinner() -> ProgressBar {
+    let progress = ProgressBar::new_spinner();
+    if is_quiet() {
+        progress.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    progress
+}
+
+/// Whether output is going somewhere that should not get styling.
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Print a horizontal rule under a section heading.
+pub fn rule(width: usize) {
+    if !is_plain() {
+        println!("{}", "=".repeat(width));
+    }
+}
+
+/// Print a lighter rule between entries within a section.
+pub fn thin_rule(width: usize) {
+    if !is_plain() {
+        println!("{}", "-".repeat(width));
+    }
+}
+
+/// Clear the terminal before redrawing a watch screen. When the output is
+/// not a terminal, refreshes are separated by a blank line instead.
+pub fn clear_screen() {
+    if is_plain() {
+        println!();
+    } else {
+        print!("\x1B[2J\x1B[H");
+    }
+}
@@ -0,0 +1,215 @@
+use serde_json::Value as JsonValue;
+
+/// One step of a query path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// `.name` or `."name"` / `["name"]`
+    Field(String),
+    /// `[0]`, with negative indexes counting from the end
+    Index(i64),
+    /// `[]`, every element of an array or value of an object
+    Iterate,
+}
+
+/// Parse a jq-style path such as `.user.addresses[0].city`. Supports field
+/// access (bare or quoted), array indexes, and `[]` to iterate; `.` alone
+/// is the whole value.
+pub fn parse_query(expr: &str) -> Result<Vec<Segment>, String> {
+    let chars: Vec<char> = expr.trim().chars().collect();
+    if chars.first() != Some(&'.') {
+        return Err(format!("query must start with '.': {}", expr));
+    }
+
+    let mut segments = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                i += 1;
+                match chars.get(i) {
+                    Some('"') => {
+                        let (name, next) = parse_quoted(&chars, i)?;
+                        segments.push(Segment::Field(name));
+                        i = next;
+                    }
+                    Some(c) if is_name_char(*c) => {
+                        let start = i;
+                        while i < chars.len() && is_name_char(chars[i]) {
+                            i += 1;
+                        }
+                        segments.push(Segment::Field(chars[start..i].iter().collect()));
+                    }
+                    // `.` on its own, or `.[0]`
+                    None | Some('[') => {}
+                    Some(c) => return Err(format!("unexpected '{}' after '.'", c)),
+                }
+            }
+            '[' => {
+                i += 1;
+                match chars.get(i) {
+                    Some(']') => {
+                        segments.push(Segment::Iterate);
+                        i += 1;
+                    }
+                    Some('"') => {
+                        let (name, next) = parse_quoted(&chars, i)?;
+                        if chars.get(next) != Some(&']') {
+                            return Err("expected ']' after field name".to_string());
+                        }
+                        segments.push(Segment::Field(name));
+                        i = next + 1;
+                    }
+                    _ => {
+                        let start = i;
+                        while i < chars.len() && chars[i] != ']' {
+                            i += 1;
+                        }
+                        if i == chars.len() {
+                            return Err("unclosed '['".to_string());
+                        }
+                        let text: String = chars[start..i].iter().collect();
+                        let index = text
+                            .trim()
+                            .parse::<i64>()
+                            .map_err(|_| format!("invalid array index: {}", text))?;
+                        segments.push(Segment::Index(index));
+                        i += 1;
+                    }
+                }
+            }
+            c => return Err(format!("unexpected '{}' in query", c)),
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Apply a parsed path to a value. Missing fields and out-of-range indexes
+/// yield null, as in jq; indexing into the wrong kind of value is an error.
+pub fn apply_query(value: &JsonValue, path: &[Segment]) -> Result<Vec<JsonValue>, String> {
+    let mut current = vec![value.clone()];
+    for segment in path {
+        let mut next = Vec::new();
+        for value in current {
+            match (segment, value) {
+                (_, JsonValue::Null) if *segment != Segment::Iterate => next.push(JsonValue::Null),
+                (Segment::Field(name), JsonValue::Object(mut map)) => {
+                    next.push(map.remove(name).unwrap_or(JsonValue::Null))
+                }
+                (Segment::Index(index), JsonValue::Array(items)) => {
+                    let position = if *index < 0 {
+                        items.len() as i64 + index
+                    } else {
+                        *index
+                    };
+                    let item = usize::try_from(position)
+                        .ok()
+                        .and_then(|p| items.get(p).cloned());
+                    next.push(item.unwrap_or(JsonValue::Null));
+                }
+                (Segment::Iterate, JsonValue::Array(items)) => next.extend(items),
+                (Segment::Iterate, JsonValue::Object(map)) => next.extend(map.into_values()),
+                (segment, value) => {
+                    return Err(format!(
+                        "cannot apply {} to {}",
+                        describe(segment),
+                        kind(&value)
+                    ))
+                }
+            }
+        }
+        current = next;
+    }
+    Ok(current)
+}
+
+fn parse_quoted(chars: &[char], start: usize) -> Result<(String, usize), String> {
+    let mut name = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '"' => return Ok((name, i + 1)),
+            '\\' if i + 1 < chars.len() => {
+                name.push(chars[i + 1]);
+                i += 2;
+            }
+            c => {
+                name.push(c);
+                i += 1;
+            }
+        }
+    }
+    Err("unclosed '\"' in query".to_string())
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '$'
+}
+
+fn describe(segment: &Segment) -> String {
+    match segment {
+        Segment::Field(name) => format!("field \"{}\"", name),
+        Segment::Index(index) => format!("index [{}]", index),
+        Segment::Iterate => "[]".to_string(),
+    }
+}
+
+fn kind(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "a boolean",
+        JsonValue::Number(_) => "a number",
+        JsonValue::String(_) => "a string",
+        JsonValue::Array(_) => "an array",
+        JsonValue::Object(_) => "an object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str) -> Segment {
+        Segment::Field(name.to_string())
+    }
+
+    #[test]
+    fn parses_identity() {
+        assert_eq!(parse_query("."), Ok(vec![]));
+        assert_eq!(parse_query("  .  "), Ok(vec![]));
+    }
+
+    #[test]
+    fn parses_fields_and_indexes() {
+        assert_eq!(
+            parse_query(".user.addresses[0].city"),
+            Ok(vec![
+                field("user"),
+                field("addresses"),
+                Segment::Index(0),
+                field("city")
+            ])
+        );
+        assert_eq!(parse_query(".[-1]"), Ok(vec![Segment::Index(-1)]));
+        assert_eq!(
+            parse_query(".items[].id"),
+            Ok(vec![field("items"), Segment::Iterate, field("id")])
+        );
+        assert_eq!(parse_query(".[]"), Ok(vec![Segment::Iterate]));
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        assert_eq!(
+            parse_query(r#"."first name"["a.b"]"#),
+            Ok(vec![field("first name"), field("a.b")])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        for expr in ["user", ".a[0", ".a[x]", ".a[\"b\"", ".!a", ".a b", ""] {
+            assert!(parse_query(expr).is_err(), "{}", expr);
+        }
+    }
+}
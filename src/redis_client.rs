@@ -1,21 +1,91 @@
-use crate::config::RedisConfig;
+use crate::config::{AppConfig, RedisConfig};
+use crate::error::AppError;
+use crate::format::{display_key, key_bytes};
+use crate::trace;
 use anyhow::{anyhow, Result};
-use colored::*;
-use redis::{aio::Connection, AsyncCommands, Value};
+use redis::{
+    aio::{AsyncStream, Connection, ConnectionLike, Monitor, PubSub},
+    Arg, AsyncCommands, Cmd, ConnectionAddr, ErrorKind, IntoConnectionInfo, Pipeline, RedisError,
+    RedisFuture, RedisResult, Value,
+};
+use serde::Serialize;
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
 
 pub struct RedisClient {
-    pub connection: Connection,
+    pub connection: ClientConnection,
+    /// Whether an LFU eviction policy is active, looked up on first use
+    lfu_policy: Option<bool>,
 }
 
 impl RedisClient {
     pub async fn connect(config: RedisConfig) -> Result<Self> {
-        let client = redis::Client::open(config.to_redis_url())?;
-        let connection = client.get_async_connection().await?;
+        let info = config.to_redis_url().into_connection_info()?;
+        let timeout = config.command_timeout();
+        let recording = Recording::default();
+        let open = async {
+            let ConnectionAddr::Tcp(host, port) = &info.addr else {
+                return Err(anyhow!("Only plain TCP connections are supported"));
+            };
+            let stream = RecordingStream {
+                stream: TcpStream::connect((host.as_str(), *port)).await?,
+                recording: recording.clone(),
+            };
+            let stream: Pin<Box<dyn AsyncStream + Send + Sync>> = Box::pin(stream);
+            Ok(Connection::new(&info.redis, stream).await?)
+        };
+        let started = Instant::now();
+        let connection = match timeout {
+            Some(limit) => tokio::time::timeout(limit, open).await.map_err(|_| {
+                timed_out(format!(
+                    "Timed out connecting to {}:{} after {}s",
+                    config.host,
+                    config.port,
+                    limit.as_secs()
+                ))
+            })??,
+            None => open.await?,
+        };
+        trace::connected(&config.host, config.port, config.db, started.elapsed());
+
+        Ok(Self {
+            connection: ClientConnection {
+                inner: connection,
+                timeout,
+                recording,
+                broken: false,
+            },
+            lfu_policy: None,
+        })
+    }
+
+    /// Connect to a configured environment, falling back to the default
+    /// environment and then "dev" like the CLI does.
+    pub async fn for_environment(environment: Option<&str>) -> Result<Self, AppError> {
+        let config = AppConfig::load()?;
+        let redis_config = config.redis_config(&config.resolve_environment(environment))?;
+        Ok(Self::connect(redis_config).await?)
+    }
 
-        Ok(Self { connection })
+    /// Turn this client into a pub/sub connection. Regular commands can no
+    /// longer be sent on it afterwards.
+    pub fn into_pubsub(self) -> PubSub {
+        self.connection.inner.into_pubsub()
+    }
+
+    /// Turn this client into a MONITOR connection that streams every command
+    /// the server processes.
+    pub async fn into_monitor(self) -> Result<Monitor> {
+        let mut monitor = self.connection.inner.into_monitor();
+        monitor.monitor().await?;
+        Ok(monitor)
     }
 
     pub async fn ping(&mut self) -> Result<String> {
@@ -23,20 +93,161 @@ impl RedisClient {
         Ok(result)
     }
 
-    pub async fn info(&mut self) -> Result<HashMap<String, String>> {
-        let result: String = redis::cmd("INFO").query_async(&mut self.connection).await?;
+    /// Send an arbitrary command and return the raw reply.
+    pub async fn raw_command(&mut self, args: &[String]) -> Result<Value> {
+        let (name, rest) = args
+            .split_first()
+            .ok_or_else(|| anyhow!("No command given"))?;
+        let mut cmd = redis::cmd(name);
+        for arg in rest {
+            cmd.arg(arg);
+        }
+        let value: Value = cmd.query_async(&mut self.connection).await?;
+        Ok(value)
+    }
 
-        let mut info_map = HashMap::new();
-        for line in result.lines() {
-            if let Some((key, value)) = line.split_once(':') {
-                info_map.insert(key.to_string(), value.to_string());
+    /// Send `commands` as one pipeline and return each command's own reply.
+    /// The driver fails the whole pipeline when any command errors, so in
+    /// that case the replies are read back from the raw bytes instead.
+    pub async fn pipeline_each(&mut self, commands: &[Cmd]) -> Result<Vec<RedisResult<Value>>> {
+        let mut pipe = redis::pipe();
+        for cmd in commands {
+            pipe.add_command(cmd.clone());
+        }
+        self.connection.start_recording();
+        let replies = pipe
+            .query_async::<_, Vec<Value>>(&mut self.connection)
+            .await;
+        let raw = self.connection.take_recording();
+        match replies {
+            Ok(replies) => Ok(replies.into_iter().map(Ok).collect()),
+            Err(error) if error.is_io_error() || error.is_timeout() => Err(error.into()),
+            Err(error) => {
+                let mut pos = 0;
+                (0..commands.len())
+                    .map(|_| read_reply(&raw, &mut pos))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| anyhow!("Could not read the pipeline replies: {}", error))
             }
         }
+    }
+
+    /// Run `commands` as one MULTI/EXEC transaction. Commands are queued
+    /// one at a time, so one the server refuses to queue (unknown command,
+    /// wrong number of arguments) is reported by position and the
+    /// transaction discarded before anything runs.
+    pub async fn transaction(&mut self, commands: &[Vec<Vec<u8>>]) -> Result<TransactionOutcome> {
+        redis::cmd("MULTI")
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+
+        for (index, args) in commands.iter().enumerate() {
+            let mut cmd = redis::cmd(&String::from_utf8_lossy(&args[0]));
+            for arg in &args[1..] {
+                cmd.arg(arg.as_slice());
+            }
+            if let Err(error) = cmd.query_async::<_, Value>(&mut self.connection).await {
+                if error.kind() != redis::ErrorKind::ResponseError {
+                    return Err(error.into());
+                }
+                redis::cmd("DISCARD")
+                    .query_async::<_, ()>(&mut self.connection)
+                    .await?;
+                return Ok(TransactionOutcome::Rejected { index, error });
+            }
+        }
+
+        self.connection.start_recording();
+        let reply = redis::cmd("EXEC")
+            .query_async::<_, Value>(&mut self.connection)
+            .await;
+        let raw = self.connection.take_recording();
+        match reply {
+            Ok(Value::Bulk(replies)) => Ok(TransactionOutcome::Executed(
+                replies.into_iter().map(Ok).collect(),
+            )),
+            Ok(other) => Err(anyhow!("Unexpected EXEC reply: {:?}", other)),
+            Err(error) if error.is_io_error() || error.is_timeout() => Err(error.into()),
+            // The driver turns an error reply anywhere in the EXEC array
+            // into an error for the whole reply, so read it item by item
+            Err(error) => match read_array_items(&raw, &mut 0) {
+                Some(Some(replies)) => Ok(TransactionOutcome::Executed(replies)),
+                _ => Err(error.into()),
+            },
+        }
+    }
+
+    /// WATCH a key so the next MULTI/EXEC on this connection is aborted if
+    /// anyone changes it first.
+    pub async fn watch(&mut self, key: &str) -> Result<()> {
+        redis::cmd("WATCH")
+            .arg(key_bytes(key))
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn unwatch(&mut self) -> Result<()> {
+        redis::cmd("UNWATCH")
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+
+    /// Send `commands` as one MULTI/EXEC transaction that only applies if no
+    /// key WATCHed on this connection changed first. Returns false when it
+    /// was aborted because one did.
+    pub async fn exec_if_unchanged(&mut self, commands: Vec<redis::Cmd>) -> Result<bool> {
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for cmd in commands {
+            pipe.add_command(cmd).ignore();
+        }
+        let reply: Value = pipe.query_async(&mut self.connection).await?;
+        Ok(!matches!(reply, Value::Nil))
+    }
+
+    pub async fn swapdb(&mut self, first: u8, second: u8) -> Result<()> {
+        redis::cmd("SWAPDB")
+            .arg(first)
+            .arg(second)
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+        Ok(())
+    }
 
-        Ok(info_map)
+    pub async fn dbsize(&mut self) -> Result<u64> {
+        let size: u64 = redis::cmd("DBSIZE")
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(size)
+    }
+
+    pub async fn info(&mut self) -> Result<HashMap<String, String>> {
+        let result: String = redis::cmd("INFO").query_async(&mut self.connection).await?;
+        Ok(parse_info(&result))
+    }
+
+    /// INFO split into its `# Section` groups, in server order. `section`
+    /// may be a single section name, `default`, `all` or `everything`.
+    pub async fn info_sections(&mut self, section: &str) -> Result<Vec<InfoSection>> {
+        let result: String = redis::cmd("INFO")
+            .arg(section)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(parse_info_sections(&result))
+    }
+
+    /// INFO for a single section, such as `commandstats` or `replication`,
+    /// including sections left out of the default INFO reply.
+    pub async fn info_section(&mut self, section: &str) -> Result<HashMap<String, String>> {
+        let result: String = redis::cmd("INFO")
+            .arg(section)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(parse_info(&result))
     }
 
-    #[allow(dead_code)]
     pub async fn select_db(&mut self, db: u8) -> Result<()> {
         redis::cmd("SELECT")
             .arg(db)
@@ -46,59 +257,241 @@ impl RedisClient {
     }
 
     pub async fn keys(&mut self, pattern: &str) -> Result<Vec<String>> {
-        let keys: Vec<String> = self.connection.keys(pattern).await?;
+        let keys: Vec<Vec<u8>> = self.connection.keys(pattern).await?;
+        Ok(keys.iter().map(|k| display_key(k)).collect())
+    }
+
+    pub async fn scan_keys(&mut self, pattern: &str) -> Result<Vec<String>> {
+        let mut iter: redis::AsyncIter<Vec<u8>> = self.connection.scan_match(pattern).await?;
+        let mut keys = Vec::new();
+        while let Some(key) = iter.next_item().await {
+            keys.push(display_key(&key));
+        }
+        // SCAN may return a key more than once
+        keys.sort();
+        keys.dedup();
         Ok(keys)
     }
 
+    /// Type and memory usage of many keys in one pipelined round trip. Keys
+    /// that no longer exist come back as type "none".
+    pub async fn type_and_memory(&mut self, keys: &[String]) -> Result<Vec<(String, Option<u64>)>> {
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.cmd("TYPE").arg(key_bytes(key));
+            pipe.cmd("MEMORY").arg("USAGE").arg(key_bytes(key));
+        }
+        let replies: Vec<Value> = pipe.query_async(&mut self.connection).await?;
+
+        Ok(replies
+            .chunks(2)
+            .map(|pair| {
+                let key_type = value_to_string(&pair[0]).unwrap_or_else(|| "none".to_string());
+                let memory = match pair.get(1) {
+                    Some(Value::Int(bytes)) => Some(*bytes as u64),
+                    _ => None,
+                };
+                (key_type, memory)
+            })
+            .collect())
+    }
+
+    /// Fetch the values of many keys in two pipelined round trips: one for
+    /// TYPE and one for the type-specific read. Keys that vanished or hold a
+    /// type without a JSON form (streams) come back with `None`, but their
+    /// digest still covers the raw reply.
+    pub async fn fetch_values(&mut self, keys: &[String]) -> Result<Vec<KeyValue>> {
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.cmd("TYPE").arg(key_bytes(key));
+        }
+        let types: Vec<String> = pipe.query_async(&mut self.connection).await?;
+
+        let mut pipe = redis::pipe();
+        for (key, key_type) in keys.iter().zip(&types) {
+            match key_type.as_str() {
+                "string" => pipe.cmd("GET").arg(key_bytes(key)),
+                "hash" => pipe.cmd("HGETALL").arg(key_bytes(key)),
+                "list" => pipe.cmd("LRANGE").arg(key_bytes(key)).arg(0).arg(-1),
+                "set" => pipe.cmd("SMEMBERS").arg(key_bytes(key)),
+                "zset" => pipe
+                    .cmd("ZRANGE")
+                    .arg(key_bytes(key))
+                    .arg(0)
+                    .arg(-1)
+                    .arg("WITHSCORES"),
+                "stream" => pipe.cmd("XRANGE").arg(key_bytes(key)).arg("-").arg("+"),
+                // Keep replies aligned with keys
+                _ => pipe.cmd("EXISTS").arg(key_bytes(key)),
+            };
+        }
+        let replies: Vec<Value> = pipe.query_async(&mut self.connection).await?;
+
+        Ok(keys
+            .iter()
+            .zip(types)
+            .zip(replies)
+            .map(|((key, key_type), reply)| {
+                let value = value_to_json(&key_type, &reply);
+                let digest = reply_digest(&key_type, &reply);
+                KeyValue {
+                    key: key.clone(),
+                    key_type,
+                    value,
+                    digest,
+                }
+            })
+            .collect())
+    }
+
+    /// Run one SCAN step, returning the next cursor (0 when done) and the
+    /// keys in this page. Unlike `scan_keys` this never holds the whole
+    /// keyspace, but a key may appear in more than one page. `key_type`
+    /// uses SCAN's TYPE option, which servers before Redis 6 reject.
+    pub async fn scan_page(
+        &mut self,
+        cursor: u64,
+        pattern: &str,
+        count: usize,
+        key_type: Option<&str>,
+    ) -> Result<(u64, Vec<String>)> {
+        let mut cmd = redis::cmd("SCAN");
+        cmd.arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(count);
+        if let Some(key_type) = key_type {
+            cmd.arg("TYPE").arg(key_type);
+        }
+        let (next, keys): (u64, Vec<Vec<u8>>) = cmd.query_async(&mut self.connection).await?;
+        Ok((next, keys.iter().map(|k| display_key(k)).collect()))
+    }
+
+    pub async fn key_type(&mut self, key: &str) -> Result<String> {
+        let key_type: String = redis::cmd("TYPE")
+            .arg(key_bytes(key))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(key_type)
+    }
+
     pub async fn key_info(&mut self, key: &str) -> Result<KeyInfo> {
+        let lfu = self.lfu_policy().await?;
         let mut pipe = redis::pipe();
-        pipe.atomic()
-            .cmd("TYPE")
-            .arg(key)
-            .cmd("TTL")
-            .arg(key)
-            .cmd("MEMORY")
-            .arg("USAGE")
-            .arg(key)
-            .cmd("OBJECT")
-            .arg("ENCODING")
-            .arg(key);
+        pipe.atomic();
+        push_key_info_commands(&mut pipe, key, lfu);
 
         let results: Vec<Value> = pipe.query_async(&mut self.connection).await?;
+        Ok(parse_key_info(key, &results, lfu))
+    }
 
-        let key_type = match &results[0] {
-            Value::Data(ref data) => String::from_utf8_lossy(data).to_string(),
-            Value::Status(ref status) => status.to_string(),
-            _ => "unknown".to_string(),
-        };
+    /// Type, TTL, memory usage, encoding and idle time (or access frequency
+    /// under LFU) of many keys, sent as a single non-transactional pipeline.
+    pub async fn key_infos(&mut self, keys: &[String]) -> Result<Vec<KeyInfo>> {
+        let lfu = self.lfu_policy().await?;
+        let mut pipe = redis::pipe();
+        for key in keys {
+            push_key_info_commands(&mut pipe, key, lfu);
+        }
 
-        let ttl = match &results[1] {
-            Value::Int(ttl) => Some(*ttl),
-            _ => None,
-        };
+        let results: Vec<Value> = pipe.query_async(&mut self.connection).await?;
+        Ok(keys
+            .iter()
+            .zip(results.chunks(5))
+            .map(|(key, results)| parse_key_info(key, results, lfu))
+            .collect())
+    }
 
-        let memory_usage = match &results[2] {
-            Value::Int(usage) => Some(*usage as usize),
-            _ => None,
-        };
+    /// Lengths of many keys whose types are already known (see
+    /// `key_length`), pipelined in one round trip.
+    pub async fn key_lengths(&mut self, infos: &[KeyInfo]) -> Result<Vec<Option<usize>>> {
+        let mut pipe = redis::pipe();
+        let mut queued = Vec::with_capacity(infos.len());
+        for info in infos {
+            let cmd = match info.key_type.as_str() {
+                "string" => "STRLEN",
+                "hash" => "HLEN",
+                "list" => "LLEN",
+                "set" => "SCARD",
+                "zset" => "ZCARD",
+                "stream" => "XLEN",
+                _ => {
+                    queued.push(false);
+                    continue;
+                }
+            };
+            pipe.cmd(cmd).arg(key_bytes(&info.key));
+            queued.push(true);
+        }
+        if !queued.contains(&true) {
+            return Ok(vec![None; infos.len()]);
+        }
 
-        let encoding = match &results[3] {
-            Value::Data(ref data) => String::from_utf8_lossy(data).to_string(),
-            Value::Status(ref status) => status.to_string(),
-            _ => "unknown".to_string(),
+        let mut lengths: std::vec::IntoIter<usize> = pipe
+            .query_async::<_, Vec<usize>>(&mut self.connection)
+            .await?
+            .into_iter();
+        Ok(queued
+            .into_iter()
+            .map(|queued| if queued { lengths.next() } else { None })
+            .collect())
+    }
+
+    /// Whether the server runs an LFU `maxmemory-policy`. OBJECT FREQ only
+    /// works under LFU and OBJECT IDLETIME only without it.
+    pub async fn lfu_policy(&mut self) -> Result<bool> {
+        if let Some(lfu) = self.lfu_policy {
+            return Ok(lfu);
+        }
+        let memory = self.info_section("memory").await?;
+        let lfu = memory
+            .get("maxmemory_policy")
+            .is_some_and(|policy| policy.contains("lfu"));
+        self.lfu_policy = Some(lfu);
+        Ok(lfu)
+    }
+
+    /// Number of elements (or bytes for strings) held by a key of the given type.
+    pub async fn key_length(&mut self, key: &str, key_type: &str) -> Result<Option<usize>> {
+        let cmd = match key_type {
+            "string" => "STRLEN",
+            "hash" => "HLEN",
+            "list" => "LLEN",
+            "set" => "SCARD",
+            "zset" => "ZCARD",
+            "stream" => "XLEN",
+            _ => return Ok(None),
         };
+        let len: usize = redis::cmd(cmd)
+            .arg(key_bytes(key))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(Some(len))
+    }
 
-        Ok(KeyInfo {
-            key: key.to_string(),
-            key_type,
-            ttl,
-            memory_usage,
-            encoding,
-        })
+    /// Seconds since the key was last accessed. Unavailable when an LFU
+    /// eviction policy is active.
+    pub async fn idle_time(&mut self, key: &str) -> Result<Option<i64>> {
+        let result: redis::RedisResult<Option<i64>> = redis::cmd("OBJECT")
+            .arg("IDLETIME")
+            .arg(key_bytes(key))
+            .query_async(&mut self.connection)
+            .await;
+        match result {
+            Ok(idle) => Ok(idle),
+            Err(e) if e.kind() == redis::ErrorKind::ResponseError => Ok(None),
+            Err(e) => Err(e.into()),
+        }
     }
 
     pub async fn get_string(&mut self, key: &str) -> Result<Option<String>> {
-        let value: Option<String> = self.connection.get(key).await?;
+        let value: Option<String> = self.connection.get(key_bytes(key)).await?;
+        Ok(value)
+    }
+
+    pub async fn get_bytes(&mut self, key: &str) -> Result<Option<Vec<u8>>> {
+        let value: Option<Vec<u8>> = self.connection.get(key_bytes(key)).await?;
         Ok(value)
     }
 
@@ -108,35 +501,199 @@ impl RedisClient {
         value: &str,
         ttl: Option<Duration>,
     ) -> Result<()> {
+        self.set_bytes(key, value.as_bytes(), ttl).await
+    }
+
+    /// Overwrite an existing key's value, keeping its TTL. Returns false if
+    /// the key no longer exists.
+    pub async fn replace_bytes(&mut self, key: &str, value: &[u8]) -> Result<bool> {
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(key_bytes(key))
+            .arg(value)
+            .arg("XX")
+            .arg("KEEPTTL")
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(reply.is_some())
+    }
+
+    /// [`replace_bytes`](Self::replace_bytes) inside MULTI/EXEC, so it only
+    /// applies if no key WATCHed on this connection changed in the meantime.
+    /// Returns `None` when the transaction was aborted because one did,
+    /// otherwise whether the value was written. With `must_exist` false the
+    /// key is created if it was deleted.
+    pub async fn replace_bytes_if_unchanged(
+        &mut self,
+        key: &str,
+        value: &[u8],
+        must_exist: bool,
+    ) -> Result<Option<bool>> {
+        let mut pipe = redis::pipe();
+        pipe.atomic().cmd("SET").arg(key_bytes(key)).arg(value);
+        if must_exist {
+            pipe.arg("XX");
+        }
+        pipe.arg("KEEPTTL");
+        let reply: Option<(Option<String>,)> = pipe.query_async(&mut self.connection).await?;
+        Ok(reply.map(|(set,)| set.is_some()))
+    }
+
+    pub async fn set_bytes(
+        &mut self,
+        key: &str,
+        value: &[u8],
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        let options = SetOptions {
+            ttl,
+            ..SetOptions::default()
+        };
+        self.set_with_options(key, value, &options).await?;
+        Ok(())
+    }
+
+    /// SET with its optional arguments in a single command, so the value and
+    /// its expiry are written atomically.
+    pub async fn set_with_options(
+        &mut self,
+        key: &str,
+        value: &[u8],
+        options: &SetOptions,
+    ) -> Result<SetOutcome> {
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(key_bytes(key)).arg(value);
+        if let Some(condition) = options.condition {
+            cmd.arg(condition);
+        }
+        if options.get {
+            cmd.arg("GET");
+        }
+        if let Some(ttl) = options.ttl {
+            cmd.arg("EX").arg(ttl.as_secs());
+        } else if options.keep_ttl {
+            cmd.arg("KEEPTTL");
+        }
+
+        let reply: Value = cmd.query_async(&mut self.connection).await?;
+        if !options.get {
+            return Ok(SetOutcome {
+                written: reply != Value::Nil,
+                previous: None,
+            });
+        }
+
+        // With GET the reply is the old value, so whether the write happened
+        // follows from the condition and whether there was an old value
+        let previous: Option<Vec<u8>> = redis::from_redis_value(&reply)?;
+        let written = match options.condition {
+            Some("NX") => previous.is_none(),
+            Some("XX") => previous.is_some(),
+            _ => true,
+        };
+        Ok(SetOutcome { written, previous })
+    }
+
+    pub async fn mget(&mut self, keys: &[String]) -> Result<Vec<Option<String>>> {
+        let values: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(keys_bytes(keys))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(values)
+    }
+
+    /// MSET the pairs, applying the TTL to every key in the same transaction.
+    pub async fn mset(&mut self, pairs: &[(String, String)], ttl: Option<Duration>) -> Result<()> {
+        let mut pipe = redis::pipe();
+        pipe.atomic().cmd("MSET");
+        for (key, value) in pairs {
+            pipe.arg(key_bytes(key)).arg(value);
+        }
+        pipe.ignore();
         if let Some(ttl) = ttl {
-            redis::pipe()
-                .cmd("SET")
-                .arg(key)
-                .arg(value)
-                .ignore()
-                .cmd("EXPIRE")
-                .arg(key)
-                .arg(ttl.as_secs() as usize)
-                .query_async::<_, ()>(&mut self.connection)
-                .await?;
-        } else {
-            redis::cmd("SET")
-                .arg(key)
-                .arg(value)
-                .query_async::<_, ()>(&mut self.connection)
-                .await?;
+            for (key, _) in pairs {
+                pipe.cmd("EXPIRE")
+                    .arg(key_bytes(key))
+                    .arg(ttl.as_secs() as usize)
+                    .ignore();
+            }
         }
+        pipe.query_async::<_, ()>(&mut self.connection).await?;
         Ok(())
     }
 
+    pub async fn getrange(&mut self, key: &str, start: i64, end: i64) -> Result<Vec<u8>> {
+        let value: Vec<u8> = redis::cmd("GETRANGE")
+            .arg(key_bytes(key))
+            .arg(start)
+            .arg(end)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(value)
+    }
+
+    pub async fn setrange(&mut self, key: &str, offset: u64, value: &[u8]) -> Result<usize> {
+        let len: usize = redis::cmd("SETRANGE")
+            .arg(key_bytes(key))
+            .arg(offset)
+            .arg(value)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(len)
+    }
+
+    pub async fn append(&mut self, key: &str, value: &[u8]) -> Result<usize> {
+        let len: usize = redis::cmd("APPEND")
+            .arg(key_bytes(key))
+            .arg(value)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(len)
+    }
+
+    pub async fn strlen(&mut self, key: &str) -> Result<usize> {
+        let len: usize = redis::cmd("STRLEN")
+            .arg(key_bytes(key))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(len)
+    }
+
     pub async fn get_hash(&mut self, key: &str) -> Result<HashMap<String, String>> {
-        let hash: HashMap<String, String> = self.connection.hgetall(key).await?;
+        let hash: HashMap<String, String> = self.connection.hgetall(key_bytes(key)).await?;
         Ok(hash)
     }
 
+    /// Run one HSCAN step over a hash, returning the next cursor (0 when
+    /// done) and the field/value pairs in this page.
+    pub async fn hscan_page(
+        &mut self,
+        key: &str,
+        cursor: u64,
+        count: usize,
+    ) -> Result<(u64, Vec<(String, String)>)> {
+        let (next, items): (u64, Vec<Vec<u8>>) = redis::cmd("HSCAN")
+            .arg(key_bytes(key))
+            .arg(cursor)
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut self.connection)
+            .await?;
+        let pairs = items
+            .chunks(2)
+            .filter(|pair| pair.len() == 2)
+            .map(|pair| {
+                (
+                    String::from_utf8_lossy(&pair[0]).to_string(),
+                    String::from_utf8_lossy(&pair[1]).to_string(),
+                )
+            })
+            .collect();
+        Ok((next, pairs))
+    }
+
     pub async fn set_hash_field(&mut self, key: &str, field: &str, value: &str) -> Result<()> {
         redis::cmd("HSET")
-            .arg(key)
+            .arg(key_bytes(key))
             .arg(field)
             .arg(value)
             .query_async::<_, ()>(&mut self.connection)
@@ -144,27 +701,262 @@ impl RedisClient {
         Ok(())
     }
 
+    pub async fn delete_hash_fields(&mut self, key: &str, fields: &[String]) -> Result<usize> {
+        let deleted: usize = redis::cmd("HDEL")
+            .arg(key_bytes(key))
+            .arg(fields)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(deleted)
+    }
+
+    pub async fn hash_field_exists(&mut self, key: &str, field: &str) -> Result<bool> {
+        let exists: bool = redis::cmd("HEXISTS")
+            .arg(key_bytes(key))
+            .arg(field)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(exists)
+    }
+
+    /// Pick random fields with HRANDFIELD. A negative count may return the
+    /// same field more than once. Values are only fetched with `with_values`.
+    pub async fn random_hash_fields(
+        &mut self,
+        key: &str,
+        count: i64,
+        with_values: bool,
+    ) -> Result<Vec<(String, Option<String>)>> {
+        let mut cmd = redis::cmd("HRANDFIELD");
+        cmd.arg(key_bytes(key)).arg(count);
+        if with_values {
+            cmd.arg("WITHVALUES");
+        }
+        let reply: Vec<String> = cmd.query_async(&mut self.connection).await?;
+        Ok(if with_values {
+            reply
+                .chunks(2)
+                .map(|pair| (pair[0].clone(), pair.get(1).cloned()))
+                .collect()
+        } else {
+            reply.into_iter().map(|field| (field, None)).collect()
+        })
+    }
+
+    /// Expire individual hash fields with HPEXPIRE (Redis 7.4+). Returns
+    /// one code per field: 1 when set, 2 when deleted straight away (zero
+    /// TTL) and -2 when the field does not exist.
+    pub async fn expire_hash_fields(
+        &mut self,
+        key: &str,
+        ttl: Duration,
+        fields: &[String],
+    ) -> Result<Vec<i64>> {
+        let codes: Vec<i64> = redis::cmd("HPEXPIRE")
+            .arg(key_bytes(key))
+            .arg(ttl.as_millis() as u64)
+            .arg("FIELDS")
+            .arg(fields.len())
+            .arg(fields)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(codes)
+    }
+
+    /// Remaining TTL of hash fields in seconds with HTTL (Redis 7.4+):
+    /// -1 for fields without expiry and -2 for missing fields.
+    pub async fn hash_field_ttls(&mut self, key: &str, fields: &[String]) -> Result<Vec<i64>> {
+        let ttls: Vec<i64> = redis::cmd("HTTL")
+            .arg(key_bytes(key))
+            .arg("FIELDS")
+            .arg(fields.len())
+            .arg(fields)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(ttls)
+    }
+
+    /// Atomically add `by` to an integer counter (INCRBY), or to a hash
+    /// field with HINCRBY, returning the new value.
+    pub async fn incr_by(&mut self, key: &str, field: Option<&str>, by: i64) -> Result<i64> {
+        let mut cmd = match field {
+            Some(field) => {
+                let mut cmd = redis::cmd("HINCRBY");
+                cmd.arg(key_bytes(key)).arg(field);
+                cmd
+            }
+            None => {
+                let mut cmd = redis::cmd("INCRBY");
+                cmd.arg(key_bytes(key));
+                cmd
+            }
+        };
+        let value: i64 = cmd.arg(by).query_async(&mut self.connection).await?;
+        Ok(value)
+    }
+
+    /// Floating point counterpart of [`incr_by`](Self::incr_by) using
+    /// INCRBYFLOAT / HINCRBYFLOAT. Redis replies with the new value as a
+    /// string, which is returned unchanged so no precision is lost.
+    pub async fn incr_by_float(
+        &mut self,
+        key: &str,
+        field: Option<&str>,
+        by: f64,
+    ) -> Result<String> {
+        let mut cmd = match field {
+            Some(field) => {
+                let mut cmd = redis::cmd("HINCRBYFLOAT");
+                cmd.arg(key_bytes(key)).arg(field);
+                cmd
+            }
+            None => {
+                let mut cmd = redis::cmd("INCRBYFLOAT");
+                cmd.arg(key_bytes(key));
+                cmd
+            }
+        };
+        let value: String = cmd.arg(by).query_async(&mut self.connection).await?;
+        Ok(value)
+    }
+
     pub async fn get_list(&mut self, key: &str, start: isize, stop: isize) -> Result<Vec<String>> {
-        let list: Vec<String> = self.connection.lrange(key, start, stop).await?;
+        let list: Vec<String> = self.connection.lrange(key_bytes(key), start, stop).await?;
         Ok(list)
     }
 
-    pub async fn push_list(&mut self, key: &str, value: &str, left: bool) -> Result<usize> {
+    pub async fn push_list(&mut self, key: &str, value: &[u8], left: bool) -> Result<usize> {
         let len = if left {
-            self.connection.lpush(key, value).await?
+            self.connection.lpush(key_bytes(key), value).await?
         } else {
-            self.connection.rpush(key, value).await?
+            self.connection.rpush(key_bytes(key), value).await?
         };
         Ok(len)
     }
 
+    /// Pop up to `count` elements from the head (or tail with `right`).
+    /// Without a count a single element is popped, which also works on
+    /// servers older than Redis 6.2.
+    pub async fn pop_list(
+        &mut self,
+        key: &str,
+        right: bool,
+        count: Option<usize>,
+    ) -> Result<Vec<String>> {
+        let mut cmd = redis::cmd(if right { "RPOP" } else { "LPOP" });
+        cmd.arg(key_bytes(key));
+        if let Some(count) = count {
+            cmd.arg(count);
+        }
+        let popped: Value = cmd.query_async(&mut self.connection).await?;
+        Ok(match popped {
+            Value::Nil => Vec::new(),
+            Value::Bulk(items) => items
+                .into_iter()
+                .map(|item| redis::from_redis_value(&item))
+                .collect::<redis::RedisResult<_>>()?,
+            value => vec![redis::from_redis_value(&value)?],
+        })
+    }
+
+    /// Move up to `count` elements from one end of `source` to an end of
+    /// `dest` with LMOVE, pipelined. Returns how many were moved, which is
+    /// less than `count` when `source` runs out.
+    pub async fn move_list(
+        &mut self,
+        source: &str,
+        dest: &str,
+        from_right: bool,
+        to_left: bool,
+        count: usize,
+    ) -> Result<usize> {
+        let mut pipe = redis::pipe();
+        for _ in 0..count {
+            pipe.cmd("LMOVE")
+                .arg(key_bytes(source))
+                .arg(key_bytes(dest))
+                .arg(if from_right { "RIGHT" } else { "LEFT" })
+                .arg(if to_left { "LEFT" } else { "RIGHT" });
+        }
+        let moved: Vec<Option<Vec<u8>>> = pipe.query_async(&mut self.connection).await?;
+        Ok(moved.iter().filter(|item| item.is_some()).count())
+    }
+
+    pub async fn list_length(&mut self, key: &str) -> Result<usize> {
+        let len: usize = self.connection.llen(key_bytes(key)).await?;
+        Ok(len)
+    }
+
+    /// Keep only the elements between `start` and `stop` (inclusive;
+    /// negative indexes count from the end).
+    pub async fn trim_list(&mut self, key: &str, start: isize, stop: isize) -> Result<()> {
+        self.connection
+            .ltrim::<_, ()>(key_bytes(key), start, stop)
+            .await?;
+        Ok(())
+    }
+
+    /// Insert `value` next to the first occurrence of `pivot`. Returns the
+    /// new length, or None when the pivot (or the key) was not found.
+    pub async fn insert_list(
+        &mut self,
+        key: &str,
+        before: bool,
+        pivot: &str,
+        value: &str,
+    ) -> Result<Option<usize>> {
+        let len: i64 = redis::cmd("LINSERT")
+            .arg(key_bytes(key))
+            .arg(if before { "BEFORE" } else { "AFTER" })
+            .arg(pivot)
+            .arg(value)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok((len > 0).then_some(len as usize))
+    }
+
+    pub async fn set_list_index(&mut self, key: &str, index: isize, value: &str) -> Result<()> {
+        self.connection
+            .lset::<_, _, ()>(key_bytes(key), index, value)
+            .await?;
+        Ok(())
+    }
+
     pub async fn get_set(&mut self, key: &str) -> Result<Vec<String>> {
-        let set: Vec<String> = self.connection.smembers(key).await?;
+        let set: Vec<String> = self.connection.smembers(key_bytes(key)).await?;
         Ok(set)
     }
 
+    /// Run SUNION, SINTER or SDIFF over `keys`. Missing keys count as
+    /// empty sets.
+    pub async fn set_algebra(&mut self, command: &str, keys: &[String]) -> Result<Vec<String>> {
+        let mut cmd = redis::cmd(command);
+        for key in keys {
+            cmd.arg(key_bytes(key));
+        }
+        let members: Vec<String> = cmd.query_async(&mut self.connection).await?;
+        Ok(members)
+    }
+
+    /// The STORE variant of [`set_algebra`](Self::set_algebra): write the
+    /// result to `destination`, replacing it, and return its cardinality.
+    pub async fn set_algebra_store(
+        &mut self,
+        command: &str,
+        destination: &str,
+        keys: &[String],
+    ) -> Result<usize> {
+        let mut cmd = redis::cmd(&format!("{}STORE", command));
+        cmd.arg(key_bytes(destination));
+        for key in keys {
+            cmd.arg(key_bytes(key));
+        }
+        let count: usize = cmd.query_async(&mut self.connection).await?;
+        Ok(count)
+    }
+
     pub async fn add_to_set(&mut self, key: &str, member: &str) -> Result<bool> {
-        let added: i32 = self.connection.sadd(key, member).await?;
+        let added: i32 = self.connection.sadd(key_bytes(key), member).await?;
         Ok(added > 0)
     }
 
@@ -176,43 +968,318 @@ impl RedisClient {
         with_scores: bool,
     ) -> Result<Vec<(String, f64)>> {
         if with_scores {
-            let zset: Vec<(String, f64)> = self
-                .connection
-                .zrangebyscore_withscores(key, start, stop)
-                .await?;
-            Ok(zset)
+            self.zrange_withscores(key, start, stop).await
         } else {
-            let zset: Vec<String> = self.connection.zrange(key, start, stop).await?;
+            let zset: Vec<String> = self.connection.zrange(key_bytes(key), start, stop).await?;
             Ok(zset.into_iter().map(|v| (v, 0.0)).collect())
         }
     }
 
+    /// Read the oldest `count` entries of a stream, keeping each entry's
+    /// fields in the order they were added.
+    pub async fn get_stream(&mut self, key: &str, count: usize) -> Result<Vec<StreamEntry>> {
+        let reply: Value = redis::cmd("XRANGE")
+            .arg(key_bytes(key))
+            .arg("-")
+            .arg("+")
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(parse_stream_entries(reply))
+    }
+
+    /// The most recent `count` entries of a stream, newest first.
+    pub async fn get_stream_newest(&mut self, key: &str, count: usize) -> Result<Vec<StreamEntry>> {
+        let reply: Value = redis::cmd("XREVRANGE")
+            .arg(key_bytes(key))
+            .arg("+")
+            .arg("-")
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(parse_stream_entries(reply))
+    }
+
+    pub async fn zrange_withscores(
+        &mut self,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<(String, f64)>> {
+        let zset: Vec<(String, f64)> = self
+            .connection
+            .zrange_withscores(key_bytes(key), start, stop)
+            .await?;
+        Ok(zset)
+    }
+
+    pub async fn zrevrange_withscores(
+        &mut self,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<(String, f64)>> {
+        let zset: Vec<(String, f64)> = self
+            .connection
+            .zrevrange_withscores(key_bytes(key), start, stop)
+            .await?;
+        Ok(zset)
+    }
+
+    pub async fn zcard(&mut self, key: &str) -> Result<usize> {
+        let len: usize = self.connection.zcard(key_bytes(key)).await?;
+        Ok(len)
+    }
+
     pub async fn add_to_sorted_set(&mut self, key: &str, member: &str, score: f64) -> Result<bool> {
-        let added: i32 = self.connection.zadd(key, member, score).await?;
+        let added: i32 = self.connection.zadd(key_bytes(key), member, score).await?;
         Ok(added > 0)
     }
 
+    /// Members with scores between `min` and `max`, which take the usual
+    /// ZRANGEBYSCORE syntax (`-inf`, `+inf`, `(5` for exclusive). With `rev`
+    /// the highest scores come first.
+    pub async fn zrange_by_score(
+        &mut self,
+        key: &str,
+        min: &str,
+        max: &str,
+        rev: bool,
+        limit: Option<(usize, usize)>,
+    ) -> Result<Vec<(String, f64)>> {
+        let mut cmd = if rev {
+            let mut cmd = redis::cmd("ZREVRANGEBYSCORE");
+            cmd.arg(key_bytes(key)).arg(max).arg(min);
+            cmd
+        } else {
+            let mut cmd = redis::cmd("ZRANGEBYSCORE");
+            cmd.arg(key_bytes(key)).arg(min).arg(max);
+            cmd
+        };
+        cmd.arg("WITHSCORES");
+        if let Some((offset, count)) = limit {
+            cmd.arg("LIMIT").arg(offset).arg(count);
+        }
+        let zset: Vec<(String, f64)> = cmd.query_async(&mut self.connection).await?;
+        Ok(zset)
+    }
+
+    /// Zero-based rank of a member, counting from the highest score with
+    /// `rev`; None when the member is not in the set.
+    pub async fn zrank(&mut self, key: &str, member: &str, rev: bool) -> Result<Option<usize>> {
+        let rank: Option<usize> = redis::cmd(if rev { "ZREVRANK" } else { "ZRANK" })
+            .arg(key_bytes(key))
+            .arg(member)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(rank)
+    }
+
+    pub async fn zscore(&mut self, key: &str, member: &str) -> Result<Option<f64>> {
+        let score: Option<f64> = self.connection.zscore(key_bytes(key), member).await?;
+        Ok(score)
+    }
+
+    pub async fn zincr(&mut self, key: &str, member: &str, by: f64) -> Result<f64> {
+        let score: f64 = self.connection.zincr(key_bytes(key), member, by).await?;
+        Ok(score)
+    }
+
+    pub async fn zrem_range_by_score(&mut self, key: &str, min: &str, max: &str) -> Result<usize> {
+        let removed: usize = redis::cmd("ZREMRANGEBYSCORE")
+            .arg(key_bytes(key))
+            .arg(min)
+            .arg(max)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(removed)
+    }
+
     pub async fn delete_key(&mut self, key: &str) -> Result<bool> {
-        let deleted: i32 = self.connection.del(key).await?;
+        let deleted: i32 = self.connection.del(key_bytes(key)).await?;
         Ok(deleted > 0)
     }
 
-    pub async fn delete_keys_by_pattern(&mut self, pattern: &str) -> Result<usize> {
-        let keys: Vec<String> = self.connection.keys(pattern).await?;
-        let deleted: i32 = self.connection.del(keys).await?;
-        Ok(deleted as usize)
+    /// Delete keys with UNLINK, which frees their memory in a background
+    /// thread instead of blocking the server.
+    pub async fn unlink(&mut self, keys: &[String]) -> Result<usize> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+        let deleted: usize = redis::cmd("UNLINK")
+            .arg(keys_bytes(keys))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(deleted)
     }
 
-    pub async fn monitor(&mut self) -> Result<()> {
-        println!("{}", "Monitor mode - press Ctrl+C to stop".yellow());
-        println!(
-            "{}",
-            "Note: Full monitor implementation requires additional Redis client features".cyan()
-        );
-        loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-            println!("{}", "Monitor: Waiting for commands...".green());
+    pub async fn pttl(&mut self, key: &str) -> Result<i64> {
+        let pttl: i64 = redis::cmd("PTTL")
+            .arg(key_bytes(key))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(pttl)
+    }
+
+    pub async fn pttl_many(&mut self, keys: &[String]) -> Result<Vec<i64>> {
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.cmd("PTTL").arg(key_bytes(key));
+        }
+        let pttls: Vec<i64> = pipe.query_async(&mut self.connection).await?;
+        Ok(pttls)
+    }
+
+    /// Set a key's TTL in seconds. `condition` is one of NX, XX, GT or LT
+    /// (Redis 7+). Returns false when the key is missing or the condition
+    /// was not met.
+    pub async fn expire(
+        &mut self,
+        key: &str,
+        seconds: u64,
+        condition: Option<&str>,
+    ) -> Result<bool> {
+        let mut cmd = redis::cmd("EXPIRE");
+        cmd.arg(key_bytes(key)).arg(seconds);
+        if let Some(condition) = condition {
+            cmd.arg(condition);
+        }
+        let applied: i64 = cmd.query_async(&mut self.connection).await?;
+        Ok(applied == 1)
+    }
+
+    pub async fn persist(&mut self, key: &str) -> Result<bool> {
+        let removed: i64 = redis::cmd("PERSIST")
+            .arg(key_bytes(key))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(removed == 1)
+    }
+
+    /// Rename a key. Without `force` RENAMENX is used, returning false if the
+    /// destination already exists.
+    pub async fn rename(&mut self, old: &str, new: &str, force: bool) -> Result<bool> {
+        if force {
+            redis::cmd("RENAME")
+                .arg(key_bytes(old))
+                .arg(key_bytes(new))
+                .query_async::<_, ()>(&mut self.connection)
+                .await?;
+            Ok(true)
+        } else {
+            let renamed: i64 = redis::cmd("RENAMENX")
+                .arg(key_bytes(old))
+                .arg(key_bytes(new))
+                .query_async(&mut self.connection)
+                .await?;
+            Ok(renamed == 1)
+        }
+    }
+
+    pub async fn exists(&mut self, key: &str) -> Result<bool> {
+        let exists: bool = self.connection.exists(key_bytes(key)).await?;
+        Ok(exists)
+    }
+
+    /// Check several keys at once, preserving the order of `keys`.
+    pub async fn exists_many(&mut self, keys: &[String]) -> Result<Vec<bool>> {
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.cmd("EXISTS").arg(key_bytes(key));
+        }
+        let exists: Vec<bool> = pipe.query_async(&mut self.connection).await?;
+        Ok(exists)
+    }
+
+    /// Update the last access time of keys, returning how many exist.
+    pub async fn touch(&mut self, keys: &[String]) -> Result<usize> {
+        let touched: usize = redis::cmd("TOUCH")
+            .arg(keys_bytes(keys))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(touched)
+    }
+
+    pub async fn dump(&mut self, key: &str) -> Result<Option<Vec<u8>>> {
+        let payload: Option<Vec<u8>> = redis::cmd("DUMP")
+            .arg(key_bytes(key))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(payload)
+    }
+
+    /// DUMP many keys in one pipelined round trip, along with their type and
+    /// PTTL. Keys that vanished come back with type "none" and no payload,
+    /// and keys that expired between DUMP and PTTL also have no payload.
+    pub async fn dump_batch(&mut self, keys: &[String]) -> Result<Vec<DumpedKey>> {
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.cmd("TYPE")
+                .arg(key_bytes(key))
+                .cmd("DUMP")
+                .arg(key_bytes(key))
+                .cmd("PTTL")
+                .arg(key_bytes(key));
+        }
+        let replies: Vec<(String, Option<Vec<u8>>, i64)> =
+            pipe.query_async(&mut self.connection).await?;
+
+        Ok(keys
+            .iter()
+            .zip(replies)
+            .map(|(key, (key_type, payload, pttl))| DumpedKey {
+                key: key.clone(),
+                key_type,
+                // A PTTL of -2 means the key expired right after DUMP
+                payload: payload.filter(|_| pttl != -2),
+                pttl,
+            })
+            .collect())
+    }
+
+    /// Server-side COPY (Redis 6.2+) into another database of this instance.
+    /// Returns false when the destination exists and `replace` is not set.
+    pub async fn copy_to_db(
+        &mut self,
+        source: &str,
+        destination: &str,
+        db: u8,
+        replace: bool,
+    ) -> Result<bool> {
+        let mut cmd = redis::cmd("COPY");
+        cmd.arg(key_bytes(source))
+            .arg(key_bytes(destination))
+            .arg("DB")
+            .arg(db);
+        if replace {
+            cmd.arg("REPLACE");
+        }
+        let copied: i64 = cmd.query_async(&mut self.connection).await?;
+        Ok(copied == 1)
+    }
+
+    /// Recreate a key from a DUMP payload. A `pttl` of 0, or -1 as PTTL
+    /// reports a persistent key, means no expiry. -2 means the key expired
+    /// after it was dumped, and is refused rather than restored without one.
+    pub async fn restore(
+        &mut self,
+        key: &str,
+        pttl: i64,
+        payload: &[u8],
+        replace: bool,
+    ) -> Result<()> {
+        if pttl < -1 {
+            return Err(anyhow!("'{}' expired before it could be restored", key));
+        }
+        let mut cmd = redis::cmd("RESTORE");
+        cmd.arg(key_bytes(key)).arg(pttl.max(0)).arg(payload);
+        if replace {
+            cmd.arg("REPLACE");
         }
+        cmd.query_async::<_, ()>(&mut self.connection).await?;
+        Ok(())
     }
 
     pub async fn slowlog_get(&mut self, count: usize) -> Result<Vec<SlowLogEntry>> {
@@ -269,6 +1336,14 @@ impl RedisClient {
         Ok(entries)
     }
 
+    pub async fn slowlog_reset(&mut self) -> Result<()> {
+        redis::cmd("SLOWLOG")
+            .arg("RESET")
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+
     pub async fn client_list(&mut self) -> Result<Vec<ClientInfo>> {
         let result: String = redis::cmd("CLIENT")
             .arg("LIST")
@@ -285,7 +1360,147 @@ impl RedisClient {
         Ok(clients)
     }
 
-    #[allow(dead_code)]
+    pub async fn eval(&mut self, script: &str, keys: &[String], args: &[String]) -> Result<Value> {
+        let value: Value = redis::cmd("EVAL")
+            .arg(script)
+            .arg(keys.len())
+            .arg(keys_bytes(keys))
+            .arg(args)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(value)
+    }
+
+    pub async fn evalsha(&mut self, sha: &str, keys: &[String], args: &[String]) -> Result<Value> {
+        let value: Value = redis::cmd("EVALSHA")
+            .arg(sha)
+            .arg(keys.len())
+            .arg(keys_bytes(keys))
+            .arg(args)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(value)
+    }
+
+    pub async fn script_load(&mut self, source: &str) -> Result<String> {
+        let sha: String = redis::cmd("SCRIPT")
+            .arg("LOAD")
+            .arg(source)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(sha)
+    }
+
+    pub async fn script_exists(&mut self, shas: &[String]) -> Result<Vec<bool>> {
+        let exists: Vec<bool> = redis::cmd("SCRIPT")
+            .arg("EXISTS")
+            .arg(shas)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(exists)
+    }
+
+    pub async fn script_flush(&mut self) -> Result<()> {
+        redis::cmd("SCRIPT")
+            .arg("FLUSH")
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn function_list(&mut self, pattern: Option<&str>) -> Result<Vec<FunctionLibrary>> {
+        let mut cmd = redis::cmd("FUNCTION");
+        cmd.arg("LIST");
+        if let Some(pattern) = pattern {
+            cmd.arg("LIBRARYNAME").arg(pattern);
+        }
+        let result: Vec<Value> = cmd.query_async(&mut self.connection).await?;
+
+        let mut libraries = Vec::new();
+        for library in result {
+            if let Value::Bulk(items) = library {
+                let mut info = FunctionLibrary::default();
+                for chunk in items.chunks(2) {
+                    if let [key, value] = chunk {
+                        match value_to_string(key).as_deref() {
+                            Some("library_name") => {
+                                info.name = value_to_string(value).unwrap_or_default()
+                            }
+                            Some("engine") => {
+                                info.engine = value_to_string(value).unwrap_or_default()
+                            }
+                            Some("functions") => {
+                                if let Value::Bulk(functions) = value {
+                                    info.functions =
+                                        functions.iter().map(FunctionInfo::from_value).collect();
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                libraries.push(info);
+            }
+        }
+
+        Ok(libraries)
+    }
+
+    pub async fn function_load(&mut self, code: &str, replace: bool) -> Result<String> {
+        let mut cmd = redis::cmd("FUNCTION");
+        cmd.arg("LOAD");
+        if replace {
+            cmd.arg("REPLACE");
+        }
+        let name: String = cmd.arg(code).query_async(&mut self.connection).await?;
+        Ok(name)
+    }
+
+    pub async fn function_delete(&mut self, library: &str) -> Result<()> {
+        redis::cmd("FUNCTION")
+            .arg("DELETE")
+            .arg(library)
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn config_get(&mut self, pattern: &str) -> Result<Vec<(String, String)>> {
+        let result: Vec<Value> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg(pattern)
+            .query_async(&mut self.connection)
+            .await?;
+
+        let mut params: Vec<(String, String)> = result
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [name, value] => Some((value_to_string(name)?, value_to_string(value)?)),
+                _ => None,
+            })
+            .collect();
+        params.sort();
+        Ok(params)
+    }
+
+    pub async fn config_set(&mut self, param: &str, value: &str) -> Result<()> {
+        redis::cmd("CONFIG")
+            .arg("SET")
+            .arg(param)
+            .arg(value)
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn config_rewrite(&mut self) -> Result<()> {
+        redis::cmd("CONFIG")
+            .arg("REWRITE")
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+
     pub async fn save(&mut self, background: bool) -> Result<()> {
         let cmd = if background { "BGSAVE" } else { "SAVE" };
         redis::cmd(cmd)
@@ -294,7 +1509,6 @@ impl RedisClient {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub async fn bgrewriteaof(&mut self) -> Result<()> {
         redis::cmd("BGREWRITEAOF")
             .query_async::<_, ()>(&mut self.connection)
@@ -302,36 +1516,120 @@ impl RedisClient {
         Ok(())
     }
 
-    #[allow(dead_code)]
-    pub async fn subscribe(&mut self, channels: &[String]) -> Result<()> {
-        // Simplified subscribe implementation
-        println!(
-            "{}",
-            format!("Subscribing to channels: {:?}", channels).yellow()
-        );
-        println!(
-            "{}",
-            "Note: Full pub/sub implementation requires additional Redis client features".cyan()
-        );
-
-        // For now, just show a placeholder
-        loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-            println!("{}", "PubSub: Waiting for messages...".green());
-        }
+    /// Channels with at least one subscriber, matching `pattern`.
+    pub async fn pubsub_channels(&mut self, pattern: &str) -> Result<Vec<String>> {
+        let channels: Vec<String> = redis::cmd("PUBSUB")
+            .arg("CHANNELS")
+            .arg(pattern)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(channels)
+    }
+
+    /// Subscriber count for each channel, in the order given. Pattern
+    /// subscriptions are not included, as with PUBSUB NUMSUB itself.
+    pub async fn pubsub_numsub(&mut self, channels: &[String]) -> Result<Vec<(String, u64)>> {
+        if channels.is_empty() {
+            return Ok(Vec::new());
+        }
+        let counts: Vec<(String, u64)> = redis::cmd("PUBSUB")
+            .arg("NUMSUB")
+            .arg(channels)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(counts)
+    }
+
+    /// Number of active pattern subscriptions across all clients.
+    pub async fn pubsub_numpat(&mut self) -> Result<u64> {
+        let count: u64 = redis::cmd("PUBSUB")
+            .arg("NUMPAT")
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(count)
+    }
+
+    /// Publish a message, returning how many subscribers received it.
+    pub async fn publish(&mut self, channel: &str, message: &[u8]) -> Result<usize> {
+        let result: usize = redis::cmd("PUBLISH")
+            .arg(channel)
+            .arg(message)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn cluster_enabled(&mut self) -> Result<bool> {
+        let info = self.info().await?;
+        Ok(info.get("cluster_enabled").map(|v| v.trim()) == Some("1"))
+    }
+
+    pub async fn cluster_keyslot(&mut self, key: &str) -> Result<u16> {
+        let slot: u16 = redis::cmd("CLUSTER")
+            .arg("KEYSLOT")
+            .arg(key_bytes(key))
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(slot)
+    }
+
+    /// CLUSTER SETSLOT `slot` `IMPORTING|MIGRATING|NODE <id>`, or `STABLE`.
+    pub async fn cluster_setslot(
+        &mut self,
+        slot: u16,
+        state: &str,
+        node_id: Option<&str>,
+    ) -> Result<()> {
+        let mut cmd = redis::cmd("CLUSTER");
+        cmd.arg("SETSLOT").arg(slot).arg(state);
+        if let Some(node_id) = node_id {
+            cmd.arg(node_id);
+        }
+        cmd.query_async::<_, ()>(&mut self.connection).await?;
+        Ok(())
+    }
+
+    /// Up to `count` keys stored in `slot` on this node, as raw bytes.
+    pub async fn cluster_getkeysinslot(&mut self, slot: u16, count: usize) -> Result<Vec<Vec<u8>>> {
+        let keys: Vec<Vec<u8>> = redis::cmd("CLUSTER")
+            .arg("GETKEYSINSLOT")
+            .arg(slot)
+            .arg(count)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(keys)
     }
 
-    #[allow(dead_code)]
-    pub async fn publish(&mut self, channel: &str, message: &str) -> Result<usize> {
-        let result: usize = redis::cmd("PUBLISH")
-            .arg(channel)
-            .arg(message)
+    pub async fn cluster_countkeysinslot(&mut self, slot: u16) -> Result<u64> {
+        let count: u64 = redis::cmd("CLUSTER")
+            .arg("COUNTKEYSINSLOT")
+            .arg(slot)
             .query_async(&mut self.connection)
             .await?;
-        Ok(result)
+        Ok(count)
+    }
+
+    /// Move `keys` to another instance with MIGRATE, authenticating there
+    /// with `password` when set. Keys that already exist on the target make
+    /// the whole call fail with BUSYKEY.
+    pub async fn migrate_keys(
+        &mut self,
+        host: &str,
+        port: u16,
+        keys: &[Vec<u8>],
+        timeout_ms: u64,
+        password: Option<&str>,
+    ) -> Result<()> {
+        let mut cmd = redis::cmd("MIGRATE");
+        cmd.arg(host).arg(port).arg("").arg(0).arg(timeout_ms);
+        if let Some(password) = password {
+            cmd.arg("AUTH").arg(password);
+        }
+        cmd.arg("KEYS").arg(keys);
+        cmd.query_async::<_, ()>(&mut self.connection).await?;
+        Ok(())
     }
 
-    #[allow(dead_code)]
     pub async fn cluster_nodes(&mut self) -> Result<Vec<ClusterNode>> {
         let result: String = redis::cmd("CLUSTER")
             .arg("NODES")
@@ -348,7 +1646,16 @@ impl RedisClient {
         Ok(nodes)
     }
 
-    #[allow(dead_code)]
+    /// This node's own CLUSTER NODES entry, the only one that shows which
+    /// of its slots are migrating or importing.
+    pub async fn cluster_myself(&mut self) -> Result<ClusterNode> {
+        self.cluster_nodes()
+            .await?
+            .into_iter()
+            .find(ClusterNode::is_myself)
+            .ok_or_else(|| anyhow!("CLUSTER NODES has no entry flagged myself"))
+    }
+
     pub async fn sentinel_masters(&mut self) -> Result<Vec<SentinelMaster>> {
         let result: Vec<Value> = redis::cmd("SENTINEL")
             .arg("MASTERS")
@@ -392,6 +1699,73 @@ impl RedisClient {
         Ok(masters)
     }
 
+    /// Address of the current master for `name`, as known to this sentinel.
+    pub async fn sentinel_master_addr(&mut self, name: &str) -> Result<Option<(String, u16)>> {
+        let addr: Option<(String, u16)> = redis::cmd("SENTINEL")
+            .arg("GET-MASTER-ADDR-BY-NAME")
+            .arg(name)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(addr)
+    }
+
+    /// Force a failover of `name` without asking the other sentinels.
+    pub async fn sentinel_failover(&mut self, name: &str) -> Result<()> {
+        redis::cmd("SENTINEL")
+            .arg("FAILOVER")
+            .arg(name)
+            .query_async::<_, ()>(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn memory_stats(&mut self) -> Result<MemoryStats> {
+        let result: Vec<Value> = redis::cmd("MEMORY")
+            .arg("STATS")
+            .query_async(&mut self.connection)
+            .await?;
+
+        let mut stats = MemoryStats::default();
+        for chunk in result.chunks(2) {
+            if chunk.len() != 2 {
+                continue;
+            }
+            let name = match value_to_string(&chunk[0]) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            match &chunk[1] {
+                // Per-database overhead is reported as a nested map (e.g. "db.0")
+                Value::Bulk(items) => {
+                    let fields = items
+                        .chunks(2)
+                        .filter_map(|pair| match pair {
+                            [k, v] => Some((value_to_string(k)?, value_to_string(v)?)),
+                            _ => None,
+                        })
+                        .collect();
+                    stats.databases.push((name, fields));
+                }
+                value => {
+                    if let Some(value) = value_to_string(value) {
+                        stats.metrics.insert(name, value);
+                    }
+                }
+            }
+        }
+
+        Ok(stats)
+    }
+
+    pub async fn memory_doctor(&mut self) -> Result<String> {
+        let report: String = redis::cmd("MEMORY")
+            .arg("DOCTOR")
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(report)
+    }
+
     pub fn pretty_print_json(&self, value: &str) -> Result<String> {
         if let Ok(json) = serde_json::from_str::<JsonValue>(value) {
             Ok(serde_json::to_string_pretty(&json)?)
@@ -401,13 +1775,566 @@ impl RedisClient {
     }
 }
 
+/// The connection behind a [`RedisClient`]. Every command is logged when
+/// `--trace` is on, and fails once the environment's timeout passes without
+/// a reply instead of waiting on a dead server.
+pub struct ClientConnection {
+    inner: Connection,
+    timeout: Option<Duration>,
+    recording: Recording,
+    /// Set once a command times out. Its reply may still arrive and would be
+    /// read as the reply to the next command, so nothing more is sent.
+    broken: bool,
+}
+
+impl ClientConnection {
+    fn check_usable(&self) -> RedisResult<()> {
+        if self.broken {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "The connection is no longer used after an earlier command timed out",
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Keep a copy of everything the server sends from now on.
+    fn start_recording(&self) {
+        *self.recording.lock().unwrap() = Some(Vec::new());
+    }
+
+    /// Stop recording and return what the server sent since
+    /// `start_recording`.
+    fn take_recording(&self) -> Vec<u8> {
+        self.recording.lock().unwrap().take().unwrap_or_default()
+    }
+}
+
+/// Server bytes copied by a [`RecordingStream`] while recording is on
+type Recording = Arc<Mutex<Option<Vec<u8>>>>;
+
+/// The TCP stream under a [`ClientConnection`]. It can keep a copy of the
+/// raw replies, because the driver turns an error reply anywhere in a
+/// pipeline or EXEC array into a single error and drops the rest.
+struct RecordingStream {
+    stream: TcpStream,
+    recording: Recording,
+}
+
+impl AsyncRead for RecordingStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let before = buf.filled().len();
+        let poll = Pin::new(&mut self.stream).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = poll {
+            if let Some(recorded) = self.recording.lock().unwrap().as_mut() {
+                recorded.extend_from_slice(&buf.filled()[before..]);
+            }
+        }
+        poll
+    }
+}
+
+impl AsyncWrite for RecordingStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
+impl ConnectionLike for ClientConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        Box::pin(async move {
+            self.check_usable()?;
+            trace::command(cmd);
+            let started = Instant::now();
+            let reply = within(
+                self.timeout,
+                &command_name(cmd),
+                self.inner.req_packed_command(cmd),
+            )
+            .await;
+            trace::reply(&reply, started.elapsed());
+            self.broken |= reply.as_ref().is_err_and(|e| e.is_timeout());
+            reply
+        })
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        pipeline: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        Box::pin(async move {
+            self.check_usable()?;
+            trace::pipeline(pipeline, offset);
+            let started = Instant::now();
+            let replies = within(
+                self.timeout,
+                "a pipeline",
+                self.inner.req_packed_commands(pipeline, offset, count),
+            )
+            .await;
+            trace::replies(&replies, started.elapsed());
+            self.broken |= replies.as_ref().is_err_and(|e| e.is_timeout());
+            replies
+        })
+    }
+
+    fn get_db(&self) -> i64 {
+        self.inner.get_db()
+    }
+}
+
+/// Wait for a reply for at most `limit`.
+async fn within<T>(
+    limit: Option<Duration>,
+    what: &str,
+    reply: RedisFuture<'_, T>,
+) -> RedisResult<T> {
+    let Some(limit) = limit else {
+        return reply.await;
+    };
+    tokio::time::timeout(limit, reply)
+        .await
+        .unwrap_or_else(|_| {
+            Err(timed_out(format!(
+                "Redis did not reply to {} within {}s (the environment's timeout)",
+                what,
+                limit.as_secs()
+            )))
+        })
+}
+
+fn command_name(cmd: &Cmd) -> String {
+    match cmd.args_iter().next() {
+        Some(Arg::Simple(name)) => String::from_utf8_lossy(name).to_uppercase(),
+        _ => "a command".to_string(),
+    }
+}
+
+/// A timeout error that reports as a connection failure (exit code 2).
+fn timed_out(message: String) -> RedisError {
+    std::io::Error::new(std::io::ErrorKind::TimedOut, message).into()
+}
+
+/// Read one RESP reply from `raw` starting at `pos`, or `None` if it is
+/// incomplete. An error inside an array fails the whole array, as in the
+/// driver; use `read_array_items` to keep the other elements.
+fn read_reply(raw: &[u8], pos: &mut usize) -> Option<RedisResult<Value>> {
+    let line = read_line(raw, pos)?;
+    let (kind, rest) = (
+        line.first()?,
+        String::from_utf8_lossy(&line[1..]).to_string(),
+    );
+    Some(match kind {
+        b'+' if rest == "OK" => Ok(Value::Okay),
+        b'+' => Ok(Value::Status(rest)),
+        b'-' => Err(error_reply(&rest)),
+        b':' => Ok(Value::Int(rest.parse().ok()?)),
+        b'$' => {
+            let Ok(len) = usize::try_from(rest.parse::<i64>().ok()?) else {
+                return Some(Ok(Value::Nil));
+            };
+            // Include the trailing CRLF so a reply cut off just before it
+            // still counts as incomplete
+            let data = raw.get(*pos..*pos + len + 2)?[..len].to_vec();
+            *pos += len + 2;
+            Ok(Value::Data(data))
+        }
+        b'*' => {
+            *pos -= line.len() + 2;
+            match read_array_items(raw, pos)? {
+                Some(items) => items
+                    .into_iter()
+                    .collect::<RedisResult<_>>()
+                    .map(Value::Bulk),
+                None => Ok(Value::Nil),
+            }
+        }
+        _ => return None,
+    })
+}
+
+/// Read a RESP array element by element, keeping error replies as errors
+/// for just that element. `Some(None)` is a nil array.
+#[allow(clippy::type_complexity)]
+fn read_array_items(raw: &[u8], pos: &mut usize) -> Option<Option<Vec<RedisResult<Value>>>> {
+    let line = read_line(raw, pos)?;
+    if line.first() != Some(&b'*') {
+        return None;
+    }
+    let Ok(count) = usize::try_from(String::from_utf8_lossy(&line[1..]).parse::<i64>().ok()?)
+    else {
+        return Some(None);
+    };
+    (0..count)
+        .map(|_| read_reply(raw, pos))
+        .collect::<Option<_>>()
+        .map(Some)
+}
+
+fn read_line<'a>(raw: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+    let start = *pos;
+    let len = raw.get(start..)?.windows(2).position(|w| w == b"\r\n")?;
+    *pos = start + len + 2;
+    Some(&raw[start..start + len])
+}
+
+/// An error reply as the driver would report it. The driver's own parser
+/// classifies the code, so `WRONGTYPE` and friends keep it in `code()`.
+fn error_reply(line: &str) -> RedisError {
+    redis::parse_redis_value(format!("-{}\r\n", line).as_bytes())
+        .err()
+        .unwrap_or_else(|| {
+            let description = "An error was signalled by the server";
+            (ErrorKind::ResponseError, description, line.to_string()).into()
+        })
+}
+
+/// Key arguments as sent to Redis. See `format::key_bytes`.
+fn keys_bytes(keys: &[String]) -> Vec<Vec<u8>> {
+    keys.iter().map(|k| key_bytes(k)).collect()
+}
+
+/// Render a scalar RESP value as a string, if it has a textual form.
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Data(data) => Some(String::from_utf8_lossy(data).to_string()),
+        Value::Status(status) => Some(status.to_string()),
+        Value::Int(i) => Some(i.to_string()),
+        Value::Okay => Some("OK".to_string()),
+        _ => None,
+    }
+}
+
+/// Convert a type-specific read reply into JSON: strings as strings, hashes
+/// and sorted sets as objects, lists and sets as arrays.
+fn value_to_json(key_type: &str, reply: &Value) -> Option<JsonValue> {
+    let items = || match reply {
+        Value::Bulk(items) => items.iter().filter_map(value_to_string).collect(),
+        _ => Vec::new(),
+    };
+
+    match key_type {
+        "string" => value_to_string(reply).map(JsonValue::String),
+        "list" | "set" => Some(JsonValue::Array(
+            items().into_iter().map(JsonValue::String).collect(),
+        )),
+        "hash" => Some(JsonValue::Object(
+            items()
+                .chunks(2)
+                .filter(|pair| pair.len() == 2)
+                .map(|pair| (pair[0].clone(), JsonValue::String(pair[1].clone())))
+                .collect(),
+        )),
+        "zset" => Some(JsonValue::Object(
+            items()
+                .chunks(2)
+                .filter(|pair| pair.len() == 2)
+                .map(|pair| {
+                    let score = pair[1]
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .map(JsonValue::Number)
+                        .unwrap_or_else(|| JsonValue::String(pair[1].clone()));
+                    (pair[0].clone(), score)
+                })
+                .collect(),
+        )),
+        _ => None,
+    }
+}
+
+/// FNV-1a hash of a type-specific read reply, taken over the raw bytes so
+/// binary values that render the same lossy text still differ. Set members
+/// and hash fields come back in arbitrary order, so they are sorted first.
+fn reply_digest(key_type: &str, reply: &Value) -> String {
+    let mut items: Vec<&Value> = match reply {
+        Value::Bulk(items) => items.iter().collect(),
+        other => vec![other],
+    };
+    match key_type {
+        "set" => items.sort_by_key(|item| scalar_bytes(item)),
+        "hash" => {
+            let mut pairs: Vec<&[&Value]> = items.chunks(2).collect();
+            pairs.sort_by(|a, b| scalar_bytes(a[0]).cmp(&scalar_bytes(b[0])));
+            items = pairs.concat();
+        }
+        _ => {}
+    }
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for item in items {
+        hash_value(&mut hash, item);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Feed one RESP value into an FNV-1a hash. Every value is tagged and
+/// length-prefixed so `["ab", "c"]` and `["a", "bc"]` hash differently.
+fn hash_value(hash: &mut u64, value: &Value) {
+    let mut feed = |bytes: &[u8]| {
+        for byte in bytes {
+            *hash ^= *byte as u64;
+            *hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+    match value {
+        Value::Nil => feed(b"_"),
+        Value::Bulk(items) => {
+            feed(b"*");
+            feed(&(items.len() as u64).to_le_bytes());
+            for item in items {
+                hash_value(hash, item);
+            }
+        }
+        scalar => {
+            let bytes = scalar_bytes(scalar);
+            feed(b"$");
+            feed(&(bytes.len() as u64).to_le_bytes());
+            feed(&bytes);
+        }
+    }
+}
+
+/// The raw bytes of a scalar RESP value; empty for nil and arrays.
+fn scalar_bytes(value: &Value) -> Vec<u8> {
+    match value {
+        Value::Data(data) => data.clone(),
+        Value::Status(status) => status.as_bytes().to_vec(),
+        Value::Int(i) => i.to_string().into_bytes(),
+        Value::Okay => b"OK".to_vec(),
+        Value::Nil | Value::Bulk(_) => Vec::new(),
+    }
+}
+
+/// How a MULTI/EXEC transaction ended.
+pub enum TransactionOutcome {
+    /// EXEC ran every command; one reply per command. A command that failed
+    /// at run time has an error, and Redis does not roll back, so the others
+    /// were still applied
+    Executed(Vec<RedisResult<Value>>),
+    /// The server refused to queue the command at `index`, so the
+    /// transaction was discarded and nothing ran
+    Rejected {
+        index: usize,
+        error: redis::RedisError,
+    },
+}
+
+/// A single stream entry as returned by XRANGE.
+/// One `# Name` group of an INFO reply, with fields in server order.
+#[derive(Debug, Clone)]
+pub struct InfoSection {
+    pub name: String,
+    pub fields: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StreamEntry {
+    pub id: String,
+    pub fields: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DumpedKey {
+    pub key: String,
+    pub key_type: String,
+    pub payload: Option<Vec<u8>>,
+    pub pttl: i64,
+}
+
 #[derive(Debug, Clone)]
+pub struct KeyValue {
+    pub key: String,
+    pub key_type: String,
+    pub value: Option<JsonValue>,
+    digest: String,
+}
+
+impl KeyValue {
+    /// Serialize the value so equal contents compare equal. Set members come
+    /// back in arbitrary order, so they are sorted first.
+    pub fn canonical_value(&self) -> String {
+        match &self.value {
+            Some(JsonValue::String(s)) => s.clone(),
+            Some(JsonValue::Array(items)) if self.key_type == "set" => {
+                let mut items = items.clone();
+                items.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+                JsonValue::Array(items).to_string()
+            }
+            Some(other) => other.to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// FNV-1a hash of the raw value, stream entries included. It is stable
+    /// across runs, platforms and server versions, unlike DUMP payloads, so
+    /// copies of a key on different servers can be compared.
+    pub fn digest(&self) -> String {
+        self.digest.clone()
+    }
+}
+
+/// Parse an INFO reply into field/value pairs, skipping section headers.
+fn parse_info(reply: &str) -> HashMap<String, String> {
+    let mut info_map = HashMap::new();
+    for line in reply.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            info_map.insert(key.to_string(), value.to_string());
+        }
+    }
+    info_map
+}
+
+fn parse_info_sections(reply: &str) -> Vec<InfoSection> {
+    let mut sections: Vec<InfoSection> = Vec::new();
+    for line in reply.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix('#') {
+            sections.push(InfoSection {
+                name: name.trim().to_string(),
+                fields: Vec::new(),
+            });
+        } else if let Some((key, value)) = line.split_once(':') {
+            if let Some(section) = sections.last_mut() {
+                section.fields.push((key.to_string(), value.to_string()));
+            }
+        }
+    }
+    sections
+}
+
+fn parse_stream_entries(reply: Value) -> Vec<StreamEntry> {
+    let entries = match reply {
+        Value::Bulk(entries) => entries,
+        _ => return Vec::new(),
+    };
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Value::Bulk(parts) if parts.len() == 2 => {
+                let id = value_to_string(&parts[0])?;
+                let fields = match &parts[1] {
+                    Value::Bulk(items) => items
+                        .chunks(2)
+                        .filter_map(|pair| {
+                            Some((value_to_string(&pair[0])?, value_to_string(pair.get(1)?)?))
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                Some(StreamEntry { id, fields })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn push_key_info_commands(pipe: &mut redis::Pipeline, key: &str, lfu: bool) {
+    pipe.cmd("TYPE")
+        .arg(key_bytes(key))
+        .cmd("TTL")
+        .arg(key_bytes(key))
+        .cmd("MEMORY")
+        .arg("USAGE")
+        .arg(key_bytes(key))
+        .cmd("OBJECT")
+        .arg("ENCODING")
+        .arg(key_bytes(key))
+        .cmd("OBJECT")
+        .arg(if lfu { "FREQ" } else { "IDLETIME" })
+        .arg(key_bytes(key));
+}
+
+fn parse_key_info(key: &str, results: &[Value], lfu: bool) -> KeyInfo {
+    let key_type = match &results[0] {
+        Value::Data(ref data) => String::from_utf8_lossy(data).to_string(),
+        Value::Status(ref status) => status.to_string(),
+        _ => "unknown".to_string(),
+    };
+
+    let ttl = match &results[1] {
+        Value::Int(ttl) => Some(*ttl),
+        _ => None,
+    };
+
+    let memory_usage = match &results[2] {
+        Value::Int(usage) => Some(*usage as usize),
+        _ => None,
+    };
+
+    let encoding = match &results[3] {
+        Value::Data(ref data) => String::from_utf8_lossy(data).to_string(),
+        Value::Status(ref status) => status.to_string(),
+        _ => "unknown".to_string(),
+    };
+
+    let access = match &results[4] {
+        Value::Int(value) => Some(*value),
+        _ => None,
+    };
+
+    KeyInfo {
+        key: key.to_string(),
+        key_type,
+        ttl,
+        memory_usage,
+        encoding,
+        idle_time: if lfu { None } else { access },
+        frequency: if lfu { access } else { None },
+    }
+}
+
+/// Optional arguments of SET.
+#[derive(Debug, Clone, Default)]
+pub struct SetOptions {
+    /// Expire after this long (EX)
+    pub ttl: Option<Duration>,
+    /// "NX" to only create the key, "XX" to only overwrite it
+    pub condition: Option<&'static str>,
+    /// Keep the key's current TTL (KEEPTTL)
+    pub keep_ttl: bool,
+    /// Return the previous value (GET)
+    pub get: bool,
+}
+
+pub struct SetOutcome {
+    /// False when the NX/XX condition was not met
+    pub written: bool,
+    /// The old value, when GET was requested and the key held a string
+    pub previous: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct KeyInfo {
     pub key: String,
     pub key_type: String,
     pub ttl: Option<i64>,
     pub memory_usage: Option<usize>,
     pub encoding: String,
+    /// Seconds since last access; not tracked under an LFU policy
+    pub idle_time: Option<i64>,
+    /// Logarithmic access counter; only tracked under an LFU policy
+    pub frequency: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
@@ -494,7 +2421,6 @@ impl ClientInfo {
 }
 
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub struct ClusterNode {
     pub id: String,
     pub addr: String,
@@ -507,8 +2433,84 @@ pub struct ClusterNode {
     pub slots: Vec<String>,
 }
 
-#[allow(dead_code)]
 impl ClusterNode {
+    pub fn is_master(&self) -> bool {
+        self.flags.split(',').any(|flag| flag == "master")
+    }
+
+    /// Whether this is the entry for the node that answered CLUSTER NODES.
+    /// Only that entry carries the node's migrating/importing markers.
+    pub fn is_myself(&self) -> bool {
+        self.flags.split(',').any(|flag| flag == "myself")
+    }
+
+    /// Whether this node is flagged as failing (`fail`) or suspected to be
+    /// (`fail?`).
+    pub fn is_failing(&self) -> bool {
+        self.flags.split(',').any(|flag| flag.starts_with("fail"))
+    }
+
+    /// Host and client port from an address such as
+    /// `10.0.0.1:6379@16379,hostname`.
+    pub fn host_port(&self) -> Option<(String, u16)> {
+        let addr = self.addr.split(['@', ',']).next()?;
+        let (host, port) = addr.rsplit_once(':')?;
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() {
+            return None;
+        }
+        Some((host.to_string(), port.parse().ok()?))
+    }
+
+    pub fn serves_slot(&self, slot: u16) -> bool {
+        self.slots
+            .iter()
+            .filter(|range| !range.starts_with('['))
+            .any(|range| match range.split_once('-') {
+                Some((start, end)) => {
+                    start.parse().is_ok_and(|start: u16| slot >= start)
+                        && end.parse().is_ok_and(|end: u16| slot <= end)
+                }
+                None => range.parse() == Ok(slot),
+            })
+    }
+
+    /// Node id `slot` is being migrated to, from a `[slot->-id]` marker.
+    pub fn migrating_to(&self, slot: u16) -> Option<&str> {
+        self.slot_marker(slot, "->-")
+    }
+
+    /// Node id `slot` is being imported from, from a `[slot-<-id]` marker.
+    pub fn importing_from(&self, slot: u16) -> Option<&str> {
+        self.slot_marker(slot, "-<-")
+    }
+
+    fn slot_marker(&self, slot: u16, arrow: &str) -> Option<&str> {
+        self.slots.iter().find_map(|range| {
+            let (marked, id) = range
+                .strip_prefix('[')?
+                .strip_suffix(']')?
+                .split_once(arrow)?;
+            (marked.parse() == Ok(slot)).then_some(id)
+        })
+    }
+
+    /// Number of slots served, ignoring migrating/importing markers.
+    pub fn slot_count(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|range| !range.starts_with('['))
+            .map(|range| match range.split_once('-') {
+                Some((start, end)) => {
+                    let start: usize = start.parse().unwrap_or(0);
+                    let end: usize = end.parse().unwrap_or(0);
+                    end.saturating_sub(start) + 1
+                }
+                None => 1,
+            })
+            .sum()
+    }
+
     fn from_line(line: &str) -> Result<Self> {
         let parts: Vec<&str> = line.split(' ').collect();
         if parts.len() < 8 {
@@ -540,7 +2542,6 @@ impl ClusterNode {
 }
 
 #[derive(Debug, Clone, Default)]
-#[allow(dead_code)]
 pub struct SentinelMaster {
     pub name: String,
     pub ip: String,
@@ -551,3 +2552,159 @@ pub struct SentinelMaster {
     pub num_other_sentinels: usize,
     pub quorum: usize,
 }
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStats {
+    pub metrics: HashMap<String, String>,
+    pub databases: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl MemoryStats {
+    pub fn get_u64(&self, name: &str) -> Option<u64> {
+        self.metrics.get(name).and_then(|v| v.parse().ok())
+    }
+
+    pub fn get_f64(&self, name: &str) -> Option<f64> {
+        self.metrics.get(name).and_then(|v| v.parse().ok())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FunctionLibrary {
+    pub name: String,
+    pub engine: String,
+    pub functions: Vec<FunctionInfo>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub description: Option<String>,
+    pub flags: Vec<String>,
+}
+
+impl FunctionInfo {
+    fn from_value(value: &Value) -> Self {
+        let mut info = FunctionInfo::default();
+        if let Value::Bulk(items) = value {
+            for chunk in items.chunks(2) {
+                if let [key, value] = chunk {
+                    match value_to_string(key).as_deref() {
+                        Some("name") => info.name = value_to_string(value).unwrap_or_default(),
+                        Some("description") => info.description = value_to_string(value),
+                        Some("flags") => {
+                            if let Value::Bulk(flags) = value {
+                                info.flags = flags.iter().filter_map(value_to_string).collect();
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &[u8]) -> Option<RedisResult<Value>> {
+        read_reply(raw, &mut 0)
+    }
+
+    #[test]
+    fn reads_scalars() {
+        assert_eq!(parse(b"+OK\r\n").unwrap().unwrap(), Value::Okay);
+        assert_eq!(
+            parse(b"+PONG\r\n").unwrap().unwrap(),
+            Value::Status("PONG".to_string())
+        );
+        assert_eq!(parse(b":-7\r\n").unwrap().unwrap(), Value::Int(-7));
+        assert_eq!(
+            parse(b"$5\r\na\r\nbc\r\n").unwrap().unwrap(),
+            Value::Data(b"a\r\nbc".to_vec())
+        );
+        assert_eq!(
+            parse(b"$0\r\n\r\n").unwrap().unwrap(),
+            Value::Data(Vec::new())
+        );
+    }
+
+    #[test]
+    fn reads_nil_bulk_and_nil_array() {
+        assert_eq!(parse(b"$-1\r\n").unwrap().unwrap(), Value::Nil);
+        assert_eq!(parse(b"*-1\r\n").unwrap().unwrap(), Value::Nil);
+        assert!(matches!(read_array_items(b"*-1\r\n", &mut 0), Some(None)));
+    }
+
+    #[test]
+    fn reads_nested_arrays() {
+        let raw = b"*3\r\n:1\r\n*2\r\n$1\r\na\r\n$-1\r\n*0\r\n";
+        let mut pos = 0;
+        assert_eq!(
+            read_reply(raw, &mut pos).unwrap().unwrap(),
+            Value::Bulk(vec![
+                Value::Int(1),
+                Value::Bulk(vec![Value::Data(b"a".to_vec()), Value::Nil]),
+                Value::Bulk(Vec::new()),
+            ])
+        );
+        assert_eq!(pos, raw.len());
+    }
+
+    #[test]
+    fn error_inside_array() {
+        let raw = b"*3\r\n+OK\r\n-WRONGTYPE Operation against a key\r\n:2\r\n";
+
+        // The whole reply fails, as in the driver
+        let mut pos = 0;
+        let error = read_reply(raw, &mut pos).unwrap().unwrap_err();
+        assert_eq!(error.code(), Some("WRONGTYPE"));
+        assert_eq!(pos, raw.len());
+
+        // Element by element, only the failing one is an error
+        let items = read_array_items(raw, &mut 0).unwrap().unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_ref().unwrap(), &Value::Okay);
+        assert_eq!(items[1].as_ref().unwrap_err().code(), Some("WRONGTYPE"));
+        assert_eq!(items[2].as_ref().unwrap(), &Value::Int(2));
+    }
+
+    #[test]
+    fn error_replies_keep_their_kind() {
+        let error = parse(b"-ERR unknown command\r\n").unwrap().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ResponseError);
+        assert_eq!(error.detail(), Some("unknown command"));
+
+        let error = parse(b"-EXECABORT Transaction discarded\r\n")
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ExecAbortError);
+    }
+
+    #[test]
+    fn truncated_replies_are_incomplete() {
+        for raw in [
+            &b""[..],
+            b"+OK",
+            b"$5\r\nab",
+            b"$3\r\nabc",
+            b"*2\r\n:1\r\n",
+            b"*2\r\n*1\r\n$1\r\n",
+        ] {
+            assert!(parse(raw).is_none(), "{:?}", String::from_utf8_lossy(raw));
+        }
+        assert!(read_array_items(b"*2\r\n:1\r\n", &mut 0).is_none());
+    }
+
+    #[test]
+    fn reads_consecutive_replies() {
+        let raw = b"+OK\r\n:5\r\n";
+        let mut pos = 0;
+        assert_eq!(read_reply(raw, &mut pos).unwrap().unwrap(), Value::Okay);
+        assert_eq!(read_reply(raw, &mut pos).unwrap().unwrap(), Value::Int(5));
+        assert!(read_reply(raw, &mut pos).is_none());
+    }
+}
@@ -0,0 +1,217 @@
+use redis::{Arg, Cmd, Pipeline, RedisResult, Value};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use crate::error::AppError;
+use crate::format::display_key;
+
+/// Longest argument or reply string shown before it is cut short
+const MAX_ARG_LEN: usize = 64;
+
+/// Arguments shown per command, and items per array reply
+const MAX_ITEMS: usize = 8;
+
+/// Where `--trace` writes, set once at startup. Unset means tracing is off.
+static SINK: OnceLock<Mutex<Box<dyn Write + Send>>> = OnceLock::new();
+
+/// Turn on command tracing. `-` writes to stderr, anything else is a file
+/// that trace lines are appended to.
+pub fn init(target: Option<&str>) -> Result<(), AppError> {
+    let sink: Box<dyn Write + Send> = match target {
+        None => return Ok(()),
+        Some("-") => Box::new(io::stderr()),
+        Some(path) => Box::new(OpenOptions::new().create(true).append(true).open(path)?),
+    };
+    let _ = SINK.set(Mutex::new(sink));
+    Ok(())
+}
+
+pub fn enabled() -> bool {
+    SINK.get().is_some()
+}
+
+fn write_line(line: &str) {
+    if let Some(sink) = SINK.get() {
+        let mut sink = sink.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(sink, "{}", line);
+    }
+}
+
+/// Record a new connection. The AUTH and SELECT handshake happens inside
+/// the driver, so only where it went and how long it took are shown.
+pub fn connected(host: &str, port: u16, db: u8, elapsed: Duration) {
+    write_line(&format!(
+        "* connected to {}:{} db {} ({})",
+        host,
+        port,
+        db,
+        format_elapsed(elapsed)
+    ));
+}
+
+/// Log a command about to be sent.
+pub fn command(cmd: &Cmd) {
+    if enabled() {
+        write_line(&format!("> {}", format_command(cmd)));
+    }
+}
+
+/// Log the reply to a single command and how long it took.
+pub fn reply(reply: &RedisResult<Value>, elapsed: Duration) {
+    if enabled() {
+        write_line(&format_reply(reply, elapsed));
+    }
+}
+
+/// Log a pipeline about to be sent. A non-zero `offset` means the driver
+/// wrapped it in MULTI/EXEC.
+pub fn pipeline(pipeline: &Pipeline, offset: usize) {
+    if !enabled() {
+        return;
+    }
+    let commands: Vec<&Cmd> = pipeline.cmd_iter().collect();
+    let kind = if offset > 0 {
+        "transaction"
+    } else {
+        "pipeline"
+    };
+    write_line(&format!("> {} of {} commands", kind, commands.len()));
+    for cmd in commands {
+        write_line(&format!(">   {}", format_command(cmd)));
+    }
+}
+
+/// Log the replies to a pipeline and how long the round trip took.
+pub fn replies(replies: &RedisResult<Vec<Value>>, elapsed: Duration) {
+    if !enabled() {
+        return;
+    }
+    match replies {
+        Ok(values) => {
+            let noun = if values.len() == 1 {
+                "reply"
+            } else {
+                "replies"
+            };
+            write_line(&format!(
+                "< {} {} ({})",
+                values.len(),
+                noun,
+                format_elapsed(elapsed)
+            ));
+            for value in values {
+                write_line(&format!("<   {}", format_value(value)));
+            }
+        }
+        Err(e) => write_line(&format!("< (error) {} ({})", e, format_elapsed(elapsed))),
+    }
+}
+
+fn format_command(cmd: &Cmd) -> String {
+    let args: Vec<&[u8]> = cmd
+        .args_iter()
+        .map(|arg| match arg {
+            Arg::Simple(bytes) => bytes,
+            Arg::Cursor => b"<cursor>".as_slice(),
+        })
+        .collect();
+    let secret = secret_args(&args);
+
+    let mut parts: Vec<String> = args
+        .iter()
+        .take(MAX_ITEMS)
+        .enumerate()
+        .map(|(i, arg)| {
+            if secret[i] {
+                "********".to_string()
+            } else {
+                truncate(&display_key(arg))
+            }
+        })
+        .collect();
+    if args.len() > MAX_ITEMS {
+        parts.push(format!("… (+{} more)", args.len() - MAX_ITEMS));
+    }
+    parts.join(" ")
+}
+
+/// Which arguments are credentials, so they are never written to a trace:
+/// everything after a leading AUTH, the password after an AUTH token
+/// (MIGRATE ... AUTH), the username and password after AUTH2 or HELLO's
+/// AUTH, and the values of CONFIG SET requirepass/masterauth.
+fn secret_args(args: &[&[u8]]) -> Vec<bool> {
+    let mut secret = vec![false; args.len()];
+    let is = |i: usize, word: &str| {
+        args.get(i)
+            .is_some_and(|a| a.eq_ignore_ascii_case(word.as_bytes()))
+    };
+
+    if is(0, "AUTH") {
+        secret.iter_mut().skip(1).for_each(|s| *s = true);
+        return secret;
+    }
+    if is(0, "CONFIG") && is(1, "SET") {
+        for i in (2..args.len()).step_by(2) {
+            if is(i, "requirepass") || is(i, "masterauth") {
+                if let Some(value) = secret.get_mut(i + 1) {
+                    *value = true;
+                }
+            }
+        }
+        return secret;
+    }
+    for i in 1..args.len() {
+        let hidden = if is(i, "AUTH2") || (is(i, "AUTH") && is(0, "HELLO")) {
+            2
+        } else if is(i, "AUTH") {
+            1
+        } else {
+            0
+        };
+        for s in secret.iter_mut().skip(i + 1).take(hidden) {
+            *s = true;
+        }
+    }
+    secret
+}
+
+fn format_reply(reply: &RedisResult<Value>, elapsed: Duration) -> String {
+    let reply = match reply {
+        Ok(value) => format_value(value),
+        Err(e) => format!("(error) {}", e),
+    };
+    format!("< {} ({})", reply, format_elapsed(elapsed))
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Nil => "(nil)".to_string(),
+        Value::Int(n) => format!("(integer) {}", n),
+        Value::Data(bytes) => truncate(&display_key(bytes)),
+        Value::Status(status) => status.clone(),
+        Value::Okay => "OK".to_string(),
+        Value::Bulk(items) => {
+            let mut parts: Vec<String> = items.iter().take(MAX_ITEMS).map(format_value).collect();
+            if items.len() > MAX_ITEMS {
+                parts.push(format!("… (+{} more)", items.len() - MAX_ITEMS));
+            }
+            format!("[{} items] {}", items.len(), parts.join(" "))
+                .trim_end()
+                .to_string()
+        }
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_ARG_LEN {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(MAX_ARG_LEN).collect();
+    format!("{}… ({} bytes)", kept, text.len())
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    format!("{:.2} ms", elapsed.as_secs_f64() * 1000.0)
+}